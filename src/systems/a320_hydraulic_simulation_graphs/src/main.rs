@@ -1,4 +1,7 @@
-use systems::simulation::UpdateContext;
+use systems::{
+    failures::FailureType,
+    simulation::{UpdateContext, VariableRegistry},
+};
 
 pub use systems::hydraulic::*;
 
@@ -219,6 +222,7 @@ impl History {
 
 /// Runs engine driven pump, checks pressure OK, shut it down, check drop of pressure after 20s
 fn green_loop_edp_simulation(path: &str) {
+    let mut registry = VariableRegistry::new();
     let green_loop_var_names = vec![
         "Loop Pressure".to_string(),
         "Loop Volume".to_string(),
@@ -230,10 +234,10 @@ fn green_loop_edp_simulation(path: &str) {
     let edp1_var_names = vec!["Delta Vol Max".to_string(), "pump rpm".to_string()];
     let mut edp1_history = History::new(edp1_var_names);
 
-    let mut edp1 = engine_driven_pump();
+    let mut edp1 = engine_driven_pump(&mut registry);
     let mut edp1_controller = TestPumpController::commanding_pressurise();
 
-    let mut green_loop = hydraulic_loop("GREEN");
+    let mut green_loop = hydraulic_loop(&mut registry, "GREEN");
     let green_loop_controller = TestHydraulicLoopController::commanding_open_fire_shutoff_valve();
 
     let edp_rpm = 3000.;
@@ -345,6 +349,7 @@ fn green_loop_edp_simulation(path: &str) {
 }
 
 fn yellow_green_ptu_loop_simulation(path: &str) {
+    let mut registry = VariableRegistry::new();
     let loop_var_names = vec![
         "GREEN Loop Pressure".to_string(),
         "YELLOW Loop Pressure".to_string(),
@@ -379,18 +384,18 @@ fn yellow_green_ptu_loop_simulation(path: &str) {
     ];
     let mut accu_yellow_history = History::new(yellow_acc_var_names);
 
-    let mut epump = electric_pump();
+    let mut epump = electric_pump(&mut registry);
     let mut epump_controller = TestPumpController::commanding_depressurise();
-    let mut yellow_loop = hydraulic_loop("YELLOW");
+    let mut yellow_loop = hydraulic_loop(&mut registry, "YELLOW");
 
-    let mut edp1 = engine_driven_pump();
+    let mut edp1 = engine_driven_pump(&mut registry);
     let mut edp1_controller = TestPumpController::commanding_depressurise();
 
-    let mut green_loop = hydraulic_loop("GREEN");
+    let mut green_loop = hydraulic_loop(&mut registry, "GREEN");
 
     let loop_controller = TestHydraulicLoopController::commanding_open_fire_shutoff_valve();
 
-    let mut ptu = PowerTransferUnit::new();
+    let mut ptu = PowerTransferUnit::new(&mut registry);
     let mut ptu_controller = TestPowerTransferUnitController::commanding_disabled();
 
     let context = context(Duration::from_millis(100));
@@ -599,6 +604,7 @@ fn yellow_green_ptu_loop_simulation(path: &str) {
 }
 
 fn yellow_epump_plus_edp2_with_ptu(path: &str) {
+    let mut registry = VariableRegistry::new();
     let loop_var_names = vec![
         "GREEN Loop Pressure".to_string(),
         "YELLOW Loop Pressure".to_string(),
@@ -633,20 +639,20 @@ fn yellow_epump_plus_edp2_with_ptu(path: &str) {
     ];
     let mut accu_yellow_history = History::new(yellow_acc_var_names);
 
-    let mut epump = electric_pump();
+    let mut epump = electric_pump(&mut registry);
     let mut epump_controller = TestPumpController::commanding_depressurise();
-    let mut yellow_loop = hydraulic_loop("YELLOW");
+    let mut yellow_loop = hydraulic_loop(&mut registry, "YELLOW");
 
-    let mut edp2 = engine_driven_pump();
+    let mut edp2 = engine_driven_pump(&mut registry);
     let mut edp2_controller = TestPumpController::commanding_depressurise();
 
     let edp_rpm = 3300.;
 
-    let mut green_loop = hydraulic_loop("GREEN");
+    let mut green_loop = hydraulic_loop(&mut registry, "GREEN");
 
     let loop_controller = TestHydraulicLoopController::commanding_open_fire_shutoff_valve();
 
-    let mut ptu = PowerTransferUnit::new();
+    let mut ptu = PowerTransferUnit::new(&mut registry);
     let ptu_controller = TestPowerTransferUnitController::commanding_enabled();
 
     let context = context(Duration::from_millis(100));
@@ -773,10 +779,12 @@ fn yellow_epump_plus_edp2_with_ptu(path: &str) {
     accu_yellow_history.show_matplotlib("yellow_epump_plus_edp2_with_ptu()_Yellow_acc", &path);
 }
 
-fn hydraulic_loop(loop_color: &str) -> HydraulicLoop {
+fn hydraulic_loop(registry: &mut VariableRegistry, loop_color: &str) -> HydraulicLoop {
     match loop_color {
         "GREEN" => HydraulicLoop::new(
+            registry,
             loop_color,
+            FailureType::GreenHydraulicLeak,
             true,
             false,
             Volume::new::<gallon>(26.41),
@@ -789,7 +797,9 @@ fn hydraulic_loop(loop_color: &str) -> HydraulicLoop {
             Pressure::new::<psi>(1750.),
         ),
         "YELLOW" => HydraulicLoop::new(
+            registry,
             loop_color,
+            FailureType::YellowHydraulicLeak,
             false,
             true,
             Volume::new::<gallon>(10.2),
@@ -802,7 +812,9 @@ fn hydraulic_loop(loop_color: &str) -> HydraulicLoop {
             Pressure::new::<psi>(1750.),
         ),
         _ => HydraulicLoop::new(
+            registry,
             loop_color,
+            FailureType::BlueHydraulicLeak,
             false,
             false,
             Volume::new::<gallon>(15.85),
@@ -817,12 +829,12 @@ fn hydraulic_loop(loop_color: &str) -> HydraulicLoop {
     }
 }
 
-fn electric_pump() -> ElectricPump {
-    ElectricPump::new("DEFAULT")
+fn electric_pump(registry: &mut VariableRegistry) -> ElectricPump {
+    ElectricPump::new(registry, "DEFAULT", FailureType::YellowElectricPump)
 }
 
-fn engine_driven_pump() -> EngineDrivenPump {
-    EngineDrivenPump::new("DEFAULT")
+fn engine_driven_pump(registry: &mut VariableRegistry) -> EngineDrivenPump {
+    EngineDrivenPump::new(registry, "DEFAULT", FailureType::EngineDrivenPump(1))
 }
 
 fn context(delta_time: Duration) -> UpdateContext {
@@ -0,0 +1,39 @@
+//! Lists every simulator variable which is read or written by the A320,
+//! together with the element accessing it and the type as which it is accessed.
+//!
+//! Usage: `cargo run --bin a320_catalogue -- [--json]`
+use a320_systems::A320;
+use std::{
+    env,
+    error::Error,
+    io::{self, Write},
+};
+use systems::simulation::Catalogue;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let json = match env::args().nth(1).as_deref() {
+        None => false,
+        Some("--json") => true,
+        Some(_) => return Err("Usage: a320_catalogue [--json]".into()),
+    };
+
+    let catalogue = Catalogue::of(A320::new);
+
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    if json {
+        // Unlike the Markdown, the JSON only contains the accessed variables.
+        for name in catalogue.unaccessed() {
+            eprintln!(
+                "Variable '{}' is registered, but wasn't read or written.",
+                name
+            );
+        }
+        catalogue.write_json(&mut writer)?;
+    } else {
+        catalogue.write_markdown(&mut writer)?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
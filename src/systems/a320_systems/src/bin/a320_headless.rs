@@ -0,0 +1,120 @@
+//! Runs a newly created A320 without the simulator. Executes the timed variable writes
+//! found in a scenario file, and writes the values of the given output variables after
+//! every tick to stdout as CSV. Optionally writes a state dump of the aircraft's systems
+//! at the end of the run as JSON, and the events emitted by the systems as CSV.
+//! Violated invariants are written to stderr.
+//!
+//! With `--dump-every`, the state dump file instead contains a JSON array of the dumps
+//! taken after every given number of ticks, for following how the state changes.
+//!
+//! Usage: `cargo run --bin a320_headless -- <scenario file> <duration in seconds>
+//! [--step <milliseconds>] [--dump <state dump file>] [--dump-every <ticks>]
+//! [--events <event file>] [<output variable>...]`
+//!
+//! A scenario file contains lines such as `at t=10s set OVHD_ELEC_BAT_1_PB_IS_AUTO=1`.
+use a320_systems::A320;
+use std::{
+    env,
+    error::Error,
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    time::Duration,
+};
+use systems::simulation::{InvariantViolationAction, Scenario, ScenarioRunner, VariableRegistry};
+
+const USAGE: &str = "Usage: a320_headless <scenario file> <duration in seconds> \
+    [--step <milliseconds>] [--dump <state dump file>] [--dump-every <ticks>] \
+    [--events <event file>] [<output variable>...]";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+    let scenario_path = args.next().ok_or(USAGE)?;
+    let duration = parse_duration(&args.next().ok_or(USAGE)?)?;
+
+    let mut step = None;
+    let mut dump_path = None;
+    let mut dump_interval = None;
+    let mut events_path = None;
+    let mut outputs = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--step" => step = Some(parse_step(&args.next().ok_or(USAGE)?)?),
+            "--dump" => dump_path = Some(args.next().ok_or(USAGE)?),
+            "--dump-every" => {
+                dump_interval = Some(parse_dump_interval(&args.next().ok_or(USAGE)?)?)
+            }
+            "--events" => events_path = Some(args.next().ok_or(USAGE)?),
+            _ => outputs.push(arg),
+        }
+    }
+    let outputs: Vec<&str> = outputs.iter().map(|output| output.as_str()).collect();
+    if dump_interval.is_some() && dump_path.is_none() {
+        return Err("--dump-every requires a state dump file given by --dump.".into());
+    }
+
+    let scenario = Scenario::read_from(BufReader::new(File::open(scenario_path)?))?;
+
+    let mut registry = VariableRegistry::new();
+    let mut aircraft = A320::new(&mut registry);
+    let mut runner = ScenarioRunner::new(&mut aircraft, &registry)
+        .with_settings(A320::simulation_settings())
+        .with_invariant_action(InvariantViolationAction::Log);
+    if let Some(step) = step {
+        runner = runner.with_step(step);
+    }
+    if let Some(dump_interval) = dump_interval {
+        runner = runner.with_dump_interval(dump_interval);
+    }
+
+    let stdout = io::stdout();
+    let mut csv = BufWriter::new(stdout.lock());
+    runner.run(&scenario, duration, &outputs, &mut csv)?;
+    csv.flush()?;
+
+    for (name, count) in runner.invariant_monitor().violation_counts() {
+        eprintln!("Invariant \"{}\" violated in {} ticks.", name, count);
+    }
+
+    if let Some(dump_path) = dump_path {
+        let mut file = BufWriter::new(File::create(dump_path)?);
+        if dump_interval.is_some() {
+            runner.write_dumps_json(&mut file)?;
+        } else {
+            runner.dump_state().write_json(&mut file)?;
+        }
+        file.flush()?;
+    }
+
+    if let Some(events_path) = events_path {
+        let mut file = BufWriter::new(File::create(events_path)?);
+        runner.events().write_csv(&mut file)?;
+        file.flush()?;
+    }
+
+    Ok(())
+}
+
+fn parse_duration(seconds: &str) -> Result<Duration, Box<dyn Error>> {
+    let seconds: f64 = seconds.parse()?;
+    Duration::try_from_secs_f64(seconds).map_err(|_| {
+        format!(
+            "The duration of {} seconds is negative, not a number or too long.",
+            seconds
+        )
+        .into()
+    })
+}
+
+fn parse_step(milliseconds: &str) -> Result<Duration, Box<dyn Error>> {
+    match milliseconds.parse::<u64>()? {
+        0 => Err("The step must be at least one millisecond.".into()),
+        milliseconds => Ok(Duration::from_millis(milliseconds)),
+    }
+}
+
+fn parse_dump_interval(ticks: &str) -> Result<usize, Box<dyn Error>> {
+    match ticks.parse::<usize>()? {
+        0 => Err("The dump interval must be at least one tick.".into()),
+        ticks => Ok(ticks),
+    }
+}
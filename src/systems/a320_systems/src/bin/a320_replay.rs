@@ -0,0 +1,38 @@
+//! Replays a recording made in the simulator on a newly created A320,
+//! listing every write which differs from the recorded write.
+//!
+//! Usage: `cargo run --bin a320_replay -- <recording file>`
+use a320_systems::A320;
+use std::{env, error::Error, fs::File, io::BufReader, process};
+use systems::simulation::{replay_with_settings, Recording, VariableRegistry};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = env::args()
+        .nth(1)
+        .ok_or("Usage: a320_replay <recording file>")?;
+    let recording = Recording::read_from(BufReader::new(File::open(path)?))?;
+
+    let mut registry = VariableRegistry::new();
+    let mut aircraft = A320::new(&mut registry);
+    let differences = replay_with_settings(
+        &mut aircraft,
+        &registry,
+        &recording,
+        A320::simulation_settings(),
+    );
+    for difference in &differences {
+        println!("{}", difference);
+    }
+
+    println!(
+        "Replayed {} ticks with {} differences.",
+        recording.ticks().len(),
+        differences.len()
+    );
+
+    if !differences.is_empty() {
+        process::exit(1);
+    }
+
+    Ok(())
+}
@@ -1,21 +1,28 @@
 use super::{
-    A320AlternatingCurrentElectricalSystem, A320DirectCurrentElectricalSystem,
+    A320AlternatingCurrentElectricalSystem, A320DirectCurrentElectricalSystem, A320Electrical,
     A320ElectricalOverheadPanel, A320EmergencyElectricalOverheadPanel,
 };
 use std::time::Duration;
 use systems::{
     electrical::{
-        consumption::SuppliedPower, AlternatingCurrentElectricalSystem, Contactor, ElectricalBus,
-        ElectricalBusType, EmergencyGenerator, EngineGenerator, ExternalPowerSource, Potential,
-        PotentialOrigin, PotentialSource, PotentialTarget, TransformerRectifier,
+        consumption::SuppliedPower, AlternatingCurrentElectricalSystem, Contactor,
+        ConverterCharacteristics, ElectricalBus, ElectricalBusType, ElectricalNetwork,
+        EmergencyGenerator, EngineGenerator, ExternalPowerSource, GeneratorControlUnit, NodeId,
+        Potential, PotentialOrigin, PotentialSource, PotentialTarget, SourceResistance,
+        TransformerRectifier,
     },
     shared::{
         AuxiliaryPowerUnitElectrical, DelayedTrueLogicGate, EngineCorrectedN2,
         EngineFirePushButtons,
     },
-    simulation::{SimulationElement, SimulationElementVisitor, UpdateContext},
+    simulation::{
+        InvariantChecker, SimulationElement, SimulationElementVisitor, UpdateContext,
+        VariableRegistry,
+    },
+};
+use uom::si::{
+    electric_potential::volt, electrical_resistance::ohm, f64::*, power::watt, velocity::knot,
 };
-use uom::si::{f64::*, velocity::knot};
 
 pub(super) struct A320AlternatingCurrentElectrical {
     main_power_sources: A320MainPowerSources,
@@ -35,34 +42,79 @@ pub(super) struct A320AlternatingCurrentElectrical {
     ac_stat_inv_bus: ElectricalBus,
     ac_gnd_flt_service_bus: ElectricalBus,
     ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor: Contactor,
+    network: ElectricalNetwork,
+    nodes: A320AlternatingCurrentNodes,
 }
 impl A320AlternatingCurrentElectrical {
-    pub fn new() -> Self {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
+        let mut network = ElectricalNetwork::new();
+        let nodes = A320AlternatingCurrentNodes::new(&mut network);
+
         A320AlternatingCurrentElectrical {
-            main_power_sources: A320MainPowerSources::new(),
-            ac_ess_feed_contactors: A320AcEssFeedContactors::new(),
-            ac_bus_1: ElectricalBus::new(ElectricalBusType::AlternatingCurrent(1)),
-            ac_bus_2: ElectricalBus::new(ElectricalBusType::AlternatingCurrent(2)),
-            ac_ess_bus: ElectricalBus::new(ElectricalBusType::AlternatingCurrentEssential),
-            ac_ess_shed_bus: ElectricalBus::new(ElectricalBusType::AlternatingCurrentEssentialShed),
-            ac_ess_shed_contactor: Contactor::new("8XH"),
-            tr_1: TransformerRectifier::new(1),
-            tr_2: TransformerRectifier::new(2),
-            ac_bus_2_to_tr_2_contactor: Contactor::new("14PU"),
-            tr_ess: TransformerRectifier::new(3),
-            ac_ess_to_tr_ess_contactor: Contactor::new("15XE1"),
-            emergency_gen_contactor: Contactor::new("2XE"),
-            static_inv_to_ac_ess_bus_contactor: Contactor::new("15XE2"),
+            main_power_sources: A320MainPowerSources::new(registry),
+            ac_ess_feed_contactors: A320AcEssFeedContactors::new(registry),
+            ac_bus_1: ElectricalBus::new(registry, ElectricalBusType::AlternatingCurrent(1)),
+            ac_bus_2: ElectricalBus::new(registry, ElectricalBusType::AlternatingCurrent(2)),
+            ac_ess_bus: ElectricalBus::new(
+                registry,
+                ElectricalBusType::AlternatingCurrentEssential,
+            ),
+            ac_ess_shed_bus: ElectricalBus::new(
+                registry,
+                ElectricalBusType::AlternatingCurrentEssentialShed,
+            ),
+            ac_ess_shed_contactor: Contactor::new(registry, "8XH"),
+            tr_1: TransformerRectifier::new(registry, 1)
+                .with_characteristics(Self::transformer_rectifier_characteristics())
+                .with_resistance(Self::transformer_rectifier_resistance()),
+            tr_2: TransformerRectifier::new(registry, 2)
+                .with_characteristics(Self::transformer_rectifier_characteristics())
+                .with_resistance(Self::transformer_rectifier_resistance()),
+            ac_bus_2_to_tr_2_contactor: Contactor::new(registry, "14PU"),
+            tr_ess: TransformerRectifier::new(registry, 3)
+                .with_characteristics(Self::transformer_rectifier_characteristics())
+                .with_resistance(Self::transformer_rectifier_resistance()),
+            ac_ess_to_tr_ess_contactor: Contactor::new(registry, "15XE1"),
+            emergency_gen_contactor: Contactor::new(registry, "2XE"),
+            static_inv_to_ac_ess_bus_contactor: Contactor::new(registry, "15XE2"),
             ac_stat_inv_bus: ElectricalBus::new(
+                registry,
                 ElectricalBusType::AlternatingCurrentStaticInverter,
             ),
             ac_gnd_flt_service_bus: ElectricalBus::new(
+                registry,
                 ElectricalBusType::AlternatingCurrentGndFltService,
             ),
-            ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor: Contactor::new("12XN"),
+            ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor: Contactor::new(registry, "12XN"),
+            network,
+            nodes,
         }
     }
 
+    /// The A320's TRs are rated at 200A. Their efficiency is lowest at low load, and
+    /// their output potential is regulated between 28.5V without load and 27.5V at
+    /// rated load.
+    fn transformer_rectifier_characteristics() -> ConverterCharacteristics {
+        ConverterCharacteristics::ideal(
+            Power::new::<watt>(200. * 28.),
+            ElectricPotential::new::<volt>(28.),
+        )
+        .with_efficiency(&[0.1, 0.25, 0.5, 0.75, 1.], &[0.75, 0.85, 0.89, 0.9, 0.89])
+        .with_regulation(
+            ElectricPotential::new::<volt>(28.5),
+            ElectricPotential::new::<volt>(27.5),
+        )
+    }
+
+    /// The TRs' output potential sags beyond their regulation due to the
+    /// resistance of the feeders towards the DC buses.
+    fn transformer_rectifier_resistance() -> SourceResistance {
+        SourceResistance::new(
+            ElectricalResistance::new::<ohm>(0.),
+            ElectricalResistance::new::<ohm>(0.004),
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn update_main_power_sources(
         &mut self,
@@ -84,10 +136,17 @@ impl A320AlternatingCurrentElectrical {
             engines,
         );
 
-        self.ac_bus_1
-            .powered_by(&self.main_power_sources.ac_bus_1_electric_sources());
-        self.ac_bus_2
-            .powered_by(&self.main_power_sources.ac_bus_2_electric_sources());
+        // The main power sources remain supplied to the network for the
+        // remainder of the tick.
+        let nodes = &self.nodes;
+        let network = &mut self.network;
+        network.supply(nodes.engine_gen_1, &self.main_power_sources.engine_1_gen);
+        network.supply(nodes.engine_gen_2, &self.main_power_sources.engine_2_gen);
+        network.supply(nodes.apu_gen, apu);
+        network.supply(nodes.ext_pwr, ext_pwr);
+
+        // The emergency generator and static inverter are updated later on.
+        self.propagate(&Potential::none(), &Potential::none());
     }
 
     pub fn update(
@@ -97,59 +156,39 @@ impl A320AlternatingCurrentElectrical {
         overhead: &A320ElectricalOverheadPanel,
         emergency_generator: &EmergencyGenerator,
     ) {
-        self.ac_bus_2_to_tr_2_contactor.powered_by(&self.ac_bus_2);
         self.ac_bus_2_to_tr_2_contactor
             .close_when(self.ac_bus_2.is_powered() && !self.tr_2.failed());
 
-        self.ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor
-            .powered_by(ext_pwr);
-
         // On the real aircraft there is a button inside the galley which is taken into
         // account when determining whether to close this contactor or not.
         // As we're not building a galley simulator, for now we assume the button is ON.
         self.ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor
             .close_when(!self.ac_bus_2.is_powered() && !self.tr_2.failed() && ext_pwr.is_powered());
 
-        self.ac_gnd_flt_service_bus
-            .powered_by(&self.ac_bus_2_to_tr_2_contactor);
-        self.ac_gnd_flt_service_bus
-            .or_powered_by(&self.ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor);
-
-        self.tr_1.powered_by(&self.ac_bus_1);
-        self.tr_2.powered_by(&self.ac_bus_2_to_tr_2_contactor);
-        self.tr_2
-            .or_powered_by(&self.ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor);
-
         self.ac_ess_feed_contactors
             .update(context, &self.ac_bus_1, &self.ac_bus_2, overhead);
 
-        self.ac_ess_bus
-            .powered_by(&self.ac_ess_feed_contactors.electric_sources());
-
         self.emergency_gen_contactor.close_when(
             !self.any_non_essential_bus_powered()
                 && emergency_generator.output_within_normal_parameters(),
         );
-        self.emergency_gen_contactor.powered_by(emergency_generator);
 
-        self.ac_ess_to_tr_ess_contactor.powered_by(&self.ac_ess_bus);
-        self.ac_ess_to_tr_ess_contactor
-            .or_powered_by(&self.emergency_gen_contactor);
+        // The static inverter is only connected to the AC ESS bus once the DC
+        // network has been updated.
+        self.propagate(emergency_generator, &Potential::none());
 
         self.ac_ess_to_tr_ess_contactor.close_when(
             (!self.tr_1_and_2_available() && self.ac_ess_feed_contactors.provides_power())
                 || self.emergency_gen_contactor.is_powered(),
         );
 
-        self.ac_ess_bus
-            .or_powered_by(&self.ac_ess_to_tr_ess_contactor);
-
-        self.ac_ess_shed_contactor.powered_by(&self.ac_ess_bus);
-
-        self.tr_ess.powered_by(&self.ac_ess_to_tr_ess_contactor);
-        self.tr_ess.or_powered_by(&self.emergency_gen_contactor);
+        let ac_bus_or_emergency_gen_provides_power = self.ac_bus_1.is_powered()
+            || self.ac_bus_2.is_powered()
+            || emergency_generator.is_powered();
+        self.ac_ess_shed_contactor
+            .close_when(ac_bus_or_emergency_gen_provides_power);
 
-        self.update_shedding(emergency_generator);
+        self.propagate(emergency_generator, &Potential::none());
     }
 
     pub fn update_after_direct_current(
@@ -158,23 +197,142 @@ impl A320AlternatingCurrentElectrical {
         emergency_generator: &EmergencyGenerator,
         dc_state: &impl A320DirectCurrentElectricalSystem,
     ) {
-        self.ac_stat_inv_bus.powered_by(dc_state.static_inverter());
         self.static_inv_to_ac_ess_bus_contactor
             .close_when(self.should_close_15xe2_contactor(context, emergency_generator));
-        self.static_inv_to_ac_ess_bus_contactor
-            .powered_by(dc_state.static_inverter());
-        self.ac_ess_bus
-            .or_powered_by(&self.static_inv_to_ac_ess_bus_contactor);
+
+        self.propagate(emergency_generator, dc_state.static_inverter());
     }
 
-    fn update_shedding(&mut self, emergency_generator: &EmergencyGenerator) {
-        let ac_bus_or_emergency_gen_provides_power = self.ac_bus_1.is_powered()
-            || self.ac_bus_2.is_powered()
-            || emergency_generator.is_powered();
-        self.ac_ess_shed_contactor
-            .close_when(ac_bus_or_emergency_gen_provides_power);
+    fn propagate(
+        &mut self,
+        emergency_generator: &impl PotentialSource,
+        static_inverter: &impl PotentialSource,
+    ) {
+        let nodes = &self.nodes;
+        let network = &mut self.network;
+
+        network.supply(nodes.emergency_gen, emergency_generator);
+        network.supply(nodes.static_inverter, static_inverter);
+        network.set_available(nodes.tr_1, !self.tr_1.failed());
+        network.set_available(nodes.tr_2, !self.tr_2.failed());
+        network.set_available(nodes.tr_ess, !self.tr_ess.failed());
+
+        self.for_each_contactor(|network, node, contactor| {
+            network.set_closed(node, contactor.is_closed())
+        });
+        self.network.propagate();
+        self.power_elements();
+    }
+
+    fn power_elements(&mut self) {
+        self.for_each_contactor(|network, node, contactor| {
+            contactor.powered_by(&network.potential(node))
+        });
+
+        let nodes = &self.nodes;
+        let network = &self.network;
+        for (node, bus) in [
+            (nodes.ac_bus_1, &mut self.ac_bus_1),
+            (nodes.ac_bus_2, &mut self.ac_bus_2),
+            (nodes.ac_ess_bus, &mut self.ac_ess_bus),
+            (nodes.ac_ess_shed_bus, &mut self.ac_ess_shed_bus),
+            (nodes.ac_stat_inv_bus, &mut self.ac_stat_inv_bus),
+            (
+                nodes.ac_gnd_flt_service_bus,
+                &mut self.ac_gnd_flt_service_bus,
+            ),
+        ] {
+            bus.powered_by(&network.potential(node));
+        }
 
-        self.ac_ess_shed_bus.powered_by(&self.ac_ess_shed_contactor);
+        for (node, tr) in [
+            (nodes.tr_1, &mut self.tr_1),
+            (nodes.tr_2, &mut self.tr_2),
+            (nodes.tr_ess, &mut self.tr_ess),
+        ] {
+            tr.powered_by(&network.potential(node));
+        }
+    }
+
+    fn for_each_contactor(
+        &mut self,
+        mut func: impl FnMut(&mut ElectricalNetwork, NodeId, &mut Contactor),
+    ) {
+        let nodes = &self.nodes;
+        let network = &mut self.network;
+        let main_power_sources = &mut self.main_power_sources;
+        let ac_ess_feed_contactors = &mut self.ac_ess_feed_contactors;
+
+        func(
+            network,
+            nodes.engine_gen_1_contactor,
+            &mut main_power_sources.engine_generator_contactors[0],
+        );
+        func(
+            network,
+            nodes.engine_gen_2_contactor,
+            &mut main_power_sources.engine_generator_contactors[1],
+        );
+        func(
+            network,
+            nodes.bus_tie_1_contactor,
+            &mut main_power_sources.bus_tie_1_contactor,
+        );
+        func(
+            network,
+            nodes.bus_tie_2_contactor,
+            &mut main_power_sources.bus_tie_2_contactor,
+        );
+        func(
+            network,
+            nodes.apu_gen_contactor,
+            &mut main_power_sources.apu_gen_contactor,
+        );
+        func(
+            network,
+            nodes.ext_pwr_contactor,
+            &mut main_power_sources.ext_pwr_contactor,
+        );
+        func(
+            network,
+            nodes.ac_ess_feed_contactor_1,
+            &mut ac_ess_feed_contactors.ac_ess_feed_contactor_1,
+        );
+        func(
+            network,
+            nodes.ac_ess_feed_contactor_2,
+            &mut ac_ess_feed_contactors.ac_ess_feed_contactor_2,
+        );
+        func(
+            network,
+            nodes.ac_ess_shed_contactor,
+            &mut self.ac_ess_shed_contactor,
+        );
+        func(
+            network,
+            nodes.ac_bus_2_to_tr_2_contactor,
+            &mut self.ac_bus_2_to_tr_2_contactor,
+        );
+        func(
+            network,
+            nodes.ac_ess_to_tr_ess_contactor,
+            &mut self.ac_ess_to_tr_ess_contactor,
+        );
+        func(
+            network,
+            nodes.emergency_gen_contactor,
+            &mut self.emergency_gen_contactor,
+        );
+        func(
+            network,
+            nodes.static_inv_to_ac_ess_bus_contactor,
+            &mut self.static_inv_to_ac_ess_bus_contactor,
+        );
+        func(
+            network,
+            nodes.ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor,
+            &mut self.ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor,
+        );
     }
 
     /// Whether or not AC BUS 1 and AC BUS 2 are powered by a single engine
@@ -237,27 +395,21 @@ impl A320AlternatingCurrentElectrical {
             && context.indicated_airspeed() >= Velocity::new::<knot>(50.)
     }
 
-    pub fn debug_assert_invariants(&self) {
-        debug_assert!(self.static_inverter_or_emergency_gen_powers_ac_ess_bus());
-    }
-
     fn static_inverter_or_emergency_gen_powers_ac_ess_bus(&self) -> bool {
         !(self.static_inv_to_ac_ess_bus_contactor.is_closed()
             && self.ac_ess_to_tr_ess_contactor.is_closed())
     }
 
-    #[cfg(test)]
-    pub fn fail_tr_1(&mut self) {
-        self.tr_1.fail();
+    pub fn gen_contactor_open(&self, number: usize) -> bool {
+        self.main_power_sources.gen_contactor_open(number)
     }
 
-    #[cfg(test)]
-    pub fn fail_tr_2(&mut self) {
-        self.tr_2.fail();
+    pub fn apu_gen_has_fault(&self) -> bool {
+        self.main_power_sources.apu_gen_has_fault()
     }
 
-    pub fn gen_contactor_open(&self, number: usize) -> bool {
-        self.main_power_sources.gen_contactor_open(number)
+    pub fn idg_has_fault(&self, number: usize) -> bool {
+        self.main_power_sources.idg_has_fault(number)
     }
 
     pub fn emergency_generator_contactor_is_closed(&self) -> bool {
@@ -329,27 +481,49 @@ impl SimulationElement for A320AlternatingCurrentElectrical {
 
         visitor.visit(self);
     }
+
+    fn check_invariants(&self, invariants: &mut InvariantChecker) {
+        invariants.check(
+            "static inverter or emergency generator powers AC ESS bus",
+            self.static_inverter_or_emergency_gen_powers_ac_ess_bus(),
+        );
+    }
 }
 
 struct A320MainPowerSources {
     engine_1_gen: EngineGenerator,
     engine_2_gen: EngineGenerator,
+    engine_generator_control_units: [GeneratorControlUnit; 2],
     engine_generator_contactors: [Contactor; 2],
     bus_tie_1_contactor: Contactor,
     bus_tie_2_contactor: Contactor,
+    apu_generator_control_unit: GeneratorControlUnit,
     apu_gen_contactor: Contactor,
     ext_pwr_contactor: Contactor,
 }
 impl A320MainPowerSources {
-    fn new() -> Self {
+    fn new(registry: &mut VariableRegistry) -> Self {
         A320MainPowerSources {
-            engine_1_gen: EngineGenerator::new(1),
-            engine_2_gen: EngineGenerator::new(2),
-            engine_generator_contactors: [Contactor::new("9XU1"), Contactor::new("9XU2")],
-            bus_tie_1_contactor: Contactor::new("11XU1"),
-            bus_tie_2_contactor: Contactor::new("11XU2"),
-            apu_gen_contactor: Contactor::new("3XS"),
-            ext_pwr_contactor: Contactor::new("3XG"),
+            engine_1_gen: EngineGenerator::new(registry, 1)
+                .with_resistance(A320Electrical::generator_resistance()),
+            engine_2_gen: EngineGenerator::new(registry, 2)
+                .with_resistance(A320Electrical::generator_resistance()),
+            engine_generator_control_units: [
+                GeneratorControlUnit::new(registry, PotentialOrigin::EngineGenerator(1)),
+                GeneratorControlUnit::new(registry, PotentialOrigin::EngineGenerator(2)),
+            ],
+            engine_generator_contactors: [
+                Contactor::new(registry, "9XU1"),
+                Contactor::new(registry, "9XU2"),
+            ],
+            bus_tie_1_contactor: Contactor::new(registry, "11XU1"),
+            bus_tie_2_contactor: Contactor::new(registry, "11XU2"),
+            apu_generator_control_unit: GeneratorControlUnit::new(
+                registry,
+                PotentialOrigin::ApuGenerator(1),
+            ),
+            apu_gen_contactor: Contactor::new(registry, "3XS"),
+            ext_pwr_contactor: Contactor::new(registry, "3XG"),
         }
     }
 
@@ -368,21 +542,32 @@ impl A320MainPowerSources {
             .update(context, engines[0], overhead, engine_fire_push_buttons);
         self.engine_2_gen
             .update(context, engines[1], overhead, engine_fire_push_buttons);
+        self.engine_generator_control_units[0].update(
+            context,
+            &self.engine_1_gen,
+            overhead.generator_is_on(1),
+        );
+        self.engine_generator_control_units[1].update(
+            context,
+            &self.engine_2_gen,
+            overhead.generator_is_on(2),
+        );
+        self.apu_generator_control_unit
+            .update(context, apu, overhead.apu_generator_is_on());
 
-        let gen_1_provides_power = overhead.generator_is_on(1)
-            && emergency_overhead.generator_1_line_is_on()
+        let gen_1_provides_power = emergency_overhead.generator_1_line_is_on()
             && !engine_fire_push_buttons.is_released(1)
-            && self.engine_1_gen.output_within_normal_parameters();
-        let gen_2_provides_power = overhead.generator_is_on(2)
-            && !engine_fire_push_buttons.is_released(2)
-            && self.engine_2_gen.output_within_normal_parameters();
+            && self.engine_generator_control_units[0].should_close_line_contactor();
+        let gen_2_provides_power = !engine_fire_push_buttons.is_released(2)
+            && self.engine_generator_control_units[1].should_close_line_contactor();
         let only_one_engine_gen_is_powered = gen_1_provides_power ^ gen_2_provides_power;
         let both_engine_gens_provide_power = gen_1_provides_power && gen_2_provides_power;
         let ext_pwr_provides_power = overhead.external_power_is_on()
             && ext_pwr.output_within_normal_parameters()
             && !both_engine_gens_provide_power;
-        let apu_gen_provides_power = overhead.apu_generator_is_on()
-            && apu.output_within_normal_parameters()
+        let apu_gen_provides_power = self
+            .apu_generator_control_unit
+            .should_close_line_contactor()
             && !ext_pwr_provides_power
             && !both_engine_gens_provide_power;
 
@@ -402,52 +587,33 @@ impl A320MainPowerSources {
                 && ((only_one_engine_gen_is_powered && !apu_or_ext_pwr_provides_power)
                     || (apu_or_ext_pwr_provides_power && !gen_2_provides_power)),
         );
+    }
 
-        self.apu_gen_contactor.powered_by(apu);
-        self.ext_pwr_contactor.powered_by(ext_pwr);
-
-        self.engine_generator_contactors[0].powered_by(&self.engine_1_gen);
-        self.bus_tie_1_contactor
-            .powered_by(&self.engine_generator_contactors[0]);
-        self.bus_tie_1_contactor
-            .or_powered_by(&self.apu_gen_contactor);
-        self.bus_tie_1_contactor
-            .or_powered_by(&self.ext_pwr_contactor);
-
-        self.engine_generator_contactors[1].powered_by(&self.engine_2_gen);
-        self.bus_tie_2_contactor
-            .powered_by(&self.engine_generator_contactors[1]);
-        self.bus_tie_2_contactor
-            .or_powered_by(&self.apu_gen_contactor);
-        self.bus_tie_2_contactor
-            .or_powered_by(&self.ext_pwr_contactor);
-
-        self.bus_tie_1_contactor
-            .or_powered_by(&self.bus_tie_2_contactor);
-        self.bus_tie_2_contactor
-            .or_powered_by(&self.bus_tie_1_contactor);
-    }
-
-    fn ac_bus_1_electric_sources(&self) -> Potential {
-        self.engine_generator_contactors[0]
-            .output()
-            .merge(&self.bus_tie_1_contactor.output())
+    pub fn gen_contactor_open(&self, number: usize) -> bool {
+        self.engine_generator_contactors[number - 1].is_open()
     }
 
-    fn ac_bus_2_electric_sources(&self) -> Potential {
-        self.engine_generator_contactors[1]
-            .output()
-            .merge(&self.bus_tie_2_contactor.output())
+    fn apu_gen_has_fault(&self) -> bool {
+        self.apu_generator_control_unit.has_fault()
     }
 
-    pub fn gen_contactor_open(&self, number: usize) -> bool {
-        self.engine_generator_contactors[number - 1].is_open()
+    fn idg_has_fault(&self, number: usize) -> bool {
+        match number {
+            1 => self.engine_1_gen.idg_has_fault(),
+            2 => self.engine_2_gen.idg_has_fault(),
+            _ => panic!("No IDG with number {} exists.", number),
+        }
     }
 }
 impl SimulationElement for A320MainPowerSources {
     fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
         self.engine_1_gen.accept(visitor);
         self.engine_2_gen.accept(visitor);
+        self.engine_generator_control_units
+            .iter_mut()
+            .for_each(|gcu| {
+                gcu.accept(visitor);
+            });
         self.engine_generator_contactors
             .iter_mut()
             .for_each(|contactor| {
@@ -455,6 +621,7 @@ impl SimulationElement for A320MainPowerSources {
             });
         self.bus_tie_1_contactor.accept(visitor);
         self.bus_tie_2_contactor.accept(visitor);
+        self.apu_generator_control_unit.accept(visitor);
         self.apu_gen_contactor.accept(visitor);
         self.ext_pwr_contactor.accept(visitor);
 
@@ -470,10 +637,10 @@ pub(super) struct A320AcEssFeedContactors {
 impl A320AcEssFeedContactors {
     pub const AC_ESS_FEED_TO_AC_BUS_2_DELAY_IN_SECONDS: Duration = Duration::from_secs(3);
 
-    fn new() -> Self {
+    fn new(registry: &mut VariableRegistry) -> Self {
         A320AcEssFeedContactors {
-            ac_ess_feed_contactor_1: Contactor::new("3XC1"),
-            ac_ess_feed_contactor_2: Contactor::new("3XC2"),
+            ac_ess_feed_contactor_1: Contactor::new(registry, "3XC1"),
+            ac_ess_feed_contactor_2: Contactor::new(registry, "3XC2"),
             ac_ess_feed_contactor_delay_logic_gate: DelayedTrueLogicGate::new(
                 A320AcEssFeedContactors::AC_ESS_FEED_TO_AC_BUS_2_DELAY_IN_SECONDS,
             ),
@@ -500,9 +667,6 @@ impl A320AcEssFeedContactors {
                 && (self.ac_ess_feed_contactor_delay_logic_gate.output()
                     || overhead.ac_ess_feed_is_altn()),
         );
-
-        self.ac_ess_feed_contactor_1.powered_by(ac_bus_1);
-        self.ac_ess_feed_contactor_2.powered_by(ac_bus_2);
     }
 
     fn electric_sources(&self) -> Potential {
@@ -523,3 +687,145 @@ impl SimulationElement for A320AcEssFeedContactors {
         visitor.visit(self);
     }
 }
+
+/// The nodes of the AC electrical network and how they are wired together.
+/// The TRs are converters which end the AC network, while the static inverter
+/// is supplied to it as a source. Their other side is part of the DC network.
+struct A320AlternatingCurrentNodes {
+    engine_gen_1: NodeId,
+    engine_gen_2: NodeId,
+    apu_gen: NodeId,
+    ext_pwr: NodeId,
+    emergency_gen: NodeId,
+    static_inverter: NodeId,
+    engine_gen_1_contactor: NodeId,
+    engine_gen_2_contactor: NodeId,
+    bus_tie_1_contactor: NodeId,
+    bus_tie_2_contactor: NodeId,
+    apu_gen_contactor: NodeId,
+    ext_pwr_contactor: NodeId,
+    ac_bus_1: NodeId,
+    ac_bus_2: NodeId,
+    ac_ess_feed_contactor_1: NodeId,
+    ac_ess_feed_contactor_2: NodeId,
+    ac_ess_bus: NodeId,
+    ac_ess_shed_contactor: NodeId,
+    ac_ess_shed_bus: NodeId,
+    tr_1: NodeId,
+    tr_2: NodeId,
+    ac_bus_2_to_tr_2_contactor: NodeId,
+    tr_ess: NodeId,
+    ac_ess_to_tr_ess_contactor: NodeId,
+    emergency_gen_contactor: NodeId,
+    static_inv_to_ac_ess_bus_contactor: NodeId,
+    ac_stat_inv_bus: NodeId,
+    ac_gnd_flt_service_bus: NodeId,
+    ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor: NodeId,
+}
+impl A320AlternatingCurrentNodes {
+    fn new(network: &mut ElectricalNetwork) -> Self {
+        let transformer_rectifier_output = ElectricPotential::new::<volt>(28.);
+        let nodes = Self {
+            engine_gen_1: network.add_source(),
+            engine_gen_2: network.add_source(),
+            apu_gen: network.add_source(),
+            ext_pwr: network.add_source(),
+            emergency_gen: network.add_source(),
+            static_inverter: network.add_source(),
+            engine_gen_1_contactor: network.add_contactor(),
+            engine_gen_2_contactor: network.add_contactor(),
+            bus_tie_1_contactor: network.add_contactor(),
+            bus_tie_2_contactor: network.add_contactor(),
+            apu_gen_contactor: network.add_contactor(),
+            ext_pwr_contactor: network.add_contactor(),
+            ac_bus_1: network.add_bus(),
+            ac_bus_2: network.add_bus(),
+            ac_ess_feed_contactor_1: network.add_contactor(),
+            ac_ess_feed_contactor_2: network.add_contactor(),
+            ac_ess_bus: network.add_bus(),
+            ac_ess_shed_contactor: network.add_contactor(),
+            ac_ess_shed_bus: network.add_bus(),
+            tr_1: network.add_converter(
+                PotentialOrigin::TransformerRectifier(1),
+                transformer_rectifier_output,
+            ),
+            tr_2: network.add_converter(
+                PotentialOrigin::TransformerRectifier(2),
+                transformer_rectifier_output,
+            ),
+            ac_bus_2_to_tr_2_contactor: network.add_contactor(),
+            tr_ess: network.add_converter(
+                PotentialOrigin::TransformerRectifier(3),
+                transformer_rectifier_output,
+            ),
+            ac_ess_to_tr_ess_contactor: network.add_contactor(),
+            emergency_gen_contactor: network.add_contactor(),
+            static_inv_to_ac_ess_bus_contactor: network.add_contactor(),
+            ac_stat_inv_bus: network.add_bus(),
+            ac_gnd_flt_service_bus: network.add_bus(),
+            ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor: network.add_contactor(),
+        };
+
+        network.connect(nodes.engine_gen_1, nodes.engine_gen_1_contactor);
+        network.connect(nodes.engine_gen_1_contactor, nodes.ac_bus_1);
+        network.connect(nodes.engine_gen_1_contactor, nodes.bus_tie_1_contactor);
+        network.connect(nodes.bus_tie_1_contactor, nodes.ac_bus_1);
+        network.connect(nodes.engine_gen_2, nodes.engine_gen_2_contactor);
+        network.connect(nodes.engine_gen_2_contactor, nodes.ac_bus_2);
+        network.connect(nodes.engine_gen_2_contactor, nodes.bus_tie_2_contactor);
+        network.connect(nodes.bus_tie_2_contactor, nodes.ac_bus_2);
+        network.connect_both(nodes.bus_tie_1_contactor, nodes.bus_tie_2_contactor);
+
+        network.connect(nodes.apu_gen, nodes.apu_gen_contactor);
+        network.connect(nodes.apu_gen_contactor, nodes.bus_tie_1_contactor);
+        network.connect(nodes.apu_gen_contactor, nodes.bus_tie_2_contactor);
+        network.connect(nodes.ext_pwr, nodes.ext_pwr_contactor);
+        network.connect(nodes.ext_pwr_contactor, nodes.bus_tie_1_contactor);
+        network.connect(nodes.ext_pwr_contactor, nodes.bus_tie_2_contactor);
+
+        network.connect(nodes.ac_bus_1, nodes.ac_ess_feed_contactor_1);
+        network.connect(nodes.ac_ess_feed_contactor_1, nodes.ac_ess_bus);
+        network.connect(nodes.ac_bus_2, nodes.ac_ess_feed_contactor_2);
+        network.connect(nodes.ac_ess_feed_contactor_2, nodes.ac_ess_bus);
+        network.connect(nodes.ac_ess_bus, nodes.ac_ess_shed_contactor);
+        network.connect(nodes.ac_ess_shed_contactor, nodes.ac_ess_shed_bus);
+
+        network.connect(nodes.ac_bus_1, nodes.tr_1);
+        network.connect(nodes.ac_bus_2, nodes.ac_bus_2_to_tr_2_contactor);
+        network.connect(nodes.ac_bus_2_to_tr_2_contactor, nodes.tr_2);
+        network.connect(
+            nodes.ac_bus_2_to_tr_2_contactor,
+            nodes.ac_gnd_flt_service_bus,
+        );
+        network.connect(
+            nodes.ext_pwr,
+            nodes.ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor,
+        );
+        network.connect(
+            nodes.ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor,
+            nodes.tr_2,
+        );
+        network.connect(
+            nodes.ext_pwr_to_ac_gnd_flt_service_bus_and_tr_2_contactor,
+            nodes.ac_gnd_flt_service_bus,
+        );
+
+        network.connect(nodes.emergency_gen, nodes.emergency_gen_contactor);
+        network.connect(
+            nodes.emergency_gen_contactor,
+            nodes.ac_ess_to_tr_ess_contactor,
+        );
+        network.connect(nodes.emergency_gen_contactor, nodes.tr_ess);
+        network.connect_both(nodes.ac_ess_bus, nodes.ac_ess_to_tr_ess_contactor);
+        network.connect(nodes.ac_ess_to_tr_ess_contactor, nodes.tr_ess);
+
+        network.connect(nodes.static_inverter, nodes.ac_stat_inv_bus);
+        network.connect(
+            nodes.static_inverter,
+            nodes.static_inv_to_ac_ess_bus_contactor,
+        );
+        network.connect(nodes.static_inv_to_ac_ess_bus_contactor, nodes.ac_ess_bus);
+
+        nodes
+    }
+}
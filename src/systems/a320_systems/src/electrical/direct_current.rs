@@ -2,18 +2,22 @@ use super::{
     A320AlternatingCurrentElectricalSystem, A320DirectCurrentElectricalSystem,
     A320ElectricalOverheadPanel,
 };
-#[cfg(test)]
-use systems::electrical::Potential;
 use systems::{
     electrical::{
-        consumption::SuppliedPower, Battery, BatteryChargeLimiter, Contactor, ElectricalBus,
-        ElectricalBusType, EmergencyElectrical, EmergencyGenerator, PotentialSource,
-        PotentialTarget, StaticInverter,
+        consumption::SuppliedPower, Battery, BatteryChargeLimiter, Contactor,
+        ConverterCharacteristics, ElectricalBus, ElectricalBusType, ElectricalNetwork,
+        EmergencyElectrical, EmergencyGenerator, NodeId, Potential, PotentialOrigin,
+        PotentialSource, PotentialTarget, SourceResistance, StaticInverter,
     },
     shared::{ApuMaster, ApuStart, AuxiliaryPowerUnitElectrical, LandingGearPosition},
-    simulation::{SimulationElement, SimulationElementVisitor, UpdateContext},
+    simulation::{
+        InvariantChecker, SimulationElement, SimulationElementVisitor, UpdateContext,
+        VariableRegistry,
+    },
+};
+use uom::si::{
+    electric_potential::volt, electrical_resistance::ohm, f64::*, power::watt, velocity::knot,
 };
-use uom::si::{f64::*, velocity::knot};
 
 pub(super) struct A320DirectCurrentElectrical {
     dc_bus_1: ElectricalBus,
@@ -43,42 +47,81 @@ pub(super) struct A320DirectCurrentElectrical {
     dc_gnd_flt_service_bus: ElectricalBus,
     tr_2_to_dc_gnd_flt_service_bus_contactor: Contactor,
     dc_bus_2_to_dc_gnd_flt_service_bus_contactor: Contactor,
+    network: ElectricalNetwork,
+    nodes: A320DirectCurrentNodes,
 }
 impl A320DirectCurrentElectrical {
-    pub fn new() -> Self {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
+        let mut network = ElectricalNetwork::new();
+        let nodes = A320DirectCurrentNodes::new(&mut network);
+
         A320DirectCurrentElectrical {
-            dc_bus_1: ElectricalBus::new(ElectricalBusType::DirectCurrent(1)),
-            dc_bus_1_tie_contactor: Contactor::new("1PC1"),
-            dc_bus_2: ElectricalBus::new(ElectricalBusType::DirectCurrent(2)),
-            dc_bus_2_tie_contactor: Contactor::new("1PC2"),
-            dc_bat_bus: ElectricalBus::new(ElectricalBusType::DirectCurrentBattery),
-            dc_ess_bus: ElectricalBus::new(ElectricalBusType::DirectCurrentEssential),
-            dc_bat_bus_to_dc_ess_bus_contactor: Contactor::new("4PC"),
-            dc_ess_shed_bus: ElectricalBus::new(ElectricalBusType::DirectCurrentEssentialShed),
-            dc_ess_shed_contactor: Contactor::new("8PH"),
-            battery_1: Battery::full(1),
-            battery_1_contactor: Contactor::new("6PB1"),
-            battery_1_charge_limiter: BatteryChargeLimiter::new(1, "6PB1"),
-            battery_2: Battery::full(2),
-            battery_2_contactor: Contactor::new("6PB2"),
-            battery_2_charge_limiter: BatteryChargeLimiter::new(2, "6PB2"),
-            hot_bus_2_to_dc_ess_bus_contactor: Contactor::new("2XB2"),
-            hot_bus_1_to_static_inv_contactor: Contactor::new("2XB1"),
-            static_inverter: StaticInverter::new(),
-            hot_bus_1: ElectricalBus::new(ElectricalBusType::DirectCurrentHot(1)),
-            hot_bus_2: ElectricalBus::new(ElectricalBusType::DirectCurrentHot(2)),
-            tr_1_contactor: Contactor::new("5PU1"),
-            tr_2_contactor: Contactor::new("5PU2"),
-            tr_ess_contactor: Contactor::new("3PE"),
-            apu_start_contactors: Contactor::new("10KA_AND_5KA"),
+            dc_bus_1: ElectricalBus::new(registry, ElectricalBusType::DirectCurrent(1)),
+            dc_bus_1_tie_contactor: Contactor::new(registry, "1PC1"),
+            dc_bus_2: ElectricalBus::new(registry, ElectricalBusType::DirectCurrent(2)),
+            dc_bus_2_tie_contactor: Contactor::new(registry, "1PC2"),
+            dc_bat_bus: ElectricalBus::new(registry, ElectricalBusType::DirectCurrentBattery),
+            dc_ess_bus: ElectricalBus::new(registry, ElectricalBusType::DirectCurrentEssential),
+            dc_bat_bus_to_dc_ess_bus_contactor: Contactor::new(registry, "4PC"),
+            dc_ess_shed_bus: ElectricalBus::new(
+                registry,
+                ElectricalBusType::DirectCurrentEssentialShed,
+            ),
+            dc_ess_shed_contactor: Contactor::new(registry, "8PH"),
+            battery_1: Battery::full(registry, 1).with_resistance(Self::battery_resistance()),
+            battery_1_contactor: Contactor::new(registry, "6PB1"),
+            battery_1_charge_limiter: BatteryChargeLimiter::new(registry, 1, "6PB1"),
+            battery_2: Battery::full(registry, 2).with_resistance(Self::battery_resistance()),
+            battery_2_contactor: Contactor::new(registry, "6PB2"),
+            battery_2_charge_limiter: BatteryChargeLimiter::new(registry, 2, "6PB2"),
+            hot_bus_2_to_dc_ess_bus_contactor: Contactor::new(registry, "2XB2"),
+            hot_bus_1_to_static_inv_contactor: Contactor::new(registry, "2XB1"),
+            static_inverter: StaticInverter::new(registry)
+                .with_characteristics(Self::static_inverter_characteristics())
+                .with_resistance(Self::static_inverter_resistance()),
+            hot_bus_1: ElectricalBus::new(registry, ElectricalBusType::DirectCurrentHot(1)),
+            hot_bus_2: ElectricalBus::new(registry, ElectricalBusType::DirectCurrentHot(2)),
+            tr_1_contactor: Contactor::new(registry, "5PU1"),
+            tr_2_contactor: Contactor::new(registry, "5PU2"),
+            tr_ess_contactor: Contactor::new(registry, "3PE"),
+            apu_start_contactors: Contactor::new(registry, "10KA_AND_5KA"),
             dc_gnd_flt_service_bus: ElectricalBus::new(
+                registry,
                 ElectricalBusType::DirectCurrentGndFltService,
             ),
-            tr_2_to_dc_gnd_flt_service_bus_contactor: Contactor::new("3PX"),
-            dc_bus_2_to_dc_gnd_flt_service_bus_contactor: Contactor::new("8PN"),
+            tr_2_to_dc_gnd_flt_service_bus_contactor: Contactor::new(registry, "3PX"),
+            dc_bus_2_to_dc_gnd_flt_service_bus_contactor: Contactor::new(registry, "8PN"),
+            network,
+            nodes,
         }
     }
 
+    /// The A320's static inverter is rated at 1000VA and regulates its output at 115V.
+    /// Its efficiency drops significantly at low load.
+    fn static_inverter_characteristics() -> ConverterCharacteristics {
+        ConverterCharacteristics::ideal(
+            Power::new::<watt>(1000.),
+            ElectricPotential::new::<volt>(115.),
+        )
+        .with_efficiency(&[0.1, 0.25, 0.5, 0.75, 1.], &[0.55, 0.7, 0.79, 0.82, 0.82])
+    }
+
+    fn static_inverter_resistance() -> SourceResistance {
+        SourceResistance::new(
+            ElectricalResistance::new::<ohm>(0.2),
+            ElectricalResistance::new::<ohm>(0.1),
+        )
+    }
+
+    /// The batteries' internal resistance is 0.011 ohm. Their feeders towards
+    /// the battery bus add to that, which limits the charging current.
+    fn battery_resistance() -> SourceResistance {
+        SourceResistance::new(
+            ElectricalResistance::new::<ohm>(0.011),
+            ElectricalResistance::new::<ohm>(0.02),
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn update(
         &mut self,
@@ -92,61 +135,34 @@ impl A320DirectCurrentElectrical {
         landing_gear: &impl LandingGearPosition,
     ) {
         self.tr_1_contactor.close_when(ac_state.tr_1().is_powered());
-        self.tr_1_contactor.powered_by(ac_state.tr_1());
-
         self.tr_2_contactor
             .close_when(ac_state.tr_2().is_powered() && ac_state.ac_bus_2_powered());
-        self.tr_2_contactor.powered_by(ac_state.tr_2());
-
         self.tr_2_to_dc_gnd_flt_service_bus_contactor
             .close_when(ac_state.tr_2().is_powered() && !ac_state.ac_bus_2_powered());
-        self.tr_2_to_dc_gnd_flt_service_bus_contactor
-            .powered_by(ac_state.tr_2());
-
         self.tr_ess_contactor
             .close_when(!ac_state.tr_1_and_2_available() && ac_state.tr_ess().is_powered());
-        self.tr_ess_contactor.powered_by(ac_state.tr_ess());
-
-        self.dc_bus_1.powered_by(&self.tr_1_contactor);
-        self.dc_bus_2.powered_by(&self.tr_2_contactor);
-
         self.dc_bus_2_to_dc_gnd_flt_service_bus_contactor
             .close_when(ac_state.tr_2().is_powered() && ac_state.ac_bus_2_powered());
-        self.dc_bus_2_to_dc_gnd_flt_service_bus_contactor
-            .powered_by(&self.dc_bus_2);
-
-        self.dc_gnd_flt_service_bus
-            .powered_by(&self.tr_2_to_dc_gnd_flt_service_bus_contactor);
-        self.dc_gnd_flt_service_bus
-            .or_powered_by(&self.dc_bus_2_to_dc_gnd_flt_service_bus_contactor);
-
-        self.dc_bus_1_tie_contactor.powered_by(&self.dc_bus_1);
-        self.dc_bus_2_tie_contactor.powered_by(&self.dc_bus_2);
 
+        // The TR contactors only close when their TR is powered.
+        let dc_bus_1_powered_by_tr = self.tr_1_contactor.is_closed();
+        let dc_bus_2_powered_by_tr = self.tr_2_contactor.is_closed();
         self.dc_bus_1_tie_contactor
-            .close_when(self.dc_bus_1.is_powered() || self.dc_bus_2.is_powered());
-        self.dc_bus_2_tie_contactor.close_when(
-            (!self.dc_bus_1.is_powered() && self.dc_bus_2.is_powered())
-                || (!self.dc_bus_2.is_powered() && self.dc_bus_1.is_powered()),
-        );
-
-        self.dc_bat_bus.powered_by(&self.dc_bus_1_tie_contactor);
-        self.dc_bat_bus.or_powered_by(&self.dc_bus_2_tie_contactor);
+            .close_when(dc_bus_1_powered_by_tr || dc_bus_2_powered_by_tr);
+        self.dc_bus_2_tie_contactor
+            .close_when(dc_bus_1_powered_by_tr ^ dc_bus_2_powered_by_tr);
 
-        self.dc_bus_1_tie_contactor.or_powered_by(&self.dc_bat_bus);
-        self.dc_bus_2_tie_contactor.or_powered_by(&self.dc_bat_bus);
-        self.dc_bus_1.or_powered_by(&self.dc_bus_1_tie_contactor);
-        self.dc_bus_2.or_powered_by(&self.dc_bus_2_tie_contactor);
-
-        self.battery_1_contactor.powered_by(&self.dc_bat_bus);
-        self.battery_2_contactor.powered_by(&self.dc_bat_bus);
+        // The battery charge limiters look at the potential supplied to the
+        // battery bus by the DC buses, thus excluding the batteries.
+        self.propagate(ac_state, false);
+        let battery_bus_potential = self.network.potential(self.nodes.dc_bat_bus);
 
         self.battery_1_charge_limiter.update(
             context,
             emergency_elec,
             emergency_generator,
             &self.battery_1,
-            &self.dc_bat_bus,
+            &battery_bus_potential,
             landing_gear,
             overhead,
             apu,
@@ -161,7 +177,7 @@ impl A320DirectCurrentElectrical {
             emergency_elec,
             emergency_generator,
             &self.battery_2,
-            &self.dc_bat_bus,
+            &battery_bus_potential,
             landing_gear,
             overhead,
             apu,
@@ -171,65 +187,150 @@ impl A320DirectCurrentElectrical {
         self.battery_2_contactor
             .close_when(self.battery_2_charge_limiter.should_close_contactor());
 
-        self.battery_1.powered_by(&self.battery_1_contactor);
-        self.battery_2.powered_by(&self.battery_2_contactor);
-
-        self.battery_1_contactor.or_powered_by(&self.battery_1);
-        self.battery_2_contactor.or_powered_by(&self.battery_2);
-
-        self.dc_bat_bus
-            .or_powered_by_both_batteries(&self.battery_1_contactor, &self.battery_2_contactor);
-
-        // Power the contactors and batteries again, for situations
-        // where BAT 1 powers BAT 2 or vice versa.
-        self.battery_1_contactor.or_powered_by(&self.dc_bat_bus);
-        self.battery_2_contactor.or_powered_by(&self.dc_bat_bus);
-        self.battery_1.or_powered_by(&self.battery_1_contactor);
-        self.battery_2.or_powered_by(&self.battery_2_contactor);
-
-        self.hot_bus_1.powered_by(&self.battery_1_contactor);
-        self.hot_bus_1.or_powered_by(&self.battery_1);
-        self.hot_bus_2.powered_by(&self.battery_2_contactor);
-        self.hot_bus_2.or_powered_by(&self.battery_2);
-
-        self.apu_start_contactors.powered_by(&self.dc_bat_bus);
         self.apu_start_contactors.close_when(
             self.battery_1_contactor.is_closed()
                 && self.battery_2_contactor.is_closed()
                 && apu.should_close_start_contactors(),
         );
 
-        apu.start_motor_powered_by(self.apu_start_contactors.output());
-
         let should_close_2xb_contactor =
             self.should_close_2xb_contactors(context, emergency_generator, ac_state);
         self.hot_bus_1_to_static_inv_contactor
             .close_when(should_close_2xb_contactor);
-        self.hot_bus_1_to_static_inv_contactor
-            .powered_by(&self.hot_bus_1);
-        self.static_inverter
-            .powered_by(&self.hot_bus_1_to_static_inv_contactor);
-
         self.hot_bus_2_to_dc_ess_bus_contactor
             .close_when(should_close_2xb_contactor);
-        self.hot_bus_2_to_dc_ess_bus_contactor
-            .powered_by(&self.hot_bus_2);
-
-        self.dc_bat_bus_to_dc_ess_bus_contactor
-            .powered_by(&self.dc_bat_bus);
         self.dc_bat_bus_to_dc_ess_bus_contactor
             .close_when(ac_state.tr_1_and_2_available());
-
-        self.dc_ess_bus
-            .powered_by(&self.dc_bat_bus_to_dc_ess_bus_contactor);
-        self.dc_ess_bus.or_powered_by(&self.tr_ess_contactor);
-        self.dc_ess_bus
-            .or_powered_by(&self.hot_bus_2_to_dc_ess_bus_contactor);
-
-        self.dc_ess_shed_contactor.powered_by(&self.dc_ess_bus);
         self.dc_ess_shed_contactor
             .close_when(self.hot_bus_2_to_dc_ess_bus_contactor.is_open());
-        self.dc_ess_shed_bus.powered_by(&self.dc_ess_shed_contactor);
+
+        self.propagate(ac_state, true);
+        self.power_elements();
+
+        apu.start_motor_powered_by(self.apu_start_contactors.output());
+        self.static_inverter
+            .powered_by(&self.network.potential(self.nodes.static_inverter));
+    }
+
+    fn propagate(
+        &mut self,
+        ac_state: &impl A320AlternatingCurrentElectricalSystem,
+        include_batteries: bool,
+    ) {
+        let nodes = &self.nodes;
+        let network = &mut self.network;
+
+        network.supply(nodes.tr_1, ac_state.tr_1());
+        network.supply(nodes.tr_2, ac_state.tr_2());
+        network.supply(nodes.tr_ess, ac_state.tr_ess());
+        if include_batteries {
+            network.supply(nodes.battery_1, &self.battery_1);
+            network.supply(nodes.battery_2, &self.battery_2);
+        } else {
+            network.supply(nodes.battery_1, &Potential::none());
+            network.supply(nodes.battery_2, &Potential::none());
+        }
+
+        self.for_each_contactor(|network, node, contactor| {
+            network.set_closed(node, contactor.is_closed())
+        });
+        self.network.propagate();
+    }
+
+    fn power_elements(&mut self) {
+        self.for_each_contactor(|network, node, contactor| {
+            contactor.powered_by(&network.potential(node))
+        });
+
+        let nodes = &self.nodes;
+        let network = &self.network;
+        for (node, bus) in [
+            (nodes.dc_bus_1, &mut self.dc_bus_1),
+            (nodes.dc_bus_2, &mut self.dc_bus_2),
+            (nodes.dc_bat_bus, &mut self.dc_bat_bus),
+            (nodes.dc_ess_bus, &mut self.dc_ess_bus),
+            (nodes.dc_ess_shed_bus, &mut self.dc_ess_shed_bus),
+            (nodes.hot_bus_1, &mut self.hot_bus_1),
+            (nodes.hot_bus_2, &mut self.hot_bus_2),
+            (
+                nodes.dc_gnd_flt_service_bus,
+                &mut self.dc_gnd_flt_service_bus,
+            ),
+        ] {
+            bus.powered_by(&network.potential(node));
+        }
+
+        self.battery_1
+            .powered_by(&network.potential(nodes.battery_1));
+        self.battery_2
+            .powered_by(&network.potential(nodes.battery_2));
+    }
+
+    fn for_each_contactor(
+        &mut self,
+        mut func: impl FnMut(&mut ElectricalNetwork, NodeId, &mut Contactor),
+    ) {
+        let nodes = &self.nodes;
+        let network = &mut self.network;
+
+        func(network, nodes.tr_1_contactor, &mut self.tr_1_contactor);
+        func(network, nodes.tr_2_contactor, &mut self.tr_2_contactor);
+        func(network, nodes.tr_ess_contactor, &mut self.tr_ess_contactor);
+        func(
+            network,
+            nodes.dc_bus_1_tie_contactor,
+            &mut self.dc_bus_1_tie_contactor,
+        );
+        func(
+            network,
+            nodes.dc_bus_2_tie_contactor,
+            &mut self.dc_bus_2_tie_contactor,
+        );
+        func(
+            network,
+            nodes.dc_bat_bus_to_dc_ess_bus_contactor,
+            &mut self.dc_bat_bus_to_dc_ess_bus_contactor,
+        );
+        func(
+            network,
+            nodes.dc_ess_shed_contactor,
+            &mut self.dc_ess_shed_contactor,
+        );
+        func(
+            network,
+            nodes.battery_1_contactor,
+            &mut self.battery_1_contactor,
+        );
+        func(
+            network,
+            nodes.battery_2_contactor,
+            &mut self.battery_2_contactor,
+        );
+        func(
+            network,
+            nodes.hot_bus_2_to_dc_ess_bus_contactor,
+            &mut self.hot_bus_2_to_dc_ess_bus_contactor,
+        );
+        func(
+            network,
+            nodes.hot_bus_1_to_static_inv_contactor,
+            &mut self.hot_bus_1_to_static_inv_contactor,
+        );
+        func(
+            network,
+            nodes.apu_start_contactors,
+            &mut self.apu_start_contactors,
+        );
+        func(
+            network,
+            nodes.tr_2_to_dc_gnd_flt_service_bus_contactor,
+            &mut self.tr_2_to_dc_gnd_flt_service_bus_contactor,
+        );
+        func(
+            network,
+            nodes.dc_bus_2_to_dc_gnd_flt_service_bus_contactor,
+            &mut self.dc_bus_2_to_dc_gnd_flt_service_bus_contactor,
+        );
     }
 
     /// Determines if the 2XB contactors should be closed. 2XB are the two contactors
@@ -251,14 +352,6 @@ impl A320DirectCurrentElectrical {
         self.battery_1_contactor.is_closed() && self.battery_2_contactor.is_closed()
     }
 
-    pub fn debug_assert_invariants(&self) {
-        debug_assert!(self.battery_never_powers_dc_ess_shed());
-        debug_assert!(self.max_one_source_powers_dc_ess_bus());
-        debug_assert!(
-            self.batteries_power_both_static_inv_and_dc_ess_bus_at_the_same_time_or_not_at_all()
-        );
-    }
-
     fn battery_never_powers_dc_ess_shed(&self) -> bool {
         !(self.hot_bus_2_to_dc_ess_bus_contactor.is_closed()
             && self.dc_ess_shed_contactor.is_closed())
@@ -291,13 +384,13 @@ impl A320DirectCurrentElectrical {
     }
 
     #[cfg(test)]
-    pub fn empty_battery_1(&mut self) {
-        self.battery_1 = Battery::empty(1);
+    pub fn empty_battery_1(&mut self, registry: &mut VariableRegistry) {
+        self.battery_1 = Battery::empty(registry, 1).with_resistance(Self::battery_resistance());
     }
 
     #[cfg(test)]
-    pub fn empty_battery_2(&mut self) {
-        self.battery_2 = Battery::empty(2);
+    pub fn empty_battery_2(&mut self, registry: &mut VariableRegistry) {
+        self.battery_2 = Battery::empty(registry, 2).with_resistance(Self::battery_resistance());
     }
 
     pub fn add_supplied_power(&self, state: &mut SuppliedPower) {
@@ -354,4 +447,143 @@ impl SimulationElement for A320DirectCurrentElectrical {
 
         visitor.visit(self);
     }
+
+    fn check_invariants(&self, invariants: &mut InvariantChecker) {
+        invariants.check(
+            "battery never powers DC ESS SHED",
+            self.battery_never_powers_dc_ess_shed(),
+        );
+        invariants.check(
+            "max one source powers DC ESS bus",
+            self.max_one_source_powers_dc_ess_bus(),
+        );
+        invariants.check(
+            "batteries power both static inverter and DC ESS bus at the same time or not at all",
+            self.batteries_power_both_static_inv_and_dc_ess_bus_at_the_same_time_or_not_at_all(),
+        );
+    }
+}
+
+/// The nodes of the DC electrical network and how they are wired together.
+/// The TRs are supplied to it as sources, while the static inverter is a converter
+/// which ends the DC network. Their other side is part of the AC network.
+struct A320DirectCurrentNodes {
+    tr_1: NodeId,
+    tr_2: NodeId,
+    tr_ess: NodeId,
+    tr_1_contactor: NodeId,
+    tr_2_contactor: NodeId,
+    tr_ess_contactor: NodeId,
+    dc_bus_1: NodeId,
+    dc_bus_2: NodeId,
+    dc_bus_1_tie_contactor: NodeId,
+    dc_bus_2_tie_contactor: NodeId,
+    dc_bat_bus: NodeId,
+    dc_ess_bus: NodeId,
+    dc_bat_bus_to_dc_ess_bus_contactor: NodeId,
+    dc_ess_shed_bus: NodeId,
+    dc_ess_shed_contactor: NodeId,
+    battery_1: NodeId,
+    battery_1_contactor: NodeId,
+    battery_2: NodeId,
+    battery_2_contactor: NodeId,
+    hot_bus_1: NodeId,
+    hot_bus_2: NodeId,
+    hot_bus_1_to_static_inv_contactor: NodeId,
+    static_inverter: NodeId,
+    hot_bus_2_to_dc_ess_bus_contactor: NodeId,
+    apu_start_contactors: NodeId,
+    dc_gnd_flt_service_bus: NodeId,
+    tr_2_to_dc_gnd_flt_service_bus_contactor: NodeId,
+    dc_bus_2_to_dc_gnd_flt_service_bus_contactor: NodeId,
+}
+impl A320DirectCurrentNodes {
+    fn new(network: &mut ElectricalNetwork) -> Self {
+        let nodes = Self {
+            tr_1: network.add_source(),
+            tr_2: network.add_source(),
+            tr_ess: network.add_source(),
+            tr_1_contactor: network.add_contactor(),
+            tr_2_contactor: network.add_contactor(),
+            tr_ess_contactor: network.add_contactor(),
+            dc_bus_1: network.add_bus(),
+            dc_bus_2: network.add_bus(),
+            dc_bus_1_tie_contactor: network.add_contactor(),
+            dc_bus_2_tie_contactor: network.add_contactor(),
+            dc_bat_bus: network.add_bus(),
+            dc_ess_bus: network.add_bus(),
+            dc_bat_bus_to_dc_ess_bus_contactor: network.add_contactor(),
+            dc_ess_shed_bus: network.add_bus(),
+            dc_ess_shed_contactor: network.add_contactor(),
+            battery_1: network.add_source(),
+            battery_1_contactor: network.add_contactor(),
+            battery_2: network.add_source(),
+            battery_2_contactor: network.add_contactor(),
+            hot_bus_1: network.add_bus(),
+            hot_bus_2: network.add_bus(),
+            hot_bus_1_to_static_inv_contactor: network.add_contactor(),
+            static_inverter: network.add_converter(
+                PotentialOrigin::StaticInverter,
+                ElectricPotential::new::<volt>(115.),
+            ),
+            hot_bus_2_to_dc_ess_bus_contactor: network.add_contactor(),
+            apu_start_contactors: network.add_contactor(),
+            dc_gnd_flt_service_bus: network.add_bus(),
+            tr_2_to_dc_gnd_flt_service_bus_contactor: network.add_contactor(),
+            dc_bus_2_to_dc_gnd_flt_service_bus_contactor: network.add_contactor(),
+        };
+
+        network.connect(nodes.tr_1, nodes.tr_1_contactor);
+        network.connect(nodes.tr_1_contactor, nodes.dc_bus_1);
+        network.connect(nodes.tr_2, nodes.tr_2_contactor);
+        network.connect(nodes.tr_2_contactor, nodes.dc_bus_2);
+        network.connect(nodes.tr_ess, nodes.tr_ess_contactor);
+        network.connect(nodes.tr_ess_contactor, nodes.dc_ess_bus);
+
+        network.connect(nodes.tr_2, nodes.tr_2_to_dc_gnd_flt_service_bus_contactor);
+        network.connect(
+            nodes.tr_2_to_dc_gnd_flt_service_bus_contactor,
+            nodes.dc_gnd_flt_service_bus,
+        );
+        network.connect(
+            nodes.dc_bus_2,
+            nodes.dc_bus_2_to_dc_gnd_flt_service_bus_contactor,
+        );
+        network.connect(
+            nodes.dc_bus_2_to_dc_gnd_flt_service_bus_contactor,
+            nodes.dc_gnd_flt_service_bus,
+        );
+
+        network.connect_both(nodes.dc_bus_1, nodes.dc_bus_1_tie_contactor);
+        network.connect_both(nodes.dc_bus_1_tie_contactor, nodes.dc_bat_bus);
+        network.connect_both(nodes.dc_bus_2, nodes.dc_bus_2_tie_contactor);
+        network.connect_both(nodes.dc_bus_2_tie_contactor, nodes.dc_bat_bus);
+
+        network.connect_both(nodes.battery_1, nodes.battery_1_contactor);
+        network.connect_both(nodes.battery_1_contactor, nodes.dc_bat_bus);
+        network.connect_both(nodes.battery_2, nodes.battery_2_contactor);
+        network.connect_both(nodes.battery_2_contactor, nodes.dc_bat_bus);
+
+        network.connect(nodes.battery_1, nodes.hot_bus_1);
+        network.connect(nodes.battery_1_contactor, nodes.hot_bus_1);
+        network.connect(nodes.battery_2, nodes.hot_bus_2);
+        network.connect(nodes.battery_2_contactor, nodes.hot_bus_2);
+
+        network.connect(nodes.dc_bat_bus, nodes.apu_start_contactors);
+
+        network.connect(nodes.hot_bus_1, nodes.hot_bus_1_to_static_inv_contactor);
+        network.connect(
+            nodes.hot_bus_1_to_static_inv_contactor,
+            nodes.static_inverter,
+        );
+        network.connect(nodes.hot_bus_2, nodes.hot_bus_2_to_dc_ess_bus_contactor);
+        network.connect(nodes.hot_bus_2_to_dc_ess_bus_contactor, nodes.dc_ess_bus);
+        network.connect(nodes.dc_bat_bus, nodes.dc_bat_bus_to_dc_ess_bus_contactor);
+        network.connect(nodes.dc_bat_bus_to_dc_ess_bus_contactor, nodes.dc_ess_bus);
+
+        network.connect(nodes.dc_ess_bus, nodes.dc_ess_shed_contactor);
+        network.connect(nodes.dc_ess_shed_contactor, nodes.dc_ess_shed_bus);
+
+        nodes
+    }
 }
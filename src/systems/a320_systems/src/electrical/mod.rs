@@ -13,7 +13,7 @@ use systems::{
     electrical::{
         consumption::SuppliedPower, AlternatingCurrentElectricalSystem, BatteryPushButtons,
         ElectricalSystem, EmergencyElectrical, EmergencyGenerator, EngineGeneratorPushButtons,
-        ExternalPowerSource, StaticInverter, TransformerRectifier,
+        ExternalPowerSource, SourceResistance, StaticInverter, TransformerRectifier,
     },
     overhead::{
         AutoOffFaultPushButton, FaultIndication, FaultReleasePushButton, MomentaryPushButton,
@@ -23,10 +23,15 @@ use systems::{
         ApuMaster, ApuStart, AuxiliaryPowerUnitElectrical, EngineCorrectedN2,
         EngineFirePushButtons, LandingGearPosition, RamAirTurbineHydraulicLoopPressurised,
     },
-    simulation::{SimulationElement, SimulationElementVisitor, SimulatorWriter, UpdateContext},
+    simulation::{
+        SimulationElement, SimulationElementVisitor, SimulatorWriter, UpdateContext,
+        VariableIdentifier, VariableRegistry,
+    },
 };
+use uom::si::{electrical_resistance::ohm, f64::*};
 
 pub(super) struct A320Electrical {
+    galley_is_shed_id: VariableIdentifier,
     alternating_current: A320AlternatingCurrentElectrical,
     direct_current: A320DirectCurrentElectrical,
     main_galley: MainGalley,
@@ -35,17 +40,37 @@ pub(super) struct A320Electrical {
     emergency_gen: EmergencyGenerator,
 }
 impl A320Electrical {
-    pub fn new() -> A320Electrical {
+    pub fn new(registry: &mut VariableRegistry) -> A320Electrical {
         A320Electrical {
-            alternating_current: A320AlternatingCurrentElectrical::new(),
-            direct_current: A320DirectCurrentElectrical::new(),
+            galley_is_shed_id: registry.get("ELEC_GALLEY_IS_SHED"),
+            alternating_current: A320AlternatingCurrentElectrical::new(registry),
+            direct_current: A320DirectCurrentElectrical::new(registry),
             main_galley: MainGalley::new(),
             secondary_galley: SecondaryGalley::new(),
             emergency_elec: EmergencyElectrical::new(),
-            emergency_gen: EmergencyGenerator::new(),
+            emergency_gen: EmergencyGenerator::new(registry)
+                .with_resistance(Self::emergency_generator_resistance()),
         }
     }
 
+    /// The resistance of the 90kVA engine and APU generators and their feeders
+    /// towards the AC buses.
+    pub fn generator_resistance() -> SourceResistance {
+        SourceResistance::new(
+            ElectricalResistance::new::<ohm>(0.004),
+            ElectricalResistance::new::<ohm>(0.003),
+        )
+    }
+
+    /// The 5kVA emergency generator has a much higher resistance than the
+    /// main generators.
+    fn emergency_generator_resistance() -> SourceResistance {
+        SourceResistance::new(
+            ElectricalResistance::new::<ohm>(0.05),
+            ElectricalResistance::new::<ohm>(0.02),
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn update(
         &mut self,
@@ -103,8 +128,6 @@ impl A320Electrical {
             .update(context, &self.alternating_current, overhead);
         self.secondary_galley
             .update(&self.alternating_current, overhead);
-
-        self.debug_assert_invariants();
     }
 
     fn emergency_generator_contactor_is_closed(&self) -> bool {
@@ -120,21 +143,6 @@ impl A320Electrical {
         self.main_galley.is_shed() || self.secondary_galley.is_shed()
     }
 
-    fn debug_assert_invariants(&self) {
-        self.alternating_current.debug_assert_invariants();
-        self.direct_current.debug_assert_invariants();
-    }
-
-    #[cfg(test)]
-    fn fail_tr_1(&mut self) {
-        self.alternating_current.fail_tr_1();
-    }
-
-    #[cfg(test)]
-    fn fail_tr_2(&mut self) {
-        self.alternating_current.fail_tr_2();
-    }
-
     #[cfg(test)]
     fn attempt_emergency_gen_start(&mut self) {
         self.emergency_gen.start();
@@ -166,19 +174,27 @@ impl A320Electrical {
     }
 
     #[cfg(test)]
-    pub fn empty_battery_1(&mut self) {
-        self.direct_current.empty_battery_1();
+    pub fn empty_battery_1(&mut self, registry: &mut VariableRegistry) {
+        self.direct_current.empty_battery_1(registry);
     }
 
     #[cfg(test)]
-    pub fn empty_battery_2(&mut self) {
-        self.direct_current.empty_battery_2();
+    pub fn empty_battery_2(&mut self, registry: &mut VariableRegistry) {
+        self.direct_current.empty_battery_2(registry);
     }
 
     pub fn gen_contactor_open(&self, number: usize) -> bool {
         self.alternating_current.gen_contactor_open(number)
     }
 
+    pub fn apu_gen_has_fault(&self) -> bool {
+        self.alternating_current.apu_gen_has_fault()
+    }
+
+    pub fn idg_has_fault(&self, number: usize) -> bool {
+        self.alternating_current.idg_has_fault(number)
+    }
+
     pub fn in_emergency_elec(&self) -> bool {
         self.emergency_elec.is_active()
     }
@@ -203,7 +219,7 @@ impl SimulationElement for A320Electrical {
     }
 
     fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_bool("ELEC_GALLEY_IS_SHED", self.galley_is_shed())
+        writer.write_bool(&self.galley_is_shed_id, self.galley_is_shed())
     }
 }
 
@@ -231,26 +247,26 @@ pub(super) struct A320ElectricalOverheadPanel {
     commercial: OnOffFaultPushButton,
 }
 impl A320ElectricalOverheadPanel {
-    pub fn new() -> A320ElectricalOverheadPanel {
+    pub fn new(registry: &mut VariableRegistry) -> A320ElectricalOverheadPanel {
         A320ElectricalOverheadPanel {
             batteries: [
-                AutoOffFaultPushButton::new_auto("ELEC_BAT_1"),
-                AutoOffFaultPushButton::new_auto("ELEC_BAT_2"),
+                AutoOffFaultPushButton::new_auto(registry, "ELEC_BAT_1"),
+                AutoOffFaultPushButton::new_auto(registry, "ELEC_BAT_2"),
             ],
             idgs: [
-                FaultReleasePushButton::new_in("ELEC_IDG_1"),
-                FaultReleasePushButton::new_in("ELEC_IDG_2"),
+                FaultReleasePushButton::new_in(registry, "ELEC_IDG_1"),
+                FaultReleasePushButton::new_in(registry, "ELEC_IDG_2"),
             ],
             generators: [
-                OnOffFaultPushButton::new_on("ELEC_ENG_GEN_1"),
-                OnOffFaultPushButton::new_on("ELEC_ENG_GEN_2"),
+                OnOffFaultPushButton::new_on(registry, "ELEC_ENG_GEN_1"),
+                OnOffFaultPushButton::new_on(registry, "ELEC_ENG_GEN_2"),
             ],
-            apu_gen: OnOffFaultPushButton::new_on("ELEC_APU_GEN"),
-            bus_tie: AutoOffFaultPushButton::new_auto("ELEC_BUS_TIE"),
-            ac_ess_feed: NormalAltnFaultPushButton::new_normal("ELEC_AC_ESS_FEED"),
-            galy_and_cab: AutoOffFaultPushButton::new_auto("ELEC_GALY_AND_CAB"),
-            ext_pwr: OnOffAvailablePushButton::new_off("ELEC_EXT_PWR"),
-            commercial: OnOffFaultPushButton::new_on("ELEC_COMMERCIAL"),
+            apu_gen: OnOffFaultPushButton::new_on(registry, "ELEC_APU_GEN"),
+            bus_tie: AutoOffFaultPushButton::new_auto(registry, "ELEC_BUS_TIE"),
+            ac_ess_feed: NormalAltnFaultPushButton::new_normal(registry, "ELEC_AC_ESS_FEED"),
+            galy_and_cab: AutoOffFaultPushButton::new_auto(registry, "ELEC_GALY_AND_CAB"),
+            ext_pwr: OnOffAvailablePushButton::new_off(registry, "ELEC_EXT_PWR"),
+            commercial: OnOffFaultPushButton::new_on(registry, "ELEC_COMMERCIAL"),
         }
     }
 
@@ -264,6 +280,12 @@ impl A320ElectricalOverheadPanel {
             .for_each(|(index, gen)| {
                 gen.set_fault(electrical.gen_contactor_open(index + 1) && gen.is_on());
             });
+
+        self.apu_gen.set_fault(electrical.apu_gen_has_fault());
+
+        self.idgs.iter_mut().enumerate().for_each(|(index, idg)| {
+            idg.set_fault(electrical.idg_has_fault(index + 1));
+        });
     }
 
     fn generator_is_on(&self, number: usize) -> bool {
@@ -345,11 +367,17 @@ pub(super) struct A320EmergencyElectricalOverheadPanel {
     rat_and_emer_gen_man_on: MomentaryPushButton,
 }
 impl A320EmergencyElectricalOverheadPanel {
-    pub fn new() -> Self {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
         Self {
-            gen_1_line: OnOffFaultPushButton::new_on("EMER_ELEC_GEN_1_LINE"),
-            rat_and_emergency_gen_fault: FaultIndication::new("EMER_ELEC_RAT_AND_EMER_GEN"),
-            rat_and_emer_gen_man_on: MomentaryPushButton::new("EMER_ELEC_RAT_AND_EMER_GEN"),
+            gen_1_line: OnOffFaultPushButton::new_on(registry, "EMER_ELEC_GEN_1_LINE"),
+            rat_and_emergency_gen_fault: FaultIndication::new(
+                registry,
+                "EMER_ELEC_RAT_AND_EMER_GEN",
+            ),
+            rat_and_emer_gen_man_on: MomentaryPushButton::new(
+                registry,
+                "EMER_ELEC_RAT_AND_EMER_GEN",
+            ),
         }
     }
 
@@ -390,8 +418,8 @@ mod a320_electrical {
 
     #[test]
     fn writes_its_state() {
-        let mut elec = A320Electrical::new();
         let mut test_bed = SimulationTestBed::new();
+        let mut elec = A320Electrical::new(test_bed.registry());
         test_bed.run_without_update(&mut elec);
 
         assert!(test_bed.contains_key("ELEC_GALLEY_IS_SHED"));
@@ -406,14 +434,15 @@ mod a320_electrical_circuit_tests {
     use std::time::Duration;
     use systems::{
         electrical::{
-            ElectricalBusType, ExternalPowerSource, Potential, PotentialOrigin, PotentialSource,
+            ControlledGenerator, ElectricalBusType, ExternalPowerSource, Potential,
+            PotentialOrigin, PotentialSource, ProvideFrequency, ProvideLoad, ProvidePotential,
             INTEGRATED_DRIVE_GENERATOR_STABILIZATION_TIME_IN_MILLISECONDS,
         },
+        failures::FailureType,
         shared::{ApuAvailable, ApuStartContactorsController},
         simulation::{test::SimulationTestBed, Aircraft},
     };
-    use uom::si::f64::*;
-    use uom::si::{electric_potential::volt, ratio::percent};
+    use uom::si::{electric_potential::volt, frequency::hertz, ratio::percent};
     use uom::si::{length::foot, velocity::knot};
 
     #[test]
@@ -1809,6 +1838,119 @@ mod a320_electrical_circuit_tests {
         assert!(!test_bed.gen_has_fault(gen_number));
     }
 
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    fn when_gen_feeder_fails_gen_is_disconnected_and_push_button_has_fault(
+        #[case] gen_number: usize,
+    ) {
+        let mut test_bed = test_bed_with()
+            .running_engines()
+            .and()
+            .failed(FailureType::GeneratorFeeder(
+                PotentialOrigin::EngineGenerator(gen_number),
+            ))
+            .run();
+
+        assert!(test_bed.gen_has_fault(gen_number));
+        assert!(test_bed
+            .ac_bus_output(gen_number as u8)
+            .is_single(PotentialOrigin::EngineGenerator(3 - gen_number)));
+    }
+
+    #[test]
+    fn gen_fault_remains_when_gen_feeder_failure_is_no_longer_active() {
+        let mut test_bed = test_bed_with()
+            .running_engines()
+            .and()
+            .failed(FailureType::GeneratorFeeder(
+                PotentialOrigin::EngineGenerator(1),
+            ))
+            .run()
+            .then_continue_with()
+            .repaired(FailureType::GeneratorFeeder(
+                PotentialOrigin::EngineGenerator(1),
+            ))
+            .run();
+
+        assert!(test_bed.gen_has_fault(1));
+        assert!(test_bed
+            .ac_bus_output(1)
+            .is_single(PotentialOrigin::EngineGenerator(2)));
+    }
+
+    #[test]
+    fn cycling_gen_push_button_resets_gen_fault() {
+        let mut test_bed = test_bed_with()
+            .running_engines()
+            .and()
+            .failed(FailureType::GeneratorFeeder(
+                PotentialOrigin::EngineGenerator(1),
+            ))
+            .run()
+            .then_continue_with()
+            .repaired(FailureType::GeneratorFeeder(
+                PotentialOrigin::EngineGenerator(1),
+            ))
+            .gen_off(1)
+            .run()
+            .then_continue_with()
+            .gen_on(1)
+            .run_waiting_for(Duration::from_millis(
+                INTEGRATED_DRIVE_GENERATOR_STABILIZATION_TIME_IN_MILLISECONDS,
+            ));
+
+        assert!(!test_bed.gen_has_fault(1));
+        assert!(test_bed
+            .ac_bus_output(1)
+            .is_single(PotentialOrigin::EngineGenerator(1)));
+    }
+
+    #[test]
+    fn when_apu_gen_feeder_fails_apu_gen_push_button_has_fault() {
+        let mut test_bed = test_bed_with()
+            .running_apu()
+            .and()
+            .failed(FailureType::GeneratorFeeder(PotentialOrigin::ApuGenerator(
+                1,
+            )))
+            .run();
+
+        assert!(test_bed.apu_gen_has_fault());
+        assert!(!test_bed
+            .ac_bus_output(1)
+            .is_single(PotentialOrigin::ApuGenerator(1)));
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    fn when_idg_oil_leaks_idg_push_button_has_fault(#[case] number: usize) {
+        let mut test_bed = test_bed_with()
+            .running_engines()
+            .and()
+            .failed(FailureType::IntegratedDriveGeneratorOilLeak(number))
+            .run();
+
+        assert!(test_bed.idg_has_fault(number));
+        assert!(!test_bed.idg_has_fault(3 - number));
+    }
+
+    #[test]
+    fn when_engines_running_normally_idg_push_buttons_do_not_have_fault() {
+        let mut test_bed = test_bed_with().running_engines().run();
+
+        assert!(!test_bed.idg_has_fault(1));
+        assert!(!test_bed.idg_has_fault(2));
+    }
+
+    #[test]
+    fn when_apu_gen_supplies_power_apu_gen_push_button_does_not_have_fault() {
+        let mut test_bed = test_bed_with().running_apu().run();
+
+        assert!(!test_bed.apu_gen_has_fault());
+    }
+
     #[rstest]
     #[case(1)]
     #[case(2)]
@@ -2076,6 +2218,38 @@ mod a320_electrical_circuit_tests {
             }
         }
     }
+    impl ProvidePotential for TestApu {
+        fn potential(&self) -> ElectricPotential {
+            self.output().raw()
+        }
+
+        fn potential_normal(&self) -> bool {
+            self.is_available
+        }
+    }
+    impl ProvideFrequency for TestApu {
+        fn frequency(&self) -> Frequency {
+            Frequency::new::<hertz>(if self.is_available { 400. } else { 0. })
+        }
+
+        fn frequency_normal(&self) -> bool {
+            self.is_available
+        }
+    }
+    impl ProvideLoad for TestApu {
+        fn load(&self) -> Ratio {
+            Ratio::new::<percent>(0.)
+        }
+
+        fn load_normal(&self) -> bool {
+            true
+        }
+    }
+    impl ControlledGenerator for TestApu {
+        fn is_driven_at_operating_speed(&self) -> bool {
+            self.is_available
+        }
+    }
     impl AuxiliaryPowerUnitElectrical for TestApu {
         fn start_motor_powered_by(&mut self, source: Potential) {
             self.start_motor_powered_by = source;
@@ -2212,13 +2386,13 @@ mod a320_electrical_circuit_tests {
         engine_fire_push_buttons: TestEngineFirePushButtons,
     }
     impl A320ElectricalTestAircraft {
-        fn new() -> Self {
+        fn new(registry: &mut VariableRegistry) -> Self {
             Self {
                 engines: [TestEngine::new(), TestEngine::new()],
-                ext_pwr: ExternalPowerSource::new(),
-                elec: A320Electrical::new(),
-                overhead: A320ElectricalOverheadPanel::new(),
-                emergency_overhead: A320EmergencyElectricalOverheadPanel::new(),
+                ext_pwr: ExternalPowerSource::new(registry),
+                elec: A320Electrical::new(registry),
+                overhead: A320ElectricalOverheadPanel::new(registry),
+                emergency_overhead: A320EmergencyElectricalOverheadPanel::new(registry),
                 apu: TestApu::new(),
                 apu_overhead: TestApuOverhead::new(),
                 engine_fire_push_buttons: TestEngineFirePushButtons::new(),
@@ -2253,20 +2427,12 @@ mod a320_electrical_circuit_tests {
             self.apu.start_motor_is_powered()
         }
 
-        fn empty_battery_1(&mut self) {
-            self.elec.empty_battery_1();
-        }
-
-        fn empty_battery_2(&mut self) {
-            self.elec.empty_battery_2();
+        fn empty_battery_1(&mut self, registry: &mut VariableRegistry) {
+            self.elec.empty_battery_1(registry);
         }
 
-        fn failed_tr_1(&mut self) {
-            self.elec.fail_tr_1();
-        }
-
-        fn failed_tr_2(&mut self) {
-            self.elec.fail_tr_2();
+        fn empty_battery_2(&mut self, registry: &mut VariableRegistry) {
+            self.elec.empty_battery_2(registry);
         }
 
         fn running_emergency_generator(&mut self) {
@@ -2343,9 +2509,10 @@ mod a320_electrical_circuit_tests {
     }
     impl A320ElectricalTestBed {
         fn new() -> Self {
-            let mut aircraft = A320ElectricalTestAircraft::new();
+            let mut registry = VariableRegistry::new();
+            let mut aircraft = A320ElectricalTestAircraft::new(&mut registry);
             Self {
-                simulation_test_bed: SimulationTestBed::seeded_with(&mut aircraft),
+                simulation_test_bed: SimulationTestBed::seeded_with(registry, &mut aircraft),
                 aircraft,
             }
         }
@@ -2384,12 +2551,14 @@ mod a320_electrical_circuit_tests {
         }
 
         fn empty_battery_1(mut self) -> Self {
-            self.aircraft.empty_battery_1();
+            self.aircraft
+                .empty_battery_1(self.simulation_test_bed.registry());
             self
         }
 
         fn empty_battery_2(mut self) -> Self {
-            self.aircraft.empty_battery_2();
+            self.aircraft
+                .empty_battery_2(self.simulation_test_bed.registry());
             self
         }
 
@@ -2422,16 +2591,26 @@ mod a320_electrical_circuit_tests {
             self
         }
 
-        fn failed_tr_1(mut self) -> Self {
-            self.aircraft.failed_tr_1();
+        fn failed(mut self, failure_type: FailureType) -> Self {
+            self.simulation_test_bed
+                .write_bool(&failure_type.variable_name(), true);
             self
         }
 
-        fn failed_tr_2(mut self) -> Self {
-            self.aircraft.failed_tr_2();
+        fn repaired(mut self, failure_type: FailureType) -> Self {
+            self.simulation_test_bed
+                .write_bool(&failure_type.variable_name(), false);
             self
         }
 
+        fn failed_tr_1(self) -> Self {
+            self.failed(FailureType::TransformerRectifier(1))
+        }
+
+        fn failed_tr_2(self) -> Self {
+            self.failed(FailureType::TransformerRectifier(2))
+        }
+
         fn running_emergency_generator(mut self) -> Self {
             self.aircraft.running_emergency_generator();
             self.run_waiting_for(Duration::from_secs(100))
@@ -2443,6 +2622,12 @@ mod a320_electrical_circuit_tests {
             self
         }
 
+        fn gen_on(mut self, number: usize) -> Self {
+            self.simulation_test_bed
+                .write_bool(&format!("OVHD_ELEC_ENG_GEN_{}_PB_IS_ON", number), true);
+            self
+        }
+
         fn released_engine_fire_push_button(mut self, engine_number: usize) -> Self {
             self.aircraft.release_engine_fire_push_button(engine_number);
             self
@@ -2652,6 +2837,16 @@ mod a320_electrical_circuit_tests {
                 .read_bool(&format!("OVHD_ELEC_ENG_GEN_{}_PB_HAS_FAULT", number))
         }
 
+        fn idg_has_fault(&mut self, number: usize) -> bool {
+            self.simulation_test_bed
+                .read_bool(&format!("OVHD_ELEC_IDG_{}_PB_HAS_FAULT", number))
+        }
+
+        fn apu_gen_has_fault(&mut self) -> bool {
+            self.simulation_test_bed
+                .read_bool("OVHD_ELEC_APU_GEN_PB_HAS_FAULT")
+        }
+
         fn rat_and_emer_gen_has_fault(&mut self) -> bool {
             self.simulation_test_bed
                 .read_bool("OVHD_EMER_ELEC_RAT_AND_EMER_GEN_HAS_FAULT")
@@ -1,29 +1,1076 @@
-use systems::simulation::{SimulationElement, SimulatorReader};
-use uom::si::{
-    f64::*,
-    mass::{kilogram, pound},
+use systems::{
+    apu::AuxiliaryPowerUnitFireOverheadPanel,
+    failures::{Failure, FailureType},
+    fuel::{
+        FuelFeedLine, FuelPressureSource, FuelPressureSwitch, FuelPump, FuelPumpController,
+        FuelTank, FuelValve, FuelValveController,
+    },
+    pneumatic::Valve,
+    shared::{ApuMaster, EngineFirePushButtons},
+    simulation::{
+        SimulationElement, SimulationElementVisitor, SimulatorReader, SimulatorWriter, Snapshot,
+        StateDump, UpdateContext, VariableIdentifier, VariableRegistry,
+    },
 };
+use uom::si::{f64::*, length::foot, mass::kilogram, pressure::psi};
 
 pub struct A320Fuel {
+    unlimited_fuel_id: VariableIdentifier,
+    engine_1_is_starved_id: VariableIdentifier,
+    engine_2_is_starved_id: VariableIdentifier,
+    apu_fuel_pressure_switch_has_pressure_id: VariableIdentifier,
+
     unlimited_fuel: bool,
-    left_inner_tank_fuel_quantity: Mass,
+
+    left_outer_tank: FuelTank,
+    left_inner_tank: FuelTank,
+    center_tank: FuelTank,
+    right_inner_tank: FuelTank,
+    right_outer_tank: FuelTank,
+
+    left_tank_pumps: [FuelPump; 2],
+    left_tank_pump_controllers: [A320FuelPumpController; 2],
+    center_tank_pumps: [FuelPump; 2],
+    center_tank_pump_controllers: [A320FuelPumpController; 2],
+    right_tank_pumps: [FuelPump; 2],
+    right_tank_pump_controllers: [A320FuelPumpController; 2],
+
+    left_feed_line: FuelFeedLine,
+    right_feed_line: FuelFeedLine,
+    cross_feed_valve: FuelValve,
+    cross_feed_valve_controller: A320CrossFeedValveController,
+
+    transfer_valves: [FuelValve; 2],
+    transfer_valve_controllers: [A320TransferValveController; 2],
+
+    engine_lp_valves: [FuelValve; 2],
+    engine_lp_valve_controllers: [A320EngineLpValveController; 2],
+    engine_starved: [bool; 2],
+
+    apu_fuel_pump: FuelPump,
+    apu_fuel_pump_controller: A320ApuFuelPumpController,
+    apu_feed_line: FuelFeedLine,
+    apu_lp_valve: FuelValve,
+    apu_lp_valve_controller: A320ApuLpValveController,
+    apu_lp_valve_stuck_failure: Failure,
+    apu_fuel_pressure_switch: FuelPressureSwitch,
 }
 impl A320Fuel {
-    pub fn new() -> Self {
+    const TANK_PUMP_OUTPUT_PRESSURE_PSI: f64 = 30.;
+    const APU_FUEL_PUMP_OUTPUT_PRESSURE_PSI: f64 = 25.;
+
+    // Below this pressure in the feed line the engine is suction fed, and the APU fuel pump runs.
+    const MINIMUM_FEED_PRESSURE_PSI: f64 = 17.;
+
+    // Above this altitude the engine cannot be suction fed reliably.
+    const SUCTION_FEED_CEILING_FEET: f64 = 15000.;
+
+    fn transfer_valve_latch_name(number: usize) -> String {
+        format!("FUEL_TRANSFER_VALVE_{}_LATCHED_OPEN", number)
+    }
+
+    pub fn new(registry: &mut VariableRegistry) -> Self {
         A320Fuel {
+            unlimited_fuel_id: registry.get("UNLIMITED FUEL"),
+            engine_1_is_starved_id: registry.get("FUEL_ENG_1_IS_STARVED"),
+            engine_2_is_starved_id: registry.get("FUEL_ENG_2_IS_STARVED"),
+            apu_fuel_pressure_switch_has_pressure_id: registry
+                .get("APU_FUEL_PRESSURE_SWITCH_HAS_PRESSURE"),
+
             unlimited_fuel: false,
-            left_inner_tank_fuel_quantity: Mass::new::<kilogram>(0.),
+
+            left_outer_tank: FuelTank::new(registry, "FUEL TANK LEFT AUX QUANTITY"),
+            left_inner_tank: FuelTank::new(registry, "FUEL TANK LEFT MAIN QUANTITY"),
+            center_tank: FuelTank::new(registry, "FUEL TANK CENTER QUANTITY"),
+            right_inner_tank: FuelTank::new(registry, "FUEL TANK RIGHT MAIN QUANTITY"),
+            right_outer_tank: FuelTank::new(registry, "FUEL TANK RIGHT AUX QUANTITY"),
+
+            left_tank_pumps: [
+                FuelPump::new(registry, "L_TK_1", Self::tank_pump_output_pressure()),
+                FuelPump::new(registry, "L_TK_2", Self::tank_pump_output_pressure()),
+            ],
+            left_tank_pump_controllers: [
+                A320FuelPumpController::new(registry, 2),
+                A320FuelPumpController::new(registry, 5),
+            ],
+            center_tank_pumps: [
+                FuelPump::new(registry, "CTR_TK_1", Self::tank_pump_output_pressure()),
+                FuelPump::new(registry, "CTR_TK_2", Self::tank_pump_output_pressure()),
+            ],
+            center_tank_pump_controllers: [
+                A320FuelPumpController::new(registry, 1),
+                A320FuelPumpController::new(registry, 4),
+            ],
+            right_tank_pumps: [
+                FuelPump::new(registry, "R_TK_1", Self::tank_pump_output_pressure()),
+                FuelPump::new(registry, "R_TK_2", Self::tank_pump_output_pressure()),
+            ],
+            right_tank_pump_controllers: [
+                A320FuelPumpController::new(registry, 3),
+                A320FuelPumpController::new(registry, 6),
+            ],
+
+            left_feed_line: FuelFeedLine::new(),
+            right_feed_line: FuelFeedLine::new(),
+            cross_feed_valve: FuelValve::new(registry, "XFEED"),
+            cross_feed_valve_controller: A320CrossFeedValveController::new(registry),
+
+            transfer_valves: [
+                FuelValve::new(registry, "L_XFR"),
+                FuelValve::new(registry, "R_XFR"),
+            ],
+            transfer_valve_controllers: [
+                A320TransferValveController::new(),
+                A320TransferValveController::new(),
+            ],
+
+            engine_lp_valves: [
+                FuelValve::new(registry, "ENG_1_LP"),
+                FuelValve::new(registry, "ENG_2_LP"),
+            ],
+            engine_lp_valve_controllers: [
+                A320EngineLpValveController::new(registry, 1),
+                A320EngineLpValveController::new(registry, 2),
+            ],
+            engine_starved: [false, false],
+
+            apu_fuel_pump: FuelPump::new(
+                registry,
+                "APU",
+                Pressure::new::<psi>(Self::APU_FUEL_PUMP_OUTPUT_PRESSURE_PSI),
+            ),
+            apu_fuel_pump_controller: A320ApuFuelPumpController::new(),
+            apu_feed_line: FuelFeedLine::new(),
+            apu_lp_valve: FuelValve::new(registry, "APU_LP"),
+            apu_lp_valve_controller: A320ApuLpValveController::new(),
+            apu_lp_valve_stuck_failure: Failure::new(registry, FailureType::ApuFuelValveStuck),
+            apu_fuel_pressure_switch: FuelPressureSwitch::new(
+                Pressure::new::<psi>(17.),
+                Pressure::new::<psi>(16.),
+            ),
+        }
+    }
+
+    fn tank_pump_output_pressure() -> Pressure {
+        Pressure::new::<psi>(Self::TANK_PUMP_OUTPUT_PRESSURE_PSI)
+    }
+
+    pub fn update<T: ApuMaster, U: EngineFirePushButtons>(
+        &mut self,
+        context: &UpdateContext,
+        apu_overhead: &T,
+        apu_fire_overhead: &AuxiliaryPowerUnitFireOverheadPanel,
+        engine_fire_push_buttons: &U,
+    ) {
+        let left_inner_has_fuel = self.has_fuel(&self.left_inner_tank);
+        let center_has_fuel = self.has_fuel(&self.center_tank);
+        let right_inner_has_fuel = self.has_fuel(&self.right_inner_tank);
+
+        for (pump, controller) in self
+            .left_tank_pumps
+            .iter_mut()
+            .zip(self.left_tank_pump_controllers.iter())
+        {
+            pump.update(controller, left_inner_has_fuel);
+        }
+        for (pump, controller) in self
+            .center_tank_pumps
+            .iter_mut()
+            .zip(self.center_tank_pump_controllers.iter())
+        {
+            pump.update(controller, center_has_fuel);
+        }
+        for (pump, controller) in self
+            .right_tank_pumps
+            .iter_mut()
+            .zip(self.right_tank_pump_controllers.iter())
+        {
+            pump.update(controller, right_inner_has_fuel);
         }
+
+        self.cross_feed_valve
+            .update(&self.cross_feed_valve_controller);
+        self.update_feed_lines();
+
+        let inner_tanks = [&self.left_inner_tank, &self.right_inner_tank];
+        for (index, controller) in self.transfer_valve_controllers.iter_mut().enumerate() {
+            controller.update(context, inner_tanks[index], &self.engine_lp_valves);
+        }
+        for (valve, controller) in self
+            .transfer_valves
+            .iter_mut()
+            .zip(self.transfer_valve_controllers.iter())
+        {
+            valve.update(controller);
+        }
+
+        for (valve, controller) in self
+            .engine_lp_valves
+            .iter_mut()
+            .zip(self.engine_lp_valve_controllers.iter_mut())
+        {
+            controller.update(engine_fire_push_buttons);
+            valve.update(controller);
+        }
+        self.update_engine_starvation(context, [left_inner_has_fuel, right_inner_has_fuel]);
+
+        self.update_apu_feed(
+            apu_overhead,
+            apu_fire_overhead,
+            left_inner_has_fuel || (self.cross_feed_valve.is_open() && right_inner_has_fuel),
+        );
     }
 
-    pub fn left_inner_tank_has_fuel_remaining(&self) -> bool {
-        self.unlimited_fuel || self.left_inner_tank_fuel_quantity > Mass::new::<kilogram>(0.)
+    fn has_fuel(&self, tank: &FuelTank) -> bool {
+        self.unlimited_fuel || !tank.is_empty()
+    }
+
+    fn update_feed_lines(&mut self) {
+        if self.cross_feed_valve.is_open() {
+            let sources: [&dyn FuelPressureSource; 6] = [
+                &self.left_tank_pumps[0],
+                &self.left_tank_pumps[1],
+                &self.center_tank_pumps[0],
+                &self.center_tank_pumps[1],
+                &self.right_tank_pumps[0],
+                &self.right_tank_pumps[1],
+            ];
+            self.left_feed_line.update(&sources);
+            self.right_feed_line.update(&sources);
+        } else {
+            self.left_feed_line.update(&[
+                &self.left_tank_pumps[0],
+                &self.left_tank_pumps[1],
+                &self.center_tank_pumps[0],
+            ]);
+            self.right_feed_line.update(&[
+                &self.right_tank_pumps[0],
+                &self.right_tank_pumps[1],
+                &self.center_tank_pumps[1],
+            ]);
+        }
+    }
+
+    fn update_engine_starvation(
+        &mut self,
+        context: &UpdateContext,
+        inner_tank_has_fuel: [bool; 2],
+    ) {
+        let can_suction_feed =
+            context.indicated_altitude() < Length::new::<foot>(Self::SUCTION_FEED_CEILING_FEET);
+        let feed_lines = [&self.left_feed_line, &self.right_feed_line];
+
+        for (index, valve) in self.engine_lp_valves.iter().enumerate() {
+            let is_pressure_fed = valve.downstream_pressure(feed_lines[index])
+                >= Pressure::new::<psi>(Self::MINIMUM_FEED_PRESSURE_PSI);
+            let is_suction_fed = can_suction_feed && inner_tank_has_fuel[index];
+
+            self.engine_starved[index] = valve.is_open() && !is_pressure_fed && !is_suction_fed;
+        }
+    }
+
+    fn update_apu_feed<T: ApuMaster>(
+        &mut self,
+        apu_overhead: &T,
+        apu_fire_overhead: &AuxiliaryPowerUnitFireOverheadPanel,
+        apu_fuel_pump_has_fuel_supply: bool,
+    ) {
+        self.apu_lp_valve_controller
+            .update(apu_overhead, apu_fire_overhead);
+        if !self.apu_lp_valve_stuck_failure.is_active() {
+            self.apu_lp_valve.update(&self.apu_lp_valve_controller);
+        }
+
+        self.apu_fuel_pump_controller
+            .update(&self.apu_lp_valve, &self.left_feed_line);
+        self.apu_fuel_pump.update(
+            &self.apu_fuel_pump_controller,
+            apu_fuel_pump_has_fuel_supply,
+        );
+
+        self.apu_feed_line
+            .update(&[&self.left_feed_line, &self.apu_fuel_pump]);
+        self.apu_fuel_pressure_switch
+            .update(self.apu_lp_valve.downstream_pressure(&self.apu_feed_line));
+    }
+
+    pub fn apu_fuel_pressure_switch(&self) -> &FuelPressureSwitch {
+        &self.apu_fuel_pressure_switch
+    }
+
+    pub fn engine_is_starved(&self, number: usize) -> bool {
+        self.engine_starved[number - 1]
+    }
+
+    #[cfg(test)]
+    fn feed_line_pressure(&self, number: usize) -> Pressure {
+        match number {
+            1 => self.left_feed_line.fuel_pressure(),
+            2 => self.right_feed_line.fuel_pressure(),
+            _ => panic!("Invalid feed line number {}.", number),
+        }
+    }
+
+    #[cfg(test)]
+    fn transfer_valve_is_open(&self, number: usize) -> bool {
+        self.transfer_valves[number - 1].is_open()
+    }
+
+    #[cfg(test)]
+    fn apu_fuel_pump_is_running(&self) -> bool {
+        self.apu_fuel_pump.is_running()
     }
 }
 impl SimulationElement for A320Fuel {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.left_outer_tank.accept(visitor);
+        self.left_inner_tank.accept(visitor);
+        self.center_tank.accept(visitor);
+        self.right_inner_tank.accept(visitor);
+        self.right_outer_tank.accept(visitor);
+
+        self.left_tank_pumps
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
+        self.left_tank_pump_controllers
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
+        self.center_tank_pumps
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
+        self.center_tank_pump_controllers
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
+        self.right_tank_pumps
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
+        self.right_tank_pump_controllers
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
+
+        self.cross_feed_valve.accept(visitor);
+        self.cross_feed_valve_controller.accept(visitor);
+        self.transfer_valves
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
+        self.engine_lp_valves
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
+        self.engine_lp_valve_controllers
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
+
+        self.apu_fuel_pump.accept(visitor);
+        self.apu_lp_valve.accept(visitor);
+        self.apu_lp_valve_stuck_failure.accept(visitor);
+
+        visitor.visit(self);
+    }
+
+    fn read(&mut self, reader: &mut SimulatorReader) {
+        self.unlimited_fuel = reader.read_bool(&self.unlimited_fuel_id);
+    }
+
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_bool(&self.engine_1_is_starved_id, self.engine_is_starved(1));
+        writer.write_bool(&self.engine_2_is_starved_id, self.engine_is_starved(2));
+        writer.write_bool(
+            &self.apu_fuel_pressure_switch_has_pressure_id,
+            self.apu_fuel_pressure_switch.has_pressure(),
+        );
+    }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        for (number, controller) in self.transfer_valve_controllers.iter().enumerate() {
+            snapshot.write_bool(
+                &Self::transfer_valve_latch_name(number + 1),
+                controller.latched_open,
+            );
+        }
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        for (number, controller) in self.transfer_valve_controllers.iter_mut().enumerate() {
+            if let Some(latched_open) =
+                snapshot.read_bool(&Self::transfer_valve_latch_name(number + 1))
+            {
+                controller.latched_open = latched_open;
+            }
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        for (number, controller) in self.transfer_valve_controllers.iter().enumerate() {
+            state
+                .node("FUEL")
+                .node("TRANSFER_VALVE")
+                .node(&(number + 1).to_string())
+                .write_bool("LATCHED_OPEN", controller.latched_open);
+        }
+    }
+}
+/// Runs the pump when its push button on the fuel panel is ON.
+struct A320FuelPumpController {
+    switch_id: VariableIdentifier,
+    is_on: bool,
+}
+impl A320FuelPumpController {
+    fn new(registry: &mut VariableRegistry, switch_number: usize) -> Self {
+        Self {
+            switch_id: registry.get(&format!("FUELSYSTEM PUMP SWITCH:{}", switch_number)),
+            is_on: false,
+        }
+    }
+}
+impl FuelPumpController for A320FuelPumpController {
+    fn should_run_fuel_pump(&self) -> bool {
+        self.is_on
+    }
+}
+impl SimulationElement for A320FuelPumpController {
+    fn read(&mut self, reader: &mut SimulatorReader) {
+        self.is_on = reader.read_bool(&self.switch_id);
+    }
+}
+
+/// Opens the crossfeed valve when the X FEED push button is ON.
+struct A320CrossFeedValveController {
+    switch_id: VariableIdentifier,
+    is_on: bool,
+}
+impl A320CrossFeedValveController {
+    fn new(registry: &mut VariableRegistry) -> Self {
+        Self {
+            switch_id: registry.get("FUELSYSTEM VALVE SWITCH:3"),
+            is_on: false,
+        }
+    }
+}
+impl FuelValveController for A320CrossFeedValveController {
+    fn should_open_fuel_valve(&self) -> bool {
+        self.is_on
+    }
+}
+impl SimulationElement for A320CrossFeedValveController {
     fn read(&mut self, reader: &mut SimulatorReader) {
-        self.unlimited_fuel = reader.read_bool("UNLIMITED FUEL");
-        self.left_inner_tank_fuel_quantity =
-            Mass::new::<pound>(reader.read_f64("FUEL TANK LEFT MAIN QUANTITY"));
+        self.is_on = reader.read_bool(&self.switch_id);
+    }
+}
+
+/// The transfer valves open when the inner tank quantity drops below 750 kg, allowing fuel to
+/// flow from the outer tank into the inner tank. They remain open until the next refuel,
+/// which we consider to happen when the aircraft is on the ground with the engines shut off.
+struct A320TransferValveController {
+    latched_open: bool,
+}
+impl A320TransferValveController {
+    const OPENING_INNER_TANK_QUANTITY_KG: f64 = 750.;
+
+    fn new() -> Self {
+        Self {
+            latched_open: false,
+        }
+    }
+
+    fn update(
+        &mut self,
+        context: &UpdateContext,
+        inner_tank: &FuelTank,
+        engine_lp_valves: &[FuelValve; 2],
+    ) {
+        if inner_tank.quantity() < Mass::new::<kilogram>(Self::OPENING_INNER_TANK_QUANTITY_KG) {
+            self.latched_open = true;
+        } else if context.is_on_ground() && engine_lp_valves.iter().all(|valve| !valve.is_open()) {
+            self.latched_open = false;
+        }
+    }
+}
+impl FuelValveController for A320TransferValveController {
+    fn should_open_fuel_valve(&self) -> bool {
+        self.latched_open
+    }
+}
+
+/// The engine LP valve opens when the ENG MASTER switch is ON and closes
+/// when it is OFF or the ENG FIRE push button is released.
+struct A320EngineLpValveController {
+    number: usize,
+    master_id: VariableIdentifier,
+    master_is_on: bool,
+    should_open: bool,
+}
+impl A320EngineLpValveController {
+    fn new(registry: &mut VariableRegistry, number: usize) -> Self {
+        Self {
+            number,
+            master_id: registry.get(&format!("GENERAL ENG STARTER ACTIVE:{}", number)),
+            master_is_on: false,
+            should_open: false,
+        }
+    }
+
+    fn update<T: EngineFirePushButtons>(&mut self, engine_fire_push_buttons: &T) {
+        self.should_open = self.master_is_on && !engine_fire_push_buttons.is_released(self.number);
+    }
+}
+impl FuelValveController for A320EngineLpValveController {
+    fn should_open_fuel_valve(&self) -> bool {
+        self.should_open
+    }
+}
+impl SimulationElement for A320EngineLpValveController {
+    fn read(&mut self, reader: &mut SimulatorReader) {
+        self.master_is_on = reader.read_bool(&self.master_id);
+    }
+}
+
+/// The APU LP valve opens when the APU MASTER SW is ON and closes
+/// when it is OFF or the APU FIRE push button is released.
+struct A320ApuLpValveController {
+    should_open: bool,
+}
+impl A320ApuLpValveController {
+    fn new() -> Self {
+        Self { should_open: false }
+    }
+
+    fn update<T: ApuMaster>(
+        &mut self,
+        apu_overhead: &T,
+        apu_fire_overhead: &AuxiliaryPowerUnitFireOverheadPanel,
+    ) {
+        self.should_open =
+            apu_overhead.master_sw_is_on() && !apu_fire_overhead.fire_button_is_released();
+    }
+}
+impl FuelValveController for A320ApuLpValveController {
+    fn should_open_fuel_valve(&self) -> bool {
+        self.should_open
+    }
+}
+
+/// The APU fuel pump runs when the APU LP valve is open and
+/// the tank pumps do not supply sufficient pressure.
+struct A320ApuFuelPumpController {
+    should_run: bool,
+}
+impl A320ApuFuelPumpController {
+    fn new() -> Self {
+        Self { should_run: false }
+    }
+
+    fn update(&mut self, apu_lp_valve: &FuelValve, feed_line: &FuelFeedLine) {
+        self.should_run = apu_lp_valve.is_open()
+            && feed_line.fuel_pressure()
+                < Pressure::new::<psi>(A320Fuel::MINIMUM_FEED_PRESSURE_PSI);
+    }
+}
+impl FuelPumpController for A320ApuFuelPumpController {
+    fn should_run_fuel_pump(&self) -> bool {
+        self.should_run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use systems::{
+        electrical::consumption::SuppliedPower,
+        simulation::{test::SimulationTestBed, Aircraft},
+    };
+    use uom::si::mass::pound;
+
+    struct TestApuOverhead {
+        master_sw_is_on: bool,
+    }
+    impl TestApuOverhead {
+        fn new() -> Self {
+            Self {
+                master_sw_is_on: false,
+            }
+        }
+    }
+    impl ApuMaster for TestApuOverhead {
+        fn master_sw_is_on(&self) -> bool {
+            self.master_sw_is_on
+        }
+    }
+
+    struct TestEngineFirePushButtons {
+        released: [bool; 2],
+    }
+    impl TestEngineFirePushButtons {
+        fn new() -> Self {
+            Self {
+                released: [false, false],
+            }
+        }
+    }
+    impl EngineFirePushButtons for TestEngineFirePushButtons {
+        fn is_released(&self, engine_number: usize) -> bool {
+            self.released[engine_number - 1]
+        }
+    }
+
+    struct A320FuelTestAircraft {
+        fuel: A320Fuel,
+        apu_overhead: TestApuOverhead,
+        apu_fire_overhead: AuxiliaryPowerUnitFireOverheadPanel,
+        engine_fire_push_buttons: TestEngineFirePushButtons,
+    }
+    impl A320FuelTestAircraft {
+        fn new(registry: &mut VariableRegistry) -> Self {
+            Self {
+                fuel: A320Fuel::new(registry),
+                apu_overhead: TestApuOverhead::new(),
+                apu_fire_overhead: AuxiliaryPowerUnitFireOverheadPanel::new(registry),
+                engine_fire_push_buttons: TestEngineFirePushButtons::new(),
+            }
+        }
+    }
+    impl Aircraft for A320FuelTestAircraft {
+        fn update_before_power_distribution(&mut self, context: &UpdateContext) {
+            self.fuel.update(
+                context,
+                &self.apu_overhead,
+                &self.apu_fire_overhead,
+                &self.engine_fire_push_buttons,
+            );
+        }
+
+        fn get_supplied_power(&mut self) -> SuppliedPower {
+            SuppliedPower::new()
+        }
+    }
+    impl SimulationElement for A320FuelTestAircraft {
+        fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+            self.fuel.accept(visitor);
+            self.apu_fire_overhead.accept(visitor);
+
+            visitor.visit(self);
+        }
+    }
+
+    struct A320FuelTestBed {
+        aircraft: A320FuelTestAircraft,
+        simulation_test_bed: SimulationTestBed,
+    }
+    impl A320FuelTestBed {
+        fn new() -> Self {
+            let mut simulation_test_bed = SimulationTestBed::new();
+            let mut test_bed = Self {
+                aircraft: A320FuelTestAircraft::new(simulation_test_bed.registry()),
+                simulation_test_bed,
+            };
+            test_bed.simulation_test_bed.set_on_ground(true);
+            test_bed
+                .simulation_test_bed
+                .set_indicated_altitude(Length::new::<foot>(0.));
+
+            test_bed
+        }
+
+        fn and(self) -> Self {
+            self
+        }
+
+        fn tank_quantity(mut self, name: &str, quantity: Mass) -> Self {
+            self.simulation_test_bed.write_f64(
+                &format!("FUEL TANK {} QUANTITY", name),
+                quantity.get::<pound>(),
+            );
+            self
+        }
+
+        fn full_inner_tanks(self) -> Self {
+            self.tank_quantity("LEFT MAIN", Mass::new::<kilogram>(5000.))
+                .tank_quantity("RIGHT MAIN", Mass::new::<kilogram>(5000.))
+        }
+
+        fn unlimited_fuel(mut self) -> Self {
+            self.simulation_test_bed.write_bool("UNLIMITED FUEL", true);
+            self
+        }
+
+        fn pump_on(mut self, switch_number: usize) -> Self {
+            self.simulation_test_bed
+                .write_bool(&format!("FUELSYSTEM PUMP SWITCH:{}", switch_number), true);
+            self
+        }
+
+        fn left_tank_pumps_on(self) -> Self {
+            self.pump_on(2).pump_on(5)
+        }
+
+        fn right_tank_pumps_on(self) -> Self {
+            self.pump_on(3).pump_on(6)
+        }
+
+        fn cross_feed_on(mut self) -> Self {
+            self.simulation_test_bed
+                .write_bool("FUELSYSTEM VALVE SWITCH:3", true);
+            self
+        }
+
+        fn engine_master_on(mut self, number: usize) -> Self {
+            self.simulation_test_bed
+                .write_bool(&format!("GENERAL ENG STARTER ACTIVE:{}", number), true);
+            self
+        }
+
+        fn apu_master_on(mut self) -> Self {
+            self.aircraft.apu_overhead.master_sw_is_on = true;
+            self
+        }
+
+        fn apu_master_off(mut self) -> Self {
+            self.aircraft.apu_overhead.master_sw_is_on = false;
+            self
+        }
+
+        fn failed(mut self, failure_type: FailureType) -> Self {
+            self.simulation_test_bed
+                .write_bool(&failure_type.variable_name(), true);
+            self
+        }
+
+        fn released_engine_fire_pb(mut self, number: usize) -> Self {
+            self.aircraft.engine_fire_push_buttons.released[number - 1] = true;
+            self
+        }
+
+        fn released_apu_fire_pb(mut self) -> Self {
+            self.simulation_test_bed.write_bool("FIRE_BUTTON_APU", true);
+            self
+        }
+
+        fn in_flight_at(mut self, altitude: Length) -> Self {
+            self.simulation_test_bed.set_on_ground(false);
+            self.simulation_test_bed.set_indicated_altitude(altitude);
+            self
+        }
+
+        fn run(mut self) -> Self {
+            self.simulation_test_bed.run_aircraft(&mut self.aircraft);
+            self
+        }
+
+        fn apu_has_fuel_pressure(&self) -> bool {
+            self.aircraft.fuel.apu_fuel_pressure_switch().has_pressure()
+        }
+
+        fn apu_fuel_pump_is_running(&self) -> bool {
+            self.aircraft.fuel.apu_fuel_pump_is_running()
+        }
+
+        fn engine_is_starved(&self, number: usize) -> bool {
+            self.aircraft.fuel.engine_is_starved(number)
+        }
+
+        fn feed_line_is_pressurised(&self, number: usize) -> bool {
+            self.aircraft.fuel.feed_line_pressure(number)
+                >= Pressure::new::<psi>(A320Fuel::MINIMUM_FEED_PRESSURE_PSI)
+        }
+
+        fn transfer_valve_is_open(&self, number: usize) -> bool {
+            self.aircraft.fuel.transfer_valve_is_open(number)
+        }
+
+        fn contains_key(&self, name: &str) -> bool {
+            self.simulation_test_bed.contains_key(name)
+        }
+    }
+
+    fn test_bed() -> A320FuelTestBed {
+        A320FuelTestBed::new()
+    }
+
+    fn test_bed_with() -> A320FuelTestBed {
+        test_bed()
+    }
+
+    #[test]
+    fn tank_pumps_pressurise_their_feed_line() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .and()
+            .left_tank_pumps_on()
+            .run();
+
+        assert!(test_bed.feed_line_is_pressurised(1));
+        assert!(!test_bed.feed_line_is_pressurised(2));
+    }
+
+    #[test]
+    fn right_tank_pumps_only_pressurise_right_feed_line() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .and()
+            .right_tank_pumps_on()
+            .run();
+
+        assert!(!test_bed.feed_line_is_pressurised(1));
+        assert!(test_bed.feed_line_is_pressurised(2));
+    }
+
+    #[test]
+    fn tank_pumps_without_fuel_do_not_pressurise_their_feed_line() {
+        let test_bed = test_bed_with().left_tank_pumps_on().run();
+
+        assert!(!test_bed.feed_line_is_pressurised(1));
+    }
+
+    #[test]
+    fn unlimited_fuel_pressurises_feed_line_without_fuel_in_tanks() {
+        let test_bed = test_bed_with()
+            .unlimited_fuel()
+            .and()
+            .left_tank_pumps_on()
+            .run();
+
+        assert!(test_bed.feed_line_is_pressurised(1));
+    }
+
+    #[test]
+    fn center_tank_pumps_pressurise_both_feed_lines() {
+        let test_bed = test_bed_with()
+            .tank_quantity("CENTER", Mass::new::<kilogram>(3000.))
+            .pump_on(1)
+            .and()
+            .pump_on(4)
+            .run();
+
+        assert!(test_bed.feed_line_is_pressurised(1));
+        assert!(test_bed.feed_line_is_pressurised(2));
+    }
+
+    #[test]
+    fn cross_feed_connects_feed_lines() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .left_tank_pumps_on()
+            .and()
+            .cross_feed_on()
+            .run();
+
+        assert!(test_bed.feed_line_is_pressurised(2));
+    }
+
+    #[test]
+    fn engine_with_master_off_is_not_starved() {
+        let test_bed = test_bed().run();
+
+        assert!(!test_bed.engine_is_starved(1));
+        assert!(!test_bed.engine_is_starved(2));
+    }
+
+    #[test]
+    fn engine_fed_by_pumps_is_not_starved() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .left_tank_pumps_on()
+            .and()
+            .engine_master_on(1)
+            .in_flight_at(Length::new::<foot>(35000.))
+            .run();
+
+        assert!(!test_bed.engine_is_starved(1));
+    }
+
+    #[test]
+    fn engine_is_suction_fed_at_low_altitude() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .and()
+            .engine_master_on(1)
+            .in_flight_at(Length::new::<foot>(10000.))
+            .run();
+
+        assert!(!test_bed.engine_is_starved(1));
+    }
+
+    #[test]
+    fn engine_without_pumps_is_starved_at_high_altitude() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .and()
+            .engine_master_on(1)
+            .in_flight_at(Length::new::<foot>(35000.))
+            .run();
+
+        assert!(test_bed.engine_is_starved(1));
+    }
+
+    #[test]
+    fn engine_without_fuel_is_starved() {
+        let test_bed = test_bed_with()
+            .left_tank_pumps_on()
+            .and()
+            .engine_master_on(1)
+            .run();
+
+        assert!(test_bed.engine_is_starved(1));
+    }
+
+    #[test]
+    fn engine_with_released_fire_pb_is_not_starved_as_lp_valve_is_closed() {
+        let test_bed = test_bed_with()
+            .engine_master_on(2)
+            .and()
+            .released_engine_fire_pb(2)
+            .run();
+
+        assert!(!test_bed.engine_is_starved(2));
+    }
+
+    #[test]
+    fn apu_has_fuel_pressure_from_tank_pumps() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .left_tank_pumps_on()
+            .and()
+            .apu_master_on()
+            .run();
+
+        assert!(test_bed.apu_has_fuel_pressure());
+        assert!(!test_bed.apu_fuel_pump_is_running());
+    }
+
+    #[test]
+    fn apu_fuel_pump_runs_when_tank_pumps_are_off() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .and()
+            .apu_master_on()
+            .run();
+
+        assert!(test_bed.apu_fuel_pump_is_running());
+        assert!(test_bed.apu_has_fuel_pressure());
+    }
+
+    #[test]
+    fn apu_has_no_fuel_pressure_without_fuel() {
+        let test_bed = test_bed_with().apu_master_on().run();
+
+        assert!(!test_bed.apu_has_fuel_pressure());
+    }
+
+    #[test]
+    fn apu_has_no_fuel_pressure_when_master_sw_off() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .and()
+            .left_tank_pumps_on()
+            .run();
+
+        assert!(!test_bed.apu_has_fuel_pressure());
+    }
+
+    #[test]
+    fn apu_has_no_fuel_pressure_when_apu_fire_pb_released() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .apu_master_on()
+            .and()
+            .released_apu_fire_pb()
+            .run();
+
+        assert!(!test_bed.apu_has_fuel_pressure());
+    }
+
+    #[test]
+    fn apu_fuel_valve_stuck_open_keeps_supplying_fuel_when_master_sw_off() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .left_tank_pumps_on()
+            .and()
+            .apu_master_on()
+            .run()
+            .failed(FailureType::ApuFuelValveStuck)
+            .and()
+            .apu_master_off()
+            .run();
+
+        assert!(test_bed.apu_has_fuel_pressure());
+    }
+
+    #[test]
+    fn apu_fuel_valve_stuck_closed_supplies_no_fuel_when_master_sw_on() {
+        let test_bed = test_bed_with()
+            .full_inner_tanks()
+            .left_tank_pumps_on()
+            .and()
+            .failed(FailureType::ApuFuelValveStuck)
+            .apu_master_on()
+            .run();
+
+        assert!(!test_bed.apu_has_fuel_pressure());
+    }
+
+    #[test]
+    fn transfer_valve_opens_when_inner_tank_below_750_kg() {
+        let test_bed = test_bed_with()
+            .tank_quantity("LEFT MAIN", Mass::new::<kilogram>(700.))
+            .and()
+            .tank_quantity("RIGHT MAIN", Mass::new::<kilogram>(800.))
+            .run();
+
+        assert!(test_bed.transfer_valve_is_open(1));
+        assert!(!test_bed.transfer_valve_is_open(2));
+    }
+
+    #[test]
+    fn transfer_valve_remains_open_in_flight_after_inner_tank_refilled() {
+        let test_bed = test_bed_with()
+            .tank_quantity("LEFT MAIN", Mass::new::<kilogram>(700.))
+            .engine_master_on(1)
+            .and()
+            .in_flight_at(Length::new::<foot>(10000.))
+            .run()
+            .tank_quantity("LEFT MAIN", Mass::new::<kilogram>(900.))
+            .run();
+
+        assert!(test_bed.transfer_valve_is_open(1));
+    }
+
+    #[test]
+    fn restored_transfer_valve_remains_open_in_flight() {
+        let mut test_bed = test_bed_with()
+            .tank_quantity("LEFT MAIN", Mass::new::<kilogram>(700.))
+            .run();
+        let snapshot = Snapshot::take(&mut test_bed.aircraft);
+
+        let mut restored = test_bed_with()
+            .tank_quantity("LEFT MAIN", Mass::new::<kilogram>(900.))
+            .engine_master_on(1)
+            .and()
+            .in_flight_at(Length::new::<foot>(10000.));
+        snapshot.restore_into(&mut restored.aircraft);
+        let restored = restored.run();
+
+        assert!(restored.transfer_valve_is_open(1));
+    }
+
+    #[test]
+    fn transfer_valve_closes_on_refuel() {
+        let test_bed = test_bed_with()
+            .tank_quantity("LEFT MAIN", Mass::new::<kilogram>(700.))
+            .run()
+            .tank_quantity("LEFT MAIN", Mass::new::<kilogram>(5000.))
+            .run();
+
+        assert!(!test_bed.transfer_valve_is_open(1));
+    }
+
+    #[test]
+    fn writes_its_state() {
+        let test_bed = test_bed().run();
+
+        assert!(test_bed.contains_key("FUEL_ENG_1_IS_STARVED"));
+        assert!(test_bed.contains_key("FUEL_ENG_2_IS_STARVED"));
+        assert!(test_bed.contains_key("FUEL_PUMP_L_TK_1_IS_RUNNING"));
+        assert!(test_bed.contains_key("FUEL_XFEED_VALVE_IS_OPEN"));
+        assert!(test_bed.contains_key("FUEL_APU_LP_VALVE_IS_OPEN"));
+        assert!(test_bed.contains_key("FUEL_L_XFR_VALVE_IS_OPEN"));
     }
 }
@@ -1,16 +1,19 @@
 use std::time::Duration;
 use uom::si::{
+    acceleration::meter_per_second_squared, angle::degree, angular_velocity::degree_per_second,
     angular_velocity::revolution_per_minute, f64::*, pressure::pascal, pressure::psi,
-    ratio::percent, velocity::knot, volume::gallon,
+    ratio::percent, velocity::knot, velocity::meter_per_second, volume::gallon,
 };
 
 use systems::{
+    failures::FailureType,
+    hydraulic::nose_steering::{SteeringActuator, SteeringController},
     hydraulic::{
         ElectricPump, EngineDrivenPump, Fluid, HydraulicLoop, HydraulicLoopController,
         PowerTransferUnit, PowerTransferUnitController, PressureSwitch, PumpController,
         RamAirTurbine, RamAirTurbineController,
     },
-    shared::EngineFirePushButtons,
+    shared::{interpolation, EngineFirePushButtons},
 };
 use systems::{
     overhead::{AutoOffFaultPushButton, AutoOnFaultPushButton, OnOffFaultPushButton},
@@ -19,19 +22,24 @@ use systems::{
 use systems::{
     shared::LandingGearPosition,
     simulation::{
-        SimulationElement, SimulationElementVisitor, SimulatorReader, SimulatorWriter,
-        UpdateContext,
+        FixedRate, SimulationElement, SimulationElementVisitor, SimulatorReader, SimulatorWriter,
+        Snapshot, StateDump, UpdateContext, VariableIdentifier, VariableRegistry,
     },
 };
 
-use systems::{engine::Engine, landing_gear::LandingGear};
 use systems::{
-    hydraulic::brake_circuit::BrakeCircuit, shared::DelayedFalseLogicGate,
+    engine::Engine,
+    landing_gear::{LandingGear, LandingGearControlInterfaceUnit},
+};
+use systems::{
+    hydraulic::brake_circuit::{AntiSkid, AutobrakeDecelerationGovernor, BrakeCircuit},
+    shared::DelayedFalseLogicGate,
     shared::DelayedTrueLogicGate,
 };
 
 pub(super) struct A320Hydraulic {
     hyd_brake_logic: A320HydraulicBrakingLogic,
+    autobrake_controller: A320AutobrakeController,
     blue_loop: HydraulicLoop,
     blue_loop_controller: A320HydraulicLoopController,
     green_loop: HydraulicLoop,
@@ -65,8 +73,14 @@ pub(super) struct A320Hydraulic {
 
     braking_circuit_norm: BrakeCircuit,
     braking_circuit_altn: BrakeCircuit,
+
+    nose_wheel_steering: SteeringActuator,
+    nose_wheel_steering_controller: A320NoseWheelSteeringController,
+
+    landing_gear: LandingGear,
+    lgciu: LandingGearControlInterfaceUnit,
+
     total_sim_time_elapsed: Duration,
-    lag_time_accumulator: Duration,
 }
 impl A320Hydraulic {
     const MIN_PRESS_EDP_SECTION_LO_HYST: f64 = 1740.0;
@@ -74,17 +88,29 @@ impl A320Hydraulic {
     const MIN_PRESS_PRESSURISED_LO_HYST: f64 = 1450.0;
     const MIN_PRESS_PRESSURISED_HI_HYST: f64 = 1750.0;
 
+    const NOSE_WHEEL_STEERING_MAX_ANGLE_DEGREES: f64 = 75.;
+    const NOSE_WHEEL_STEERING_MAX_SPEED_DEGREES_PER_SECOND: f64 = 20.;
+    const NOSE_WHEEL_STEERING_TOTAL_DISPLACEMENT_GALLON: f64 = 0.1;
+
     // Refresh rate of hydraulic simulation
     const HYDRAULIC_SIM_TIME_STEP_MILLISECONDS: u64 = 100;
     // Refresh rate of actuators as multiplier of hydraulics. 2 means double frequency update.
     const ACTUATORS_SIM_TIME_STEP_MULTIPLIER: u32 = 2;
 
-    pub(super) fn new() -> A320Hydraulic {
+    pub(super) const FIXED_RATE: FixedRate = FixedRate::new(Duration::from_millis(
+        Self::HYDRAULIC_SIM_TIME_STEP_MILLISECONDS,
+    ))
+    .with_sub_steps(Self::ACTUATORS_SIM_TIME_STEP_MULTIPLIER);
+
+    pub(super) fn new(registry: &mut VariableRegistry) -> A320Hydraulic {
         A320Hydraulic {
-            hyd_brake_logic: A320HydraulicBrakingLogic::new(),
+            hyd_brake_logic: A320HydraulicBrakingLogic::new(registry),
+            autobrake_controller: A320AutobrakeController::new(registry),
 
             blue_loop: HydraulicLoop::new(
+                registry,
                 "BLUE",
+                FailureType::BlueHydraulicLeak,
                 false,
                 false,
                 Volume::new::<gallon>(15.8),
@@ -98,7 +124,9 @@ impl A320Hydraulic {
             ),
             blue_loop_controller: A320HydraulicLoopController::new(None),
             green_loop: HydraulicLoop::new(
+                registry,
                 "GREEN",
+                FailureType::GreenHydraulicLeak,
                 true,
                 false,
                 Volume::new::<gallon>(26.38),
@@ -112,7 +140,9 @@ impl A320Hydraulic {
             ),
             green_loop_controller: A320HydraulicLoopController::new(Some(1)),
             yellow_loop: HydraulicLoop::new(
+                registry,
                 "YELLOW",
+                FailureType::YellowHydraulicLeak,
                 false,
                 true,
                 Volume::new::<gallon>(19.81),
@@ -130,33 +160,46 @@ impl A320Hydraulic {
                 Pressure::new::<psi>(Self::MIN_PRESS_EDP_SECTION_HI_HYST),
                 Pressure::new::<psi>(Self::MIN_PRESS_EDP_SECTION_LO_HYST),
             ),
-            engine_driven_pump_1: EngineDrivenPump::new("GREEN"),
-            engine_driven_pump_1_controller: A320EngineDrivenPumpController::new(1),
+            engine_driven_pump_1: EngineDrivenPump::new(
+                registry,
+                "GREEN",
+                FailureType::EngineDrivenPump(1),
+            ),
+            engine_driven_pump_1_controller: A320EngineDrivenPumpController::new(registry, 1),
 
             engine_driven_pump_2_pressure_switch: PressureSwitch::new(
                 Pressure::new::<psi>(Self::MIN_PRESS_EDP_SECTION_HI_HYST),
                 Pressure::new::<psi>(Self::MIN_PRESS_EDP_SECTION_LO_HYST),
             ),
-            engine_driven_pump_2: EngineDrivenPump::new("YELLOW"),
-            engine_driven_pump_2_controller: A320EngineDrivenPumpController::new(2),
+            engine_driven_pump_2: EngineDrivenPump::new(
+                registry,
+                "YELLOW",
+                FailureType::EngineDrivenPump(2),
+            ),
+            engine_driven_pump_2_controller: A320EngineDrivenPumpController::new(registry, 2),
 
-            blue_electric_pump: ElectricPump::new("BLUE"),
-            blue_electric_pump_controller: A320BlueElectricPumpController::new(),
+            blue_electric_pump: ElectricPump::new(registry, "BLUE", FailureType::BlueElectricPump),
+            blue_electric_pump_controller: A320BlueElectricPumpController::new(registry),
 
-            yellow_electric_pump: ElectricPump::new("YELLOW"),
-            yellow_electric_pump_controller: A320YellowElectricPumpController::new(),
+            yellow_electric_pump: ElectricPump::new(
+                registry,
+                "YELLOW",
+                FailureType::YellowElectricPump,
+            ),
+            yellow_electric_pump_controller: A320YellowElectricPumpController::new(registry),
 
-            forward_cargo_door: Door::new(5),
-            aft_cargo_door: Door::new(3),
-            pushback_tug: PushbackTug::new(),
+            forward_cargo_door: Door::new(registry, 5),
+            aft_cargo_door: Door::new(registry, 3),
+            pushback_tug: PushbackTug::new(registry),
 
-            ram_air_turbine: RamAirTurbine::new(),
-            ram_air_turbine_controller: A320RamAirTurbineController::new(),
+            ram_air_turbine: RamAirTurbine::new(registry),
+            ram_air_turbine_controller: A320RamAirTurbineController::new(registry),
 
-            power_transfer_unit: PowerTransferUnit::new(),
-            power_transfer_unit_controller: A320PowerTransferUnitController::new(),
+            power_transfer_unit: PowerTransferUnit::new(registry),
+            power_transfer_unit_controller: A320PowerTransferUnitController::new(registry),
 
             braking_circuit_norm: BrakeCircuit::new(
+                registry,
                 "NORM",
                 Volume::new::<gallon>(0.),
                 Volume::new::<gallon>(0.),
@@ -164,89 +207,75 @@ impl A320Hydraulic {
             ),
 
             braking_circuit_altn: BrakeCircuit::new(
+                registry,
                 "ALTN",
                 Volume::new::<gallon>(1.5),
                 Volume::new::<gallon>(0.5),
                 Volume::new::<gallon>(0.13),
             ),
 
+            nose_wheel_steering: SteeringActuator::new(
+                registry,
+                "HYD_NOSE_WHEEL",
+                Angle::new::<degree>(Self::NOSE_WHEEL_STEERING_MAX_ANGLE_DEGREES),
+                AngularVelocity::new::<degree_per_second>(
+                    Self::NOSE_WHEEL_STEERING_MAX_SPEED_DEGREES_PER_SECOND,
+                ),
+                Volume::new::<gallon>(Self::NOSE_WHEEL_STEERING_TOTAL_DISPLACEMENT_GALLON),
+            ),
+            nose_wheel_steering_controller: A320NoseWheelSteeringController::new(registry),
+
+            landing_gear: LandingGear::new(registry),
+            lgciu: LandingGearControlInterfaceUnit::new(registry, 1),
+
             total_sim_time_elapsed: Duration::new(0, 0),
-            lag_time_accumulator: Duration::new(0, 0),
         }
     }
 
-    pub(super) fn update<T: Engine, U: EngineFirePushButtons>(
+    // Here we update everything requiring same refresh as the sim calls us, more likely visual stuff
+    pub(super) fn update(&mut self, context: &UpdateContext) {
+        self.total_sim_time_elapsed += context.delta();
+
+        self.update_at_every_frames(context);
+    }
+
+    // Fixed update loop for main hydraulics, run at FIXED_RATE
+    pub(super) fn update_at_fixed_rate<T: Engine, U: EngineFirePushButtons>(
         &mut self,
         context: &UpdateContext,
         engine1: &T,
         engine2: &T,
         overhead_panel: &A320HydraulicOverheadPanel,
         engine_fire_push_buttons: &U,
-        landing_gear: &LandingGear,
     ) {
-        let min_hyd_loop_timestep =
-            Duration::from_millis(Self::HYDRAULIC_SIM_TIME_STEP_MILLISECONDS);
-
-        self.total_sim_time_elapsed += context.delta();
+        // First update what is currently consumed and given back by each actuator
+        // Todo: might have to split the actuator volumes by expected number of loops
+        self.update_actuators_volume();
 
-        // Time to catch up in our simulation = new delta + time not updated last iteration
-        let time_to_catch = context.delta() + self.lag_time_accumulator;
-
-        // Number of time steps (with floating part) to do according to required time step
-        let number_of_steps_floating_point =
-            time_to_catch.as_secs_f64() / min_hyd_loop_timestep.as_secs_f64();
-
-        // Here we update everything requiring same refresh as the sim calls us, more likely visual stuff
-        self.update_at_every_frames(&context);
-
-        if number_of_steps_floating_point < 1.0 {
-            // Can't do a full time step
-            // we can decide either do an update with smaller step or wait next iteration
-            // for now we only update lag accumulator and chose a hard fixed step: if smaller
-            // than chosen time step we do nothing and wait next iteration
+        self.update_fixed_step(
+            context,
+            engine1,
+            engine2,
+            overhead_panel,
+            engine_fire_push_buttons,
+        );
+    }
 
-            // Time lag is float part only of num of steps (because is < 1.0 here) * fixed time step to get a result in time
-            self.lag_time_accumulator = Duration::from_secs_f64(
-                number_of_steps_floating_point * min_hyd_loop_timestep.as_secs_f64(),
-            );
-        } else {
-            // Int part is the actual number of loops to do
-            // rest of floating part goes into accumulator
-            let num_of_update_loops = number_of_steps_floating_point.floor() as u32;
-
-            self.lag_time_accumulator = Duration::from_secs_f64(
-                (number_of_steps_floating_point - (num_of_update_loops as f64))
-                    * min_hyd_loop_timestep.as_secs_f64(),
-            ); // Keep track of time left after all fixed loop are done
-
-            // Then run fixed update loop for main hydraulics
-            for _ in 0..num_of_update_loops {
-                // First update what is currently consumed and given back by each actuator
-                // Todo: might have to split the actuator volumes by expected number of loops
-                self.update_actuators_volume();
-
-                self.update_fixed_step(
-                    &context.with_delta(min_hyd_loop_timestep),
-                    engine1,
-                    engine2,
-                    overhead_panel,
-                    engine_fire_push_buttons,
-                    landing_gear,
-                );
-            }
+    // This is the "fast" update loop refreshing ACTUATORS_SIM_TIME_STEP_MULTIPLIER times faster
+    // here put everything that needs higher simulation rates like physics solving
+    pub(super) fn update_at_sub_rate(&mut self, context: &UpdateContext) {
+        self.update_fast_rate(context);
+    }
 
-            // This is the "fast" update loop refreshing ACTUATORS_SIM_TIME_STEP_MULT times faster
-            // here put everything that needs higher simulation rates like physics solving
-            let num_of_actuators_update_loops =
-                num_of_update_loops * Self::ACTUATORS_SIM_TIME_STEP_MULTIPLIER;
+    // Interpolates the pressures written to the simulator between the last two fixed steps
+    pub(super) fn interpolate(&mut self, fraction: f64) {
+        self.green_loop.interpolate(fraction);
+        self.blue_loop.interpolate(fraction);
+        self.yellow_loop.interpolate(fraction);
+    }
 
-            // If X times faster we divide step by X
-            let delta_time_physics =
-                min_hyd_loop_timestep / Self::ACTUATORS_SIM_TIME_STEP_MULTIPLIER;
-            for _ in 0..num_of_actuators_update_loops {
-                self.update_fast_rate(&context, &delta_time_physics);
-            }
-        }
+    pub(super) fn landing_gear_control_interface_unit(&self) -> &LandingGearControlInterfaceUnit {
+        &self.lgciu
     }
 
     fn green_edp_has_low_press_fault(&self) -> bool {
@@ -304,9 +333,9 @@ impl A320Hydraulic {
     }
 
     // All the higher frequency updates like physics
-    fn update_fast_rate(&mut self, context: &UpdateContext, delta_time_physics: &Duration) {
+    fn update_fast_rate(&mut self, context: &UpdateContext) {
         self.ram_air_turbine
-            .update_physics(&delta_time_physics, &context.indicated_airspeed());
+            .update_physics(&context.delta(), &context.indicated_airspeed());
     }
 
     // For each hydraulic loop retrieves volumes from and to each actuator and pass it to the loops
@@ -320,12 +349,19 @@ impl A320Hydraulic {
         self.green_loop
             .update_actuator_volumes(&self.braking_circuit_norm);
         self.braking_circuit_norm.reset_accumulators();
+
+        self.green_loop.update_actuator_volumes(&self.landing_gear);
+        self.landing_gear.reset_accumulators();
     }
 
     fn update_yellow_actuators_volume(&mut self) {
         self.yellow_loop
             .update_actuator_volumes(&self.braking_circuit_altn);
         self.braking_circuit_altn.reset_accumulators();
+
+        self.yellow_loop
+            .update_actuator_volumes(&self.nose_wheel_steering);
+        self.nose_wheel_steering.reset_accumulators();
     }
 
     fn update_blue_actuators_volume(&mut self) {}
@@ -338,14 +374,19 @@ impl A320Hydraulic {
         engine2: &T,
         overhead_panel: &A320HydraulicOverheadPanel,
         engine_fire_push_buttons: &U,
-        landing_gear: &LandingGear,
     ) {
+        self.lgciu.update(context, &self.landing_gear);
+
+        self.autobrake_controller
+            .update(context, &self.hyd_brake_logic);
+
         // Process brake logic (which circuit brakes) and send brake demands (how much)
         self.hyd_brake_logic.update_brake_demands(
             context,
             &self.green_loop,
             &self.braking_circuit_altn,
-            &landing_gear,
+            &self.lgciu,
+            &self.autobrake_controller,
         );
         self.hyd_brake_logic.update_brake_pressure_limitation(
             &mut self.braking_circuit_norm,
@@ -470,6 +511,20 @@ impl A320Hydraulic {
 
         self.braking_circuit_norm.update(context, &self.green_loop);
         self.braking_circuit_altn.update(context, &self.yellow_loop);
+
+        self.nose_wheel_steering_controller.update(
+            context,
+            self.power_transfer_unit_controller
+                .nose_wheel_steering_pin_is_inserted(),
+        );
+        self.nose_wheel_steering.update(
+            context,
+            self.yellow_loop.pressure(),
+            &self.nose_wheel_steering_controller,
+        );
+
+        self.landing_gear
+            .update(context, self.green_loop.pressure(), &self.lgciu);
     }
 }
 impl RamAirTurbineHydraulicLoopPressurised for A320Hydraulic {
@@ -506,10 +561,17 @@ impl SimulationElement for A320Hydraulic {
         self.yellow_loop.accept(visitor);
 
         self.hyd_brake_logic.accept(visitor);
+        self.autobrake_controller.accept(visitor);
 
         self.braking_circuit_norm.accept(visitor);
         self.braking_circuit_altn.accept(visitor);
 
+        self.nose_wheel_steering.accept(visitor);
+        self.nose_wheel_steering_controller.accept(visitor);
+
+        self.landing_gear.accept(visitor);
+        self.lgciu.accept(visitor);
+
         visitor.visit(self);
     }
 }
@@ -539,8 +601,10 @@ impl HydraulicLoopController for A320HydraulicLoopController {
 }
 
 struct A320EngineDrivenPumpController {
+    weight_on_wheels_id: VariableIdentifier,
+    low_press_id: VariableIdentifier,
     engine_number: usize,
-    engine_master_on_id: String,
+    engine_master_on_id: VariableIdentifier,
     engine_master_on: bool,
     weight_on_wheels: bool,
     should_pressurise: bool,
@@ -550,10 +614,17 @@ struct A320EngineDrivenPumpController {
 impl A320EngineDrivenPumpController {
     const MIN_ENGINE_OIL_PRESS_THRESHOLD_TO_INHIBIT_FAULT: f64 = 18.;
 
-    fn new(engine_number: usize) -> Self {
+    fn new(registry: &mut VariableRegistry, engine_number: usize) -> Self {
         Self {
+            weight_on_wheels_id: registry.get("SIM ON GROUND"),
+            low_press_id: registry.get(match engine_number {
+                1 => "HYD_GREEN_EDPUMP_LOW_PRESS",
+                2 => "HYD_YELLOW_EDPUMP_LOW_PRESS",
+                _ => panic!("The A320 only supports two engines."),
+            }),
             engine_number,
-            engine_master_on_id: format!("GENERAL ENG STARTER ACTIVE:{}", engine_number),
+            engine_master_on_id: registry
+                .get(&format!("GENERAL ENG STARTER ACTIVE:{}", engine_number)),
             engine_master_on: false,
             weight_on_wheels: true,
             should_pressurise: true,
@@ -617,21 +688,17 @@ impl PumpController for A320EngineDrivenPumpController {
 impl SimulationElement for A320EngineDrivenPumpController {
     fn read(&mut self, state: &mut SimulatorReader) {
         self.engine_master_on = state.read_bool(&self.engine_master_on_id);
-        self.weight_on_wheels = state.read_bool("SIM ON GROUND");
+        self.weight_on_wheels = state.read_bool(&self.weight_on_wheels_id);
     }
 
     fn write(&self, writer: &mut SimulatorWriter) {
-        if self.engine_number == 1 {
-            writer.write_bool("HYD_GREEN_EDPUMP_LOW_PRESS", self.is_pressure_low);
-        } else if self.engine_number == 2 {
-            writer.write_bool("HYD_YELLOW_EDPUMP_LOW_PRESS", self.is_pressure_low);
-        } else {
-            panic!("The A320 only supports two engines.");
-        }
+        writer.write_bool(&self.low_press_id, self.is_pressure_low);
     }
 }
 
 struct A320BlueElectricPumpController {
+    weight_on_wheels_id: VariableIdentifier,
+    low_press_id: VariableIdentifier,
     should_pressurise: bool,
     has_pressure_low_fault: bool,
     is_pressure_low: bool,
@@ -640,8 +707,10 @@ struct A320BlueElectricPumpController {
 impl A320BlueElectricPumpController {
     const MIN_ENGINE_OIL_PRESS_THRESHOLD_TO_INHIBIT_FAULT: f64 = 18.;
 
-    fn new() -> Self {
+    fn new(registry: &mut VariableRegistry) -> Self {
         Self {
+            weight_on_wheels_id: registry.get("SIM ON GROUND"),
+            low_press_id: registry.get("HYD_BLUE_EPUMP_LOW_PRESS"),
             should_pressurise: false,
             has_pressure_low_fault: false,
             is_pressure_low: true,
@@ -713,21 +782,16 @@ impl PumpController for A320BlueElectricPumpController {
 
 impl SimulationElement for A320BlueElectricPumpController {
     fn read(&mut self, state: &mut SimulatorReader) {
-        self.weight_on_wheels = state.read_bool("SIM ON GROUND");
+        self.weight_on_wheels = state.read_bool(&self.weight_on_wheels_id);
     }
 
     fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_bool("HYD_BLUE_EPUMP_LOW_PRESS", self.is_pressure_low);
-    }
-}
-
-impl Default for A320BlueElectricPumpController {
-    fn default() -> Self {
-        Self::new()
+        writer.write_bool(&self.low_press_id, self.is_pressure_low);
     }
 }
 
 struct A320YellowElectricPumpController {
+    low_press_id: VariableIdentifier,
     should_pressurise: bool,
     has_pressure_low_fault: bool,
     is_pressure_low: bool,
@@ -737,8 +801,9 @@ impl A320YellowElectricPumpController {
     const DURATION_OF_YELLOW_PUMP_ACTIVATION_AFTER_CARGO_DOOR_OPERATION: Duration =
         Duration::from_secs(20);
 
-    fn new() -> Self {
+    fn new(registry: &mut VariableRegistry) -> Self {
         Self {
+            low_press_id: registry.get("HYD_YELLOW_EPUMP_LOW_PRESS"),
             should_pressurise: false,
             has_pressure_low_fault: false,
             is_pressure_low: true,
@@ -793,16 +858,14 @@ impl PumpController for A320YellowElectricPumpController {
 }
 impl SimulationElement for A320YellowElectricPumpController {
     fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_bool("HYD_YELLOW_EPUMP_LOW_PRESS", self.is_pressure_low);
-    }
-}
-impl Default for A320YellowElectricPumpController {
-    fn default() -> Self {
-        Self::new()
+        writer.write_bool(&self.low_press_id, self.is_pressure_low);
     }
 }
-
 struct A320PowerTransferUnitController {
+    parking_brake_id: VariableIdentifier,
+    eng_1_master_on_id: VariableIdentifier,
+    eng_2_master_on_id: VariableIdentifier,
+    weight_on_wheels_id: VariableIdentifier,
     should_enable: bool,
     should_inhibit_ptu_after_cargo_door_operation: DelayedFalseLogicGate,
     nose_wheel_steering_pin_inserted: DelayedFalseLogicGate,
@@ -817,8 +880,12 @@ impl A320PowerTransferUnitController {
     const DURATION_AFTER_WHICH_NWS_PIN_IS_REMOVED_AFTER_PUSHBACK: Duration =
         Duration::from_secs(15);
 
-    fn new() -> Self {
+    fn new(registry: &mut VariableRegistry) -> Self {
         Self {
+            parking_brake_id: registry.get("BRAKE PARKING INDICATOR"),
+            eng_1_master_on_id: registry.get("GENERAL ENG STARTER ACTIVE:1"),
+            eng_2_master_on_id: registry.get("GENERAL ENG STARTER ACTIVE:2"),
+            weight_on_wheels_id: registry.get("SIM ON GROUND"),
             should_enable: false,
             should_inhibit_ptu_after_cargo_door_operation: DelayedFalseLogicGate::new(
                 Self::DURATION_OF_PTU_INHIBIT_AFTER_CARGO_DOOR_OPERATION,
@@ -861,7 +928,6 @@ impl A320PowerTransferUnitController {
             && !ptu_inhibited;
     }
 
-    #[cfg(test)]
     fn nose_wheel_steering_pin_is_inserted(&self) -> bool {
         self.nose_wheel_steering_pin_inserted.output()
     }
@@ -873,21 +939,25 @@ impl PowerTransferUnitController for A320PowerTransferUnitController {
 }
 impl SimulationElement for A320PowerTransferUnitController {
     fn read(&mut self, state: &mut SimulatorReader) {
-        self.parking_brake_lever_pos = state.read_bool("BRAKE PARKING INDICATOR");
-        self.eng_1_master_on = state.read_bool("GENERAL ENG STARTER ACTIVE:1");
-        self.eng_2_master_on = state.read_bool("GENERAL ENG STARTER ACTIVE:2");
-        self.weight_on_wheels = state.read_bool("SIM ON GROUND");
+        self.parking_brake_lever_pos = state.read_bool(&self.parking_brake_id);
+        self.eng_1_master_on = state.read_bool(&self.eng_1_master_on_id);
+        self.eng_2_master_on = state.read_bool(&self.eng_2_master_on_id);
+        self.weight_on_wheels = state.read_bool(&self.weight_on_wheels_id);
     }
 }
 
 struct A320RamAirTurbineController {
+    eng_1_master_on_id: VariableIdentifier,
+    eng_2_master_on_id: VariableIdentifier,
     should_deploy: bool,
     eng_1_master_on: bool,
     eng_2_master_on: bool,
 }
 impl A320RamAirTurbineController {
-    fn new() -> Self {
+    fn new(registry: &mut VariableRegistry) -> Self {
         Self {
+            eng_1_master_on_id: registry.get("GENERAL ENG STARTER ACTIVE:1"),
+            eng_2_master_on_id: registry.get("GENERAL ENG STARTER ACTIVE:2"),
             should_deploy: false,
             eng_1_master_on: false,
             eng_2_master_on: false,
@@ -910,12 +980,102 @@ impl RamAirTurbineController for A320RamAirTurbineController {
 }
 impl SimulationElement for A320RamAirTurbineController {
     fn read(&mut self, state: &mut SimulatorReader) {
-        self.eng_1_master_on = state.read_bool("GENERAL ENG STARTER ACTIVE:1");
-        self.eng_2_master_on = state.read_bool("GENERAL ENG STARTER ACTIVE:2");
+        self.eng_1_master_on = state.read_bool(&self.eng_1_master_on_id);
+        self.eng_2_master_on = state.read_bool(&self.eng_2_master_on_id);
+    }
+}
+
+/// Computes the nose wheel steering angle from the tillers and rudder pedals. The authority
+/// of both decreases with ground speed, such that steering is only possible with the tillers
+/// while taxiing and with the pedals during the takeoff and landing roll.
+struct A320NoseWheelSteeringController {
+    tiller_position_id: VariableIdentifier,
+    rudder_pedal_position_id: VariableIdentifier,
+    anti_skid_id: VariableIdentifier,
+    should_engage: bool,
+    requested_position: Angle,
+
+    tiller_position: f64,
+    rudder_pedal_position: f64,
+    anti_skid_and_nose_wheel_steering_on: bool,
+}
+impl A320NoseWheelSteeringController {
+    const MAX_TILLER_ANGLE_DEGREES: f64 = 75.;
+    const TILLER_AUTHORITY_SPEED_BREAKPOINTS_KNOT: [f64; 3] = [0., 20., 70.];
+    const TILLER_AUTHORITY: [f64; 3] = [1., 1., 0.];
+
+    const MAX_PEDAL_ANGLE_DEGREES: f64 = 6.;
+    const PEDAL_AUTHORITY_SPEED_BREAKPOINTS_KNOT: [f64; 3] = [0., 40., 130.];
+    const PEDAL_AUTHORITY: [f64; 3] = [1., 1., 0.];
+
+    fn new(registry: &mut VariableRegistry) -> Self {
+        Self {
+            tiller_position_id: registry.get("TILLER_HANDLE_POSITION"),
+            rudder_pedal_position_id: registry.get("RUDDER PEDAL POSITION"),
+            anti_skid_id: registry.get("ANTISKID BRAKES ACTIVE"),
+            should_engage: false,
+            requested_position: Angle::new::<degree>(0.),
+
+            tiller_position: 0.,
+            rudder_pedal_position: 0.,
+            anti_skid_and_nose_wheel_steering_on: true,
+        }
+    }
+
+    fn update(&mut self, context: &UpdateContext, nose_wheel_steering_pin_is_inserted: bool) {
+        // The towing pin bypasses the steering actuator such that the tug can turn the wheel
+        self.should_engage = self.anti_skid_and_nose_wheel_steering_on
+            && context.is_on_ground()
+            && !nose_wheel_steering_pin_is_inserted;
+
+        let ground_speed_knot = context.ground_speed().get::<knot>().abs();
+        let tiller_angle = self.tiller_position
+            * Self::MAX_TILLER_ANGLE_DEGREES
+            * interpolation(
+                &Self::TILLER_AUTHORITY_SPEED_BREAKPOINTS_KNOT,
+                &Self::TILLER_AUTHORITY,
+                ground_speed_knot,
+            );
+        let pedal_angle = self.rudder_pedal_position
+            * Self::MAX_PEDAL_ANGLE_DEGREES
+            * interpolation(
+                &Self::PEDAL_AUTHORITY_SPEED_BREAKPOINTS_KNOT,
+                &Self::PEDAL_AUTHORITY,
+                ground_speed_knot,
+            );
+
+        self.requested_position = Angle::new::<degree>((tiller_angle + pedal_angle).clamp(
+            -Self::MAX_TILLER_ANGLE_DEGREES,
+            Self::MAX_TILLER_ANGLE_DEGREES,
+        ));
+    }
+}
+impl SteeringController for A320NoseWheelSteeringController {
+    fn should_engage(&self) -> bool {
+        self.should_engage
+    }
+
+    fn requested_position(&self) -> Angle {
+        self.requested_position
+    }
+}
+impl SimulationElement for A320NoseWheelSteeringController {
+    fn read(&mut self, state: &mut SimulatorReader) {
+        self.tiller_position = state.read_f64(&self.tiller_position_id);
+        self.rudder_pedal_position = state.read_f64(&self.rudder_pedal_position_id);
+        self.anti_skid_and_nose_wheel_steering_on = state.read_bool(&self.anti_skid_id);
     }
 }
 
 struct A320HydraulicBrakingLogic {
+    parking_brake_id: VariableIdentifier,
+    weight_on_wheels_id: VariableIdentifier,
+    gear_handle_position_id: VariableIdentifier,
+    anti_skid_id: VariableIdentifier,
+    left_brake_position_id: VariableIdentifier,
+    right_brake_position_id: VariableIdentifier,
+    left_wheel_rpm_id: VariableIdentifier,
+    right_wheel_rpm_id: VariableIdentifier,
     parking_brake_demand: bool,
     weight_on_wheels: bool,
     is_gear_lever_down: bool,
@@ -928,7 +1088,9 @@ struct A320HydraulicBrakingLogic {
     normal_brakes_available: bool,
     should_disable_auto_brake_when_retracting: DelayedTrueLogicGate,
     anti_skid_activated: bool,
-    autobrakes_setting: u8,
+    anti_skid: AntiSkid,
+    left_wheel_rpm: f64,
+    right_wheel_rpm: f64,
 }
 /// Implements brakes computers logic
 impl A320HydraulicBrakingLogic {
@@ -943,8 +1105,18 @@ impl A320HydraulicBrakingLogic {
 
     const AUTOBRAKE_GEAR_RETRACTION_DURATION_S: f64 = 3.;
 
-    fn new() -> A320HydraulicBrakingLogic {
+    const MAIN_WHEEL_RADIUS_METER: f64 = 0.584;
+
+    fn new(registry: &mut VariableRegistry) -> A320HydraulicBrakingLogic {
         A320HydraulicBrakingLogic {
+            parking_brake_id: registry.get("BRAKE PARKING INDICATOR"),
+            weight_on_wheels_id: registry.get("SIM ON GROUND"),
+            gear_handle_position_id: registry.get("GEAR HANDLE POSITION"),
+            anti_skid_id: registry.get("ANTISKID BRAKES ACTIVE"),
+            left_brake_position_id: registry.get("BRAKE LEFT POSITION"),
+            right_brake_position_id: registry.get("BRAKE RIGHT POSITION"),
+            left_wheel_rpm_id: registry.get("LEFT WHEEL RPM"),
+            right_wheel_rpm_id: registry.get("RIGHT WHEEL RPM"),
             // Position of parking brake lever
             parking_brake_demand: true,
             weight_on_wheels: true,
@@ -964,7 +1136,9 @@ impl A320HydraulicBrakingLogic {
                 Duration::from_secs_f64(Self::AUTOBRAKE_GEAR_RETRACTION_DURATION_S),
             ),
             anti_skid_activated: true,
-            autobrakes_setting: 0,
+            anti_skid: AntiSkid::new(),
+            left_wheel_rpm: 0.,
+            right_wheel_rpm: 0.,
         }
     }
 
@@ -1012,13 +1186,30 @@ impl A320HydraulicBrakingLogic {
         }
     }
 
-    /// Updates final brake demands per hydraulic loop based on pilot pedal demands
+    fn normal_brakes_available(&self) -> bool {
+        self.normal_brakes_available
+    }
+
+    fn anti_skid_activated(&self) -> bool {
+        self.anti_skid_activated
+    }
+
+    fn left_brake_pilot_input(&self) -> f64 {
+        self.left_brake_pilot_input
+    }
+
+    fn right_brake_pilot_input(&self) -> f64 {
+        self.right_brake_pilot_input
+    }
+
+    /// Updates final brake demands per hydraulic loop based on pilot pedal and autobrake demands
     fn update_brake_demands(
         &mut self,
         context: &UpdateContext,
         green_loop: &HydraulicLoop,
         alternate_circuit: &BrakeCircuit,
-        landing_gear: &LandingGear,
+        landing_gear: &impl LandingGearPosition,
+        autobrake_controller: &A320AutobrakeController,
     ) {
         self.update_normal_braking_availability(&green_loop.pressure());
 
@@ -1046,8 +1237,13 @@ impl A320HydraulicBrakingLogic {
                 && !self.parking_brake_demand;
 
             if green_used_for_brakes {
-                self.left_brake_green_output = self.left_brake_pilot_input;
-                self.right_brake_green_output = self.right_brake_pilot_input;
+                // Pilot can always brake harder than the autobrake
+                self.left_brake_green_output = self
+                    .left_brake_pilot_input
+                    .max(autobrake_controller.brake_output());
+                self.right_brake_green_output = self
+                    .right_brake_pilot_input
+                    .max(autobrake_controller.brake_output());
                 self.left_brake_yellow_output = 0.;
                 self.right_brake_yellow_output = 0.;
             } else {
@@ -1075,6 +1271,16 @@ impl A320HydraulicBrakingLogic {
             }
         }
 
+        // Anti-skid works on whichever circuit brakes, but only on ground as it needs a reference speed.
+        // Without anti-skid, alternate braking relies on the pressure limitation instead.
+        self.anti_skid.update(
+            context,
+            self.anti_skid_activated && self.weight_on_wheels,
+            context.ground_speed(),
+            Self::wheel_speed(self.left_wheel_rpm),
+            Self::wheel_speed(self.right_wheel_rpm),
+        );
+
         // Limiting final values
         self.left_brake_yellow_output = self.left_brake_yellow_output.min(1.).max(0.);
         self.right_brake_yellow_output = self.right_brake_yellow_output.min(1.).max(0.);
@@ -1082,35 +1288,260 @@ impl A320HydraulicBrakingLogic {
         self.right_brake_green_output = self.right_brake_green_output.min(1.).max(0.);
     }
 
+    fn wheel_speed(wheel_rpm: f64) -> Velocity {
+        Velocity::new::<meter_per_second>(
+            wheel_rpm * 2. * std::f64::consts::PI * Self::MAIN_WHEEL_RADIUS_METER / 60.,
+        )
+    }
+
     fn send_brake_demands(&mut self, norm: &mut BrakeCircuit, altn: &mut BrakeCircuit) {
         norm.set_brake_demand_left(self.left_brake_green_output);
         norm.set_brake_demand_right(self.right_brake_green_output);
         altn.set_brake_demand_left(self.left_brake_yellow_output);
         altn.set_brake_demand_right(self.right_brake_yellow_output);
+
+        norm.set_anti_skid_release_left(self.anti_skid.left_release());
+        norm.set_anti_skid_release_right(self.anti_skid.right_release());
+        altn.set_anti_skid_release_left(self.anti_skid.left_release());
+        altn.set_anti_skid_release_right(self.anti_skid.right_release());
     }
 }
 
 impl SimulationElement for A320HydraulicBrakingLogic {
     fn read(&mut self, state: &mut SimulatorReader) {
-        self.parking_brake_demand = state.read_bool("BRAKE PARKING INDICATOR");
-        self.weight_on_wheels = state.read_bool("SIM ON GROUND");
-        self.is_gear_lever_down = state.read_bool("GEAR HANDLE POSITION");
-        self.anti_skid_activated = state.read_bool("ANTISKID BRAKES ACTIVE");
-        self.left_brake_pilot_input = state.read_f64("BRAKE LEFT POSITION") / 100.0;
-        self.right_brake_pilot_input = state.read_f64("BRAKE RIGHT POSITION") / 100.0;
-        self.autobrakes_setting = state.read_f64("AUTOBRAKES SETTING").floor() as u8;
+        self.parking_brake_demand = state.read_bool(&self.parking_brake_id);
+        self.weight_on_wheels = state.read_bool(&self.weight_on_wheels_id);
+        self.is_gear_lever_down = state.read_bool(&self.gear_handle_position_id);
+        self.anti_skid_activated = state.read_bool(&self.anti_skid_id);
+        self.left_brake_pilot_input = state.read_f64(&self.left_brake_position_id) / 100.0;
+        self.right_brake_pilot_input = state.read_f64(&self.right_brake_position_id) / 100.0;
+        self.left_wheel_rpm = state.read_f64(&self.left_wheel_rpm_id);
+        self.right_wheel_rpm = state.read_f64(&self.right_wheel_rpm_id);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum A320AutobrakeMode {
+    None = 0,
+    Low = 1,
+    Med = 2,
+    Max = 3,
+}
+impl From<f64> for A320AutobrakeMode {
+    fn from(value: f64) -> Self {
+        match value.floor() as u8 {
+            1 => A320AutobrakeMode::Low,
+            2 => A320AutobrakeMode::Med,
+            3 => A320AutobrakeMode::Max,
+            _ => A320AutobrakeMode::None,
+        }
+    }
+}
+
+/// Implements the autobrake logic: arming of the selected mode, activation at touchdown
+/// (LO and MED) or at rejected takeoff (MAX), and disarming by pedal braking.
+/// The brake demand computed here is only applied on the green normal brake circuit.
+struct A320AutobrakeController {
+    autobrakes_setting_id: VariableIdentifier,
+    spoilers_handle_position_id: VariableIdentifier,
+    armed_mode_id: VariableIdentifier,
+    is_active_id: VariableIdentifier,
+    decel_light_id: VariableIdentifier,
+
+    selected_mode: A320AutobrakeMode,
+    last_selected_mode: A320AutobrakeMode,
+    armed_mode: A320AutobrakeMode,
+    is_active: bool,
+
+    spoilers_handle_position: f64,
+
+    low_mode_activation: DelayedTrueLogicGate,
+    med_mode_activation: DelayedTrueLogicGate,
+
+    deceleration_governor: AutobrakeDecelerationGovernor,
+}
+impl A320AutobrakeController {
+    const LOW_MODE_TARGET_DECELERATION_METER_PER_S2: f64 = 1.7;
+    const MED_MODE_TARGET_DECELERATION_METER_PER_S2: f64 = 3.;
+    const MAX_MODE_TARGET_DECELERATION_METER_PER_S2: f64 = 6.;
+
+    const LOW_MODE_ACTIVATION_DELAY_AFTER_GROUND_SPOILERS_S: f64 = 4.;
+    const MED_MODE_ACTIVATION_DELAY_AFTER_GROUND_SPOILERS_S: f64 = 2.;
+
+    // MAX mode is for rejected takeoffs and activates above this speed only
+    const MAX_MODE_MIN_ACTIVATION_SPEED_KNOT: f64 = 72.;
+
+    // Pedal deflection above which an active autobrake is disarmed
+    const PEDAL_DISARM_THRESHOLD: f64 = 0.5;
+
+    // Spoilers handle position above which ground spoilers are considered deployed
+    const GROUND_SPOILERS_DEPLOYED_HANDLE_POSITION: f64 = 0.9;
+
+    fn new(registry: &mut VariableRegistry) -> Self {
+        Self {
+            autobrakes_setting_id: registry.get("AUTOBRAKES SETTING"),
+            spoilers_handle_position_id: registry.get("SPOILERS HANDLE POSITION"),
+            armed_mode_id: registry.get("AUTOBRAKES_ARMED_MODE"),
+            is_active_id: registry.get("AUTOBRAKES_ACTIVE"),
+            decel_light_id: registry.get("AUTOBRAKES_DECEL_LIGHT"),
+
+            selected_mode: A320AutobrakeMode::None,
+            last_selected_mode: A320AutobrakeMode::None,
+            armed_mode: A320AutobrakeMode::None,
+            is_active: false,
+
+            spoilers_handle_position: 0.,
+
+            low_mode_activation: DelayedTrueLogicGate::new(Duration::from_secs_f64(
+                Self::LOW_MODE_ACTIVATION_DELAY_AFTER_GROUND_SPOILERS_S,
+            )),
+            med_mode_activation: DelayedTrueLogicGate::new(Duration::from_secs_f64(
+                Self::MED_MODE_ACTIVATION_DELAY_AFTER_GROUND_SPOILERS_S,
+            )),
+
+            deceleration_governor: AutobrakeDecelerationGovernor::new(),
+        }
+    }
+
+    fn update(&mut self, context: &UpdateContext, braking_logic: &A320HydraulicBrakingLogic) {
+        self.update_armed_mode(context, braking_logic);
+
+        let ground_spoilers_are_deployed = context.is_on_ground()
+            && self.spoilers_handle_position > Self::GROUND_SPOILERS_DEPLOYED_HANDLE_POSITION;
+        self.low_mode_activation
+            .update(context, ground_spoilers_are_deployed);
+        self.med_mode_activation
+            .update(context, ground_spoilers_are_deployed);
+
+        let should_activate = match self.armed_mode {
+            A320AutobrakeMode::None => false,
+            A320AutobrakeMode::Low => self.low_mode_activation.output(),
+            A320AutobrakeMode::Med => self.med_mode_activation.output(),
+            A320AutobrakeMode::Max => {
+                ground_spoilers_are_deployed
+                    && context.indicated_airspeed()
+                        > Velocity::new::<knot>(Self::MAX_MODE_MIN_ACTIVATION_SPEED_KNOT)
+            }
+        };
+
+        self.is_active = self.armed_mode != A320AutobrakeMode::None
+            && ground_spoilers_are_deployed
+            && (self.is_active || should_activate);
+
+        self.deceleration_governor.engage_when(self.is_active);
+        self.deceleration_governor
+            .update(context, self.target_deceleration());
+    }
+
+    fn update_armed_mode(
+        &mut self,
+        context: &UpdateContext,
+        braking_logic: &A320HydraulicBrakingLogic,
+    ) {
+        if self.selected_mode != self.last_selected_mode {
+            let can_arm_selected_mode =
+                self.selected_mode != A320AutobrakeMode::Max || context.is_on_ground();
+            self.armed_mode = if can_arm_selected_mode {
+                self.selected_mode
+            } else {
+                A320AutobrakeMode::None
+            };
+            self.last_selected_mode = self.selected_mode;
+        }
+
+        let pedals_disarm_autobrake = self.is_active
+            && (braking_logic.left_brake_pilot_input() > Self::PEDAL_DISARM_THRESHOLD
+                || braking_logic.right_brake_pilot_input() > Self::PEDAL_DISARM_THRESHOLD);
+
+        // Once disarmed, the autobrake remains disarmed until a mode is selected again
+        if self.selected_mode == A320AutobrakeMode::None
+            || !braking_logic.normal_brakes_available()
+            || !braking_logic.anti_skid_activated()
+            || pedals_disarm_autobrake
+        {
+            self.armed_mode = A320AutobrakeMode::None;
+            self.is_active = false;
+        }
+    }
+
+    fn target_deceleration(&self) -> Acceleration {
+        Acceleration::new::<meter_per_second_squared>(match self.armed_mode {
+            A320AutobrakeMode::None => 0.,
+            A320AutobrakeMode::Low => Self::LOW_MODE_TARGET_DECELERATION_METER_PER_S2,
+            A320AutobrakeMode::Med => Self::MED_MODE_TARGET_DECELERATION_METER_PER_S2,
+            A320AutobrakeMode::Max => Self::MAX_MODE_TARGET_DECELERATION_METER_PER_S2,
+        })
+    }
+
+    /// Brake demand of the autobrake for both the left and right green brakes.
+    fn brake_output(&self) -> f64 {
+        self.deceleration_governor.output()
+    }
+
+    #[cfg(test)]
+    fn armed_mode(&self) -> A320AutobrakeMode {
+        self.armed_mode
+    }
+
+    #[cfg(test)]
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+}
+impl SimulationElement for A320AutobrakeController {
+    fn read(&mut self, state: &mut SimulatorReader) {
+        self.selected_mode = state.read_f64(&self.autobrakes_setting_id).into();
+        self.spoilers_handle_position = state.read_f64(&self.spoilers_handle_position_id) / 100.0;
+    }
+
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_f64(&self.armed_mode_id, self.armed_mode as u8 as f64);
+        writer.write_bool(&self.is_active_id, self.is_active);
+        writer.write_bool(
+            &self.decel_light_id,
+            self.deceleration_governor.is_on_target(),
+        );
+    }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        snapshot.write_f64("AUTOBRAKES_ARMED_MODE", self.armed_mode as u8 as f64);
+        snapshot.write_f64(
+            "AUTOBRAKES_LAST_SELECTED_MODE",
+            self.last_selected_mode as u8 as f64,
+        );
+        snapshot.write_bool("AUTOBRAKES_ACTIVE", self.is_active);
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(mode) = snapshot.read_f64("AUTOBRAKES_ARMED_MODE") {
+            self.armed_mode = mode.into();
+        }
+        // Restoring the last selected mode prevents the mode read on the next tick
+        // from being considered a new selection, which would re-arm it.
+        if let Some(mode) = snapshot.read_f64("AUTOBRAKES_LAST_SELECTED_MODE") {
+            self.last_selected_mode = mode.into();
+        }
+        if let Some(is_active) = snapshot.read_bool("AUTOBRAKES_ACTIVE") {
+            self.is_active = is_active;
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state.node("AUTOBRAKES");
+        node.write_f64("ARMED_MODE", self.armed_mode as u8 as f64);
+        node.write_bool("IS_ACTIVE", self.is_active);
     }
 }
 
 struct Door {
-    exit_id: String,
+    exit_id: VariableIdentifier,
     position: f64,
     previous_position: f64,
 }
 impl Door {
-    fn new(id: usize) -> Self {
+    fn new(registry: &mut VariableRegistry, id: usize) -> Self {
         Self {
-            exit_id: format!("EXIT OPEN:{}", id),
+            exit_id: registry.get(&format!("EXIT OPEN:{}", id)),
             position: 0.,
             previous_position: 0.,
         }
@@ -1128,6 +1559,8 @@ impl SimulationElement for Door {
 }
 
 struct PushbackTug {
+    angle_id: VariableIdentifier,
+    state_id: VariableIdentifier,
     angle: f64,
     previous_angle: f64,
     // Type of pushback:
@@ -1142,8 +1575,10 @@ struct PushbackTug {
 impl PushbackTug {
     const STATE_NO_PUSHBACK: f64 = 3.;
 
-    fn new() -> Self {
+    fn new(registry: &mut VariableRegistry) -> Self {
         Self {
+            angle_id: registry.get("PUSHBACK ANGLE"),
+            state_id: registry.get("PUSHBACK STATE"),
             angle: 0.,
             previous_angle: 0.,
             state: Self::STATE_NO_PUSHBACK,
@@ -1172,8 +1607,8 @@ impl PushbackTug {
 impl SimulationElement for PushbackTug {
     fn read(&mut self, state: &mut SimulatorReader) {
         self.previous_angle = self.angle;
-        self.angle = state.read_f64("PUSHBACK ANGLE");
-        self.state = state.read_f64("PUSHBACK STATE");
+        self.angle = state.read_f64(&self.angle_id);
+        self.state = state.read_f64(&self.state_id);
     }
 }
 
@@ -1187,15 +1622,18 @@ pub(super) struct A320HydraulicOverheadPanel {
     blue_epump_override_push_button: OnOffFaultPushButton,
 }
 impl A320HydraulicOverheadPanel {
-    pub(super) fn new() -> A320HydraulicOverheadPanel {
+    pub(super) fn new(registry: &mut VariableRegistry) -> A320HydraulicOverheadPanel {
         A320HydraulicOverheadPanel {
-            edp1_push_button: AutoOffFaultPushButton::new_auto("HYD_ENG_1_PUMP"),
-            edp2_push_button: AutoOffFaultPushButton::new_auto("HYD_ENG_2_PUMP"),
-            blue_epump_push_button: AutoOffFaultPushButton::new_auto("HYD_EPUMPB"),
-            ptu_push_button: AutoOffFaultPushButton::new_auto("HYD_PTU"),
-            rat_push_button: AutoOffFaultPushButton::new_off("HYD_RAT"),
-            yellow_epump_push_button: AutoOnFaultPushButton::new_auto("HYD_EPUMPY"),
-            blue_epump_override_push_button: OnOffFaultPushButton::new_off("HYD_EPUMPY_OVRD"),
+            edp1_push_button: AutoOffFaultPushButton::new_auto(registry, "HYD_ENG_1_PUMP"),
+            edp2_push_button: AutoOffFaultPushButton::new_auto(registry, "HYD_ENG_2_PUMP"),
+            blue_epump_push_button: AutoOffFaultPushButton::new_auto(registry, "HYD_EPUMPB"),
+            ptu_push_button: AutoOffFaultPushButton::new_auto(registry, "HYD_PTU"),
+            rat_push_button: AutoOffFaultPushButton::new_off(registry, "HYD_RAT"),
+            yellow_epump_push_button: AutoOnFaultPushButton::new_auto(registry, "HYD_EPUMPY"),
+            blue_epump_override_push_button: OnOffFaultPushButton::new_off(
+                registry,
+                "HYD_EPUMPY_OVRD",
+            ),
         }
     }
 
@@ -1276,17 +1714,15 @@ mod tests {
             hydraulics: A320Hydraulic,
             overhead: A320HydraulicOverheadPanel,
             engine_fire_overhead: EngineFireOverheadPanel,
-            landing_gear: LandingGear,
         }
         impl A320HydraulicsTestAircraft {
-            fn new() -> Self {
+            fn new(registry: &mut VariableRegistry) -> Self {
                 Self {
-                    engine_1: LeapEngine::new(1),
-                    engine_2: LeapEngine::new(2),
-                    hydraulics: A320Hydraulic::new(),
-                    overhead: A320HydraulicOverheadPanel::new(),
-                    engine_fire_overhead: EngineFireOverheadPanel::new(),
-                    landing_gear: LandingGear::new(),
+                    engine_1: LeapEngine::new(registry, 1),
+                    engine_2: LeapEngine::new(registry, 2),
+                    hydraulics: A320Hydraulic::new(registry),
+                    overhead: A320HydraulicOverheadPanel::new(registry),
+                    engine_fire_overhead: EngineFireOverheadPanel::new(registry),
                 }
             }
 
@@ -1335,18 +1771,33 @@ mod tests {
         }
 
         impl Aircraft for A320HydraulicsTestAircraft {
+            fn fixed_rates(&self) -> Vec<FixedRate> {
+                vec![A320Hydraulic::FIXED_RATE]
+            }
+
             fn update_after_power_distribution(&mut self, context: &UpdateContext) {
-                self.hydraulics.update(
+                self.hydraulics.update(context);
+            }
+
+            fn update_at_fixed_rate(&mut self, _rate: FixedRate, context: &UpdateContext) {
+                self.hydraulics.update_at_fixed_rate(
                     context,
                     &self.engine_1,
                     &self.engine_2,
                     &self.overhead,
                     &self.engine_fire_overhead,
-                    &self.landing_gear,
                 );
 
                 self.overhead.update(&self.hydraulics);
             }
+
+            fn update_at_sub_rate(&mut self, _rate: FixedRate, context: &UpdateContext) {
+                self.hydraulics.update_at_sub_rate(context);
+            }
+
+            fn interpolate_fixed_rate(&mut self, _rate: FixedRate, fraction: f64) {
+                self.hydraulics.interpolate(fraction);
+            }
         }
         impl SimulationElement for A320HydraulicsTestAircraft {
             fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
@@ -1355,7 +1806,6 @@ mod tests {
                 self.hydraulics.accept(visitor);
                 self.overhead.accept(visitor);
                 self.engine_fire_overhead.accept(visitor);
-                self.landing_gear.accept(visitor);
 
                 visitor.visit(self);
             }
@@ -1367,9 +1817,10 @@ mod tests {
         }
         impl A320HydraulicsTestBed {
             fn new() -> Self {
-                let mut aircraft = A320HydraulicsTestAircraft::new();
+                let mut registry = VariableRegistry::new();
+                let mut aircraft = A320HydraulicsTestAircraft::new(&mut registry);
                 Self {
-                    simulation_test_bed: SimulationTestBed::seeded_with(&mut aircraft),
+                    simulation_test_bed: SimulationTestBed::seeded_with(registry, &mut aircraft),
                     aircraft,
                 }
             }
@@ -1659,8 +2110,6 @@ mod tests {
             }
 
             fn set_gear_up(mut self) -> Self {
-                self.simulation_test_bed
-                    .write_f64("GEAR CENTER POSITION", 0.);
                 self.simulation_test_bed
                     .write_bool("GEAR HANDLE POSITION", false);
 
@@ -1668,14 +2117,19 @@ mod tests {
             }
 
             fn set_gear_down(mut self) -> Self {
-                self.simulation_test_bed
-                    .write_f64("GEAR CENTER POSITION", 100.);
                 self.simulation_test_bed
                     .write_bool("GEAR HANDLE POSITION", true);
 
                 self
             }
 
+            fn set_gravity_extension_handle(mut self, is_cranked: bool) -> Self {
+                self.simulation_test_bed
+                    .write_bool("GEAR EMERGENCY HANDLE POSITION", is_cranked);
+
+                self
+            }
+
             fn set_anti_skid(mut self, is_set: bool) -> Self {
                 self.simulation_test_bed
                     .write_bool("ANTISKID BRAKES ACTIVE", is_set);
@@ -1747,6 +2201,90 @@ mod tests {
                 self
             }
 
+            fn set_autobrake_mode(mut self, mode: A320AutobrakeMode) -> Self {
+                self.simulation_test_bed
+                    .write_f64("AUTOBRAKES SETTING", mode as u8 as f64);
+                self
+            }
+
+            fn set_ground_spoilers_deployed(mut self, is_deployed: bool) -> Self {
+                self.simulation_test_bed.write_f64(
+                    "SPOILERS HANDLE POSITION",
+                    if is_deployed { 100. } else { 0. },
+                );
+                self
+            }
+
+            fn rolling_on_runway_at(mut self, airspeed: Velocity) -> Self {
+                self.simulation_test_bed.set_on_ground(true);
+                self.simulation_test_bed.set_indicated_airspeed(airspeed);
+                self
+            }
+
+            fn decelerating_at(mut self, deceleration: Acceleration) -> Self {
+                self.simulation_test_bed
+                    .set_long_acceleration(-deceleration);
+                self
+            }
+
+            fn set_ground_speed(mut self, ground_speed: Velocity) -> Self {
+                self.simulation_test_bed.set_ground_speed(ground_speed);
+                self
+            }
+
+            fn set_wheels_rpm(mut self, left_rpm: f64, right_rpm: f64) -> Self {
+                self.simulation_test_bed
+                    .write_f64("LEFT WHEEL RPM", left_rpm);
+                self.simulation_test_bed
+                    .write_f64("RIGHT WHEEL RPM", right_rpm);
+                self
+            }
+
+            fn set_tiller_position(mut self, position: f64) -> Self {
+                self.simulation_test_bed
+                    .write_f64("TILLER_HANDLE_POSITION", position);
+                self
+            }
+
+            fn set_rudder_pedal_position(mut self, position: f64) -> Self {
+                self.simulation_test_bed
+                    .write_f64("RUDDER PEDAL POSITION", position);
+                self
+            }
+
+            fn nose_wheel_steering_angle(&mut self) -> Angle {
+                Angle::new::<degree>(
+                    self.simulation_test_bed
+                        .read_f64("HYD_NOSE_WHEEL_STEERING_ANGLE"),
+                )
+            }
+
+            fn is_nose_wheel_steering_available(&mut self) -> bool {
+                self.simulation_test_bed
+                    .read_bool("HYD_NOSE_WHEEL_STEERING_AVAILABLE")
+            }
+
+            fn is_gear_up_and_locked(&self) -> bool {
+                self.aircraft.hydraulics.lgciu.is_up_and_locked()
+            }
+
+            fn is_gear_down_and_locked(&self) -> bool {
+                self.aircraft.hydraulics.lgciu.is_down_and_locked()
+            }
+
+            fn autobrake_armed_mode(&self) -> A320AutobrakeMode {
+                self.aircraft.hydraulics.autobrake_controller.armed_mode()
+            }
+
+            fn is_autobrake_active(&mut self) -> bool {
+                self.aircraft.hydraulics.autobrake_controller.is_active()
+                    && self.simulation_test_bed.read_bool("AUTOBRAKES_ACTIVE")
+            }
+
+            fn is_autobrake_decel_light_on(&mut self) -> bool {
+                self.simulation_test_bed.read_bool("AUTOBRAKES_DECEL_LIGHT")
+            }
+
             fn empty_brake_accumulator_using_park_brake(mut self) -> Self {
                 self = self
                     .set_park_brake(true)
@@ -2020,6 +2558,26 @@ mod tests {
             assert!(test_bed.yellow_pressure() < Pressure::new::<psi>(3100.));
         }
 
+        #[test]
+        fn green_pressure_written_at_frame_rate_rises_smoothly_between_fixed_steps() {
+            let mut test_bed = test_bed_with()
+                .engines_off()
+                .on_the_ground()
+                .set_cold_dark_inputs()
+                .run_one_tick()
+                .start_eng1(Ratio::new::<percent>(80.))
+                .run_one_tick();
+
+            let mut previous_pressure = test_bed.green_pressure();
+            for _ in 0..8 {
+                test_bed = test_bed.run_waiting_for(Duration::from_millis(25));
+                let pressure = test_bed.green_pressure();
+
+                assert!(pressure > previous_pressure);
+                previous_pressure = pressure;
+            }
+        }
+
         #[test]
         fn green_edp_buildup() {
             let mut test_bed = test_bed_with()
@@ -3151,6 +3709,364 @@ mod tests {
             assert!(test_bed.get_brake_right_yellow_pressure() < Pressure::new::<psi>(50.));
         }
 
+        fn test_bed_ready_for_autobrake() -> A320HydraulicsTestBed {
+            test_bed_with()
+                .engines_off()
+                .on_the_ground()
+                .set_cold_dark_inputs()
+                .start_eng1(Ratio::new::<percent>(80.))
+                .start_eng2(Ratio::new::<percent>(80.))
+                .set_park_brake(false)
+                .run_waiting_for(Duration::from_secs(10))
+        }
+
+        #[test]
+        fn autobrake_arms_selected_mode() {
+            let test_bed = test_bed_ready_for_autobrake()
+                .set_autobrake_mode(A320AutobrakeMode::Med)
+                .run_one_tick();
+
+            assert!(test_bed.autobrake_armed_mode() == A320AutobrakeMode::Med);
+        }
+
+        #[test]
+        fn autobrake_max_cannot_be_armed_in_flight() {
+            let test_bed = test_bed_ready_for_autobrake()
+                .in_flight()
+                .set_gear_down()
+                .run_waiting_for(Duration::from_secs(1))
+                .set_autobrake_mode(A320AutobrakeMode::Max)
+                .run_one_tick();
+
+            assert!(test_bed.autobrake_armed_mode() == A320AutobrakeMode::None);
+        }
+
+        #[test]
+        fn autobrake_does_not_arm_without_anti_skid() {
+            let test_bed = test_bed_ready_for_autobrake()
+                .set_anti_skid(false)
+                .set_autobrake_mode(A320AutobrakeMode::Low)
+                .run_one_tick();
+
+            assert!(test_bed.autobrake_armed_mode() == A320AutobrakeMode::None);
+        }
+
+        #[test]
+        fn autobrake_restored_from_snapshot_remains_armed_in_flight() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_autobrake_mode(A320AutobrakeMode::Max)
+                .run_one_tick();
+            let snapshot = Snapshot::take(&mut test_bed.aircraft.hydraulics.autobrake_controller);
+
+            // MAX cannot be selected in flight, thus it only remains armed if the restore
+            // doesn't consider the selected mode read on the next tick a new selection.
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .in_flight()
+                .set_gear_down()
+                .run_waiting_for(Duration::from_secs(1));
+            snapshot.restore_into(&mut test_bed.aircraft.hydraulics.autobrake_controller);
+            test_bed = test_bed
+                .set_autobrake_mode(A320AutobrakeMode::Max)
+                .run_one_tick();
+
+            assert!(test_bed.autobrake_armed_mode() == A320AutobrakeMode::Max);
+        }
+
+        #[test]
+        fn autobrake_med_activates_two_seconds_after_ground_spoilers_deploy() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_autobrake_mode(A320AutobrakeMode::Med)
+                .rolling_on_runway_at(Velocity::new::<knot>(130.))
+                .set_ground_spoilers_deployed(true)
+                .run_waiting_for(Duration::from_secs(1));
+
+            assert!(!test_bed.is_autobrake_active());
+            assert!(test_bed.get_brake_left_green_pressure() < Pressure::new::<psi>(50.));
+
+            test_bed = test_bed.run_waiting_for(Duration::from_secs(2));
+
+            assert!(test_bed.is_autobrake_active());
+            assert!(test_bed.get_brake_left_green_pressure() > Pressure::new::<psi>(50.));
+            assert!(test_bed.get_brake_right_green_pressure() > Pressure::new::<psi>(50.));
+            assert!(test_bed.get_brake_left_yellow_pressure() < Pressure::new::<psi>(50.));
+            assert!(test_bed.get_brake_right_yellow_pressure() < Pressure::new::<psi>(50.));
+        }
+
+        #[test]
+        fn autobrake_low_is_not_active_two_seconds_after_ground_spoilers_deploy() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_autobrake_mode(A320AutobrakeMode::Low)
+                .rolling_on_runway_at(Velocity::new::<knot>(130.))
+                .set_ground_spoilers_deployed(true)
+                .run_waiting_for(Duration::from_secs(3));
+
+            assert!(!test_bed.is_autobrake_active());
+
+            test_bed = test_bed.run_waiting_for(Duration::from_secs(2));
+
+            assert!(test_bed.is_autobrake_active());
+        }
+
+        #[test]
+        fn autobrake_max_activates_on_rejected_takeoff() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_autobrake_mode(A320AutobrakeMode::Max)
+                .rolling_on_runway_at(Velocity::new::<knot>(100.))
+                .set_ground_spoilers_deployed(true)
+                .run_one_tick();
+
+            assert!(test_bed.is_autobrake_active());
+
+            test_bed = test_bed.run_waiting_for(Duration::from_secs(2));
+
+            assert!(test_bed.get_brake_left_green_pressure() > Pressure::new::<psi>(500.));
+            assert!(test_bed.get_brake_right_green_pressure() > Pressure::new::<psi>(500.));
+        }
+
+        #[test]
+        fn autobrake_max_does_not_activate_below_72_knots() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_autobrake_mode(A320AutobrakeMode::Max)
+                .rolling_on_runway_at(Velocity::new::<knot>(50.))
+                .set_ground_spoilers_deployed(true)
+                .run_waiting_for(Duration::from_secs(1));
+
+            assert!(!test_bed.is_autobrake_active());
+        }
+
+        #[test]
+        fn autobrake_pedal_braking_disarms_autobrake() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_autobrake_mode(A320AutobrakeMode::Max)
+                .rolling_on_runway_at(Velocity::new::<knot>(100.))
+                .set_ground_spoilers_deployed(true)
+                .run_one_tick();
+
+            assert!(test_bed.is_autobrake_active());
+
+            test_bed = test_bed
+                .set_left_brake(Ratio::new::<percent>(80.))
+                .run_one_tick()
+                .set_left_brake(Ratio::new::<percent>(0.))
+                .run_waiting_for(Duration::from_secs(2));
+
+            assert!(!test_bed.is_autobrake_active());
+            assert!(test_bed.autobrake_armed_mode() == A320AutobrakeMode::None);
+            assert!(test_bed.get_brake_left_green_pressure() < Pressure::new::<psi>(50.));
+        }
+
+        #[test]
+        fn autobrake_decel_light_comes_on_when_reaching_target_deceleration() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_autobrake_mode(A320AutobrakeMode::Med)
+                .rolling_on_runway_at(Velocity::new::<knot>(130.))
+                .set_ground_spoilers_deployed(true)
+                .run_waiting_for(Duration::from_secs(3));
+
+            assert!(test_bed.is_autobrake_active());
+            assert!(!test_bed.is_autobrake_decel_light_on());
+
+            test_bed = test_bed
+                .decelerating_at(Acceleration::new::<meter_per_second_squared>(2.9))
+                .run_one_tick();
+
+            assert!(test_bed.is_autobrake_decel_light_on());
+        }
+
+        #[test]
+        fn nose_wheel_steering_follows_tiller_with_yellow_pressure() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_ground_speed(Velocity::new::<knot>(10.))
+                .set_tiller_position(0.5)
+                .run_waiting_for(Duration::from_secs(5));
+
+            assert!(test_bed.is_yellow_pressurised());
+            assert!(test_bed.is_nose_wheel_steering_available());
+            assert!((test_bed.nose_wheel_steering_angle().get::<degree>() - 37.5).abs() < 0.1);
+        }
+
+        #[test]
+        fn nose_wheel_steering_tiller_authority_decreases_with_ground_speed() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_ground_speed(Velocity::new::<knot>(45.))
+                .set_tiller_position(1.)
+                .run_waiting_for(Duration::from_secs(5));
+
+            assert!((test_bed.nose_wheel_steering_angle().get::<degree>() - 37.5).abs() < 0.1);
+        }
+
+        #[test]
+        fn nose_wheel_steering_pedals_have_small_authority() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_ground_speed(Velocity::new::<knot>(30.))
+                .set_rudder_pedal_position(-1.)
+                .run_waiting_for(Duration::from_secs(5));
+
+            assert!((test_bed.nose_wheel_steering_angle().get::<degree>() + 6.).abs() < 0.1);
+        }
+
+        #[test]
+        fn nose_wheel_steering_unavailable_without_yellow_pressure() {
+            let mut test_bed = test_bed_with()
+                .engines_off()
+                .on_the_ground()
+                .set_cold_dark_inputs()
+                .set_tiller_position(0.5)
+                .run_waiting_for(Duration::from_secs(5));
+
+            assert!(!test_bed.is_yellow_pressurised());
+            assert!(!test_bed.is_nose_wheel_steering_available());
+            assert!(test_bed.nose_wheel_steering_angle().get::<degree>().abs() < 0.1);
+        }
+
+        #[test]
+        fn nose_wheel_steering_unavailable_with_towing_pin_inserted() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_pushback_state(true)
+                .set_tiller_position(0.5)
+                .run_waiting_for(Duration::from_secs(1));
+
+            assert!(test_bed.aircraft.is_nws_pin_inserted());
+            assert!(!test_bed.is_nose_wheel_steering_available());
+        }
+
+        #[test]
+        fn nose_wheel_steering_unavailable_with_anti_skid_and_steering_switch_off() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_anti_skid(false)
+                .set_tiller_position(0.5)
+                .run_waiting_for(Duration::from_secs(1));
+
+            assert!(!test_bed.is_nose_wheel_steering_available());
+            assert!(test_bed.nose_wheel_steering_angle().get::<degree>().abs() < 0.1);
+        }
+
+        #[test]
+        fn nose_wheel_steering_unavailable_in_flight() {
+            let mut test_bed = test_bed_with()
+                .set_cold_dark_inputs()
+                .in_flight()
+                .set_tiller_position(0.5)
+                .run_waiting_for(Duration::from_secs(5));
+
+            assert!(test_bed.is_yellow_pressurised());
+            assert!(!test_bed.is_nose_wheel_steering_available());
+            assert!(test_bed.nose_wheel_steering_angle().get::<degree>().abs() < 0.1);
+        }
+
+        #[test]
+        fn gear_retracts_in_flight_with_green_pressure() {
+            let test_bed = test_bed_with()
+                .set_cold_dark_inputs()
+                .in_flight()
+                .run_waiting_for(Duration::from_secs(20));
+
+            assert!(test_bed.is_green_pressurised());
+            assert!(test_bed.is_gear_up_and_locked());
+        }
+
+        #[test]
+        fn gear_does_not_retract_without_green_pressure() {
+            let test_bed = test_bed_with()
+                .set_cold_dark_inputs()
+                .in_flight()
+                .set_green_ed_pump(false)
+                .set_ptu_state(false)
+                .run_waiting_for(Duration::from_secs(20));
+
+            assert!(!test_bed.is_green_pressurised());
+            assert!(test_bed.is_gear_down_and_locked());
+            assert!(!test_bed.is_gear_up_and_locked());
+        }
+
+        #[test]
+        fn gear_extends_after_green_pressure_loss_with_gravity_extension() {
+            let mut test_bed = test_bed_with()
+                .set_cold_dark_inputs()
+                .in_flight()
+                .run_waiting_for(Duration::from_secs(20));
+            assert!(test_bed.is_gear_up_and_locked());
+
+            test_bed = test_bed
+                .set_green_ed_pump(false)
+                .set_ptu_state(false)
+                .set_gear_down()
+                .set_gravity_extension_handle(true)
+                .run_waiting_for(Duration::from_secs(30));
+
+            assert!(test_bed.is_gear_down_and_locked());
+        }
+
+        // Wheel rpm of a main wheel rolling without slip at 100 knots
+        const WHEEL_RPM_AT_100_KNOTS: f64 = 840.;
+
+        #[test]
+        fn anti_skid_releases_normal_brake_of_locked_wheel() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_ground_speed(Velocity::new::<knot>(100.))
+                .set_wheels_rpm(0., WHEEL_RPM_AT_100_KNOTS)
+                .set_left_brake(Ratio::new::<percent>(100.))
+                .set_right_brake(Ratio::new::<percent>(100.))
+                .run_waiting_for(Duration::from_secs(2));
+
+            assert!(test_bed.get_brake_left_green_pressure() < Pressure::new::<psi>(50.));
+            assert!(test_bed.get_brake_right_green_pressure() > Pressure::new::<psi>(2000.));
+        }
+
+        #[test]
+        fn anti_skid_reapplies_normal_brake_once_wheel_spins_up() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_ground_speed(Velocity::new::<knot>(100.))
+                .set_wheels_rpm(0., 0.)
+                .set_left_brake(Ratio::new::<percent>(100.))
+                .set_right_brake(Ratio::new::<percent>(100.))
+                .run_waiting_for(Duration::from_secs(2));
+
+            assert!(test_bed.get_brake_left_green_pressure() < Pressure::new::<psi>(50.));
+
+            test_bed = test_bed
+                .set_wheels_rpm(WHEEL_RPM_AT_100_KNOTS, WHEEL_RPM_AT_100_KNOTS)
+                .run_waiting_for(Duration::from_secs(2));
+
+            assert!(test_bed.get_brake_left_green_pressure() > Pressure::new::<psi>(2000.));
+            assert!(test_bed.get_brake_right_green_pressure() > Pressure::new::<psi>(2000.));
+        }
+
+        #[test]
+        fn alternate_brake_without_anti_skid_does_not_release_locked_wheel() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_anti_skid(false)
+                .set_ground_speed(Velocity::new::<knot>(100.))
+                .set_wheels_rpm(0., 0.)
+                .set_left_brake(Ratio::new::<percent>(100.))
+                .set_right_brake(Ratio::new::<percent>(100.))
+                .run_waiting_for(Duration::from_secs(2));
+
+            assert!(test_bed.get_brake_left_green_pressure() < Pressure::new::<psi>(50.));
+            assert!(test_bed.get_brake_left_yellow_pressure() > Pressure::new::<psi>(950.));
+            assert!(test_bed.get_brake_left_yellow_pressure() < Pressure::new::<psi>(1200.));
+        }
+
+        #[test]
+        fn alternate_brake_with_anti_skid_releases_locked_wheel() {
+            let mut test_bed = test_bed_ready_for_autobrake()
+                .set_ptu_state(false)
+                .stop_eng1()
+                .run_waiting_for(Duration::from_secs(30));
+
+            assert!(!test_bed.is_green_pressurised());
+
+            test_bed = test_bed
+                .set_ground_speed(Velocity::new::<knot>(100.))
+                .set_wheels_rpm(0., WHEEL_RPM_AT_100_KNOTS)
+                .set_left_brake(Ratio::new::<percent>(100.))
+                .set_right_brake(Ratio::new::<percent>(100.))
+                .run_waiting_for(Duration::from_secs(2));
+
+            assert!(test_bed.get_brake_left_yellow_pressure() < Pressure::new::<psi>(50.));
+            assert!(test_bed.get_brake_right_yellow_pressure() > Pressure::new::<psi>(950.));
+        }
+
         #[test]
         fn alternate_brake_accumulator_is_emptying_while_braking() {
             let mut test_bed = test_bed_with()
@@ -3320,11 +4236,12 @@ mod tests {
 
         #[test]
         fn controller_blue_epump_activates_when_no_weight_on_wheels() {
+            let mut registry = VariableRegistry::new();
             let engine_off_oil_pressure = Pressure::new::<psi>(10.);
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
             overhead_panel.blue_epump_override_push_button.push_off();
 
-            let mut blue_epump_controller = A320BlueElectricPumpController::new();
+            let mut blue_epump_controller = A320BlueElectricPumpController::new(&mut registry);
 
             let eng1_above_idle = false;
             let eng2_above_idle = false;
@@ -3355,12 +4272,13 @@ mod tests {
 
         #[test]
         fn controller_blue_epump_split_engine_states() {
+            let mut registry = VariableRegistry::new();
             let engine_on_oil_pressure = Pressure::new::<psi>(30.);
             let engine_off_oil_pressure = Pressure::new::<psi>(10.);
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
             overhead_panel.blue_epump_override_push_button.push_off();
 
-            let mut blue_epump_controller = A320BlueElectricPumpController::new();
+            let mut blue_epump_controller = A320BlueElectricPumpController::new(&mut registry);
 
             let eng1_above_idle = false;
             let eng2_above_idle = false;
@@ -3401,12 +4319,13 @@ mod tests {
 
         #[test]
         fn controller_blue_epump_on_off_engines() {
+            let mut registry = VariableRegistry::new();
             let engine_on_oil_pressure = Pressure::new::<psi>(30.);
             let engine_off_oil_pressure = Pressure::new::<psi>(10.);
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
             overhead_panel.blue_epump_override_push_button.push_off();
 
-            let mut blue_epump_controller = A320BlueElectricPumpController::new();
+            let mut blue_epump_controller = A320BlueElectricPumpController::new(&mut registry);
 
             let eng1_above_idle = true;
             let eng2_above_idle = true;
@@ -3435,10 +4354,11 @@ mod tests {
 
         #[test]
         fn controller_blue_epump_override() {
+            let mut registry = VariableRegistry::new();
             let engine_off_oil_pressure = Pressure::new::<psi>(10.);
 
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
-            let mut blue_epump_controller = A320BlueElectricPumpController::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
+            let mut blue_epump_controller = A320BlueElectricPumpController::new(&mut registry);
 
             let eng1_above_idle = false;
             let eng2_above_idle = false;
@@ -3469,13 +4389,14 @@ mod tests {
 
         #[test]
         fn controller_yellow_epump_overhead_button_logic() {
-            let fwd_door = Door::new(1);
-            let aft_door = Door::new(2);
+            let mut registry = VariableRegistry::new();
+            let fwd_door = Door::new(&mut registry, 1);
+            let aft_door = Door::new(&mut registry, 2);
             let context = context(Duration::from_millis(100));
 
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
 
-            let mut yellow_epump_controller = A320YellowElectricPumpController::new();
+            let mut yellow_epump_controller = A320YellowElectricPumpController::new(&mut registry);
 
             overhead_panel.yellow_epump_push_button.push_auto();
             yellow_epump_controller.update(&context, &overhead_panel, &fwd_door, &aft_door, true);
@@ -3492,11 +4413,12 @@ mod tests {
 
         #[test]
         fn controller_yellow_epump_cargo_doors_starts_pump_for_timeout_delay() {
+            let mut registry = VariableRegistry::new();
             let context = context(Duration::from_millis(100));
 
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
 
-            let mut yellow_epump_controller = A320YellowElectricPumpController::new();
+            let mut yellow_epump_controller = A320YellowElectricPumpController::new(&mut registry);
 
             overhead_panel.yellow_epump_push_button.push_auto();
             assert!(!yellow_epump_controller.should_pressurise());
@@ -3521,11 +4443,12 @@ mod tests {
 
         #[test]
         fn controller_engine_driven_pump1_overhead_button_logic_with_eng_on() {
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
-            let fire_overhead_panel = EngineFireOverheadPanel::new();
+            let mut registry = VariableRegistry::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
+            let fire_overhead_panel = EngineFireOverheadPanel::new(&mut registry);
             overhead_panel.edp1_push_button.push_auto();
 
-            let mut edp1_controller = A320EngineDrivenPumpController::new(1);
+            let mut edp1_controller = A320EngineDrivenPumpController::new(&mut registry, 1);
             edp1_controller.engine_master_on = true;
 
             edp1_controller.update(
@@ -3560,11 +4483,12 @@ mod tests {
 
         #[test]
         fn controller_engine_driven_pump1_fire_overhead_released_stops_pump() {
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
-            let mut fire_overhead_panel = EngineFireOverheadPanel::new();
+            let mut test_bed = SimulationTestBed::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(test_bed.registry());
+            let mut fire_overhead_panel = EngineFireOverheadPanel::new(test_bed.registry());
             overhead_panel.edp1_push_button.push_auto();
 
-            let mut edp1_controller = A320EngineDrivenPumpController::new(1);
+            let mut edp1_controller = A320EngineDrivenPumpController::new(test_bed.registry(), 1);
             edp1_controller.engine_master_on = true;
 
             edp1_controller.update(
@@ -3576,7 +4500,6 @@ mod tests {
             );
             assert!(edp1_controller.should_pressurise());
 
-            let mut test_bed = SimulationTestBed::new();
             test_bed.write_bool("FIRE_BUTTON_ENG1", true);
             test_bed.run(&mut fire_overhead_panel, |_, _| {});
 
@@ -3592,11 +4515,12 @@ mod tests {
 
         #[test]
         fn controller_engine_driven_pump2_overhead_button_logic_with_eng_on() {
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
-            let fire_overhead_panel = EngineFireOverheadPanel::new();
+            let mut registry = VariableRegistry::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
+            let fire_overhead_panel = EngineFireOverheadPanel::new(&mut registry);
             overhead_panel.edp2_push_button.push_auto();
 
-            let mut edp2_controller = A320EngineDrivenPumpController::new(2);
+            let mut edp2_controller = A320EngineDrivenPumpController::new(&mut registry, 2);
             edp2_controller.engine_master_on = true;
 
             edp2_controller.update(
@@ -3631,11 +4555,12 @@ mod tests {
 
         #[test]
         fn controller_engine_driven_pump2_fire_overhead_released_stops_pump() {
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
-            let mut fire_overhead_panel = EngineFireOverheadPanel::new();
+            let mut test_bed = SimulationTestBed::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(test_bed.registry());
+            let mut fire_overhead_panel = EngineFireOverheadPanel::new(test_bed.registry());
             overhead_panel.edp2_push_button.push_auto();
 
-            let mut edp2_controller = A320EngineDrivenPumpController::new(2);
+            let mut edp2_controller = A320EngineDrivenPumpController::new(test_bed.registry(), 2);
             edp2_controller.engine_master_on = true;
 
             edp2_controller.update(
@@ -3647,7 +4572,6 @@ mod tests {
             );
             assert!(edp2_controller.should_pressurise());
 
-            let mut test_bed = SimulationTestBed::new();
             test_bed.write_bool("FIRE_BUTTON_ENG2", true);
             test_bed.run(&mut fire_overhead_panel, |_, _| {});
 
@@ -3663,12 +4587,13 @@ mod tests {
 
         #[test]
         fn controller_ptu_on_off_cargo_door() {
-            let tug = PushbackTug::new();
+            let mut registry = VariableRegistry::new();
+            let tug = PushbackTug::new(&mut registry);
             let context = context(Duration::from_millis(100));
 
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
 
-            let mut ptu_controller = A320PowerTransferUnitController::new();
+            let mut ptu_controller = A320PowerTransferUnitController::new(&mut registry);
 
             overhead_panel.ptu_push_button.push_auto();
 
@@ -3709,14 +4634,15 @@ mod tests {
 
         #[test]
         fn controller_ptu_tug() {
-            let fwd_door = Door::new(1);
-            let aft_door = Door::new(2);
-            let mut tug = PushbackTug::new();
+            let mut registry = VariableRegistry::new();
+            let fwd_door = Door::new(&mut registry, 1);
+            let aft_door = Door::new(&mut registry, 2);
+            let mut tug = PushbackTug::new(&mut registry);
             let context = context(Duration::from_millis(100));
 
-            let mut overhead_panel = A320HydraulicOverheadPanel::new();
+            let mut overhead_panel = A320HydraulicOverheadPanel::new(&mut registry);
 
-            let mut ptu_controller = A320PowerTransferUnitController::new();
+            let mut ptu_controller = A320PowerTransferUnitController::new(&mut registry);
             overhead_panel.ptu_push_button.push_auto();
 
             ptu_controller.update(&context, &overhead_panel, &fwd_door, &aft_door, &tug);
@@ -3776,19 +4702,19 @@ mod tests {
         }
 
         fn moving_door(id: usize) -> Door {
-            let mut door = Door::new(id);
+            let mut door = Door::new(&mut VariableRegistry::new(), id);
             door.position += 0.01;
             door
         }
 
         fn non_moving_door(id: usize) -> Door {
-            let mut door = Door::new(id);
+            let mut door = Door::new(&mut VariableRegistry::new(), id);
             door.previous_position = door.position;
             door
         }
 
         fn attached_tug() -> PushbackTug {
-            let mut tug = PushbackTug::new();
+            let mut tug = PushbackTug::new(&mut VariableRegistry::new());
             tug.angle = tug.previous_angle + 0.1;
             tug.state = 0.;
             tug.update();
@@ -3796,7 +4722,7 @@ mod tests {
         }
 
         fn detached_tug() -> PushbackTug {
-            let mut tug = PushbackTug::new();
+            let mut tug = PushbackTug::new(&mut VariableRegistry::new());
             tug.angle = tug.previous_angle;
             tug.state = 3.;
             tug.update();
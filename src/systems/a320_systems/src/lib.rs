@@ -3,8 +3,14 @@ mod fuel;
 mod hydraulic;
 mod pneumatic;
 mod power_consumption;
+pub mod test;
 
-use self::{fuel::A320Fuel, pneumatic::A320PneumaticOverheadPanel};
+use std::time::Duration;
+
+use self::{
+    fuel::A320Fuel,
+    pneumatic::{A320Pneumatic, A320PneumaticOverheadPanel},
+};
 
 use electrical::{
     A320Electrical, A320ElectricalOverheadPanel, A320EmergencyElectricalOverheadPanel,
@@ -20,14 +26,17 @@ use systems::{
     },
     electrical::{consumption::SuppliedPower, ElectricalSystem, ExternalPowerSource},
     engine::{leap_engine::LeapEngine, EngineFireOverheadPanel},
-    landing_gear::LandingGear,
-    simulation::{Aircraft, SimulationElement, SimulationElementVisitor, UpdateContext},
+    simulation::{
+        Aircraft, FixedRate, SimulationElement, SimulationElementVisitor, SimulationSettings,
+        UpdateContext, VariableRegistry,
+    },
 };
 
 pub struct A320 {
     apu: AuxiliaryPowerUnit<Aps3200ApuGenerator, Aps3200StartMotor>,
     apu_fire_overhead: AuxiliaryPowerUnitFireOverheadPanel,
     apu_overhead: AuxiliaryPowerUnitOverheadPanel,
+    pneumatic: A320Pneumatic,
     pneumatic_overhead: A320PneumaticOverheadPanel,
     electrical_overhead: A320ElectricalOverheadPanel,
     emergency_electrical_overhead: A320EmergencyElectricalOverheadPanel,
@@ -40,49 +49,77 @@ pub struct A320 {
     ext_pwr: ExternalPowerSource,
     hydraulic: A320Hydraulic,
     hydraulic_overhead: A320HydraulicOverheadPanel,
-    landing_gear: LandingGear,
 }
 impl A320 {
-    pub fn new() -> A320 {
+    // Longer deltas, which occur at a high simulation rate or during a stutter,
+    // are split into multiple steps
+    const MAX_SIMULATION_STEP: Duration = Duration::from_millis(50);
+
+    pub fn new(registry: &mut VariableRegistry) -> A320 {
         A320 {
-            apu: AuxiliaryPowerUnitFactory::new_aps3200(1),
-            apu_fire_overhead: AuxiliaryPowerUnitFireOverheadPanel::new(),
-            apu_overhead: AuxiliaryPowerUnitOverheadPanel::new(),
-            pneumatic_overhead: A320PneumaticOverheadPanel::new(),
-            electrical_overhead: A320ElectricalOverheadPanel::new(),
-            emergency_electrical_overhead: A320EmergencyElectricalOverheadPanel::new(),
-            fuel: A320Fuel::new(),
-            engine_1: LeapEngine::new(1),
-            engine_2: LeapEngine::new(2),
-            engine_fire_overhead: EngineFireOverheadPanel::new(),
-            electrical: A320Electrical::new(),
-            power_consumption: A320PowerConsumption::new(),
-            ext_pwr: ExternalPowerSource::new(),
-            hydraulic: A320Hydraulic::new(),
-            hydraulic_overhead: A320HydraulicOverheadPanel::new(),
-            landing_gear: LandingGear::new(),
+            apu: AuxiliaryPowerUnitFactory::new_aps3200(registry, 1)
+                .with_generator_resistance(A320Electrical::generator_resistance()),
+            apu_fire_overhead: AuxiliaryPowerUnitFireOverheadPanel::new(registry),
+            apu_overhead: AuxiliaryPowerUnitOverheadPanel::new(registry),
+            pneumatic: A320Pneumatic::new(registry),
+            pneumatic_overhead: A320PneumaticOverheadPanel::new(registry),
+            electrical_overhead: A320ElectricalOverheadPanel::new(registry),
+            emergency_electrical_overhead: A320EmergencyElectricalOverheadPanel::new(registry),
+            fuel: A320Fuel::new(registry),
+            engine_1: LeapEngine::new(registry, 1),
+            engine_2: LeapEngine::new(registry, 2),
+            engine_fire_overhead: EngineFireOverheadPanel::new(registry),
+            electrical: A320Electrical::new(registry),
+            power_consumption: A320PowerConsumption::new(registry),
+            ext_pwr: ExternalPowerSource::new(registry),
+            hydraulic: A320Hydraulic::new(registry),
+            hydraulic_overhead: A320HydraulicOverheadPanel::new(registry),
         }
     }
-}
-impl Default for A320 {
-    fn default() -> Self {
-        Self::new()
+
+    /// The settings with which the A320 should be simulated.
+    pub fn simulation_settings() -> SimulationSettings {
+        SimulationSettings::new()
+            .with_max_step(Self::MAX_SIMULATION_STEP)
+            .with_pause_detection()
     }
 }
 impl Aircraft for A320 {
+    fn fixed_rates(&self) -> Vec<FixedRate> {
+        vec![A320Hydraulic::FIXED_RATE]
+    }
+
     fn update_before_power_distribution(&mut self, context: &UpdateContext) {
+        self.pneumatic
+            .update_before_apu([&self.engine_1, &self.engine_2], &self.pneumatic_overhead);
+
+        self.fuel.update(
+            context,
+            &self.apu_overhead,
+            &self.apu_fire_overhead,
+            &self.engine_fire_overhead,
+        );
+
         self.apu.update_before_electrical(
             context,
             &self.apu_overhead,
             &self.apu_fire_overhead,
-            self.pneumatic_overhead.apu_bleed_is_on(),
+            self.pneumatic.apu_bleed_air_is_demanded(),
             // This will be replaced when integrating the whole electrical system.
             // For now we use the same logic as found in the JavaScript code; ignoring whether or not
             // the engine generators are supplying electricity.
             self.electrical_overhead.apu_generator_is_on()
                 && !(self.electrical_overhead.external_power_is_on()
                     && self.electrical_overhead.external_power_is_available()),
-            self.fuel.left_inner_tank_has_fuel_remaining(),
+            self.fuel.apu_fuel_pressure_switch(),
+        );
+
+        self.pneumatic.update_after_apu(
+            context,
+            [&self.engine_1, &self.engine_2],
+            &self.apu,
+            &self.pneumatic_overhead,
+            &self.engine_fire_overhead,
         );
 
         self.electrical.update(
@@ -95,7 +132,7 @@ impl Aircraft for A320 {
             &self.engine_fire_overhead,
             [&self.engine_1, &self.engine_2],
             &self.hydraulic,
-            &self.landing_gear,
+            self.hydraulic.landing_gear_control_interface_unit(),
         );
 
         self.apu.update_after_electrical();
@@ -108,20 +145,37 @@ impl Aircraft for A320 {
     }
 
     fn update_after_power_distribution(&mut self, context: &UpdateContext) {
-        self.hydraulic.update(
-            context,
-            &self.engine_1,
-            &self.engine_2,
-            &self.hydraulic_overhead,
-            &self.engine_fire_overhead,
-            &self.landing_gear,
-        );
-
-        self.hydraulic_overhead.update(&self.hydraulic);
+        self.hydraulic.update(context);
 
         self.power_consumption.update(context);
     }
 
+    fn update_at_fixed_rate(&mut self, rate: FixedRate, context: &UpdateContext) {
+        if rate == A320Hydraulic::FIXED_RATE {
+            self.hydraulic.update_at_fixed_rate(
+                context,
+                &self.engine_1,
+                &self.engine_2,
+                &self.hydraulic_overhead,
+                &self.engine_fire_overhead,
+            );
+
+            self.hydraulic_overhead.update(&self.hydraulic);
+        }
+    }
+
+    fn update_at_sub_rate(&mut self, rate: FixedRate, context: &UpdateContext) {
+        if rate == A320Hydraulic::FIXED_RATE {
+            self.hydraulic.update_at_sub_rate(context);
+        }
+    }
+
+    fn interpolate_fixed_rate(&mut self, rate: FixedRate, fraction: f64) {
+        if rate == A320Hydraulic::FIXED_RATE {
+            self.hydraulic.interpolate(fraction);
+        }
+    }
+
     fn get_supplied_power(&mut self) -> SuppliedPower {
         self.electrical.get_supplied_power()
     }
@@ -134,6 +188,7 @@ impl SimulationElement for A320 {
         self.electrical_overhead.accept(visitor);
         self.emergency_electrical_overhead.accept(visitor);
         self.fuel.accept(visitor);
+        self.pneumatic.accept(visitor);
         self.pneumatic_overhead.accept(visitor);
         self.engine_1.accept(visitor);
         self.engine_2.accept(visitor);
@@ -143,8 +198,89 @@ impl SimulationElement for A320 {
         self.ext_pwr.accept(visitor);
         self.hydraulic.accept(visitor);
         self.hydraulic_overhead.accept(visitor);
-        self.landing_gear.accept(visitor);
 
         visitor.visit(self);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use systems::simulation::{test::SimulationTestBed, Catalogue};
+    use uom::si::{f64::*, velocity::knot};
+
+    fn run_at_sim_rate(delta: Duration) -> SimulationTestBed {
+        let mut test_bed = SimulationTestBed::new_with_delta(delta);
+        let mut aircraft = A320::new(test_bed.registry());
+        test_bed.set_simulation_settings(A320::simulation_settings());
+        test_bed.set_on_ground(true);
+        test_bed.set_indicated_airspeed(Velocity::new::<knot>(0.));
+        test_bed.write_bool("UNLIMITED FUEL", true);
+        test_bed.write_bool("OVHD_ELEC_BAT_1_PB_IS_AUTO", true);
+        test_bed.write_bool("OVHD_ELEC_BAT_2_PB_IS_AUTO", true);
+        test_bed.write_bool("OVHD_APU_MASTER_SW_PB_IS_ON", true);
+
+        let ticks_in = |seconds: f64| (seconds / delta.as_secs_f64()).round() as usize;
+        for _ in 0..ticks_in(32.) {
+            test_bed.run_aircraft(&mut aircraft);
+        }
+
+        test_bed.write_bool("OVHD_APU_START_PB_IS_ON", true);
+        for _ in 0..ticks_in(60.) {
+            test_bed.run_aircraft(&mut aircraft);
+        }
+
+        test_bed
+    }
+
+    #[test]
+    fn reaches_same_state_at_normal_and_sixteen_times_sim_rate() {
+        let mut normal_rate = run_at_sim_rate(Duration::from_millis(50));
+        let mut high_rate = run_at_sim_rate(Duration::from_millis(800));
+
+        for name in &[
+            "APU_N",
+            "APU_EGT",
+            "ELEC_BAT_1_POTENTIAL",
+            "ELEC_BAT_2_POTENTIAL",
+            "HYD_GREEN_PRESSURE",
+        ] {
+            let normal_rate_value = normal_rate.read_f64(name);
+            let high_rate_value = high_rate.read_f64(name);
+            assert!(
+                (normal_rate_value - high_rate_value).abs() < 1e-6,
+                "{} differs: {} at 1x, {} at 16x",
+                name,
+                normal_rate_value,
+                high_rate_value
+            );
+        }
+        assert!(normal_rate.read_f64("APU_N") > 0.);
+    }
+
+    #[test]
+    fn documentation_lists_every_variable_read_and_written() {
+        const START: &str = "<!-- systems catalogue start -->\n";
+        const END: &str = "<!-- systems catalogue end -->";
+
+        let documentation = fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../../docs/a320-simvars.md"
+        ))
+        .unwrap();
+        let start = documentation.find(START).unwrap() + START.len();
+        let end = documentation.find(END).unwrap();
+
+        let mut markdown = Vec::new();
+        Catalogue::of(A320::new)
+            .write_markdown(&mut markdown)
+            .unwrap();
+
+        assert!(
+            documentation[start..end] == String::from_utf8(markdown).unwrap(),
+            "docs/a320-simvars.md is out of date, replace its systems catalogue \
+            with the output of `cargo run --bin a320_catalogue`."
+        );
+    }
+}
@@ -1,26 +1,722 @@
 use systems::{
     overhead::OnOffFaultPushButton,
-    simulation::{SimulationElement, SimulationElementVisitor},
+    pneumatic::{
+        BleedAirSource, BleedAirValveState, CrossBleedValve, CrossBleedValveController,
+        EngineBleedAirSystem, PneumaticDuct, PressureRegulatingValveController, Valve,
+    },
+    shared::{EngineCorrectedN2, EngineFirePushButtons},
+    simulation::{
+        SimulationElement, SimulationElementVisitor, SimulatorReader, UpdateContext,
+        VariableIdentifier, VariableRegistry,
+    },
 };
+use uom::si::{f64::*, ratio::percent};
+
+pub(super) struct A320Pneumatic {
+    engine_1_bleed: EngineBleedAirSystem,
+    engine_1_bleed_valve_controller: A320EngineBleedValveController,
+    engine_2_bleed: EngineBleedAirSystem,
+    engine_2_bleed_valve_controller: A320EngineBleedValveController,
+    cross_bleed_valve: CrossBleedValve,
+    cross_bleed_valve_controller: A320CrossBleedValveController,
+    left_duct: PneumaticDuct,
+    right_duct: PneumaticDuct,
+    consumers: A320BleedAirConsumers,
+    apu_bleed_air_is_demanded: bool,
+}
+impl A320Pneumatic {
+    pub(super) fn new(registry: &mut VariableRegistry) -> Self {
+        Self {
+            engine_1_bleed: EngineBleedAirSystem::new(registry, 1),
+            engine_1_bleed_valve_controller: A320EngineBleedValveController::new(1),
+            engine_2_bleed: EngineBleedAirSystem::new(registry, 2),
+            engine_2_bleed_valve_controller: A320EngineBleedValveController::new(2),
+            cross_bleed_valve: CrossBleedValve::new(registry),
+            cross_bleed_valve_controller: A320CrossBleedValveController::new(),
+            left_duct: PneumaticDuct::new(registry, 1),
+            right_duct: PneumaticDuct::new(registry, 2),
+            consumers: A320BleedAirConsumers::new(registry),
+            apu_bleed_air_is_demanded: false,
+        }
+    }
+
+    /// Determines the demand for bleed air. This needs to happen before the APU is updated,
+    /// as the APU only supplies bleed air when there is demand for it.
+    pub(super) fn update_before_apu<T: EngineCorrectedN2>(
+        &mut self,
+        engines: [&T; 2],
+        overhead: &A320PneumaticOverheadPanel,
+    ) {
+        self.consumers.update(engines);
+        self.apu_bleed_air_is_demanded =
+            overhead.apu_bleed_is_on() && self.consumers.demand_bleed_air();
+    }
+
+    pub(super) fn update_after_apu<
+        T: EngineCorrectedN2,
+        U: BleedAirSource + BleedAirValveState,
+        V: EngineFirePushButtons,
+    >(
+        &mut self,
+        context: &UpdateContext,
+        engines: [&T; 2],
+        apu: &U,
+        overhead: &A320PneumaticOverheadPanel,
+        engine_fire_push_buttons: &V,
+    ) {
+        self.cross_bleed_valve_controller.update(overhead, apu);
+        self.cross_bleed_valve
+            .update(&self.cross_bleed_valve_controller);
+
+        self.engine_1_bleed_valve_controller.update(
+            overhead,
+            engine_fire_push_buttons,
+            apu,
+            &self.cross_bleed_valve,
+            &self.consumers,
+        );
+        self.engine_1_bleed
+            .update(context, engines[0], &self.engine_1_bleed_valve_controller);
+
+        self.engine_2_bleed_valve_controller.update(
+            overhead,
+            engine_fire_push_buttons,
+            apu,
+            &self.cross_bleed_valve,
+            &self.consumers,
+        );
+        self.engine_2_bleed
+            .update(context, engines[1], &self.engine_2_bleed_valve_controller);
+
+        // The APU bleed air enters the left side of the duct.
+        if self.cross_bleed_valve.is_open() {
+            let sources: [&dyn BleedAirSource; 3] =
+                [&self.engine_1_bleed, &self.engine_2_bleed, apu];
+            self.left_duct.update(context, &sources);
+            self.right_duct.update(context, &sources);
+        } else {
+            self.left_duct.update(context, &[&self.engine_1_bleed, apu]);
+            self.right_duct.update(context, &[&self.engine_2_bleed]);
+        }
+    }
+
+    pub(super) fn apu_bleed_air_is_demanded(&self) -> bool {
+        self.apu_bleed_air_is_demanded
+    }
+
+    #[cfg(test)]
+    fn duct_pressure(&self, number: usize) -> Pressure {
+        match number {
+            1 => self.left_duct.pressure(),
+            2 => self.right_duct.pressure(),
+            _ => panic!("Invalid duct number {}.", number),
+        }
+    }
+
+    #[cfg(test)]
+    fn engine_bleed_pressure_regulating_valve_is_open(&self, number: usize) -> bool {
+        match number {
+            1 => self.engine_1_bleed.pressure_regulating_valve_is_open(),
+            2 => self.engine_2_bleed.pressure_regulating_valve_is_open(),
+            _ => panic!("Invalid engine number {}.", number),
+        }
+    }
+
+    #[cfg(test)]
+    fn cross_bleed_valve_is_open(&self) -> bool {
+        self.cross_bleed_valve.is_open()
+    }
+}
+impl SimulationElement for A320Pneumatic {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.engine_1_bleed.accept(visitor);
+        self.engine_2_bleed.accept(visitor);
+        self.cross_bleed_valve.accept(visitor);
+        self.left_duct.accept(visitor);
+        self.right_duct.accept(visitor);
+        self.consumers.accept(visitor);
+
+        visitor.visit(self);
+    }
+}
+
+/// The consumers of bleed air which are of interest to the pneumatic system.
+/// As we've not yet modelled air conditioning and engine starting in detail, this type
+/// only determines whether or not the packs and engine starters demand bleed air.
+struct A320BleedAirConsumers {
+    pack_on_ids: [VariableIdentifier; 2],
+    starter_active_ids: [VariableIdentifier; 2],
+
+    pack_on: [bool; 2],
+    starter_active: [bool; 2],
+    engine_starting: [bool; 2],
+}
+impl A320BleedAirConsumers {
+    const STARTER_CUT_OUT_N2_PERCENT: f64 = 50.;
+
+    fn new(registry: &mut VariableRegistry) -> Self {
+        Self {
+            pack_on_ids: [
+                registry.get("AIRCOND_PACK1_TOGGLE"),
+                registry.get("AIRCOND_PACK2_TOGGLE"),
+            ],
+            starter_active_ids: [
+                registry.get("GENERAL ENG STARTER ACTIVE:1"),
+                registry.get("GENERAL ENG STARTER ACTIVE:2"),
+            ],
+
+            pack_on: [false, false],
+            starter_active: [false, false],
+            engine_starting: [false, false],
+        }
+    }
+
+    fn update<T: EngineCorrectedN2>(&mut self, engines: [&T; 2]) {
+        for (index, engine) in engines.iter().enumerate() {
+            self.engine_starting[index] = self.starter_active[index]
+                && engine.corrected_n2() < Ratio::new::<percent>(Self::STARTER_CUT_OUT_N2_PERCENT);
+        }
+    }
+
+    fn engine_is_starting(&self, number: usize) -> bool {
+        self.engine_starting[number - 1]
+    }
+
+    fn demand_bleed_air(&self) -> bool {
+        self.pack_on.iter().any(|&on| on) || self.engine_starting.iter().any(|&starting| starting)
+    }
+}
+impl SimulationElement for A320BleedAirConsumers {
+    fn read(&mut self, reader: &mut SimulatorReader) {
+        self.pack_on = [
+            reader.read_bool(&self.pack_on_ids[0]),
+            reader.read_bool(&self.pack_on_ids[1]),
+        ];
+        self.starter_active = [
+            reader.read_bool(&self.starter_active_ids[0]),
+            reader.read_bool(&self.starter_active_ids[1]),
+        ];
+    }
+}
+
+/// Signals the engine bleed pressure regulating valve to close when:
+/// - The ENG BLEED push button is off.
+/// - The ENG FIRE push button is released.
+/// - The APU bleed air valve is open and supplies air to this side of the duct.
+/// - The engine is starting.
+struct A320EngineBleedValveController {
+    number: usize,
+    should_open: bool,
+}
+impl A320EngineBleedValveController {
+    fn new(number: usize) -> Self {
+        Self {
+            number,
+            should_open: false,
+        }
+    }
+
+    fn update<T: EngineFirePushButtons, U: BleedAirValveState>(
+        &mut self,
+        overhead: &A320PneumaticOverheadPanel,
+        engine_fire_push_buttons: &T,
+        apu: &U,
+        cross_bleed_valve: &CrossBleedValve,
+        consumers: &A320BleedAirConsumers,
+    ) {
+        let apu_supplies_this_side =
+            apu.bleed_air_valve_is_open() && (self.number == 1 || cross_bleed_valve.is_open());
+
+        self.should_open = overhead.engine_bleed_is_on(self.number)
+            && !engine_fire_push_buttons.is_released(self.number)
+            && !apu_supplies_this_side
+            && !consumers.engine_is_starting(self.number);
+    }
+}
+impl PressureRegulatingValveController for A320EngineBleedValveController {
+    fn should_open_pressure_regulating_valve(&self) -> bool {
+        self.should_open
+    }
+}
+
+/// In AUTO the crossbleed valve opens when the APU bleed air valve is open.
+struct A320CrossBleedValveController {
+    should_open: bool,
+}
+impl A320CrossBleedValveController {
+    fn new() -> Self {
+        Self { should_open: false }
+    }
+
+    fn update<T: BleedAirValveState>(&mut self, overhead: &A320PneumaticOverheadPanel, apu: &T) {
+        self.should_open = match overhead.cross_bleed_mode() {
+            CrossBleedMode::Shut => false,
+            CrossBleedMode::Auto => apu.bleed_air_valve_is_open(),
+            CrossBleedMode::Open => true,
+        };
+    }
+}
+impl CrossBleedValveController for A320CrossBleedValveController {
+    fn should_open_cross_bleed_valve(&self) -> bool {
+        self.should_open
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CrossBleedMode {
+    Shut,
+    Auto,
+    Open,
+}
 
 pub struct A320PneumaticOverheadPanel {
+    cross_bleed_knob_id: VariableIdentifier,
+
     apu_bleed: OnOffFaultPushButton,
+    engine_bleed: [OnOffFaultPushButton; 2],
+    cross_bleed_mode: CrossBleedMode,
 }
 impl A320PneumaticOverheadPanel {
-    pub fn new() -> Self {
+    const CROSS_BLEED_KNOB_KEY: &'static str = "KNOB_OVHD_AIRCOND_XBLEED_Position";
+
+    pub fn new(registry: &mut VariableRegistry) -> Self {
         A320PneumaticOverheadPanel {
-            apu_bleed: OnOffFaultPushButton::new_on("PNEU_APU_BLEED"),
+            cross_bleed_knob_id: registry.get(Self::CROSS_BLEED_KNOB_KEY),
+
+            apu_bleed: OnOffFaultPushButton::new_on(registry, "PNEU_APU_BLEED"),
+            engine_bleed: [
+                OnOffFaultPushButton::new_on(registry, "PNEU_ENG_1_BLEED"),
+                OnOffFaultPushButton::new_on(registry, "PNEU_ENG_2_BLEED"),
+            ],
+            cross_bleed_mode: CrossBleedMode::Auto,
         }
     }
 
     pub fn apu_bleed_is_on(&self) -> bool {
         self.apu_bleed.is_on()
     }
+
+    pub fn engine_bleed_is_on(&self, number: usize) -> bool {
+        self.engine_bleed[number - 1].is_on()
+    }
+
+    pub fn cross_bleed_mode(&self) -> CrossBleedMode {
+        self.cross_bleed_mode
+    }
 }
 impl SimulationElement for A320PneumaticOverheadPanel {
     fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
         self.apu_bleed.accept(visitor);
+        self.engine_bleed
+            .iter_mut()
+            .for_each(|el| el.accept(visitor));
 
         visitor.visit(self);
     }
+
+    fn read(&mut self, reader: &mut SimulatorReader) {
+        self.cross_bleed_mode = match reader.read_f64(&self.cross_bleed_knob_id) as u8 {
+            0 => CrossBleedMode::Shut,
+            2 => CrossBleedMode::Open,
+            _ => CrossBleedMode::Auto,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use systems::{
+        electrical::consumption::SuppliedPower,
+        simulation::{test::SimulationTestBed, Aircraft},
+    };
+    use uom::si::{pressure::psi, thermodynamic_temperature::degree_celsius};
+
+    struct TestEngine {
+        corrected_n2: Ratio,
+    }
+    impl TestEngine {
+        fn new() -> Self {
+            Self {
+                corrected_n2: Ratio::new::<percent>(0.),
+            }
+        }
+
+        fn set_corrected_n2(&mut self, n2: Ratio) {
+            self.corrected_n2 = n2;
+        }
+    }
+    impl EngineCorrectedN2 for TestEngine {
+        fn corrected_n2(&self) -> Ratio {
+            self.corrected_n2
+        }
+    }
+
+    struct TestApu {
+        bleed_air_demanded: bool,
+        available: bool,
+    }
+    impl TestApu {
+        fn new() -> Self {
+            Self {
+                bleed_air_demanded: false,
+                available: false,
+            }
+        }
+
+        fn update(&mut self, bleed_air_demanded: bool) {
+            self.bleed_air_demanded = bleed_air_demanded;
+        }
+
+        fn set_available(&mut self, available: bool) {
+            self.available = available;
+        }
+    }
+    impl BleedAirValveState for TestApu {
+        fn bleed_air_valve_is_open(&self) -> bool {
+            self.available && self.bleed_air_demanded
+        }
+    }
+    impl BleedAirSource for TestApu {
+        fn bleed_air_pressure(&self) -> Pressure {
+            if self.bleed_air_valve_is_open() {
+                Pressure::new::<psi>(38.)
+            } else {
+                Pressure::new::<psi>(0.)
+            }
+        }
+
+        fn bleed_air_temperature(&self) -> ThermodynamicTemperature {
+            ThermodynamicTemperature::new::<degree_celsius>(210.)
+        }
+    }
+
+    struct TestEngineFirePushButtons {
+        released: [bool; 2],
+    }
+    impl TestEngineFirePushButtons {
+        fn new() -> Self {
+            Self {
+                released: [false, false],
+            }
+        }
+
+        fn release(&mut self, number: usize) {
+            self.released[number - 1] = true;
+        }
+    }
+    impl EngineFirePushButtons for TestEngineFirePushButtons {
+        fn is_released(&self, engine_number: usize) -> bool {
+            self.released[engine_number - 1]
+        }
+    }
+
+    struct A320PneumaticTestAircraft {
+        pneumatic: A320Pneumatic,
+        overhead: A320PneumaticOverheadPanel,
+        engines: [TestEngine; 2],
+        apu: TestApu,
+        engine_fire_push_buttons: TestEngineFirePushButtons,
+    }
+    impl A320PneumaticTestAircraft {
+        fn new(registry: &mut VariableRegistry) -> Self {
+            Self {
+                pneumatic: A320Pneumatic::new(registry),
+                overhead: A320PneumaticOverheadPanel::new(registry),
+                engines: [TestEngine::new(), TestEngine::new()],
+                apu: TestApu::new(),
+                engine_fire_push_buttons: TestEngineFirePushButtons::new(),
+            }
+        }
+    }
+    impl Aircraft for A320PneumaticTestAircraft {
+        fn update_before_power_distribution(&mut self, context: &UpdateContext) {
+            self.pneumatic
+                .update_before_apu([&self.engines[0], &self.engines[1]], &self.overhead);
+            self.apu.update(self.pneumatic.apu_bleed_air_is_demanded());
+            self.pneumatic.update_after_apu(
+                context,
+                [&self.engines[0], &self.engines[1]],
+                &self.apu,
+                &self.overhead,
+                &self.engine_fire_push_buttons,
+            );
+        }
+
+        fn get_supplied_power(&mut self) -> SuppliedPower {
+            SuppliedPower::new()
+        }
+    }
+    impl SimulationElement for A320PneumaticTestAircraft {
+        fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+            self.pneumatic.accept(visitor);
+            self.overhead.accept(visitor);
+
+            visitor.visit(self);
+        }
+    }
+
+    struct A320PneumaticTestBed {
+        aircraft: A320PneumaticTestAircraft,
+        simulation_test_bed: SimulationTestBed,
+    }
+    impl A320PneumaticTestBed {
+        fn new() -> Self {
+            let mut registry = VariableRegistry::new();
+            let mut aircraft = A320PneumaticTestAircraft::new(&mut registry);
+            let mut simulation_test_bed = SimulationTestBed::seeded_with(registry, &mut aircraft);
+            simulation_test_bed.write_f64(A320PneumaticOverheadPanel::CROSS_BLEED_KNOB_KEY, 1.);
+
+            Self {
+                aircraft,
+                simulation_test_bed,
+            }
+        }
+
+        fn and(self) -> Self {
+            self
+        }
+
+        fn running_engine(mut self, number: usize) -> Self {
+            self.aircraft.engines[number - 1].set_corrected_n2(Ratio::new::<percent>(70.));
+            self
+        }
+
+        fn starting_engine(mut self, number: usize) -> Self {
+            self.aircraft.engines[number - 1].set_corrected_n2(Ratio::new::<percent>(30.));
+            self.simulation_test_bed
+                .write_bool(&format!("GENERAL ENG STARTER ACTIVE:{}", number), true);
+            self
+        }
+
+        fn running_apu(mut self) -> Self {
+            self.aircraft.apu.set_available(true);
+            self
+        }
+
+        fn apu_bleed_off(mut self) -> Self {
+            self.simulation_test_bed
+                .write_bool("OVHD_PNEU_APU_BLEED_PB_IS_ON", false);
+            self
+        }
+
+        fn engine_bleed_off(mut self, number: usize) -> Self {
+            self.simulation_test_bed
+                .write_bool(&format!("OVHD_PNEU_ENG_{}_BLEED_PB_IS_ON", number), false);
+            self
+        }
+
+        fn released_engine_fire_pb(mut self, number: usize) -> Self {
+            self.aircraft.engine_fire_push_buttons.release(number);
+            self
+        }
+
+        fn pack_on(mut self, number: usize) -> Self {
+            self.simulation_test_bed
+                .write_bool(&format!("AIRCOND_PACK{}_TOGGLE", number), true);
+            self
+        }
+
+        fn cross_bleed_mode(mut self, mode: CrossBleedMode) -> Self {
+            self.simulation_test_bed.write_f64(
+                A320PneumaticOverheadPanel::CROSS_BLEED_KNOB_KEY,
+                match mode {
+                    CrossBleedMode::Shut => 0.,
+                    CrossBleedMode::Auto => 1.,
+                    CrossBleedMode::Open => 2.,
+                },
+            );
+            self
+        }
+
+        fn run(mut self) -> Self {
+            self.simulation_test_bed.set_delta(Duration::from_secs(1));
+            for _ in 0..30 {
+                self.simulation_test_bed.run_aircraft(&mut self.aircraft);
+            }
+
+            self
+        }
+
+        fn apu_bleed_air_is_demanded(&self) -> bool {
+            self.aircraft.pneumatic.apu_bleed_air_is_demanded()
+        }
+
+        fn duct_pressure(&self, number: usize) -> Pressure {
+            self.aircraft.pneumatic.duct_pressure(number)
+        }
+
+        fn engine_bleed_valve_is_open(&self, number: usize) -> bool {
+            self.aircraft
+                .pneumatic
+                .engine_bleed_pressure_regulating_valve_is_open(number)
+        }
+
+        fn cross_bleed_valve_is_open(&self) -> bool {
+            self.aircraft.pneumatic.cross_bleed_valve_is_open()
+        }
+
+        fn is_pressurised(&self, number: usize) -> bool {
+            self.duct_pressure(number) > Pressure::new::<psi>(30.)
+        }
+    }
+
+    fn test_bed() -> A320PneumaticTestBed {
+        A320PneumaticTestBed::new()
+    }
+
+    fn test_bed_with() -> A320PneumaticTestBed {
+        test_bed()
+    }
+
+    #[test]
+    fn cold_and_dark_ducts_are_unpressurised() {
+        let test_bed = test_bed().run();
+
+        assert!(!test_bed.is_pressurised(1));
+        assert!(!test_bed.is_pressurised(2));
+    }
+
+    #[test]
+    fn apu_bleed_air_is_not_demanded_without_consumers() {
+        let test_bed = test_bed_with().running_apu().run();
+
+        assert!(!test_bed.apu_bleed_air_is_demanded());
+        assert!(!test_bed.is_pressurised(1));
+    }
+
+    #[test]
+    fn apu_bleed_air_is_demanded_by_packs() {
+        let test_bed = test_bed_with().running_apu().and().pack_on(1).run();
+
+        assert!(test_bed.apu_bleed_air_is_demanded());
+    }
+
+    #[test]
+    fn apu_bleed_air_is_demanded_by_starting_engine() {
+        let test_bed = test_bed_with().running_apu().and().starting_engine(2).run();
+
+        assert!(test_bed.apu_bleed_air_is_demanded());
+    }
+
+    #[test]
+    fn apu_bleed_air_is_not_demanded_when_apu_bleed_push_button_is_off() {
+        let test_bed = test_bed_with()
+            .running_apu()
+            .pack_on(1)
+            .and()
+            .apu_bleed_off()
+            .run();
+
+        assert!(!test_bed.apu_bleed_air_is_demanded());
+    }
+
+    #[test]
+    fn apu_bleed_air_pressurises_both_ducts_through_cross_bleed_in_auto() {
+        let test_bed = test_bed_with().running_apu().and().pack_on(1).run();
+
+        assert!(test_bed.cross_bleed_valve_is_open());
+        assert!(test_bed.is_pressurised(1));
+        assert!(test_bed.is_pressurised(2));
+    }
+
+    #[test]
+    fn apu_bleed_air_only_pressurises_left_duct_when_cross_bleed_shut() {
+        let test_bed = test_bed_with()
+            .running_apu()
+            .pack_on(1)
+            .and()
+            .cross_bleed_mode(CrossBleedMode::Shut)
+            .run();
+
+        assert!(!test_bed.cross_bleed_valve_is_open());
+        assert!(test_bed.is_pressurised(1));
+        assert!(!test_bed.is_pressurised(2));
+    }
+
+    #[test]
+    fn cross_bleed_open_connects_ducts() {
+        let test_bed = test_bed_with()
+            .running_engine(1)
+            .and()
+            .cross_bleed_mode(CrossBleedMode::Open)
+            .run();
+
+        assert!(test_bed.cross_bleed_valve_is_open());
+        assert!(test_bed.is_pressurised(2));
+    }
+
+    #[test]
+    fn running_engines_pressurise_their_ducts() {
+        let test_bed = test_bed_with()
+            .running_engine(1)
+            .and()
+            .running_engine(2)
+            .run();
+
+        assert!(test_bed.engine_bleed_valve_is_open(1));
+        assert!(test_bed.engine_bleed_valve_is_open(2));
+        assert!(test_bed.is_pressurised(1));
+        assert!(test_bed.is_pressurised(2));
+    }
+
+    #[test]
+    fn engine_bleed_valve_closes_when_push_button_off() {
+        let test_bed = test_bed_with()
+            .running_engine(1)
+            .and()
+            .engine_bleed_off(1)
+            .run();
+
+        assert!(!test_bed.engine_bleed_valve_is_open(1));
+        assert!(!test_bed.is_pressurised(1));
+    }
+
+    #[test]
+    fn engine_bleed_valve_closes_when_engine_fire_push_button_released() {
+        let test_bed = test_bed_with()
+            .running_engine(2)
+            .and()
+            .released_engine_fire_pb(2)
+            .run();
+
+        assert!(!test_bed.engine_bleed_valve_is_open(2));
+    }
+
+    #[test]
+    fn engine_bleed_valves_close_when_apu_supplies_both_sides() {
+        let test_bed = test_bed_with()
+            .running_engine(1)
+            .running_engine(2)
+            .running_apu()
+            .and()
+            .pack_on(1)
+            .run();
+
+        assert!(!test_bed.engine_bleed_valve_is_open(1));
+        assert!(!test_bed.engine_bleed_valve_is_open(2));
+        assert!(test_bed.is_pressurised(1));
+        assert!(test_bed.is_pressurised(2));
+    }
+
+    #[test]
+    fn engine_2_bleed_valve_remains_open_when_apu_supplies_left_side_only() {
+        let test_bed = test_bed_with()
+            .running_engine(2)
+            .running_apu()
+            .pack_on(1)
+            .and()
+            .cross_bleed_mode(CrossBleedMode::Shut)
+            .run();
+
+        assert!(test_bed.engine_bleed_valve_is_open(2));
+    }
+
+    #[test]
+    fn engine_bleed_valve_closes_while_engine_is_starting() {
+        let test_bed = test_bed_with().starting_engine(1).run();
+
+        assert!(!test_bed.engine_bleed_valve_is_open(1));
+    }
 }
@@ -3,7 +3,7 @@ use systems::{
         consumption::{FlightPhasePowerConsumer, PowerConsumerFlightPhase},
         ElectricalBusType,
     },
-    simulation::{SimulationElement, SimulationElementVisitor, UpdateContext},
+    simulation::{SimulationElement, SimulationElementVisitor, UpdateContext, VariableRegistry},
 };
 use uom::si::{f64::*, power::watt};
 
@@ -27,10 +27,11 @@ pub(super) struct A320PowerConsumption {
     dc_gnd_flt_service_consumer: FlightPhasePowerConsumer,
 }
 impl A320PowerConsumption {
-    pub fn new() -> Self {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
         // The watts in this function are all provided by komp.
         Self {
             ac_bus_1_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::AlternatingCurrent(1),
             )
             .demand([
@@ -60,6 +61,7 @@ impl A320PowerConsumption {
                 ),
             ]),
             ac_bus_2_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::AlternatingCurrent(2),
             )
             .demand([
@@ -89,6 +91,7 @@ impl A320PowerConsumption {
                 ),
             ]),
             ac_ess_bus_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::AlternatingCurrentEssential,
             )
             .demand([
@@ -106,6 +109,7 @@ impl A320PowerConsumption {
                 (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(715.7)),
             ]),
             ac_ess_shed_bus_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::AlternatingCurrentEssentialShed,
             )
             .demand([
@@ -123,6 +127,7 @@ impl A320PowerConsumption {
                 (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(823.5)),
             ]),
             ac_stat_inv_bus_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::AlternatingCurrentStaticInverter,
             )
             .demand([
@@ -140,6 +145,7 @@ impl A320PowerConsumption {
                 (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(135.)),
             ]),
             ac_gnd_flt_service_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::AlternatingCurrentGndFltService,
             )
             .demand([
@@ -156,37 +162,44 @@ impl A320PowerConsumption {
                 (PowerConsumerFlightPhase::Landing, Power::new::<watt>(2628.)),
                 (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(3663.)),
             ]),
-            dc_bus_1_consumer: FlightPhasePowerConsumer::from(ElectricalBusType::DirectCurrent(1))
-                .demand([
-                    (
-                        PowerConsumerFlightPhase::BeforeStart,
-                        Power::new::<watt>(252.),
-                    ),
-                    (
-                        PowerConsumerFlightPhase::AfterStart,
-                        Power::new::<watt>(308.),
-                    ),
-                    (PowerConsumerFlightPhase::Takeoff, Power::new::<watt>(364.)),
-                    (PowerConsumerFlightPhase::Flight, Power::new::<watt>(280.)),
-                    (PowerConsumerFlightPhase::Landing, Power::new::<watt>(364.)),
-                    (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(336.)),
-                ]),
-            dc_bus_2_consumer: FlightPhasePowerConsumer::from(ElectricalBusType::DirectCurrent(2))
-                .demand([
-                    (
-                        PowerConsumerFlightPhase::BeforeStart,
-                        Power::new::<watt>(532.),
-                    ),
-                    (
-                        PowerConsumerFlightPhase::AfterStart,
-                        Power::new::<watt>(448.),
-                    ),
-                    (PowerConsumerFlightPhase::Takeoff, Power::new::<watt>(392.)),
-                    (PowerConsumerFlightPhase::Flight, Power::new::<watt>(392.)),
-                    (PowerConsumerFlightPhase::Landing, Power::new::<watt>(392.)),
-                    (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(448.)),
-                ]),
+            dc_bus_1_consumer: FlightPhasePowerConsumer::from(
+                registry,
+                ElectricalBusType::DirectCurrent(1),
+            )
+            .demand([
+                (
+                    PowerConsumerFlightPhase::BeforeStart,
+                    Power::new::<watt>(252.),
+                ),
+                (
+                    PowerConsumerFlightPhase::AfterStart,
+                    Power::new::<watt>(308.),
+                ),
+                (PowerConsumerFlightPhase::Takeoff, Power::new::<watt>(364.)),
+                (PowerConsumerFlightPhase::Flight, Power::new::<watt>(280.)),
+                (PowerConsumerFlightPhase::Landing, Power::new::<watt>(364.)),
+                (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(336.)),
+            ]),
+            dc_bus_2_consumer: FlightPhasePowerConsumer::from(
+                registry,
+                ElectricalBusType::DirectCurrent(2),
+            )
+            .demand([
+                (
+                    PowerConsumerFlightPhase::BeforeStart,
+                    Power::new::<watt>(532.),
+                ),
+                (
+                    PowerConsumerFlightPhase::AfterStart,
+                    Power::new::<watt>(448.),
+                ),
+                (PowerConsumerFlightPhase::Takeoff, Power::new::<watt>(392.)),
+                (PowerConsumerFlightPhase::Flight, Power::new::<watt>(392.)),
+                (PowerConsumerFlightPhase::Landing, Power::new::<watt>(392.)),
+                (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(448.)),
+            ]),
             dc_ess_bus_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::DirectCurrentEssential,
             )
             .demand([
@@ -204,6 +217,7 @@ impl A320PowerConsumption {
                 (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(140.)),
             ]),
             dc_ess_shed_bus_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::DirectCurrentEssentialShed,
             )
             .demand([
@@ -221,6 +235,7 @@ impl A320PowerConsumption {
                 (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(168.)),
             ]),
             dc_bat_bus_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::DirectCurrentBattery,
             )
             .demand([
@@ -238,6 +253,7 @@ impl A320PowerConsumption {
                 (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(28.)),
             ]),
             dc_hot_bus_1_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::DirectCurrentHot(1),
             )
             .demand([
@@ -255,6 +271,7 @@ impl A320PowerConsumption {
                 (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(11.)),
             ]),
             dc_hot_bus_2_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::DirectCurrentHot(2),
             )
             .demand([
@@ -272,6 +289,7 @@ impl A320PowerConsumption {
                 (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(24.3)),
             ]),
             dc_gnd_flt_service_consumer: FlightPhasePowerConsumer::from(
+                registry,
                 ElectricalBusType::DirectCurrentGndFltService,
             )
             .demand([
@@ -328,8 +346,3 @@ impl SimulationElement for A320PowerConsumption {
         visitor.visit(self);
     }
 }
-impl Default for A320PowerConsumption {
-    fn default() -> Self {
-        A320PowerConsumption::new()
-    }
-}
@@ -4,18 +4,97 @@ use msfs::{
     legacy::{execute_calculator_code, AircraftVariable, NamedVariable},
     MSFSEvent,
 };
-use std::collections::HashMap;
-use systems::simulation::{Simulation, SimulatorReaderWriter};
+#[cfg(feature = "record")]
+use std::io::BufWriter;
+use std::{fs::File, io::BufReader, time::Duration};
+use systems::{
+    shared::SystemRandomSource,
+    simulation::{
+        EventBus, InvariantMonitor, InvariantViolationAction, Scheduler, Simulation,
+        SimulatorReaderWriter, Snapshot, VariableIdentifier, VariableRegistry,
+    },
+};
+#[cfg(feature = "record")]
+use systems::{
+    shared::{RandomSource, SeededRandomSource},
+    simulation::RecordingSimulatorReaderWriter,
+};
+
+const SNAPSHOT_PATH: &str = "\\work\\systems.snapshot";
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(30);
+// The events are written to a file, rather than exposed as indexed L-vars, as they are meant
+// for finding out what happened to the systems during a flight, such as when reporting a bug.
+// Moreover an L-var only holds a number, while the source, kind and cause of an event are text.
+// Instruments can tell that something happened through the L-vars written by the event bus.
+const EVENTS_PATH: &str = "\\work\\systems.events";
+const EVENTS_CAPACITY: usize = 1000;
+#[cfg(feature = "record")]
+const RECORDING_PATH: &str = "\\work\\systems.recording";
 
 #[msfs::gauge(name=systems)]
 async fn systems(mut gauge: msfs::Gauge) -> Result<(), Box<dyn std::error::Error>> {
-    let mut reader_writer = A320SimulatorReaderWriter::new()?;
-    let mut a320 = A320::new();
-    let mut simulation = Simulation::new(&mut a320, &mut reader_writer);
+    let mut registry = VariableRegistry::new();
+    let mut a320 = A320::new(&mut registry);
+    let mut event_bus =
+        EventBus::ring_buffer(EVENTS_CAPACITY).with_simulator_variables(&mut registry);
+
+    #[cfg(not(feature = "record"))]
+    let mut reader_writer = A320SimulatorReaderWriter::new(&registry)?;
+    #[cfg(not(feature = "record"))]
+    let mut random_source = SystemRandomSource::new();
+    // The recording contains the random seed, such that a replay produces the same random numbers.
+    #[cfg(feature = "record")]
+    let random_seed = SystemRandomSource::new().next_u64();
+    #[cfg(feature = "record")]
+    let mut reader_writer = RecordingSimulatorReaderWriter::new(
+        A320SimulatorReaderWriter::new(&registry)?,
+        BufWriter::new(File::create(RECORDING_PATH)?),
+        random_seed,
+        &registry,
+    );
+    #[cfg(feature = "record")]
+    let mut random_source = SeededRandomSource::new(random_seed);
+    let mut invariant_monitor = InvariantMonitor::new(InvariantViolationAction::Log);
+    let mut scheduler = Scheduler::new();
+    let mut simulation = Simulation::new_with_settings(
+        &mut a320,
+        &mut reader_writer,
+        &mut random_source,
+        &mut scheduler,
+        A320::simulation_settings(),
+    )
+    .with_invariant_monitor(&mut invariant_monitor)
+    .with_event_bus(&mut event_bus);
+
+    // A recording is replayed on a newly created aircraft,
+    // thus the snapshot isn't restored when recording.
+    if !cfg!(feature = "record") {
+        if let Ok(file) = File::open(SNAPSHOT_PATH) {
+            // An unreadable snapshot is ignored, such that the systems start in their default state.
+            if let Ok(snapshot) = Snapshot::read_from(BufReader::new(file)) {
+                simulation.restore(&snapshot);
+            }
+        }
+    }
 
+    let mut time_since_snapshot = Duration::from_secs(0);
     while let Some(event) = gauge.next_event().await {
         if let MSFSEvent::PreDraw(d) = event {
             simulation.tick(d.delta_time());
+
+            time_since_snapshot += d.delta_time();
+            if time_since_snapshot >= SNAPSHOT_INTERVAL {
+                time_since_snapshot = Duration::from_secs(0);
+                // Failing to save a snapshot shouldn't stop the simulation.
+                let _ = File::create(SNAPSHOT_PATH)
+                    .and_then(|mut file| simulation.snapshot().write_to(&mut file));
+                // The most recent events are written alongside, for finding out what
+                // happened to the systems during the flight.
+                if let Some(event_bus) = simulation.event_bus() {
+                    let _ = File::create(EVENTS_PATH)
+                        .and_then(|mut file| event_bus.write_csv(&mut file));
+                }
+            }
         }
     }
 
@@ -23,208 +102,205 @@ async fn systems(mut gauge: msfs::Gauge) -> Result<(), Box<dyn std::error::Error
 }
 
 struct A320SimulatorReaderWriter {
-    dynamic_named_variables: HashMap<String, NamedVariable>,
-    electrical_bus_connections: ElectricalBusConnections,
-
-    ambient_temperature: AircraftVariable,
-    apu_generator_pb_on: AircraftVariable,
-    external_power_available: AircraftVariable,
-    external_power_pb_on: AircraftVariable,
-    engine_generator_1_pb_on: AircraftVariable,
-    engine_generator_2_pb_on: AircraftVariable,
-    gear_center_position: AircraftVariable,
-    gear_handle_position: AircraftVariable,
-    turb_eng_corrected_n2_1: AircraftVariable,
-    turb_eng_corrected_n2_2: AircraftVariable,
-    airspeed_indicated: AircraftVariable,
-    indicated_altitude: AircraftVariable,
-    fuel_tank_left_main_quantity: AircraftVariable,
-    sim_on_ground: AircraftVariable,
-    unlimited_fuel: AircraftVariable,
-    parking_brake_demand: AircraftVariable,
-    master_eng_1: AircraftVariable,
-    master_eng_2: AircraftVariable,
-    cargo_door_front_pos: AircraftVariable,
-    cargo_door_back_pos: AircraftVariable,
-    pushback_angle: AircraftVariable,
-    pushback_state: AircraftVariable,
-    anti_skid_activated: AircraftVariable,
-    left_brake_command: AircraftVariable,
-    right_brake_command: AircraftVariable,
-    longitudinal_accel: AircraftVariable,
+    // The variable of each identifier, indexed by the identifier.
+    variables: Vec<Variable>,
 }
 impl A320SimulatorReaderWriter {
-    fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    fn new(registry: &VariableRegistry) -> Result<Self, Box<dyn std::error::Error>> {
         Ok(A320SimulatorReaderWriter {
-            dynamic_named_variables: HashMap::new(),
-            electrical_bus_connections: ElectricalBusConnections::new(),
-
-            ambient_temperature: AircraftVariable::from("AMBIENT TEMPERATURE", "celsius", 0)?,
-            apu_generator_pb_on: AircraftVariable::from("APU GENERATOR SWITCH", "Bool", 0)?,
-            external_power_available: AircraftVariable::from(
-                "EXTERNAL POWER AVAILABLE",
-                "Bool",
-                1,
-            )?,
-            external_power_pb_on: AircraftVariable::from("EXTERNAL POWER ON", "Bool", 1)?,
-            engine_generator_1_pb_on: AircraftVariable::from(
-                "GENERAL ENG MASTER ALTERNATOR",
-                "Bool",
-                1,
-            )?,
-            engine_generator_2_pb_on: AircraftVariable::from(
-                "GENERAL ENG MASTER ALTERNATOR",
-                "Bool",
-                2,
-            )?,
-            gear_center_position: AircraftVariable::from("GEAR CENTER POSITION", "Percent", 0)?,
-            gear_handle_position: AircraftVariable::from("GEAR HANDLE POSITION", "Bool", 0)?,
-            turb_eng_corrected_n2_1: AircraftVariable::from("TURB ENG CORRECTED N2", "Percent", 1)?,
-            turb_eng_corrected_n2_2: AircraftVariable::from("TURB ENG CORRECTED N2", "Percent", 2)?,
-            airspeed_indicated: AircraftVariable::from("AIRSPEED INDICATED", "Knots", 0)?,
-            indicated_altitude: AircraftVariable::from("INDICATED ALTITUDE", "Feet", 0)?,
-            fuel_tank_left_main_quantity: AircraftVariable::from(
-                "FUEL TANK LEFT MAIN QUANTITY",
-                "Pounds",
-                0,
-            )?,
-            sim_on_ground: AircraftVariable::from("SIM ON GROUND", "Bool", 0)?,
-            unlimited_fuel: AircraftVariable::from("UNLIMITED FUEL", "Bool", 0)?,
-            parking_brake_demand: AircraftVariable::from("BRAKE PARKING INDICATOR", "Bool", 0)?,
-            master_eng_1: AircraftVariable::from("GENERAL ENG STARTER ACTIVE", "Bool", 1)?,
-            master_eng_2: AircraftVariable::from("GENERAL ENG STARTER ACTIVE", "Bool", 2)?,
-            cargo_door_front_pos: AircraftVariable::from("EXIT OPEN", "Percent", 5)?,
-
-            // TODO It is the catering door for now.
-            cargo_door_back_pos: AircraftVariable::from("EXIT OPEN", "Percent", 3)?,
-
-            pushback_angle: AircraftVariable::from("PUSHBACK ANGLE", "Radian", 0)?,
-            pushback_state: AircraftVariable::from("PUSHBACK STATE", "Enum", 0)?,
-            anti_skid_activated: AircraftVariable::from("ANTISKID BRAKES ACTIVE", "Bool", 0)?,
-            left_brake_command: AircraftVariable::from("BRAKE LEFT POSITION", "Percent", 0)?,
-            right_brake_command: AircraftVariable::from("BRAKE RIGHT POSITION", "Percent", 0)?,
-            longitudinal_accel: AircraftVariable::from(
-                "ACCELERATION BODY Z",
-                "feet per second squared",
-                0,
-            )?,
+            variables: registry
+                .iter()
+                .map(|(_, name)| Variable::for_name(name))
+                .collect::<Result<_, _>>()?,
         })
     }
 }
 impl SimulatorReaderWriter for A320SimulatorReaderWriter {
-    fn read(&mut self, name: &str) -> f64 {
-        match name {
-            "OVHD_ELEC_APU_GEN_PB_IS_ON" => self.apu_generator_pb_on.get(),
-            "OVHD_ELEC_EXT_PWR_PB_IS_AVAILABLE" => self.external_power_available.get(),
-            "OVHD_ELEC_EXT_PWR_PB_IS_ON" => self.external_power_pb_on.get(),
-            "OVHD_ELEC_ENG_GEN_1_PB_IS_ON" => self.engine_generator_1_pb_on.get(),
-            "OVHD_ELEC_ENG_GEN_2_PB_IS_ON" => self.engine_generator_2_pb_on.get(),
-            "AMBIENT TEMPERATURE" => self.ambient_temperature.get(),
-            "EXTERNAL POWER AVAILABLE:1" => self.external_power_available.get(),
-            "GEAR CENTER POSITION" => self.gear_center_position.get(),
-            "GEAR HANDLE POSITION" => self.gear_handle_position.get(),
-            "TURB ENG CORRECTED N2:1" => self.turb_eng_corrected_n2_1.get(),
-            "TURB ENG CORRECTED N2:2" => self.turb_eng_corrected_n2_2.get(),
-            "FUEL TANK LEFT MAIN QUANTITY" => self.fuel_tank_left_main_quantity.get(),
-            "UNLIMITED FUEL" => self.unlimited_fuel.get(),
-            "AIRSPEED INDICATED" => self.airspeed_indicated.get(),
-            "INDICATED ALTITUDE" => self.indicated_altitude.get(),
-            "SIM ON GROUND" => self.sim_on_ground.get(),
-            "GENERAL ENG STARTER ACTIVE:1" => self.master_eng_1.get(),
-            "GENERAL ENG STARTER ACTIVE:2" => self.master_eng_2.get(),
-            "BRAKE PARKING INDICATOR" => self.parking_brake_demand.get(),
-            "EXIT OPEN:5" => self.cargo_door_front_pos.get(),
-            "EXIT OPEN:3" => self.cargo_door_back_pos.get(),
-            "PUSHBACK ANGLE" => self.pushback_angle.get(),
-            "PUSHBACK STATE" => self.pushback_state.get(),
-            "ANTISKID BRAKES ACTIVE" => self.anti_skid_activated.get(),
-            "BRAKE LEFT POSITION" => self.left_brake_command.get(),
-            "BRAKE RIGHT POSITION" => self.right_brake_command.get(),
-            "ACCELERATION BODY Z" => self.longitudinal_accel.get(),
-            _ => {
-                lookup_named_variable(&mut self.dynamic_named_variables, "A32NX_", name).get_value()
-            }
+    fn read(&mut self, identifier: &VariableIdentifier) -> f64 {
+        match &self.variables[identifier.index()] {
+            Variable::Aircraft(variable) => variable.get(),
+            Variable::GearPosition(gear) => gear.position(),
+            Variable::Named(variable)
+            | Variable::ElectricalBus(variable, _)
+            | Variable::SteeringAngle(variable, _) => variable.get_value(),
         }
     }
 
-    fn write(&mut self, name: &str, value: f64) {
-        if name.starts_with("ELEC_") && name.ends_with("_BUS_IS_POWERED") {
-            self.electrical_bus_connections.update(name, value);
+    fn write(&mut self, identifier: &VariableIdentifier, value: f64) {
+        match &mut self.variables[identifier.index()] {
+            // Aircraft variables cannot be set directly, these are only read.
+            Variable::Aircraft(_) => {}
+            // The flight model's gear follows the hydraulically actuated gear.
+            Variable::GearPosition(gear) => gear.update(value),
+            Variable::Named(variable) => variable.set_value(value),
+            Variable::ElectricalBus(variable, connection) => {
+                connection.update(value);
+                variable.set_value(value);
+            }
+            Variable::SteeringAngle(variable, steering) => {
+                steering.update(value);
+                variable.set_value(value);
+            }
         }
+    }
+}
 
-        let named_variable =
-            lookup_named_variable(&mut self.dynamic_named_variables, "A32NX_", name);
+/// The simulator variable backing an identifier of the [`VariableRegistry`].
+enum Variable {
+    Aircraft(AircraftVariable),
+    /// The position of a gear, which is read from and written to the flight model.
+    GearPosition(FlightModelGear),
+    Named(NamedVariable),
+    /// The powered state of a bus, which also connects the simulator's bus of the same name.
+    ElectricalBus(NamedVariable, ElectricalBusConnection),
+    /// The angle of the nose wheel, which the flight model's steering also follows.
+    SteeringAngle(NamedVariable, FlightModelSteering),
+}
+impl Variable {
+    fn for_name(name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let aircraft = |name, units, index| -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(Variable::Aircraft(AircraftVariable::from(
+                name, units, index,
+            )?))
+        };
 
-        named_variable.set_value(value);
+        match name {
+            "OVHD_ELEC_APU_GEN_PB_IS_ON" => aircraft("APU GENERATOR SWITCH", "Bool", 0),
+            "OVHD_ELEC_EXT_PWR_PB_IS_AVAILABLE" => aircraft("EXTERNAL POWER AVAILABLE", "Bool", 1),
+            "OVHD_ELEC_EXT_PWR_PB_IS_ON" => aircraft("EXTERNAL POWER ON", "Bool", 1),
+            "OVHD_ELEC_ENG_GEN_1_PB_IS_ON" => aircraft("GENERAL ENG MASTER ALTERNATOR", "Bool", 1),
+            "OVHD_ELEC_ENG_GEN_2_PB_IS_ON" => aircraft("GENERAL ENG MASTER ALTERNATOR", "Bool", 2),
+            "OVHD_PNEU_ENG_1_BLEED_PB_IS_ON" => aircraft("BLEED AIR ENGINE", "Bool", 1),
+            "OVHD_PNEU_ENG_2_BLEED_PB_IS_ON" => aircraft("BLEED AIR ENGINE", "Bool", 2),
+            "AMBIENT TEMPERATURE" => aircraft("AMBIENT TEMPERATURE", "celsius", 0),
+            "EXTERNAL POWER AVAILABLE:1" => aircraft("EXTERNAL POWER AVAILABLE", "Bool", 1),
+            "GEAR EMERGENCY HANDLE POSITION" => {
+                aircraft("GEAR EMERGENCY HANDLE POSITION", "Bool", 0)
+            }
+            "GEAR HANDLE POSITION" => aircraft("GEAR HANDLE POSITION", "Bool", 0),
+            "GEAR CENTER POSITION" | "GEAR LEFT POSITION" | "GEAR RIGHT POSITION" => {
+                Ok(Variable::GearPosition(FlightModelGear::new(name)?))
+            }
+            "TURB ENG CORRECTED N2:1" => aircraft("TURB ENG CORRECTED N2", "Percent", 1),
+            "TURB ENG CORRECTED N2:2" => aircraft("TURB ENG CORRECTED N2", "Percent", 2),
+            "FUEL TANK LEFT MAIN QUANTITY" => aircraft("FUEL TANK LEFT MAIN QUANTITY", "Pounds", 0),
+            "FUEL TANK RIGHT MAIN QUANTITY" => {
+                aircraft("FUEL TANK RIGHT MAIN QUANTITY", "Pounds", 0)
+            }
+            "FUEL TANK CENTER QUANTITY" => aircraft("FUEL TANK CENTER QUANTITY", "Pounds", 0),
+            "FUEL TANK LEFT AUX QUANTITY" => aircraft("FUEL TANK LEFT AUX QUANTITY", "Pounds", 0),
+            "FUEL TANK RIGHT AUX QUANTITY" => aircraft("FUEL TANK RIGHT AUX QUANTITY", "Pounds", 0),
+            "FUELSYSTEM PUMP SWITCH:1" => aircraft("FUELSYSTEM PUMP SWITCH", "Bool", 1),
+            "FUELSYSTEM PUMP SWITCH:2" => aircraft("FUELSYSTEM PUMP SWITCH", "Bool", 2),
+            "FUELSYSTEM PUMP SWITCH:3" => aircraft("FUELSYSTEM PUMP SWITCH", "Bool", 3),
+            "FUELSYSTEM PUMP SWITCH:4" => aircraft("FUELSYSTEM PUMP SWITCH", "Bool", 4),
+            "FUELSYSTEM PUMP SWITCH:5" => aircraft("FUELSYSTEM PUMP SWITCH", "Bool", 5),
+            "FUELSYSTEM PUMP SWITCH:6" => aircraft("FUELSYSTEM PUMP SWITCH", "Bool", 6),
+            "FUELSYSTEM VALVE SWITCH:3" => aircraft("FUELSYSTEM VALVE SWITCH", "Bool", 3),
+            "UNLIMITED FUEL" => aircraft("UNLIMITED FUEL", "Bool", 0),
+            "AIRSPEED INDICATED" => aircraft("AIRSPEED INDICATED", "Knots", 0),
+            "INDICATED ALTITUDE" => aircraft("INDICATED ALTITUDE", "Feet", 0),
+            "SIM ON GROUND" => aircraft("SIM ON GROUND", "Bool", 0),
+            "GENERAL ENG STARTER ACTIVE:1" => aircraft("GENERAL ENG STARTER ACTIVE", "Bool", 1),
+            "GENERAL ENG STARTER ACTIVE:2" => aircraft("GENERAL ENG STARTER ACTIVE", "Bool", 2),
+            "BRAKE PARKING INDICATOR" => aircraft("BRAKE PARKING INDICATOR", "Bool", 0),
+            "EXIT OPEN:5" => aircraft("EXIT OPEN", "Percent", 5),
+            // TODO It is the catering door for now.
+            "EXIT OPEN:3" => aircraft("EXIT OPEN", "Percent", 3),
+            "PUSHBACK ANGLE" => aircraft("PUSHBACK ANGLE", "Radian", 0),
+            "PUSHBACK STATE" => aircraft("PUSHBACK STATE", "Enum", 0),
+            "ANTISKID BRAKES ACTIVE" => aircraft("ANTISKID BRAKES ACTIVE", "Bool", 0),
+            "BRAKE LEFT POSITION" => aircraft("BRAKE LEFT POSITION", "Percent", 0),
+            "BRAKE RIGHT POSITION" => aircraft("BRAKE RIGHT POSITION", "Percent", 0),
+            "ACCELERATION BODY Z" => aircraft("ACCELERATION BODY Z", "feet per second squared", 0),
+            "SPOILERS HANDLE POSITION" => aircraft("SPOILERS HANDLE POSITION", "Percent", 0),
+            "AUTOBRAKES SETTING" => Ok(Variable::Named(NamedVariable::from(
+                "XMLVAR_Autobrakes_Level",
+            ))),
+            "GROUND VELOCITY" => aircraft("GROUND VELOCITY", "Knots", 0),
+            "AMBIENT PRESSURE" => aircraft("AMBIENT PRESSURE", "inHg", 0),
+            "PRESSURE ALTITUDE" => aircraft("PRESSURE ALTITUDE", "Feet", 0),
+            "AIRSPEED TRUE" => aircraft("AIRSPEED TRUE", "Knots", 0),
+            "AIRSPEED MACH" => aircraft("AIRSPEED MACH", "Mach", 0),
+            "VERTICAL SPEED" => aircraft("VERTICAL SPEED", "Feet per minute", 0),
+            "PLANE PITCH DEGREES" => aircraft("PLANE PITCH DEGREES", "Degrees", 0),
+            "PLANE BANK DEGREES" => aircraft("PLANE BANK DEGREES", "Degrees", 0),
+            "CONTACT POINT COMPRESSION:0" => {
+                aircraft("CONTACT POINT COMPRESSION", "Percent Over 100", 0)
+            }
+            "CONTACT POINT COMPRESSION:1" => {
+                aircraft("CONTACT POINT COMPRESSION", "Percent Over 100", 1)
+            }
+            "CONTACT POINT COMPRESSION:2" => {
+                aircraft("CONTACT POINT COMPRESSION", "Percent Over 100", 2)
+            }
+            "LEFT WHEEL RPM" => aircraft("LEFT WHEEL RPM", "RPM", 0),
+            "RIGHT WHEEL RPM" => aircraft("RIGHT WHEEL RPM", "RPM", 0),
+            "RUDDER PEDAL POSITION" => aircraft("RUDDER PEDAL POSITION", "Position", 0),
+            "HYD_NOSE_WHEEL_STEERING_ANGLE" => Ok(Variable::SteeringAngle(
+                NamedVariable::from(&format!("A32NX_{}", name)),
+                FlightModelSteering::new(),
+            )),
+            _ => {
+                let variable = NamedVariable::from(&format!("A32NX_{}", name));
+                Ok(match ElectricalBusConnection::for_name(name) {
+                    Some(connection) => Variable::ElectricalBus(variable, connection),
+                    None => Variable::Named(variable),
+                })
+            }
+        }
     }
 }
 
-fn lookup_named_variable<'a>(
-    collection: &'a mut HashMap<String, NamedVariable>,
-    key_prefix: &str,
-    key: &str,
-) -> &'a mut NamedVariable {
-    let key = format!("{}{}", key_prefix, key);
+/// A gear of the simulator's flight model, which follows the hydraulically actuated gear.
+struct FlightModelGear {
+    variable: AircraftVariable,
+    // The calculator code which sets the gear's position, following the position itself.
+    set_position_code: String,
+}
+impl FlightModelGear {
+    fn new(name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            variable: AircraftVariable::from(name, "Percent", 0)?,
+            set_position_code: format!(" (>A:{}, Percent)", name),
+        })
+    }
+
+    fn position(&self) -> f64 {
+        self.variable.get()
+    }
 
-    collection
-        .entry(key.clone())
-        .or_insert_with(|| NamedVariable::from(&key))
+    fn update(&self, position: f64) {
+        // Executing calculator code is expensive, thus it is only done when
+        // the flight model's gear isn't in the given position already.
+        if (self.position() - position).abs() > f64::EPSILON {
+            execute_calculator_code::<()>(&format!("{}{}", position, self.set_position_code));
+        }
+    }
 }
 
-struct ElectricalBusConnections {
-    ac_bus_1: ElectricalBusConnection,
-    ac_bus_2: ElectricalBusConnection,
-    ac_ess_bus: ElectricalBusConnection,
-    ac_ess_shed_bus: ElectricalBusConnection,
-    ac_stat_inv_bus: ElectricalBusConnection,
-    ac_gnd_flt_svc_bus: ElectricalBusConnection,
-    dc_bus_1: ElectricalBusConnection,
-    dc_bus_2: ElectricalBusConnection,
-    dc_ess_bus: ElectricalBusConnection,
-    dc_ess_shed_bus: ElectricalBusConnection,
-    dc_bat_bus: ElectricalBusConnection,
-    dc_hot_bus_1: ElectricalBusConnection,
-    dc_hot_bus_2: ElectricalBusConnection,
-    dc_gnd_flt_svc_bus: ElectricalBusConnection,
+/// The nose wheel steering of the simulator's flight model, which follows the hydraulically
+/// actuated nose wheel instead of the rudder pedals.
+struct FlightModelSteering {
+    // The angle last set, such that the flight model is only set when the angle changes.
+    angle: Option<f64>,
 }
-impl ElectricalBusConnections {
+impl FlightModelSteering {
+    // The maximum steering angle of the nose wheel contact point,
+    // as defined in the flight_model.cfg [CONTACT_POINTS] section.
+    const MAX_ANGLE_DEGREES: f64 = 75.;
+    // The steering value which turns the wheel to its maximum angle.
+    const MAX_STEERING_VALUE: f64 = 16383.;
+
     fn new() -> Self {
-        Self {
-            // The numbers used here are those defined for buses in the systems.cfg [ELECTRICAL] section.
-            ac_bus_1: ElectricalBusConnection::new(1, 2),
-            ac_bus_2: ElectricalBusConnection::new(1, 3),
-            ac_ess_bus: ElectricalBusConnection::new(1, 4),
-            ac_ess_shed_bus: ElectricalBusConnection::new(1, 5),
-            ac_stat_inv_bus: ElectricalBusConnection::new(1, 6),
-            ac_gnd_flt_svc_bus: ElectricalBusConnection::new(1, 14),
-            dc_bus_1: ElectricalBusConnection::new(1, 7),
-            dc_bus_2: ElectricalBusConnection::new(1, 8),
-            dc_ess_bus: ElectricalBusConnection::new(1, 9),
-            dc_ess_shed_bus: ElectricalBusConnection::new(1, 10),
-            dc_bat_bus: ElectricalBusConnection::new(1, 11),
-            dc_hot_bus_1: ElectricalBusConnection::new(1, 12),
-            dc_hot_bus_2: ElectricalBusConnection::new(1, 13),
-            dc_gnd_flt_svc_bus: ElectricalBusConnection::new(1, 15),
-        }
+        Self { angle: None }
     }
 
-    fn update(&mut self, name: &str, value: f64) {
-        match name {
-            "ELEC_AC_1_BUS_IS_POWERED" => self.ac_bus_1.update(value),
-            "ELEC_AC_2_BUS_IS_POWERED" => self.ac_bus_2.update(value),
-            "ELEC_AC_ESS_BUS_IS_POWERED" => self.ac_ess_bus.update(value),
-            "ELEC_AC_ESS_SHED_BUS_IS_POWERED" => self.ac_ess_shed_bus.update(value),
-            "ELEC_AC_STAT_INV_BUS_IS_POWERED" => self.ac_stat_inv_bus.update(value),
-            "ELEC_AC_GND_FLT_SVC_BUS_IS_POWERED" => self.ac_gnd_flt_svc_bus.update(value),
-            "ELEC_DC_1_BUS_IS_POWERED" => self.dc_bus_1.update(value),
-            "ELEC_DC_2_BUS_IS_POWERED" => self.dc_bus_2.update(value),
-            "ELEC_DC_ESS_BUS_IS_POWERED" => self.dc_ess_bus.update(value),
-            "ELEC_DC_ESS_SHED_BUS_IS_POWERED" => self.dc_ess_shed_bus.update(value),
-            "ELEC_DC_BAT_BUS_IS_POWERED" => self.dc_bat_bus.update(value),
-            "ELEC_DC_HOT_1_BUS_IS_POWERED" => self.dc_hot_bus_1.update(value),
-            "ELEC_DC_HOT_2_BUS_IS_POWERED" => self.dc_hot_bus_2.update(value),
-            "ELEC_DC_GND_FLT_SVC_BUS_IS_POWERED" => self.dc_gnd_flt_svc_bus.update(value),
-            _ => panic!("No known connection for electrical bus '{}'.", name),
+    fn update(&mut self, angle: f64) {
+        if self.angle != Some(angle) {
+            let value = (angle / Self::MAX_ANGLE_DEGREES).clamp(-1., 1.) * Self::MAX_STEERING_VALUE;
+            execute_calculator_code::<()>(&format!("{} (>K:STEERING_SET)", value.round()));
+            self.angle = Some(angle);
         }
     }
 }
@@ -235,6 +311,31 @@ struct ElectricalBusConnection {
     to: usize,
 }
 impl ElectricalBusConnection {
+    /// Returns the connection of the simulator's bus whose powered state
+    /// is written to the given variable name.
+    fn for_name(name: &str) -> Option<Self> {
+        // The numbers used here are those defined for buses in the systems.cfg [ELECTRICAL] section.
+        let to = match name {
+            "ELEC_AC_1_BUS_IS_POWERED" => 2,
+            "ELEC_AC_2_BUS_IS_POWERED" => 3,
+            "ELEC_AC_ESS_BUS_IS_POWERED" => 4,
+            "ELEC_AC_ESS_SHED_BUS_IS_POWERED" => 5,
+            "ELEC_AC_STAT_INV_BUS_IS_POWERED" => 6,
+            "ELEC_DC_1_BUS_IS_POWERED" => 7,
+            "ELEC_DC_2_BUS_IS_POWERED" => 8,
+            "ELEC_DC_ESS_BUS_IS_POWERED" => 9,
+            "ELEC_DC_ESS_SHED_BUS_IS_POWERED" => 10,
+            "ELEC_DC_BAT_BUS_IS_POWERED" => 11,
+            "ELEC_DC_HOT_1_BUS_IS_POWERED" => 12,
+            "ELEC_DC_HOT_2_BUS_IS_POWERED" => 13,
+            "ELEC_AC_GND_FLT_SVC_BUS_IS_POWERED" => 14,
+            "ELEC_DC_GND_FLT_SVC_BUS_IS_POWERED" => 15,
+            _ => return None,
+        };
+
+        Some(Self::new(1, to))
+    }
+
     fn new(from: usize, to: usize) -> Self {
         Self {
             connected: true,
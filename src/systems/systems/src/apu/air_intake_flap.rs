@@ -1,50 +1,54 @@
 use super::AirIntakeFlapController;
-use crate::{shared::random_number, simulation::UpdateContext};
+use crate::simulation::UpdateContext;
 use std::time::Duration;
 use uom::si::{f64::*, ratio::percent};
 
 pub struct AirIntakeFlap {
     open_amount: Ratio,
-    delay: Duration,
+    delay: Option<Duration>,
 }
 impl AirIntakeFlap {
     const MINIMUM_TRAVEL_TIME_SECS: u8 = 6;
     const MAXIMUM_TRAVEL_TIME_SECS: u8 = 12;
 
     pub fn new() -> AirIntakeFlap {
-        let random_above_minimum_mod =
-            AirIntakeFlap::MAXIMUM_TRAVEL_TIME_SECS - AirIntakeFlap::MINIMUM_TRAVEL_TIME_SECS + 1;
-        let delay = Duration::from_secs(
-            (AirIntakeFlap::MINIMUM_TRAVEL_TIME_SECS + (random_number() % random_above_minimum_mod))
-                as u64,
-        );
-
         AirIntakeFlap {
             open_amount: Ratio::new::<percent>(0.),
-            delay,
+            delay: None,
         }
     }
 
     pub fn update(&mut self, context: &UpdateContext, controller: &impl AirIntakeFlapController) {
+        // The travel time is determined on the first update, as only then a random number is available.
+        let delay = *self.delay.get_or_insert_with(|| {
+            let random_above_minimum_mod = AirIntakeFlap::MAXIMUM_TRAVEL_TIME_SECS
+                - AirIntakeFlap::MINIMUM_TRAVEL_TIME_SECS
+                + 1;
+            Duration::from_secs(
+                (AirIntakeFlap::MINIMUM_TRAVEL_TIME_SECS
+                    + (context.random_number() % random_above_minimum_mod)) as u64,
+            )
+        });
+
         if controller.should_open_air_intake_flap()
             && self.open_amount < Ratio::new::<percent>(100.)
         {
             self.open_amount += Ratio::new::<percent>(
-                self.get_flap_change_for_delta(context)
+                Self::get_flap_change_for_delta(context, delay)
                     .min(100. - self.open_amount.get::<percent>()),
             );
         } else if !controller.should_open_air_intake_flap()
             && self.open_amount > Ratio::new::<percent>(0.)
         {
             self.open_amount -= Ratio::new::<percent>(
-                self.get_flap_change_for_delta(context)
+                Self::get_flap_change_for_delta(context, delay)
                     .min(self.open_amount.get::<percent>()),
             );
         }
     }
 
-    fn get_flap_change_for_delta(&self, context: &UpdateContext) -> f64 {
-        100. * (context.delta_as_secs_f64() / self.delay.as_secs_f64())
+    fn get_flap_change_for_delta(context: &UpdateContext, delay: Duration) -> f64 {
+        100. * (context.delta_as_secs_f64() / delay.as_secs_f64())
     }
 
     pub fn is_fully_open(&self) -> bool {
@@ -57,7 +61,7 @@ impl AirIntakeFlap {
 
     #[cfg(test)]
     pub fn set_delay(&mut self, delay: Duration) {
-        self.delay = delay;
+        self.delay = Some(delay);
     }
 }
 
@@ -2,11 +2,11 @@ use super::{ApuGenerator, ApuStartMotor, Turbine, TurbineController, TurbineStat
 use crate::{
     electrical::{
         consumption::{PowerConsumption, PowerConsumptionReport},
-        ElectricalStateWriter, Potential, PotentialOrigin, PotentialSource, PotentialTarget,
-        ProvideFrequency, ProvideLoad, ProvidePotential,
+        ControlledGenerator, ElectricalStateWriter, Potential, PotentialOrigin, PotentialSource,
+        PotentialTarget, ProvideFrequency, ProvideLoad, ProvidePotential, SourceResistance,
     },
-    shared::{calculate_towards_target_temperature, random_number},
-    simulation::{SimulationElement, SimulatorWriter, UpdateContext},
+    shared::calculate_towards_target_temperature,
+    simulation::{SimulationElement, SimulatorWriter, UpdateContext, VariableRegistry},
 };
 use std::time::Duration;
 use uom::si::{
@@ -56,6 +56,10 @@ impl Turbine for ShutdownAps3200Turbine {
     fn state(&self) -> TurbineState {
         TurbineState::Shutdown
     }
+
+    fn shut_down_with_egt(self: Box<Self>, egt: ThermodynamicTemperature) -> Box<dyn Turbine> {
+        Box::new(ShutdownAps3200Turbine::new_with_egt(egt))
+    }
 }
 
 struct Starting {
@@ -187,7 +191,7 @@ impl Turbine for Starting {
         if controller.should_stop() {
             Box::new(Stopping::new(self.egt, self.n))
         } else if (self.n.get::<percent>() - 100.).abs() < f64::EPSILON {
-            Box::new(Running::new(self.egt))
+            Box::new(Running::new(context, self.egt))
         } else {
             self
         }
@@ -204,6 +208,10 @@ impl Turbine for Starting {
     fn state(&self) -> TurbineState {
         TurbineState::Starting
     }
+
+    fn shut_down_with_egt(self: Box<Self>, egt: ThermodynamicTemperature) -> Box<dyn Turbine> {
+        Box::new(ShutdownAps3200Turbine::new_with_egt(egt))
+    }
 }
 
 struct BleedAirUsageEgtDelta {
@@ -213,8 +221,8 @@ struct BleedAirUsageEgtDelta {
     min: f64,
 }
 impl BleedAirUsageEgtDelta {
-    fn new() -> Self {
-        let randomisation = 0.95 + ((random_number() % 101) as f64 / 1000.);
+    fn new(context: &UpdateContext) -> Self {
+        let randomisation = 0.95 + ((context.random_number() % 101) as f64 / 1000.);
 
         Self {
             current: 0.,
@@ -285,10 +293,10 @@ struct ApuGenUsageEgtDelta {
 impl ApuGenUsageEgtDelta {
     // We just assume it takes 10 seconds to get to our target.
     const SECONDS_TO_REACH_TARGET: u64 = 10;
-    fn new() -> Self {
+    fn new(context: &UpdateContext) -> Self {
         Self {
             time: Duration::from_secs(0),
-            base_egt_delta_per_second: (10. + ((random_number() % 6) as f64))
+            base_egt_delta_per_second: (10. + ((context.random_number() % 6) as f64))
                 / ApuGenUsageEgtDelta::SECONDS_TO_REACH_TARGET as f64,
         }
     }
@@ -318,8 +326,8 @@ struct Running {
     apu_gen_usage: ApuGenUsageEgtDelta,
 }
 impl Running {
-    fn new(egt: ThermodynamicTemperature) -> Running {
-        let base_egt = 340. + ((random_number() % 11) as f64);
+    fn new(context: &UpdateContext, egt: ThermodynamicTemperature) -> Running {
+        let base_egt = 340. + ((context.random_number() % 11) as f64);
         Running {
             egt,
             base_egt: ThermodynamicTemperature::new::<degree_celsius>(base_egt),
@@ -329,8 +337,8 @@ impl Running {
             base_egt_deviation: TemperatureInterval::new::<temperature_interval::degree_celsius>(
                 egt.get::<degree_celsius>() - base_egt,
             ),
-            bleed_air_usage: BleedAirUsageEgtDelta::new(),
-            apu_gen_usage: ApuGenUsageEgtDelta::new(),
+            bleed_air_usage: BleedAirUsageEgtDelta::new(context),
+            apu_gen_usage: ApuGenUsageEgtDelta::new(context),
         }
     }
 
@@ -386,6 +394,10 @@ impl Turbine for Running {
     fn state(&self) -> TurbineState {
         TurbineState::Running
     }
+
+    fn shut_down_with_egt(self: Box<Self>, egt: ThermodynamicTemperature) -> Box<dyn Turbine> {
+        Box::new(ShutdownAps3200Turbine::new_with_egt(egt))
+    }
 }
 
 struct Stopping {
@@ -518,6 +530,10 @@ impl Turbine for Stopping {
     fn state(&self) -> TurbineState {
         TurbineState::Stopping
     }
+
+    fn shut_down_with_egt(self: Box<Self>, egt: ThermodynamicTemperature) -> Box<dyn Turbine> {
+        Box::new(ShutdownAps3200Turbine::new_with_egt(egt))
+    }
 }
 
 fn calculate_towards_ambient_egt(
@@ -542,22 +558,31 @@ pub struct Aps3200ApuGenerator {
     output_potential: ElectricPotential,
     load: Ratio,
     is_emergency_shutdown: bool,
+    resistance: Option<SourceResistance>,
 }
 impl Aps3200ApuGenerator {
     const APU_GEN_POWERED_N: f64 = 84.;
+    const APU_GEN_OPERATING_SPEED_N: f64 = 95.;
 
-    pub fn new(number: usize) -> Aps3200ApuGenerator {
+    pub fn new(registry: &mut VariableRegistry, number: usize) -> Aps3200ApuGenerator {
         Aps3200ApuGenerator {
             number,
             n: Ratio::new::<percent>(0.),
-            writer: ElectricalStateWriter::new(&format!("APU_GEN_{}", number)),
+            writer: ElectricalStateWriter::new(registry, &format!("APU_GEN_{}", number)),
             output_potential: ElectricPotential::new::<volt>(0.),
             output_frequency: Frequency::new::<hertz>(0.),
             load: Ratio::new::<percent>(0.),
             is_emergency_shutdown: false,
+            resistance: None,
         }
     }
 
+    /// Makes the output potential sag under load, in addition to its dependency on N.
+    pub fn with_resistance(mut self, resistance: SourceResistance) -> Self {
+        self.resistance = Some(resistance);
+        self
+    }
+
     fn calculate_potential(&self, n: Ratio) -> ElectricPotential {
         let n = n.get::<percent>();
 
@@ -637,6 +662,12 @@ impl ApuGenerator for Aps3200ApuGenerator {
 provide_potential!(Aps3200ApuGenerator, (110.0..=120.0));
 provide_frequency!(Aps3200ApuGenerator, (390.0..=410.0));
 provide_load!(Aps3200ApuGenerator);
+impl ControlledGenerator for Aps3200ApuGenerator {
+    fn is_driven_at_operating_speed(&self) -> bool {
+        self.should_provide_output()
+            && self.n.get::<percent>() >= Aps3200ApuGenerator::APU_GEN_OPERATING_SPEED_N
+    }
+}
 impl PotentialSource for Aps3200ApuGenerator {
     fn output(&self) -> Potential {
         if self.should_provide_output() {
@@ -655,8 +686,14 @@ impl SimulationElement for Aps3200ApuGenerator {
     }
 
     fn process_power_consumption_report<T: PowerConsumptionReport>(&mut self, report: &T) {
+        let consumption = report.total_consumption_of(PotentialOrigin::ApuGenerator(self.number));
+
         self.output_potential = if self.should_provide_output() {
-            self.calculate_potential(self.n)
+            let potential = self.calculate_potential(self.n);
+            match self.resistance {
+                Some(resistance) => resistance.solve(potential, consumption).potential(),
+                None => potential,
+            }
         } else {
             ElectricPotential::new::<volt>(0.)
         };
@@ -667,9 +704,7 @@ impl SimulationElement for Aps3200ApuGenerator {
             Frequency::new::<hertz>(0.)
         };
 
-        let power_consumption = report
-            .total_consumption_of(PotentialOrigin::ApuGenerator(self.number))
-            .get::<watt>();
+        let power_consumption = consumption.get::<watt>();
         let power_factor_correction = 0.8;
         let maximum_load = 90000.;
         self.load = Ratio::new::<percent>(
@@ -749,13 +784,13 @@ mod apu_generator_tests {
 
     #[test]
     fn starts_without_output() {
-        assert!(apu_generator().is_unpowered());
+        assert!(apu_generator(&mut VariableRegistry::new()).is_unpowered());
     }
 
     #[test]
     fn when_apu_running_provides_output() {
-        let mut generator = apu_generator();
         let mut test_bed = SimulationTestBed::new();
+        let mut generator = apu_generator(test_bed.registry());
         update_below_threshold(&mut test_bed, &mut generator);
         update_above_threshold(&mut test_bed, &mut generator);
 
@@ -764,8 +799,8 @@ mod apu_generator_tests {
 
     #[test]
     fn when_apu_shutdown_provides_no_output() {
-        let mut generator = apu_generator();
         let mut test_bed = SimulationTestBed::new();
+        let mut generator = apu_generator(test_bed.registry());
         update_above_threshold(&mut test_bed, &mut generator);
         update_below_threshold(&mut test_bed, &mut generator);
 
@@ -891,6 +926,18 @@ mod apu_generator_tests {
         assert!(test_bed.load() > Ratio::new::<percent>(0.));
     }
 
+    #[test]
+    fn when_running_with_resistance_and_potential_used_potential_sags() {
+        let mut test_bed = test_bed_with()
+            .generator_resistance()
+            .running_apu()
+            .power_demand(Power::new::<watt>(50000.))
+            .run(Duration::from_secs(1_000));
+
+        assert!(test_bed.potential() < ElectricPotential::new::<volt>(114.));
+        assert!(test_bed.potential_within_normal_range());
+    }
+
     #[test]
     fn when_load_below_maximum_it_is_normal() {
         let mut test_bed = test_bed_with()
@@ -924,8 +971,8 @@ mod apu_generator_tests {
 
     #[test]
     fn writes_its_state() {
-        let mut apu_gen = apu_generator();
         let mut test_bed = SimulationTestBed::new();
+        let mut apu_gen = apu_generator(test_bed.registry());
         test_bed.run_without_update(&mut apu_gen);
 
         assert!(test_bed.contains_key("ELEC_APU_GEN_1_POTENTIAL"));
@@ -936,8 +983,8 @@ mod apu_generator_tests {
         assert!(test_bed.contains_key("ELEC_APU_GEN_1_LOAD_NORMAL"));
     }
 
-    fn apu_generator() -> Aps3200ApuGenerator {
-        Aps3200ApuGenerator::new(1)
+    fn apu_generator(registry: &mut VariableRegistry) -> Aps3200ApuGenerator {
+        Aps3200ApuGenerator::new(registry, 1)
     }
 
     fn update_above_threshold(
@@ -1,9 +1,10 @@
 use super::{
     AirIntakeFlap, AirIntakeFlapController, AuxiliaryPowerUnitFireOverheadPanel,
-    AuxiliaryPowerUnitOverheadPanel, FuelPressureSwitch, Turbine, TurbineController, TurbineState,
+    AuxiliaryPowerUnitOverheadPanel, Turbine, TurbineController, TurbineState,
 };
 use crate::{
     electrical::PotentialSource,
+    fuel::FuelPressureSwitch,
     pneumatic::{BleedAirValveController, Valve},
     shared::{ApuMaster, ApuStart, ApuStartContactorsController},
     simulation::UpdateContext,
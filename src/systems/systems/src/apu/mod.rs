@@ -3,18 +3,25 @@ use self::{
     electronic_control_box::ElectronicControlBox,
 };
 use crate::{
-    electrical::{Potential, PotentialSource, PotentialTarget, ProvideFrequency, ProvidePotential},
+    electrical::{
+        ControlledGenerator, Potential, PotentialSource, PotentialTarget, ProvideFrequency,
+        ProvideLoad, ProvidePotential, SourceResistance,
+    },
+    fuel::FuelPressureSwitch,
     overhead::{FirePushButton, OnOffAvailablePushButton, OnOffFaultPushButton},
-    pneumatic::{BleedAirValve, BleedAirValveState, Valve},
+    pneumatic::{BleedAirSource, BleedAirValve, BleedAirValveState, Valve},
     shared::{
         ApuAvailable, ApuMaster, ApuStart, ApuStartContactorsController,
         AuxiliaryPowerUnitElectrical,
     },
-    simulation::{SimulationElement, SimulationElementVisitor, SimulatorWriter, UpdateContext},
+    simulation::{
+        ChangeDetector, EventEmitter, EventKind, SimulationElement, SimulationElementVisitor,
+        SimulatorWriter, Snapshot, StateDump, UpdateContext, VariableIdentifier, VariableRegistry,
+    },
 };
 #[cfg(test)]
 use std::time::Duration;
-use uom::si::{f64::*, ratio::percent, thermodynamic_temperature::degree_celsius};
+use uom::si::{f64::*, pressure::psi, ratio::percent, thermodynamic_temperature::degree_celsius};
 
 mod air_intake_flap;
 mod aps3200;
@@ -24,11 +31,14 @@ mod electronic_control_box;
 pub struct AuxiliaryPowerUnitFactory {}
 impl AuxiliaryPowerUnitFactory {
     pub fn new_aps3200(
+        registry: &mut VariableRegistry,
         number: usize,
     ) -> AuxiliaryPowerUnit<Aps3200ApuGenerator, Aps3200StartMotor> {
+        let generator = Aps3200ApuGenerator::new(registry, number);
         AuxiliaryPowerUnit::new(
+            registry,
             Box::new(ShutdownAps3200Turbine::new()),
-            Aps3200ApuGenerator::new(number),
+            generator,
             Aps3200StartMotor::new(),
         )
     }
@@ -36,30 +46,6 @@ impl AuxiliaryPowerUnitFactory {
 
 pub trait ApuStartMotor: PotentialTarget + PotentialSource + SimulationElement {}
 
-/// Komp: There is a pressure switch between the fuel valve and the APU.
-/// It switches from 0 to 1 when the pressure is >=17 PSI and the signal is received by the ECB
-/// And there is a small hysteresis, means it switches back to 0 when <=16 PSI
-/// This type exists because we don't have a full fuel implementation yet. Once we do, we will
-/// probably move this type and the logic to there.
-pub struct FuelPressureSwitch {
-    has_fuel_remaining: bool,
-}
-impl FuelPressureSwitch {
-    fn new() -> Self {
-        FuelPressureSwitch {
-            has_fuel_remaining: false,
-        }
-    }
-
-    fn update(&mut self, has_fuel_remaining: bool) {
-        self.has_fuel_remaining = has_fuel_remaining;
-    }
-
-    fn has_pressure(&self) -> bool {
-        self.has_fuel_remaining
-    }
-}
-
 /// Signals to the APU air intake flap what position it should move towards.
 pub trait AirIntakeFlapController {
     fn should_open_air_intake_flap(&self) -> bool;
@@ -72,24 +58,54 @@ pub trait TurbineController {
 }
 
 pub struct AuxiliaryPowerUnit<T: ApuGenerator, U: ApuStartMotor> {
+    flap_open_percentage_id: VariableIdentifier,
+    bleed_air_valve_open_id: VariableIdentifier,
+    egt_caution_id: VariableIdentifier,
+    egt_id: VariableIdentifier,
+    inoperable_id: VariableIdentifier,
+    is_auto_shutdown_id: VariableIdentifier,
+    is_emergency_shutdown_id: VariableIdentifier,
+    low_fuel_pressure_fault_id: VariableIdentifier,
+    n_id: VariableIdentifier,
+    egt_warning_id: VariableIdentifier,
+
     turbine: Option<Box<dyn Turbine>>,
+    turbine_state_change: ChangeDetector<TurbineState>,
     generator: T,
     ecb: ElectronicControlBox,
     start_motor: U,
     air_intake_flap: AirIntakeFlap,
     bleed_air_valve: BleedAirValve,
-    fuel_pressure_switch: FuelPressureSwitch,
 }
 impl<T: ApuGenerator, U: ApuStartMotor> AuxiliaryPowerUnit<T, U> {
-    pub fn new(turbine: Box<dyn Turbine>, generator: T, start_motor: U) -> Self {
+    // The load compressor delivers air at this pressure and temperature when running at 100% N.
+    const BLEED_AIR_PRESSURE_PSI: f64 = 38.;
+    const BLEED_AIR_TEMPERATURE_DEGREE_CELSIUS: f64 = 210.;
+
+    pub fn new(
+        registry: &mut VariableRegistry,
+        turbine: Box<dyn Turbine>,
+        generator: T,
+        start_motor: U,
+    ) -> Self {
         AuxiliaryPowerUnit {
+            flap_open_percentage_id: registry.get("APU_FLAP_OPEN_PERCENTAGE"),
+            bleed_air_valve_open_id: registry.get("APU_BLEED_AIR_VALVE_OPEN"),
+            egt_caution_id: registry.get("APU_EGT_CAUTION"),
+            egt_id: registry.get("APU_EGT"),
+            inoperable_id: registry.get("ECAM_INOP_SYS_APU"),
+            is_auto_shutdown_id: registry.get("APU_IS_AUTO_SHUTDOWN"),
+            is_emergency_shutdown_id: registry.get("APU_IS_EMERGENCY_SHUTDOWN"),
+            low_fuel_pressure_fault_id: registry.get("APU_LOW_FUEL_PRESSURE_FAULT"),
+            n_id: registry.get("APU_N"),
+            egt_warning_id: registry.get("APU_EGT_WARNING"),
             turbine: Some(turbine),
+            turbine_state_change: ChangeDetector::new(TurbineState::Shutdown),
             generator,
             ecb: ElectronicControlBox::new(),
             start_motor,
             air_intake_flap: AirIntakeFlap::new(),
             bleed_air_valve: BleedAirValve::new(),
-            fuel_pressure_switch: FuelPressureSwitch::new(),
         }
     }
 
@@ -100,13 +116,12 @@ impl<T: ApuGenerator, U: ApuStartMotor> AuxiliaryPowerUnit<T, U> {
         fire_overhead: &AuxiliaryPowerUnitFireOverheadPanel,
         apu_bleed_is_on: bool,
         apu_gen_is_used: bool,
-        has_fuel_remaining: bool,
+        fuel_pressure_switch: &FuelPressureSwitch,
     ) {
         self.ecb
             .update_overhead_panel_state(overhead, fire_overhead, apu_bleed_is_on);
-        self.fuel_pressure_switch.update(has_fuel_remaining);
         self.ecb
-            .update_fuel_pressure_switch_state(&self.fuel_pressure_switch);
+            .update_fuel_pressure_switch_state(fuel_pressure_switch);
         self.bleed_air_valve.update(&self.ecb);
         self.ecb
             .update_bleed_air_valve_state(context, &self.bleed_air_valve);
@@ -164,6 +179,13 @@ impl<T: ApuGenerator, U: ApuStartMotor> AuxiliaryPowerUnit<T, U> {
         self.air_intake_flap.set_delay(duration);
     }
 }
+impl<U: ApuStartMotor> AuxiliaryPowerUnit<Aps3200ApuGenerator, U> {
+    /// Makes the generator's output potential sag under load.
+    pub fn with_generator_resistance(mut self, resistance: SourceResistance) -> Self {
+        self.generator = self.generator.with_resistance(resistance);
+        self
+    }
+}
 impl<T: ApuGenerator, U: ApuStartMotor> AuxiliaryPowerUnitElectrical for AuxiliaryPowerUnit<T, U> {
     fn start_motor_powered_by(&mut self, source: Potential) {
         self.start_motor.powered_by(&source);
@@ -173,6 +195,38 @@ impl<T: ApuGenerator, U: ApuStartMotor> AuxiliaryPowerUnitElectrical for Auxilia
         self.generator.output_within_normal_parameters()
     }
 }
+impl<T: ApuGenerator, U: ApuStartMotor> ProvidePotential for AuxiliaryPowerUnit<T, U> {
+    fn potential(&self) -> ElectricPotential {
+        ProvidePotential::potential(&self.generator)
+    }
+
+    fn potential_normal(&self) -> bool {
+        self.generator.potential_normal()
+    }
+}
+impl<T: ApuGenerator, U: ApuStartMotor> ProvideFrequency for AuxiliaryPowerUnit<T, U> {
+    fn frequency(&self) -> Frequency {
+        self.generator.frequency()
+    }
+
+    fn frequency_normal(&self) -> bool {
+        self.generator.frequency_normal()
+    }
+}
+impl<T: ApuGenerator, U: ApuStartMotor> ProvideLoad for AuxiliaryPowerUnit<T, U> {
+    fn load(&self) -> Ratio {
+        self.generator.load()
+    }
+
+    fn load_normal(&self) -> bool {
+        self.generator.load_normal()
+    }
+}
+impl<T: ApuGenerator, U: ApuStartMotor> ControlledGenerator for AuxiliaryPowerUnit<T, U> {
+    fn is_driven_at_operating_speed(&self) -> bool {
+        self.generator.is_driven_at_operating_speed()
+    }
+}
 impl<T: ApuGenerator, U: ApuStartMotor> ApuAvailable for AuxiliaryPowerUnit<T, U> {
     fn is_available(&self) -> bool {
         self.ecb.is_available()
@@ -196,35 +250,107 @@ impl<T: ApuGenerator, U: ApuStartMotor> SimulationElement for AuxiliaryPowerUnit
     }
 
     fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_f64(
-            "APU_FLAP_OPEN_PERCENTAGE",
-            self.air_intake_flap.open_amount().get::<percent>(),
+        writer.write_quantity(
+            &self.flap_open_percentage_id,
+            self.air_intake_flap.open_amount(),
+            percent,
+        );
+        writer.write_bool(
+            &self.bleed_air_valve_open_id,
+            self.bleed_air_valve_is_open(),
         );
-        writer.write_bool("APU_BLEED_AIR_VALVE_OPEN", self.bleed_air_valve_is_open());
-        writer.write_f64(
-            "APU_EGT_CAUTION",
-            self.ecb.egt_caution_temperature().get::<degree_celsius>(),
+        writer.write_quantity(
+            &self.egt_caution_id,
+            self.ecb.egt_caution_temperature(),
+            degree_celsius,
         );
-        writer.write_f64("APU_EGT", self.ecb.egt().get::<degree_celsius>());
-        writer.write_bool("ECAM_INOP_SYS_APU", self.ecb.is_inoperable());
-        writer.write_bool("APU_IS_AUTO_SHUTDOWN", self.ecb.is_auto_shutdown());
-        writer.write_bool("APU_IS_EMERGENCY_SHUTDOWN", self.is_emergency_shutdown());
+        writer.write_quantity(&self.egt_id, self.ecb.egt(), degree_celsius);
+        writer.write_bool(&self.inoperable_id, self.ecb.is_inoperable());
+        writer.write_bool(&self.is_auto_shutdown_id, self.ecb.is_auto_shutdown());
+        writer.write_bool(&self.is_emergency_shutdown_id, self.is_emergency_shutdown());
         writer.write_bool(
-            "APU_LOW_FUEL_PRESSURE_FAULT",
+            &self.low_fuel_pressure_fault_id,
             self.ecb.has_fuel_low_pressure_fault(),
         );
-        writer.write_f64("APU_N", self.n().get::<percent>());
-        writer.write_f64(
-            "APU_EGT_WARNING",
-            self.ecb.egt_warning_temperature().get::<degree_celsius>(),
+        writer.write_quantity(&self.n_id, self.n(), percent);
+        writer.write_quantity(
+            &self.egt_warning_id,
+            self.ecb.egt_warning_temperature(),
+            degree_celsius,
         );
     }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        if let Some(turbine) = &self.turbine {
+            snapshot.write_f64("APU_EGT", turbine.egt().get::<degree_celsius>());
+        }
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(egt) = snapshot.read_f64("APU_EGT") {
+            if let Some(turbine) = self.turbine.take() {
+                self.turbine = Some(
+                    turbine
+                        .shut_down_with_egt(ThermodynamicTemperature::new::<degree_celsius>(egt)),
+                );
+            }
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state.node("APU");
+        if let Some(turbine) = &self.turbine {
+            let turbine_node = node.node("TURBINE");
+            turbine_node.write_text("STATE", &format!("{:?}", turbine.state()));
+            turbine_node.write_f64("N", turbine.n().get::<percent>());
+            turbine_node.write_f64("EGT", turbine.egt().get::<degree_celsius>());
+        }
+        node.write_bool("IS_AVAILABLE", self.is_available());
+        node.write_bool("IS_EMERGENCY_SHUTDOWN", self.is_emergency_shutdown());
+        node.write_bool("BLEED_AIR_VALVE_OPEN", self.bleed_air_valve_is_open());
+    }
+
+    fn emit_events(&mut self, events: &mut EventEmitter) {
+        let state = match &self.turbine {
+            Some(turbine) => turbine.state(),
+            None => return,
+        };
+
+        if let Some(previous_state) = self.turbine_state_change.detect(state) {
+            let kind = EventKind::StateChanged {
+                from: format!("{:?}", previous_state),
+                to: format!("{:?}", state),
+            };
+            if state == TurbineState::Stopping && self.is_emergency_shutdown() {
+                events.emit_with_cause("APU/TURBINE", kind, "emergency shutdown");
+            } else if state == TurbineState::Stopping && self.ecb.is_auto_shutdown() {
+                events.emit_with_cause("APU/TURBINE", kind, "auto shutdown");
+            } else {
+                events.emit("APU/TURBINE", kind);
+            }
+        }
+    }
 }
 impl<T: ApuGenerator, U: ApuStartMotor> BleedAirValveState for AuxiliaryPowerUnit<T, U> {
     fn bleed_air_valve_is_open(&self) -> bool {
         self.bleed_air_valve.is_open()
     }
 }
+impl<T: ApuGenerator, U: ApuStartMotor> BleedAirSource for AuxiliaryPowerUnit<T, U> {
+    fn bleed_air_pressure(&self) -> Pressure {
+        if self.bleed_air_valve_is_open() {
+            Pressure::new::<psi>(
+                Self::BLEED_AIR_PRESSURE_PSI * (self.n().get::<percent>() / 100.).min(1.),
+            )
+        } else {
+            Pressure::new::<psi>(0.)
+        }
+    }
+
+    fn bleed_air_temperature(&self) -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<degree_celsius>(Self::BLEED_AIR_TEMPERATURE_DEGREE_CELSIUS)
+    }
+}
 
 pub trait Turbine {
     fn update(
@@ -237,9 +363,12 @@ pub trait Turbine {
     fn n(&self) -> Ratio;
     fn egt(&self) -> ThermodynamicTemperature;
     fn state(&self) -> TurbineState;
+    /// Returns the turbine in its shut down state with the given EGT. This is used when
+    /// restoring a snapshot, as the other states of the turbine aren't persisted.
+    fn shut_down_with_egt(self: Box<Self>, egt: ThermodynamicTemperature) -> Box<dyn Turbine>;
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TurbineState {
     Shutdown,
     Starting,
@@ -247,9 +376,7 @@ pub enum TurbineState {
     Stopping,
 }
 
-pub trait ApuGenerator:
-    PotentialSource + SimulationElement + ProvidePotential + ProvideFrequency
-{
+pub trait ApuGenerator: PotentialSource + SimulationElement + ControlledGenerator {
     fn update(&mut self, n: Ratio, is_emergency_shutdown: bool);
     fn output_within_normal_parameters(&self) -> bool;
 }
@@ -258,13 +385,13 @@ pub struct AuxiliaryPowerUnitFireOverheadPanel {
     apu_fire_button: FirePushButton,
 }
 impl AuxiliaryPowerUnitFireOverheadPanel {
-    pub fn new() -> Self {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
         AuxiliaryPowerUnitFireOverheadPanel {
-            apu_fire_button: FirePushButton::new("APU"),
+            apu_fire_button: FirePushButton::new(registry, "APU"),
         }
     }
 
-    fn fire_button_is_released(&self) -> bool {
+    pub fn fire_button_is_released(&self) -> bool {
         self.apu_fire_button.is_released()
     }
 }
@@ -275,21 +402,16 @@ impl SimulationElement for AuxiliaryPowerUnitFireOverheadPanel {
         visitor.visit(self);
     }
 }
-impl Default for AuxiliaryPowerUnitFireOverheadPanel {
-    fn default() -> Self {
-        Self::new()
-    }
-}
 
 pub struct AuxiliaryPowerUnitOverheadPanel {
     pub master: OnOffFaultPushButton,
     pub start: OnOffAvailablePushButton,
 }
 impl AuxiliaryPowerUnitOverheadPanel {
-    pub fn new() -> AuxiliaryPowerUnitOverheadPanel {
+    pub fn new(registry: &mut VariableRegistry) -> AuxiliaryPowerUnitOverheadPanel {
         AuxiliaryPowerUnitOverheadPanel {
-            master: OnOffFaultPushButton::new_off("APU_MASTER_SW"),
-            start: OnOffAvailablePushButton::new_off("APU_START"),
+            master: OnOffFaultPushButton::new_off(registry, "APU_MASTER_SW"),
+            start: OnOffAvailablePushButton::new_off(registry, "APU_START"),
         }
     }
 
@@ -327,11 +449,6 @@ impl SimulationElement for AuxiliaryPowerUnitOverheadPanel {
         visitor.visit(self);
     }
 }
-impl Default for AuxiliaryPowerUnitOverheadPanel {
-    fn default() -> Self {
-        Self::new()
-    }
-}
 
 #[cfg(test)]
 pub mod tests {
@@ -340,14 +457,14 @@ pub mod tests {
             consumption::{PowerConsumer, SuppliedPower},
             ElectricalBusType, PotentialOrigin,
         },
-        simulation::{test::SimulationTestBed, Aircraft},
+        simulation::{test::SimulationTestBed, Aircraft, EventBus},
     };
 
     use super::*;
     use std::time::Duration;
     use uom::si::{
-        electric_potential::volt, frequency::hertz, length::foot, power::watt, ratio::percent,
-        thermodynamic_temperature::degree_celsius,
+        electric_potential::volt, electrical_resistance::ohm, frequency::hertz, length::foot,
+        power::watt, ratio::percent, thermodynamic_temperature::degree_celsius,
     };
 
     pub fn test_bed_with() -> AuxiliaryPowerUnitTestBed {
@@ -388,6 +505,10 @@ pub mod tests {
         fn state(&self) -> TurbineState {
             TurbineState::Starting
         }
+
+        fn shut_down_with_egt(self: Box<Self>, _: ThermodynamicTemperature) -> Box<dyn Turbine> {
+            self
+        }
     }
 
     struct AuxiliaryPowerUnitTestAircraft {
@@ -397,6 +518,7 @@ pub mod tests {
         apu_bleed: OnOffFaultPushButton,
         apu_gen_is_used: bool,
         has_fuel_remaining: bool,
+        fuel_pressure_switch: FuelPressureSwitch,
         power_consumer: PowerConsumer,
         cut_start_motor_power: bool,
         power_consumption: Power,
@@ -404,14 +526,18 @@ pub mod tests {
             bool,
     }
     impl AuxiliaryPowerUnitTestAircraft {
-        fn new() -> Self {
+        fn new(registry: &mut VariableRegistry) -> Self {
             Self {
-                apu: AuxiliaryPowerUnitFactory::new_aps3200(1),
-                apu_fire_overhead: AuxiliaryPowerUnitFireOverheadPanel::new(),
-                apu_overhead: AuxiliaryPowerUnitOverheadPanel::new(),
-                apu_bleed: OnOffFaultPushButton::new_on("APU_BLEED"),
+                apu: AuxiliaryPowerUnitFactory::new_aps3200(registry, 1),
+                apu_fire_overhead: AuxiliaryPowerUnitFireOverheadPanel::new(registry),
+                apu_overhead: AuxiliaryPowerUnitOverheadPanel::new(registry),
+                apu_bleed: OnOffFaultPushButton::new_on(registry, "APU_BLEED"),
                 apu_gen_is_used: true,
                 has_fuel_remaining: true,
+                fuel_pressure_switch: FuelPressureSwitch::new(
+                    Pressure::new::<psi>(17.),
+                    Pressure::new::<psi>(16.),
+                ),
                 power_consumer: PowerConsumer::from(ElectricalBusType::AlternatingCurrent(1)),
                 cut_start_motor_power: false,
                 power_consumption: Power::new::<watt>(0.),
@@ -440,6 +566,14 @@ pub mod tests {
             self.apu.output()
         }
 
+        fn with_generator_resistance(mut self) -> Self {
+            self.apu = self.apu.with_generator_resistance(SourceResistance::new(
+                ElectricalResistance::new::<ohm>(0.004),
+                ElectricalResistance::new::<ohm>(0.004),
+            ));
+            self
+        }
+
         fn set_power_demand(&mut self, power: Power) {
             self.power_consumer.demand(power);
         }
@@ -470,13 +604,20 @@ pub mod tests {
     }
     impl Aircraft for AuxiliaryPowerUnitTestAircraft {
         fn update_before_power_distribution(&mut self, context: &UpdateContext) {
+            self.fuel_pressure_switch
+                .update(Pressure::new::<psi>(if self.has_fuel_remaining {
+                    25.
+                } else {
+                    0.
+                }));
+
             self.apu.update_before_electrical(
                 context,
                 &self.apu_overhead,
                 &self.apu_fire_overhead,
                 self.apu_bleed.is_on(),
                 self.apu_gen_is_used,
-                self.has_fuel_remaining,
+                &self.fuel_pressure_switch,
             );
 
             self.apu.start_motor_powered_by(
@@ -542,11 +683,12 @@ pub mod tests {
     }
     impl AuxiliaryPowerUnitTestBed {
         fn new() -> Self {
+            let mut simulation_test_bed = SimulationTestBed::new();
             let mut apu_test_bed = Self {
-                aircraft: AuxiliaryPowerUnitTestAircraft::new(),
+                aircraft: AuxiliaryPowerUnitTestAircraft::new(simulation_test_bed.registry()),
                 ambient_temperature: ThermodynamicTemperature::new::<degree_celsius>(0.),
                 indicated_altitude: Length::new::<foot>(5000.),
-                simulation_test_bed: SimulationTestBed::new(),
+                simulation_test_bed,
             };
 
             apu_test_bed
@@ -556,11 +698,21 @@ pub mod tests {
             apu_test_bed
         }
 
+        fn random_seed(mut self, seed: u64) -> Self {
+            self.simulation_test_bed.set_random_seed(seed);
+            self
+        }
+
         fn air_intake_flap_that_opens_in(mut self, duration: Duration) -> Self {
             self.aircraft.set_air_intake_flap_opening_delay(duration);
             self
         }
 
+        pub fn generator_resistance(mut self) -> Self {
+            self.aircraft = self.aircraft.with_generator_resistance();
+            self
+        }
+
         pub fn power_demand(mut self, power: Power) -> Self {
             self.aircraft.set_power_demand(power);
             self
@@ -701,6 +853,23 @@ pub mod tests {
             self
         }
 
+        fn snapshot(&mut self) -> Snapshot {
+            Snapshot::take(&mut self.aircraft)
+        }
+
+        fn state_dump(&mut self) -> StateDump {
+            StateDump::take(&mut self.aircraft)
+        }
+
+        fn events(&self) -> &EventBus {
+            self.simulation_test_bed.events()
+        }
+
+        fn restored_from(mut self, snapshot: &Snapshot) -> Self {
+            snapshot.restore_into(&mut self.aircraft);
+            self
+        }
+
         pub fn and(self) -> Self {
             self
         }
@@ -871,6 +1040,10 @@ pub mod tests {
                 .read_bool("APU_BLEED_AIR_VALVE_OPEN")
         }
 
+        fn bleed_air_pressure(&self) -> Pressure {
+            self.aircraft.apu.bleed_air_pressure()
+        }
+
         fn apu_generator_output_within_normal_parameters(&self) -> bool {
             self.aircraft.apu_generator_output_within_normal_parameters_after_processing_power_consumption_report()
         }
@@ -1263,6 +1436,31 @@ pub mod tests {
             assert!((340.0..=350.0).contains(&egt));
         }
 
+        #[test]
+        fn running_apu_egt_is_the_same_for_the_same_random_seed() {
+            let mut first = test_bed_with()
+                .random_seed(42)
+                .running_apu_without_bleed_air()
+                .run(Duration::from_secs(1_000));
+            let mut second = test_bed_with()
+                .random_seed(42)
+                .running_apu_without_bleed_air()
+                .run(Duration::from_secs(1_000));
+
+            assert_eq!(first.egt(), second.egt());
+        }
+
+        #[test]
+        fn running_apu_egt_for_random_seed_42_is_always_the_same() {
+            let mut test_bed = test_bed_with()
+                .random_seed(42)
+                .running_apu_without_bleed_air()
+                .run(Duration::from_secs(1_000));
+
+            // Changes to how the random numbers are generated or consumed change this value.
+            assert_eq!(test_bed.egt().get::<degree_celsius>(), 362.);
+        }
+
         #[test]
         /// Komp: APU generator supplying will add maybe like 10-15 degrees.
         fn running_apu_with_generator_supplying_electricity_increases_egt_by_10_to_15_degrees_to_between_350_to_365_degrees(
@@ -1599,6 +1797,63 @@ pub mod tests {
             }
         }
 
+        #[test]
+        #[timeout(500)]
+        fn restored_snapshot_of_running_apu_has_shut_down_turbine_with_saved_egt() {
+            let mut running_test_bed = test_bed_with().running_apu();
+            let saved_egt = running_test_bed.egt();
+            let snapshot = running_test_bed.snapshot();
+
+            let mut test_bed = test_bed()
+                .restored_from(&snapshot)
+                .run(Duration::from_millis(1));
+
+            assert_eq!(test_bed.n(), Ratio::new::<percent>(0.));
+            assert!(
+                (test_bed.egt().get::<degree_celsius>() - saved_egt.get::<degree_celsius>()).abs()
+                    < 1.
+            );
+        }
+
+        #[test]
+        #[timeout(500)]
+        fn dumps_state_of_running_turbine() {
+            let mut test_bed = test_bed_with().running_apu();
+
+            let dump = test_bed.state_dump();
+            let apu = dump.find(&["APU"]).unwrap();
+
+            assert_eq!(apu.read_bool("IS_AVAILABLE"), Some(true));
+            assert_eq!(
+                apu.find(&["TURBINE"]).unwrap().read_text("STATE"),
+                Some("Running")
+            );
+        }
+
+        #[test]
+        fn emits_turbine_state_changes() {
+            let test_bed = test_bed_with()
+                .running_apu_going_in_emergency_shutdown()
+                .run(Duration::from_secs(1));
+
+            let changes: Vec<_> = test_bed
+                .events()
+                .iter_from("APU/TURBINE")
+                .map(|event| (event.kind().to_string(), event.cause()))
+                .collect();
+            assert_eq!(
+                changes,
+                vec![
+                    (String::from("changed from Shutdown to Starting"), None),
+                    (String::from("changed from Starting to Running"), None),
+                    (
+                        String::from("changed from Running to Stopping"),
+                        Some("emergency shutdown")
+                    ),
+                ]
+            );
+        }
+
         #[test]
         fn apu_start_motor_contactor_commanded_vs_reality_disagreement_results_in_fault() {
             let mut test_bed = test_bed_with()
@@ -1683,6 +1938,24 @@ pub mod tests {
             assert_eq!(test_bed.is_inoperable(), true);
         }
 
+        #[test]
+        fn running_apu_with_bleed_air_supplies_bleed_air() {
+            let test_bed = test_bed_with()
+                .running_apu_with_bleed_air()
+                .run(Duration::from_secs(1));
+
+            assert!(test_bed.bleed_air_pressure() > Pressure::new::<psi>(35.));
+        }
+
+        #[test]
+        fn running_apu_without_bleed_air_supplies_no_bleed_air() {
+            let test_bed = test_bed_with()
+                .running_apu_without_bleed_air()
+                .run(Duration::from_secs(1));
+
+            assert_eq!(test_bed.bleed_air_pressure(), Pressure::new::<psi>(0.));
+        }
+
         #[test]
         fn when_fire_pb_released_bleed_valve_closes() {
             let mut test_bed = test_bed_with()
@@ -1,9 +1,14 @@
 use super::{
     consumption::{PowerConsumption, PowerConsumptionReport},
     ElectricalStateWriter, Potential, PotentialOrigin, PotentialSource, PotentialTarget,
-    ProvideCurrent, ProvidePotential,
+    ProvideCurrent, ProvidePotential, SourceResistance,
+};
+use crate::{
+    failures::{Failure, FailureType},
+    simulation::{
+        SimulationElement, SimulationElementVisitor, SimulatorWriter, Snapshot, VariableRegistry,
+    },
 };
-use crate::simulation::{SimulationElement, SimulatorWriter};
 use uom::si::{
     electric_charge::ampere_hour, electric_current::ampere, electric_potential::volt,
     electrical_resistance::ohm, f64::*, time::second,
@@ -16,47 +21,80 @@ pub struct Battery {
     charge: ElectricCharge,
     output_potential: ElectricPotential,
     current: ElectricCurrent,
+    cell_failure: Failure,
+    resistance: Option<SourceResistance>,
+    /// The potential lost over the resistance while discharging.
+    potential_sag: ElectricPotential,
 }
 impl Battery {
     const RATED_CAPACITY_AMPERE_HOURS: f64 = 23.;
 
-    pub fn full(number: usize) -> Battery {
+    pub fn full(registry: &mut VariableRegistry, number: usize) -> Battery {
         Battery::new(
+            registry,
             number,
             ElectricCharge::new::<ampere_hour>(Battery::RATED_CAPACITY_AMPERE_HOURS),
         )
     }
 
-    pub fn half(number: usize) -> Battery {
+    pub fn half(registry: &mut VariableRegistry, number: usize) -> Battery {
         Battery::new(
+            registry,
             number,
             ElectricCharge::new::<ampere_hour>(Battery::RATED_CAPACITY_AMPERE_HOURS / 2.),
         )
     }
 
-    pub fn empty(number: usize) -> Battery {
-        Battery::new(number, ElectricCharge::new::<ampere_hour>(0.))
+    pub fn empty(registry: &mut VariableRegistry, number: usize) -> Battery {
+        Battery::new(registry, number, ElectricCharge::new::<ampere_hour>(0.))
     }
 
-    pub fn new(number: usize, charge: ElectricCharge) -> Self {
+    pub fn new(registry: &mut VariableRegistry, number: usize, charge: ElectricCharge) -> Self {
         Self {
             number,
-            writer: ElectricalStateWriter::new(&format!("BAT_{}", number)),
+            writer: ElectricalStateWriter::new(registry, &format!("BAT_{}", number)),
             input_potential: Potential::none(),
             charge,
             output_potential: Battery::calculate_output_potential_for_charge(charge),
             current: ElectricCurrent::new::<ampere>(0.),
+            cell_failure: Failure::new(registry, FailureType::BatteryCell(number)),
+            resistance: None,
+            potential_sag: ElectricPotential::new::<volt>(0.),
         }
     }
 
+    /// Makes charging and discharging current depend on the given resistance,
+    /// such that the battery's potential sags under load.
+    pub fn with_resistance(mut self, resistance: SourceResistance) -> Self {
+        self.resistance = Some(resistance);
+        self
+    }
+
     pub fn needs_charging(&self) -> bool {
         self.charge <= ElectricCharge::new::<ampere_hour>(Battery::RATED_CAPACITY_AMPERE_HOURS - 3.)
     }
 
+    /// The potential at the battery's terminals while it isn't being charged.
+    fn terminal_potential(&self) -> ElectricPotential {
+        self.output_potential - self.potential_sag
+    }
+
+    /// Whether the input contains potential which doesn't originate from the battery itself.
+    fn receives_potential_of_other_origins(&self) -> bool {
+        self.input_potential
+            .origins()
+            .any(|origin| origin != PotentialOrigin::Battery(self.number))
+    }
+
     fn is_powered_by_other_potential(&self) -> bool {
         self.input_potential.raw() > self.output_potential
     }
 
+    /// A battery with a failed cell neither provides output nor accepts a charge.
+    fn has_failed_cell(&self) -> bool {
+        self.cell_failure.is_active()
+    }
+
     pub fn input_potential(&self) -> Potential {
         self.input_potential
     }
@@ -103,24 +141,31 @@ impl Battery {
         })
     }
 
-    fn calculate_charging_current(
-        input: ElectricPotential,
-        output: ElectricPotential,
-    ) -> ElectricCurrent {
-        // Internal resistance = 0.011 ohm. However that would make current go through the roof.
-        // Thus we add some fake wire resistance here too. If needed, later one can
-        // add resistance of wires between buses to calculate correct values.
-        let resistance = ElectricalResistance::new::<ohm>(0.15);
-        ((input - output) / resistance)
-            .min(ElectricCurrent::new::<ampere>(10.))
-            .max(ElectricCurrent::new::<ampere>(0.))
+    fn calculate_charging_current(&self) -> ElectricCurrent {
+        let input = self.input_potential.raw();
+        let output = self.output_potential;
+        match self.resistance {
+            Some(resistance) => resistance.charging_current(input, output),
+            None => {
+                // Internal resistance = 0.011 ohm. However that would make current go through
+                // the roof. Thus we add some fake wire resistance here too. Batteries given a
+                // `SourceResistance` use the resistance of their wires instead.
+                let resistance = ElectricalResistance::new::<ohm>(0.15);
+                ((input - output) / resistance)
+                    .min(ElectricCurrent::new::<ampere>(10.))
+                    .max(ElectricCurrent::new::<ampere>(0.))
+            }
+        }
     }
 }
 potential_target!(Battery);
 impl PotentialSource for Battery {
     fn output(&self) -> Potential {
-        if self.output_potential > ElectricPotential::new::<volt>(0.) {
-            Potential::single(PotentialOrigin::Battery(self.number), self.output_potential)
+        if !self.has_failed_cell() && self.output_potential > ElectricPotential::new::<volt>(0.) {
+            Potential::single(
+                PotentialOrigin::Battery(self.number),
+                self.terminal_potential(),
+            )
         } else {
             Potential::none()
         }
@@ -138,7 +183,13 @@ impl ProvideCurrent for Battery {
 }
 impl ProvidePotential for Battery {
     fn potential(&self) -> ElectricPotential {
-        self.output_potential.max(self.input_potential.raw())
+        if self.has_failed_cell() {
+            self.input_potential.raw()
+        } else if self.receives_potential_of_other_origins() {
+            self.terminal_potential().max(self.input_potential.raw())
+        } else {
+            self.terminal_potential()
+        }
     }
 
     fn potential_normal(&self) -> bool {
@@ -147,16 +198,33 @@ impl ProvidePotential for Battery {
     }
 }
 impl SimulationElement for Battery {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.cell_failure.accept(visitor);
+
+        visitor.visit(self);
+    }
+
     fn write(&self, writer: &mut SimulatorWriter) {
         self.writer.write_direct(self, writer);
     }
 
+    fn save(&self, snapshot: &mut Snapshot) {
+        snapshot.write_f64(
+            &format!("ELEC_BAT_{}_CHARGE", self.number),
+            self.charge.get::<ampere_hour>(),
+        );
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(charge) = snapshot.read_f64(&format!("ELEC_BAT_{}_CHARGE", self.number)) {
+            self.charge = ElectricCharge::new::<ampere_hour>(charge);
+            self.output_potential = Battery::calculate_output_potential_for_charge(self.charge);
+        }
+    }
+
     fn consume_power(&mut self, consumption: &mut PowerConsumption) {
-        if self.is_powered_by_other_potential() {
-            self.current = Battery::calculate_charging_current(
-                self.input_potential.raw(),
-                self.output_potential,
-            );
+        if !self.has_failed_cell() && self.is_powered_by_other_potential() {
+            self.current = self.calculate_charging_current();
 
             let power = self.input_potential.raw() * self.current;
             consumption.add(&self.input_potential, power);
@@ -168,18 +236,29 @@ impl SimulationElement for Battery {
     }
 
     fn process_power_consumption_report<T: PowerConsumptionReport>(&mut self, report: &T) {
-        if !self.is_powered_by_other_potential() {
-            let consumption = report.total_consumption_of(PotentialOrigin::Battery(self.number));
+        self.potential_sag = ElectricPotential::new::<volt>(0.);
 
-            self.current = if self.output_potential > ElectricPotential::new::<volt>(0.) {
-                -(consumption / self.output_potential)
-            } else {
-                ElectricCurrent::new::<ampere>(0.)
-            };
+        if self.has_failed_cell() {
+            self.current = ElectricCurrent::new::<ampere>(0.);
+        } else if !self.is_powered_by_other_potential() {
+            let consumption = report.total_consumption_of(PotentialOrigin::Battery(self.number));
 
             if self.output_potential > ElectricPotential::new::<volt>(0.) {
+                let discharge_current = match self.resistance {
+                    Some(resistance) => {
+                        let solution = resistance.solve(self.output_potential, consumption);
+                        self.potential_sag = self.output_potential - solution.potential();
+                        solution.current()
+                    }
+                    None => consumption / self.output_potential,
+                };
+
+                self.current = -discharge_current;
+
                 let time = Time::new::<second>(report.delta().as_secs_f64());
-                self.charge -= ((consumption * time) / self.output_potential).min(self.charge);
+                self.charge -= (discharge_current * time).min(self.charge);
+            } else {
+                self.current = ElectricCurrent::new::<ampere>(0.);
             }
         }
 
@@ -204,7 +283,7 @@ mod tests {
             },
         };
         use std::time::Duration;
-        use uom::si::power::watt;
+        use uom::si::{electrical_resistance::ohm, power::watt};
 
         struct BatteryTestBed {
             test_bed: SimulationTestBed,
@@ -220,6 +299,10 @@ mod tests {
                 }
             }
 
+            fn registry(&mut self) -> &mut VariableRegistry {
+                self.test_bed.registry()
+            }
+
             fn run_aircraft(&mut self, aircraft: &mut impl Aircraft) {
                 self.test_bed.run_aircraft(aircraft);
             }
@@ -247,6 +330,11 @@ mod tests {
                         .read_f64(&format!("ELEC_BAT_{}_POTENTIAL", number)),
                 )
             }
+
+            fn fail_cell(&mut self, number: usize) {
+                self.test_bed
+                    .write_bool(&format!("FAILURE_BAT_{}_CELL", number), true);
+            }
         }
 
         struct TestAircraft {
@@ -260,13 +348,17 @@ mod tests {
             supplied_input_potential: Potential,
         }
         impl TestAircraft {
-            fn new(battery_1: Battery, battery_2: Battery) -> Self {
+            fn new(
+                registry: &mut VariableRegistry,
+                battery_1: Battery,
+                battery_2: Battery,
+            ) -> Self {
                 let mut aircraft = Self {
                     battery_1,
                     battery_2,
-                    bat_bus: ElectricalBus::new(ElectricalBusType::DirectCurrentBattery),
-                    battery_1_contactor: Contactor::new("BAT1"),
-                    battery_2_contactor: Contactor::new("BAT2"),
+                    bat_bus: ElectricalBus::new(registry, ElectricalBusType::DirectCurrentBattery),
+                    battery_1_contactor: Contactor::new(registry, "BAT1"),
+                    battery_2_contactor: Contactor::new(registry, "BAT2"),
                     consumer: PowerConsumer::from(ElectricalBusType::DirectCurrentBattery),
                     battery_consumption: Power::new::<watt>(0.),
                     supplied_input_potential: Potential::none(),
@@ -277,34 +369,62 @@ mod tests {
                 aircraft
             }
 
-            fn with_full_batteries() -> Self {
-                Self::new(Battery::full(1), Battery::full(2))
+            fn with_full_batteries(registry: &mut VariableRegistry) -> Self {
+                let battery_1 = Battery::full(registry, 1);
+                let battery_2 = Battery::full(registry, 2);
+
+                Self::new(registry, battery_1, battery_2)
             }
 
-            fn with_half_charged_batteries() -> Self {
-                Self::new(Battery::half(1), Battery::half(2))
+            fn with_half_charged_batteries(registry: &mut VariableRegistry) -> Self {
+                let battery_1 = Battery::half(registry, 1);
+                let battery_2 = Battery::half(registry, 2);
+
+                Self::new(registry, battery_1, battery_2)
             }
 
-            fn with_nearly_empty_batteries() -> Self {
-                Self::new(
-                    Battery::new(1, ElectricCharge::new::<ampere_hour>(0.001)),
-                    Battery::new(2, ElectricCharge::new::<ampere_hour>(0.001)),
-                )
+            fn with_nearly_empty_batteries(registry: &mut VariableRegistry) -> Self {
+                let battery_1 =
+                    Battery::new(registry, 1, ElectricCharge::new::<ampere_hour>(0.001));
+                let battery_2 =
+                    Battery::new(registry, 2, ElectricCharge::new::<ampere_hour>(0.001));
+
+                Self::new(registry, battery_1, battery_2)
             }
 
-            fn with_nearly_empty_dissimilarly_charged_batteries() -> Self {
-                Self::new(
-                    Battery::new(1, ElectricCharge::new::<ampere_hour>(0.002)),
-                    Battery::new(2, ElectricCharge::new::<ampere_hour>(0.001)),
-                )
+            fn with_nearly_empty_dissimilarly_charged_batteries(
+                registry: &mut VariableRegistry,
+            ) -> Self {
+                let battery_1 =
+                    Battery::new(registry, 1, ElectricCharge::new::<ampere_hour>(0.002));
+                let battery_2 =
+                    Battery::new(registry, 2, ElectricCharge::new::<ampere_hour>(0.001));
+
+                Self::new(registry, battery_1, battery_2)
+            }
+
+            fn with_empty_batteries(registry: &mut VariableRegistry) -> Self {
+                let battery_1 = Battery::empty(registry, 1);
+                let battery_2 = Battery::empty(registry, 2);
+
+                Self::new(registry, battery_1, battery_2)
             }
 
-            fn with_empty_batteries() -> Self {
-                Self::new(Battery::empty(1), Battery::empty(2))
+            fn with_full_and_empty_battery(registry: &mut VariableRegistry) -> Self {
+                let battery_1 = Battery::full(registry, 1);
+                let battery_2 = Battery::empty(registry, 2);
+
+                Self::new(registry, battery_1, battery_2)
             }
 
-            fn with_full_and_empty_battery() -> Self {
-                Self::new(Battery::full(1), Battery::empty(2))
+            fn with_resistance(mut self) -> Self {
+                let resistance = SourceResistance::new(
+                    ElectricalResistance::new::<ohm>(0.011),
+                    ElectricalResistance::new::<ohm>(0.02),
+                );
+                self.battery_1 = self.battery_1.with_resistance(resistance);
+                self.battery_2 = self.battery_2.with_resistance(resistance);
+                self
             }
 
             fn supply_input_potential(&mut self, potential: ElectricPotential) {
@@ -386,8 +506,8 @@ mod tests {
 
         #[test]
         fn when_full_has_potential() {
-            let mut aircraft = TestAircraft::with_full_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_full_batteries(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -396,8 +516,8 @@ mod tests {
 
         #[test]
         fn when_full_potential_is_normal() {
-            let mut aircraft = TestAircraft::with_full_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_full_batteries(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -406,8 +526,8 @@ mod tests {
 
         #[test]
         fn when_empty_has_no_potential() {
-            let mut aircraft = TestAircraft::with_empty_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_empty_batteries(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -416,8 +536,8 @@ mod tests {
 
         #[test]
         fn when_empty_potential_is_abnormal() {
-            let mut aircraft = TestAircraft::with_empty_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_empty_batteries(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -427,8 +547,8 @@ mod tests {
         #[test]
         fn when_input_potential_is_greater_than_output_potential_returns_input_potential_for_ecam_and_overhead_indication(
         ) {
-            let mut aircraft = TestAircraft::with_half_charged_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_half_charged_batteries(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -445,8 +565,8 @@ mod tests {
         #[test]
         fn when_input_potential_is_less_than_output_potential_returns_output_potential_for_ecam_and_overhead_indication(
         ) {
-            let mut aircraft = TestAircraft::with_full_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_full_batteries(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -462,8 +582,8 @@ mod tests {
 
         #[test]
         fn when_charging_current_is_normal() {
-            let mut aircraft = TestAircraft::with_empty_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_empty_batteries(test_bed.registry());
 
             aircraft.supply_input_potential(ElectricPotential::new::<volt>(28.));
             test_bed.run_aircraft(&mut aircraft);
@@ -473,8 +593,8 @@ mod tests {
 
         #[test]
         fn when_charging_battery_current_is_charge_current() {
-            let mut aircraft = TestAircraft::with_half_charged_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_half_charged_batteries(test_bed.registry());
 
             aircraft.supply_input_potential(ElectricPotential::new::<volt>(28.));
             test_bed.run_aircraft(&mut aircraft);
@@ -484,8 +604,8 @@ mod tests {
 
         #[test]
         fn when_discharging_slowly_current_is_normal() {
-            let mut aircraft = TestAircraft::with_full_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_full_batteries(test_bed.registry());
 
             aircraft.power_demand(Power::new::<watt>(40.));
             test_bed.run_aircraft(&mut aircraft);
@@ -495,8 +615,8 @@ mod tests {
 
         #[test]
         fn when_discharging_quickly_current_is_abnormal() {
-            let mut aircraft = TestAircraft::with_full_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_full_batteries(test_bed.registry());
 
             aircraft.power_demand(Power::new::<watt>(500.));
             test_bed.run_aircraft(&mut aircraft);
@@ -506,8 +626,8 @@ mod tests {
 
         #[test]
         fn when_discharging_battery_current_is_discharge_current() {
-            let mut aircraft = TestAircraft::with_full_batteries();
             let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_full_batteries(test_bed.registry());
 
             aircraft.power_demand(Power::new::<watt>(100.));
             test_bed.run_aircraft(&mut aircraft);
@@ -515,10 +635,52 @@ mod tests {
             assert!(test_bed.current(1) < ElectricCurrent::new::<ampere>(0.))
         }
 
+        #[test]
+        fn with_resistance_potential_sags_while_discharging() {
+            let mut test_bed = BatteryTestBed::new();
+            let mut aircraft =
+                TestAircraft::with_full_batteries(test_bed.registry()).with_resistance();
+            let open_circuit_potential = ProvidePotential::potential(&aircraft.battery_1);
+
+            aircraft.power_demand(Power::new::<watt>(1000.));
+            test_bed.run_aircraft(&mut aircraft);
+
+            assert!(test_bed.potential(1) < open_circuit_potential);
+            assert!(test_bed.potential_is_normal(1));
+        }
+
+        #[test]
+        fn with_resistance_discharge_current_depends_on_load() {
+            let mut test_bed = BatteryTestBed::new();
+            let mut aircraft =
+                TestAircraft::with_full_batteries(test_bed.registry()).with_resistance();
+            aircraft.power_demand(Power::new::<watt>(100.));
+            test_bed.run_aircraft(&mut aircraft);
+            let low_load_current = test_bed.current(1);
+
+            aircraft.power_demand(Power::new::<watt>(1000.));
+            test_bed.run_aircraft(&mut aircraft);
+
+            assert!(test_bed.current(1) < low_load_current);
+            assert!(low_load_current < ElectricCurrent::new::<ampere>(0.));
+        }
+
+        #[test]
+        fn with_resistance_charging_current_is_not_limited_by_fake_wire_resistance() {
+            let mut test_bed = BatteryTestBed::new();
+            let mut aircraft =
+                TestAircraft::with_half_charged_batteries(test_bed.registry()).with_resistance();
+
+            aircraft.supply_input_potential(ElectricPotential::new::<volt>(28.));
+            test_bed.run_aircraft(&mut aircraft);
+
+            assert!(test_bed.current(1) > ElectricCurrent::new::<ampere>(10.));
+        }
+
         #[test]
         fn when_discharging_loses_charge() {
-            let mut aircraft = TestAircraft::with_full_batteries();
             let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(60));
+            let mut aircraft = TestAircraft::with_full_batteries(test_bed.registry());
 
             let charge_prior_to_run = aircraft.battery_1_charge();
 
@@ -530,8 +692,8 @@ mod tests {
 
         #[test]
         fn when_charging_gains_charge() {
-            let mut aircraft = TestAircraft::with_empty_batteries();
             let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(60));
+            let mut aircraft = TestAircraft::with_empty_batteries(test_bed.registry());
 
             let charge_prior_to_run = aircraft.battery_1_charge();
 
@@ -541,10 +703,46 @@ mod tests {
             assert!(aircraft.battery_1_charge() > charge_prior_to_run);
         }
 
+        #[test]
+        fn when_cell_failed_has_no_potential() {
+            let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_full_batteries(test_bed.registry());
+
+            test_bed.fail_cell(1);
+            test_bed.run_aircraft(&mut aircraft);
+
+            assert_eq!(test_bed.potential(1), ElectricPotential::new::<volt>(0.));
+        }
+
+        #[test]
+        fn when_cell_failed_does_not_power_bat_bus() {
+            let mut test_bed = BatteryTestBed::new();
+            let mut aircraft = TestAircraft::with_full_and_empty_battery(test_bed.registry());
+
+            test_bed.fail_cell(1);
+            test_bed.run_aircraft(&mut aircraft);
+
+            assert!(!aircraft.bat_bus_is_powered());
+        }
+
+        #[test]
+        fn when_cell_failed_does_not_charge() {
+            let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(60));
+            let mut aircraft = TestAircraft::with_empty_batteries(test_bed.registry());
+
+            let charge_prior_to_run = aircraft.battery_1_charge();
+
+            test_bed.fail_cell(1);
+            aircraft.supply_input_potential(ElectricPotential::new::<volt>(28.));
+            test_bed.run_aircraft(&mut aircraft);
+
+            assert_eq!(aircraft.battery_1_charge(), charge_prior_to_run);
+        }
+
         #[test]
         fn can_charge_beyond_rated_capacity() {
-            let mut aircraft = TestAircraft::with_full_batteries();
             let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut aircraft = TestAircraft::with_full_batteries(test_bed.registry());
 
             let charge_prior_to_run = aircraft.battery_1_charge();
 
@@ -556,8 +754,8 @@ mod tests {
 
         #[test]
         fn does_not_charge_when_input_potential_lower_than_battery_potential() {
-            let mut aircraft = TestAircraft::with_half_charged_batteries();
             let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut aircraft = TestAircraft::with_half_charged_batteries(test_bed.registry());
 
             let charge_prior_to_run = aircraft.battery_1_charge();
 
@@ -569,8 +767,8 @@ mod tests {
 
         #[test]
         fn when_neither_charging_nor_discharging_charge_remains_equal() {
-            let mut aircraft = TestAircraft::with_half_charged_batteries();
             let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut aircraft = TestAircraft::with_half_charged_batteries(test_bed.registry());
 
             let charge_prior_to_run = aircraft.battery_1_charge();
 
@@ -581,8 +779,8 @@ mod tests {
 
         #[test]
         fn when_neither_charging_nor_discharging_current_is_zero() {
-            let mut aircraft = TestAircraft::with_half_charged_batteries();
             let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut aircraft = TestAircraft::with_half_charged_batteries(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -591,8 +789,8 @@ mod tests {
 
         #[test]
         fn cannot_discharge_below_zero() {
-            let mut aircraft = TestAircraft::with_nearly_empty_batteries();
             let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(50));
+            let mut aircraft = TestAircraft::with_nearly_empty_batteries(test_bed.registry());
 
             aircraft.power_demand(Power::new::<watt>(5000.));
             test_bed.run_aircraft(&mut aircraft);
@@ -605,8 +803,9 @@ mod tests {
 
         #[test]
         fn dissimilar_charged_batteries_in_parallel_deplete() {
-            let mut aircraft = TestAircraft::with_nearly_empty_dissimilarly_charged_batteries();
             let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(1));
+            let mut aircraft =
+                TestAircraft::with_nearly_empty_dissimilarly_charged_batteries(test_bed.registry());
 
             aircraft.power_demand(Power::new::<watt>(10.));
             aircraft.close_battery_2_contactor();
@@ -622,8 +821,8 @@ mod tests {
 
         #[test]
         fn batteries_charge_each_other_until_relatively_equal_charge() {
-            let mut aircraft = TestAircraft::with_full_and_empty_battery();
             let mut test_bed = BatteryTestBed::new_with_delta(Duration::from_secs(120));
+            let mut aircraft = TestAircraft::with_full_and_empty_battery(test_bed.registry());
 
             let original_charge = aircraft.battery_1_charge();
 
@@ -643,5 +842,34 @@ mod tests {
                     < ElectricCharge::new::<ampere_hour>(0.001)
             );
         }
+
+        #[test]
+        fn restores_charge_from_snapshot() {
+            let mut registry = VariableRegistry::new();
+            let snapshot = Snapshot::take(&mut Battery::empty(&mut registry, 1));
+            let mut battery = Battery::full(&mut registry, 1);
+
+            snapshot.restore_into(&mut battery);
+
+            assert_eq!(battery.charge(), ElectricCharge::new::<ampere_hour>(0.));
+            assert_eq!(
+                ProvidePotential::potential(&battery),
+                ElectricPotential::new::<volt>(0.)
+            );
+        }
+
+        #[test]
+        fn restore_from_snapshot_of_other_battery_leaves_charge_untouched() {
+            let mut registry = VariableRegistry::new();
+            let snapshot = Snapshot::take(&mut Battery::empty(&mut registry, 2));
+            let mut battery = Battery::full(&mut registry, 1);
+
+            snapshot.restore_into(&mut battery);
+
+            assert_eq!(
+                battery.charge(),
+                ElectricCharge::new::<ampere_hour>(Battery::RATED_CAPACITY_AMPERE_HOURS)
+            );
+        }
     }
 }
@@ -4,7 +4,10 @@ use super::{
 };
 use crate::{
     shared::{ApuAvailable, ApuMaster, ApuStart, DelayedTrueLogicGate, LandingGearPosition},
-    simulation::{SimulationElement, SimulatorWriter, UpdateContext},
+    simulation::{
+        ChangeDetector, EventEmitter, EventKind, SimulationElement, SimulatorWriter, Snapshot,
+        StateDump, UpdateContext, VariableIdentifier, VariableRegistry,
+    },
 };
 use std::time::Duration;
 use uom::si::{electric_current::ampere, electric_potential::volt, f64::*, velocity::knot};
@@ -70,24 +73,86 @@ impl State {
     fn should_close_contactor(&self) -> bool {
         matches!(self, State::Closed(_))
     }
+
+    fn name(&self) -> &'static str {
+        match self {
+            State::Off(_) => "OFF",
+            State::Open(_) => "OPEN",
+            State::Closed(_) => "CLOSED",
+        }
+    }
+
+    fn cause(&self) -> Option<&'static str> {
+        match self {
+            State::Open(open) => open.cause(),
+            _ => None,
+        }
+    }
+
+    /// Saves the state, apart from any delays in progress.
+    fn save(&self, snapshot: &mut Snapshot, battery_number: usize) {
+        let name = |suffix| format!("ELEC_BCL_{}_{}", battery_number, suffix);
+        match self {
+            State::Off(_) => snapshot.write_f64(&name("STATE"), 0.),
+            State::Open(open) => {
+                snapshot.write_f64(&name("STATE"), 1.);
+                snapshot.write_bool(
+                    &name("OPEN_DUE_TO_DISCHARGE_PROTECTION"),
+                    open.open_due_to_discharge_protection,
+                );
+                snapshot.write_bool(
+                    &name("OPEN_DUE_TO_EMER_ELEC_CLOSING_TIME"),
+                    open.open_due_to_exceeding_emergency_elec_closing_time_allowance,
+                );
+            }
+            State::Closed(closed) => {
+                snapshot.write_f64(&name("STATE"), 2.);
+                snapshot.write_bool(&name("HAD_APU_START"), closed.had_apu_start);
+                snapshot.write_bool(
+                    &name("ENTERED_IN_EMER_ELEC"),
+                    closed.entered_in_emergency_elec,
+                );
+            }
+        }
+    }
+
+    fn restore(snapshot: &Snapshot, battery_number: usize) -> Option<State> {
+        let name = |suffix| format!("ELEC_BCL_{}_{}", battery_number, suffix);
+        let read_bool = |suffix| snapshot.read_bool(&name(suffix)).unwrap_or_default();
+        match snapshot.read_f64(&name("STATE"))? as u8 {
+            0 => Some(State::Off(Off::new())),
+            1 => Some(State::Open(Open::new(
+                read_bool("OPEN_DUE_TO_DISCHARGE_PROTECTION"),
+                read_bool("OPEN_DUE_TO_EMER_ELEC_CLOSING_TIME"),
+            ))),
+            2 => {
+                let mut closed = Closed::new(read_bool("ENTERED_IN_EMER_ELEC"));
+                closed.had_apu_start = read_bool("HAD_APU_START");
+                Some(State::Closed(closed))
+            }
+            _ => None,
+        }
+    }
 }
 
 pub struct BatteryChargeLimiter {
     number: usize,
-    should_show_arrow_when_contactor_closed_id: String,
+    should_show_arrow_when_contactor_closed_id: VariableIdentifier,
     arrow: ArrowBetweenBatteryAndBatBus,
     observer: Option<State>,
+    state_change: ChangeDetector<&'static str>,
 }
 impl BatteryChargeLimiter {
-    pub fn new(number: usize, contactor_id: &str) -> Self {
+    pub fn new(registry: &mut VariableRegistry, number: usize, contactor_id: &str) -> Self {
         Self {
             number,
-            should_show_arrow_when_contactor_closed_id: format!(
+            should_show_arrow_when_contactor_closed_id: registry.get(&format!(
                 "ELEC_CONTACTOR_{}_SHOW_ARROW_WHEN_CLOSED",
                 contactor_id
-            ),
+            )),
             arrow: ArrowBetweenBatteryAndBatBus::new(),
             observer: Some(State::new()),
+            state_change: ChangeDetector::new(State::new().name()),
         }
     }
 
@@ -135,6 +200,48 @@ impl SimulationElement for BatteryChargeLimiter {
             self.arrow.should_show_when_contactor_closed(),
         );
     }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        if let Some(state) = &self.observer {
+            state.save(snapshot, self.number);
+        }
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(state) = State::restore(snapshot, self.number) {
+            self.state_change = ChangeDetector::new(state.name());
+            self.observer = Some(state);
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        if let Some(observer) = &self.observer {
+            let node = state
+                .node("ELEC")
+                .node("BCL")
+                .node(&self.number.to_string());
+            node.write_text("STATE", observer.name());
+            if let Some(cause) = observer.cause() {
+                node.write_text("CAUSE", cause);
+            }
+        }
+    }
+
+    fn emit_events(&mut self, events: &mut EventEmitter) {
+        if let Some(state) = &self.observer {
+            if let Some(previous_name) = self.state_change.detect(state.name()) {
+                let source = format!("ELEC/BCL/{}", self.number);
+                let kind = EventKind::StateChanged {
+                    from: previous_name.to_owned(),
+                    to: state.name().to_owned(),
+                };
+                match state.cause() {
+                    Some(cause) => events.emit_with_cause(&source, kind, cause),
+                    None => events.emit(&source, kind),
+                }
+            }
+        }
+    }
 }
 
 /// The BCL is not powered when the BAT push button is in the OFF
@@ -213,6 +320,16 @@ impl Open {
         Self::new(false, true)
     }
 
+    fn cause(&self) -> Option<&'static str> {
+        if self.open_due_to_discharge_protection {
+            Some("discharge protection")
+        } else if self.open_due_to_exceeding_emergency_elec_closing_time_allowance {
+            Some("emergency elec closing time allowance exceeded")
+        } else {
+            None
+        }
+    }
+
     fn update_state(
         &mut self,
         context: &UpdateContext,
@@ -544,7 +661,7 @@ mod tests {
                 Contactor, ElectricalBus, ElectricalBusType, Potential, PotentialOrigin,
                 PotentialTarget,
             },
-            simulation::{test::SimulationTestBed, Aircraft, SimulationElementVisitor},
+            simulation::{test::SimulationTestBed, Aircraft, EventBus, SimulationElementVisitor},
         };
         use std::time::Duration;
         use uom::si::{length::foot, power::watt};
@@ -555,10 +672,11 @@ mod tests {
         }
         impl BatteryChargeLimiterTestBed {
             fn new() -> Self {
-                Self {
-                    test_bed: SimulationTestBed::new(),
-                    aircraft: TestAircraft::new(Battery::half(1)),
-                }
+                let mut test_bed = SimulationTestBed::new();
+                let battery = Battery::half(test_bed.registry(), 1);
+                let aircraft = TestAircraft::new(test_bed.registry(), battery);
+
+                Self { test_bed, aircraft }
             }
 
             fn on_the_ground(mut self) -> Self {
@@ -591,6 +709,10 @@ mod tests {
                 self
             }
 
+            fn events(&self) -> &EventBus {
+                self.test_bed.events()
+            }
+
             fn wait_for_closed_contactor(mut self, assert_is_closed: bool) -> Self {
                 self.aircraft.set_battery_bus_at_minimum_charging_voltage();
                 self = self.run(Duration::from_millis(
@@ -895,12 +1017,15 @@ mod tests {
             any_non_essential_bus_powered: bool,
         }
         impl TestAircraft {
-            fn new(battery: Battery) -> Self {
+            fn new(registry: &mut VariableRegistry, battery: Battery) -> Self {
                 Self {
                     battery,
-                    battery_charge_limiter: BatteryChargeLimiter::new(1, "TEST"),
-                    battery_bus: ElectricalBus::new(ElectricalBusType::DirectCurrentBattery),
-                    battery_contactor: Contactor::new("TEST"),
+                    battery_charge_limiter: BatteryChargeLimiter::new(registry, 1, "TEST"),
+                    battery_bus: ElectricalBus::new(
+                        registry,
+                        ElectricalBusType::DirectCurrentBattery,
+                    ),
+                    battery_contactor: Contactor::new(registry, "TEST"),
                     consumer: PowerConsumer::from(ElectricalBusType::DirectCurrentBattery),
                     apu_master_sw_pb_on: false,
                     apu_start_pb_on: false,
@@ -1454,6 +1579,51 @@ mod tests {
             assert!(!test_bed.battery_contactor_is_closed());
         }
 
+        #[test]
+        fn emits_state_changes_with_their_cause() {
+            let test_bed =
+                test_bed_with()
+                    .pre_discharge_protection_state()
+                    .run(Duration::from_secs(
+                        Closed::BATTERY_DISCHARGE_PROTECTION_DELAY_SECONDS,
+                    ));
+
+            let changes: Vec<_> = test_bed
+                .events()
+                .iter_from("ELEC/BCL/1")
+                .map(|event| (event.kind().to_string(), event.cause()))
+                .collect();
+            assert_eq!(
+                changes,
+                vec![
+                    (String::from("changed from OPEN to CLOSED"), None),
+                    (
+                        String::from("changed from CLOSED to OPEN"),
+                        Some("discharge protection")
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn restores_state_and_its_cause_from_snapshot() {
+            let mut test_bed =
+                test_bed_with()
+                    .pre_discharge_protection_state()
+                    .run(Duration::from_secs(
+                        Closed::BATTERY_DISCHARGE_PROTECTION_DELAY_SECONDS,
+                    ));
+            let snapshot = Snapshot::take(&mut test_bed.aircraft.battery_charge_limiter);
+            let mut bcl = BatteryChargeLimiter::new(&mut VariableRegistry::new(), 1, "TEST");
+
+            snapshot.restore_into(&mut bcl);
+
+            let dump = StateDump::take(&mut bcl);
+            let node = dump.find(&["ELEC", "BCL", "1"]).unwrap();
+            assert_eq!(node.read_text("STATE"), Some("OPEN"));
+            assert_eq!(node.read_text("CAUSE"), Some("discharge protection"));
+        }
+
         #[test]
         fn complete_discharge_protection_is_reset_by_cycling_the_battery_push_button() {
             let mut test_bed =
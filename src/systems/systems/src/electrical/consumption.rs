@@ -20,8 +20,11 @@ use std::{collections::HashMap, time::Duration};
 
 use super::{ElectricalBus, ElectricalBusType, Potential, PotentialOrigin, PotentialSource};
 use crate::{
-    shared::{random_number, FwcFlightPhase},
-    simulation::{SimulationElement, SimulationElementVisitor, SimulatorReader, UpdateContext},
+    shared::FwcFlightPhase,
+    simulation::{
+        SimulationElement, SimulationElementVisitor, SimulatorReader, Snapshot, UpdateContext,
+        VariableIdentifier, VariableRegistry,
+    },
 };
 use num_traits::FromPrimitive;
 use uom::si::{f64::*, power::watt};
@@ -153,14 +156,19 @@ impl SimulationElement for PowerConsumer {
 /// A special type of power consumer which changes its consumption
 /// based on the phase of the flight.
 pub struct FlightPhasePowerConsumer {
+    flight_phase_id: VariableIdentifier,
+    bus_type: ElectricalBusType,
+
     consumer: PowerConsumer,
     base_demand: [Power; PowerConsumerFlightPhase::TaxiIn as usize + 1],
     current_flight_phase: PowerConsumerFlightPhase,
     update_after: Duration,
 }
 impl FlightPhasePowerConsumer {
-    pub fn from(bus_type: ElectricalBusType) -> Self {
+    pub fn from(registry: &mut VariableRegistry, bus_type: ElectricalBusType) -> Self {
         Self {
+            flight_phase_id: registry.get("FWC_FLIGHT_PHASE"),
+            bus_type,
             consumer: PowerConsumer::from(bus_type),
             base_demand: Default::default(),
             current_flight_phase: PowerConsumerFlightPhase::BeforeStart,
@@ -170,10 +178,11 @@ impl FlightPhasePowerConsumer {
 
     pub fn update(&mut self, context: &UpdateContext) {
         if self.update_after <= context.delta() {
-            self.update_after = Duration::from_secs_f64(5. + ((random_number() % 26) as f64));
+            self.update_after =
+                Duration::from_secs_f64(5. + ((context.random_number() % 26) as f64));
             let base_demand = self.base_demand[self.current_flight_phase as usize].get::<watt>();
             self.consumer.demand(Power::new::<watt>(
-                base_demand * ((90. + ((random_number() % 21) as f64)) / 100.),
+                base_demand * ((90. + ((context.random_number() % 21) as f64)) / 100.),
             ));
         } else {
             self.update_after -= context.delta();
@@ -200,11 +209,35 @@ impl SimulationElement for FlightPhasePowerConsumer {
 
     fn read(&mut self, reader: &mut SimulatorReader) {
         let flight_phase: Option<FwcFlightPhase> =
-            FromPrimitive::from_f64(reader.read_f64("FWC_FLIGHT_PHASE"));
+            FromPrimitive::from_f64(reader.read_f64(&self.flight_phase_id));
         if let Some(phase) = flight_phase {
             self.current_flight_phase = PowerConsumerFlightPhase::from(phase);
         }
     }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        // The demand is randomised, thus it is saved as is instead of being drawn again.
+        snapshot.write_f64(
+            &format!("ELEC_{}_CONSUMER_DEMAND", self.bus_type),
+            self.consumer.demand.get::<watt>(),
+        );
+        snapshot.write_f64(
+            &format!("ELEC_{}_CONSUMER_UPDATE_AFTER", self.bus_type),
+            self.update_after.as_secs_f64(),
+        );
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(demand) = snapshot.read_f64(&format!("ELEC_{}_CONSUMER_DEMAND", self.bus_type))
+        {
+            self.consumer.demand(Power::new::<watt>(demand));
+        }
+        if let Some(update_after) =
+            snapshot.read_f64(&format!("ELEC_{}_CONSUMER_UPDATE_AFTER", self.bus_type))
+        {
+            self.update_after = Duration::from_secs_f64(update_after.max(0.));
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -370,7 +403,7 @@ mod tests {
         }
 
         fn unpowered_bus(bus_type: ElectricalBusType) -> ElectricalBus {
-            ElectricalBus::new(bus_type)
+            ElectricalBus::new(&mut VariableRegistry::new(), bus_type)
         }
 
         #[test]
@@ -402,7 +435,7 @@ mod tests {
         use crate::electrical::PotentialTarget;
 
         fn powered_bus(bus_type: ElectricalBusType) -> ElectricalBus {
-            let mut bus = ElectricalBus::new(bus_type);
+            let mut bus = ElectricalBus::new(&mut VariableRegistry::new(), bus_type);
             bus.powered_by(&ApuStub::new());
 
             bus
@@ -514,7 +547,7 @@ mod tests {
             }
 
             fn powered_bus(bus_type: ElectricalBusType) -> ElectricalBus {
-                let mut bus = ElectricalBus::new(bus_type);
+                let mut bus = ElectricalBus::new(&mut VariableRegistry::new(), bus_type);
                 bus.powered_by(&ApuStub::new());
 
                 bus
@@ -556,8 +589,13 @@ mod tests {
 
         #[test]
         fn when_flight_phase_doesnt_have_demand_usage_is_zero() {
+            let mut test_bed = SimulationTestBed::new();
             let mut aircraft = FlightPhasePowerConsumerTestAircraft::new(
-                FlightPhasePowerConsumer::from(ElectricalBusType::AlternatingCurrent(1)).demand([
+                FlightPhasePowerConsumer::from(
+                    test_bed.registry(),
+                    ElectricalBusType::AlternatingCurrent(1),
+                )
+                .demand([
                     (
                         PowerConsumerFlightPhase::BeforeStart,
                         Power::new::<watt>(0.),
@@ -571,7 +609,6 @@ mod tests {
                 true,
             );
 
-            let mut test_bed = SimulationTestBed::new();
             apply_flight_phase(&mut test_bed, FwcFlightPhase::FirstEngineStarted);
 
             test_bed.run_aircraft(&mut aircraft);
@@ -582,8 +619,13 @@ mod tests {
         #[test]
         fn when_flight_phase_does_have_demand_usage_is_close_to_demand() {
             let input = Power::new::<watt>(20000.);
+            let mut test_bed = SimulationTestBed::new();
             let mut aircraft = FlightPhasePowerConsumerTestAircraft::new(
-                FlightPhasePowerConsumer::from(ElectricalBusType::AlternatingCurrent(1)).demand([
+                FlightPhasePowerConsumer::from(
+                    test_bed.registry(),
+                    ElectricalBusType::AlternatingCurrent(1),
+                )
+                .demand([
                     (
                         PowerConsumerFlightPhase::BeforeStart,
                         Power::new::<watt>(0.),
@@ -597,7 +639,6 @@ mod tests {
                 true,
             );
 
-            let mut test_bed = SimulationTestBed::new();
             apply_flight_phase(&mut test_bed, FwcFlightPhase::AtOrAbove1500Feet);
 
             test_bed.run_aircraft(&mut aircraft);
@@ -605,10 +646,103 @@ mod tests {
             assert!(aircraft.consumption_within_range(input * 0.9, input * 1.1));
         }
 
+        fn consumption_with_seed(seed: u64) -> Option<Power> {
+            let mut test_bed = SimulationTestBed::new();
+            let mut aircraft = FlightPhasePowerConsumerTestAircraft::new(
+                FlightPhasePowerConsumer::from(
+                    test_bed.registry(),
+                    ElectricalBusType::AlternatingCurrent(1),
+                )
+                .demand([
+                    (
+                        PowerConsumerFlightPhase::BeforeStart,
+                        Power::new::<watt>(0.),
+                    ),
+                    (PowerConsumerFlightPhase::AfterStart, Power::new::<watt>(0.)),
+                    (PowerConsumerFlightPhase::Takeoff, Power::new::<watt>(0.)),
+                    (PowerConsumerFlightPhase::Flight, Power::new::<watt>(20000.)),
+                    (PowerConsumerFlightPhase::Landing, Power::new::<watt>(0.)),
+                    (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(0.)),
+                ]),
+                true,
+            );
+
+            test_bed.set_random_seed(seed);
+            apply_flight_phase(&mut test_bed, FwcFlightPhase::AtOrAbove1500Feet);
+
+            test_bed.run_aircraft(&mut aircraft);
+
+            aircraft.apu_generator_consumption
+        }
+
+        #[test]
+        fn usage_is_the_same_for_the_same_random_seed() {
+            assert!(consumption_with_seed(42).is_some());
+            assert_eq!(consumption_with_seed(42), consumption_with_seed(42));
+        }
+
+        #[test]
+        fn usage_for_a_random_seed_is_always_the_same() {
+            // Changes to how the random numbers are generated or consumed change these values.
+            assert_eq!(consumption_with_seed(42), Some(Power::new::<watt>(22000.)));
+            assert_eq!(consumption_with_seed(7), Some(Power::new::<watt>(21600.)));
+        }
+
+        #[test]
+        fn restores_randomised_demand_from_snapshot() {
+            fn test_aircraft(
+                test_bed: &mut SimulationTestBed,
+            ) -> FlightPhasePowerConsumerTestAircraft {
+                FlightPhasePowerConsumerTestAircraft::new(
+                    FlightPhasePowerConsumer::from(
+                        test_bed.registry(),
+                        ElectricalBusType::AlternatingCurrent(1),
+                    )
+                    .demand([
+                        (
+                            PowerConsumerFlightPhase::BeforeStart,
+                            Power::new::<watt>(0.),
+                        ),
+                        (PowerConsumerFlightPhase::AfterStart, Power::new::<watt>(0.)),
+                        (PowerConsumerFlightPhase::Takeoff, Power::new::<watt>(0.)),
+                        (PowerConsumerFlightPhase::Flight, Power::new::<watt>(20000.)),
+                        (PowerConsumerFlightPhase::Landing, Power::new::<watt>(0.)),
+                        (PowerConsumerFlightPhase::TaxiIn, Power::new::<watt>(0.)),
+                    ]),
+                    true,
+                )
+            }
+
+            let mut test_bed = SimulationTestBed::new();
+            let mut aircraft = test_aircraft(&mut test_bed);
+            test_bed.set_random_seed(42);
+            apply_flight_phase(&mut test_bed, FwcFlightPhase::AtOrAbove1500Feet);
+            test_bed.run_aircraft(&mut aircraft);
+            let snapshot = Snapshot::take(&mut aircraft);
+
+            let mut restored_test_bed = SimulationTestBed::new();
+            let mut restored = test_aircraft(&mut restored_test_bed);
+            snapshot.restore_into(&mut restored);
+            restored_test_bed.set_random_seed(7);
+            apply_flight_phase(&mut restored_test_bed, FwcFlightPhase::AtOrAbove1500Feet);
+            restored_test_bed.run_aircraft(&mut restored);
+
+            assert!(aircraft.apu_generator_consumption.is_some());
+            assert_eq!(
+                restored.apu_generator_consumption,
+                aircraft.apu_generator_consumption
+            );
+        }
+
         #[test]
         fn when_flight_phase_does_have_demand_but_consumer_unpowered_usage_is_zero() {
+            let mut test_bed = SimulationTestBed::new();
             let mut aircraft = FlightPhasePowerConsumerTestAircraft::new(
-                FlightPhasePowerConsumer::from(ElectricalBusType::AlternatingCurrent(1)).demand([
+                FlightPhasePowerConsumer::from(
+                    test_bed.registry(),
+                    ElectricalBusType::AlternatingCurrent(1),
+                )
+                .demand([
                     (
                         PowerConsumerFlightPhase::BeforeStart,
                         Power::new::<watt>(20000.),
@@ -631,7 +765,6 @@ mod tests {
                 false,
             );
 
-            let mut test_bed = SimulationTestBed::new();
             apply_flight_phase(&mut test_bed, FwcFlightPhase::FirstEngineStarted);
 
             test_bed.run_aircraft(&mut aircraft);
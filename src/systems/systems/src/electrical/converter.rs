@@ -0,0 +1,236 @@
+use crate::shared::interpolation;
+use uom::si::{electric_potential::volt, f64::*, power::watt, ratio::ratio};
+
+/// Describes how a converter, such as a transformer rectifier or static inverter,
+/// converts power. The efficiency of the conversion depends on the load, which is the
+/// output power relative to the converter's rated output power. Power which is lost
+/// in conversion is dissipated as heat. The output potential is regulated such that it
+/// droops linearly from the no load potential to the rated load potential.
+///
+/// # Examples
+/// ```rust
+/// # use systems::electrical::ConverterCharacteristics;
+/// # use uom::si::{electric_potential::volt, f64::*, power::watt};
+/// let characteristics = ConverterCharacteristics::ideal(
+///     Power::new::<watt>(5600.),
+///     ElectricPotential::new::<volt>(28.),
+/// )
+/// .with_efficiency(&[0.1, 1.], &[0.7, 0.9])
+/// .with_regulation(
+///     ElectricPotential::new::<volt>(28.5),
+///     ElectricPotential::new::<volt>(27.5),
+/// );
+///
+/// let output = Power::new::<watt>(5600.);
+/// assert!(characteristics.input_power(output) > output);
+/// assert_eq!(
+///     characteristics.output_potential(output),
+///     ElectricPotential::new::<volt>(27.5)
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConverterCharacteristics {
+    rated_output_power: Power,
+    load_breakpoints: Vec<f64>,
+    efficiencies: Vec<f64>,
+    no_load_output_potential: ElectricPotential,
+    rated_load_output_potential: ElectricPotential,
+}
+impl ConverterCharacteristics {
+    /// A converter which converts without losses and always outputs the given potential.
+    pub fn ideal(rated_output_power: Power, output_potential: ElectricPotential) -> Self {
+        Self {
+            rated_output_power,
+            load_breakpoints: vec![0., 1.],
+            efficiencies: vec![1., 1.],
+            no_load_output_potential: output_potential,
+            rated_load_output_potential: output_potential,
+        }
+    }
+
+    /// Sets the efficiency (0 to 1) of the converter at the given loads, where a load
+    /// of 1 equals the rated output power. Efficiency is interpolated between the given
+    /// loads and remains constant beyond them.
+    pub fn with_efficiency(mut self, load_breakpoints: &[f64], efficiencies: &[f64]) -> Self {
+        debug_assert!(
+            efficiencies.iter().all(|&efficiency| efficiency > 0.),
+            "A converter's efficiency must be greater than zero."
+        );
+
+        self.load_breakpoints = load_breakpoints.to_vec();
+        self.efficiencies = efficiencies.to_vec();
+        self
+    }
+
+    pub fn with_regulation(
+        mut self,
+        no_load_output_potential: ElectricPotential,
+        rated_load_output_potential: ElectricPotential,
+    ) -> Self {
+        self.no_load_output_potential = no_load_output_potential;
+        self.rated_load_output_potential = rated_load_output_potential;
+        self
+    }
+
+    pub fn rated_output_power(&self) -> Power {
+        self.rated_output_power
+    }
+
+    pub fn load(&self, output_power: Power) -> Ratio {
+        Ratio::new::<ratio>(
+            output_power.get::<watt>().max(0.) / self.rated_output_power.get::<watt>(),
+        )
+    }
+
+    pub fn efficiency(&self, output_power: Power) -> Ratio {
+        Ratio::new::<ratio>(interpolation(
+            &self.load_breakpoints,
+            &self.efficiencies,
+            self.load(output_power).get::<ratio>(),
+        ))
+    }
+
+    /// The power the converter consumes at its input to provide the given output power.
+    pub fn input_power(&self, output_power: Power) -> Power {
+        if output_power <= Power::new::<watt>(0.) {
+            Power::new::<watt>(0.)
+        } else {
+            output_power / self.efficiency(output_power).get::<ratio>()
+        }
+    }
+
+    /// The power lost in conversion, which the converter dissipates as heat.
+    pub fn heat_dissipation(&self, output_power: Power) -> Power {
+        self.input_power(output_power) - output_power.max(Power::new::<watt>(0.))
+    }
+
+    pub fn output_potential(&self, output_power: Power) -> ElectricPotential {
+        let droop = self.no_load_output_potential - self.rated_load_output_potential;
+
+        (self.no_load_output_potential - droop * self.load(output_power).get::<ratio>())
+            .max(ElectricPotential::new::<volt>(0.))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::assert_about_eq;
+
+    fn characteristics() -> ConverterCharacteristics {
+        ConverterCharacteristics::ideal(
+            Power::new::<watt>(1000.),
+            ElectricPotential::new::<volt>(28.),
+        )
+        .with_efficiency(&[0.1, 0.5, 1.], &[0.6, 0.8, 0.9])
+        .with_regulation(
+            ElectricPotential::new::<volt>(29.),
+            ElectricPotential::new::<volt>(27.),
+        )
+    }
+
+    #[test]
+    fn ideal_converter_has_no_losses() {
+        let characteristics = ConverterCharacteristics::ideal(
+            Power::new::<watt>(1000.),
+            ElectricPotential::new::<volt>(115.),
+        );
+
+        assert_about_eq!(
+            characteristics
+                .input_power(Power::new::<watt>(400.))
+                .get::<watt>(),
+            400.
+        );
+        assert_about_eq!(
+            characteristics
+                .heat_dissipation(Power::new::<watt>(400.))
+                .get::<watt>(),
+            0.
+        );
+        assert_about_eq!(
+            characteristics
+                .output_potential(Power::new::<watt>(400.))
+                .get::<volt>(),
+            115.
+        );
+    }
+
+    #[test]
+    fn efficiency_is_interpolated_by_load() {
+        assert_about_eq!(
+            characteristics()
+                .efficiency(Power::new::<watt>(750.))
+                .get::<ratio>(),
+            0.85
+        );
+    }
+
+    #[test]
+    fn efficiency_remains_constant_beyond_the_given_loads() {
+        assert_about_eq!(
+            characteristics()
+                .efficiency(Power::new::<watt>(50.))
+                .get::<ratio>(),
+            0.6
+        );
+        assert_about_eq!(
+            characteristics()
+                .efficiency(Power::new::<watt>(2000.))
+                .get::<ratio>(),
+            0.9
+        );
+    }
+
+    #[test]
+    fn input_power_includes_conversion_losses() {
+        assert_about_eq!(
+            characteristics()
+                .input_power(Power::new::<watt>(500.))
+                .get::<watt>(),
+            625.
+        );
+    }
+
+    #[test]
+    fn lost_power_is_dissipated_as_heat() {
+        assert_about_eq!(
+            characteristics()
+                .heat_dissipation(Power::new::<watt>(500.))
+                .get::<watt>(),
+            125.
+        );
+    }
+
+    #[test]
+    fn without_output_power_no_power_is_consumed() {
+        assert_about_eq!(
+            characteristics()
+                .input_power(Power::new::<watt>(0.))
+                .get::<watt>(),
+            0.
+        );
+    }
+
+    #[test]
+    fn output_potential_droops_with_load() {
+        assert_about_eq!(
+            characteristics()
+                .output_potential(Power::new::<watt>(0.))
+                .get::<volt>(),
+            29.
+        );
+        assert_about_eq!(
+            characteristics()
+                .output_potential(Power::new::<watt>(500.))
+                .get::<volt>(),
+            28.
+        );
+        assert_about_eq!(
+            characteristics()
+                .output_potential(Power::new::<watt>(1000.))
+                .get::<volt>(),
+            27.
+        );
+    }
+}
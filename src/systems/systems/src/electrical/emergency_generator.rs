@@ -2,11 +2,11 @@ use std::time::Duration;
 
 use super::{
     consumption::PowerConsumptionReport, ElectricalStateWriter, Potential, PotentialOrigin,
-    PotentialSource, ProvideFrequency, ProvidePotential,
+    PotentialSource, ProvideFrequency, ProvidePotential, SourceResistance,
 };
 use crate::{
     shared::RamAirTurbineHydraulicLoopPressurised,
-    simulation::{SimulationElement, SimulatorWriter, UpdateContext},
+    simulation::{SimulationElement, SimulatorWriter, UpdateContext, VariableRegistry},
 };
 use uom::si::{electric_potential::volt, f64::*, frequency::hertz};
 
@@ -17,19 +17,27 @@ pub struct EmergencyGenerator {
     output_potential: ElectricPotential,
     time_since_start: Duration,
     starting_or_started: bool,
+    resistance: Option<SourceResistance>,
 }
 impl EmergencyGenerator {
-    pub fn new() -> EmergencyGenerator {
+    pub fn new(registry: &mut VariableRegistry) -> EmergencyGenerator {
         EmergencyGenerator {
-            writer: ElectricalStateWriter::new("EMER_GEN"),
+            writer: ElectricalStateWriter::new(registry, "EMER_GEN"),
             supplying: false,
             output_frequency: Frequency::new::<hertz>(0.),
             output_potential: ElectricPotential::new::<volt>(0.),
             time_since_start: Duration::from_secs(0),
             starting_or_started: false,
+            resistance: None,
         }
     }
 
+    /// Makes the output potential sag under load, instead of always being 115V.
+    pub fn with_resistance(mut self, resistance: SourceResistance) -> Self {
+        self.resistance = Some(resistance);
+        self
+    }
+
     pub fn update(
         &mut self,
         context: &UpdateContext,
@@ -78,7 +86,7 @@ impl PotentialSource for EmergencyGenerator {
 provide_frequency!(EmergencyGenerator, (390.0..=410.0));
 provide_potential!(EmergencyGenerator, (110.0..=120.0));
 impl SimulationElement for EmergencyGenerator {
-    fn process_power_consumption_report<T: PowerConsumptionReport>(&mut self, _report: &T) {
+    fn process_power_consumption_report<T: PowerConsumptionReport>(&mut self, report: &T) {
         self.output_frequency = if self.should_provide_output() {
             Frequency::new::<hertz>(400.)
         } else {
@@ -86,7 +94,16 @@ impl SimulationElement for EmergencyGenerator {
         };
 
         self.output_potential = if self.should_provide_output() {
-            ElectricPotential::new::<volt>(115.)
+            let nominal = ElectricPotential::new::<volt>(115.);
+            match self.resistance {
+                Some(resistance) => resistance
+                    .solve(
+                        nominal,
+                        report.total_consumption_of(PotentialOrigin::EmergencyGenerator),
+                    )
+                    .potential(),
+                None => nominal,
+            }
         } else {
             ElectricPotential::new::<volt>(0.)
         };
@@ -96,18 +113,17 @@ impl SimulationElement for EmergencyGenerator {
         self.writer.write_alternating(self, writer);
     }
 }
-impl Default for EmergencyGenerator {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[cfg(test)]
 mod emergency_generator_tests {
     use super::*;
-    use crate::simulation::{
-        test::SimulationTestBed, Aircraft, SimulationElementVisitor, UpdateContext,
+    use crate::{
+        electrical::{
+            consumption::{PowerConsumer, SuppliedPower},
+            ElectricalBusType,
+        },
+        simulation::{test::SimulationTestBed, Aircraft, SimulationElementVisitor, UpdateContext},
     };
+    use uom::si::{electrical_resistance::ohm, power::watt};
 
     struct EmergencyGeneratorTestBed {
         test_bed: SimulationTestBed,
@@ -119,6 +135,10 @@ mod emergency_generator_tests {
             }
         }
 
+        fn registry(&mut self) -> &mut VariableRegistry {
+            self.test_bed.registry()
+        }
+
         fn run_aircraft(&mut self, aircraft: &mut impl Aircraft, delta: Duration) {
             self.test_bed.set_delta(delta);
             self.test_bed.run_aircraft(aircraft);
@@ -131,6 +151,10 @@ mod emergency_generator_tests {
         fn potential_is_normal(&mut self) -> bool {
             self.test_bed.read_bool("ELEC_EMER_GEN_POTENTIAL_NORMAL")
         }
+
+        fn potential(&mut self) -> ElectricPotential {
+            ElectricPotential::new::<volt>(self.test_bed.read_f64("ELEC_EMER_GEN_POTENTIAL"))
+        }
     }
 
     struct TestHydraulicSystem {
@@ -156,17 +180,31 @@ mod emergency_generator_tests {
     struct TestAircraft {
         emer_gen: EmergencyGenerator,
         hydraulic: TestHydraulicSystem,
+        consumer: PowerConsumer,
         generator_output_within_normal_parameters_before_processing_power_consumption_report: bool,
     }
     impl TestAircraft {
-        fn new() -> Self {
+        fn new(registry: &mut VariableRegistry) -> Self {
             Self {
-                emer_gen: EmergencyGenerator::new(),
+                emer_gen: EmergencyGenerator::new(registry),
                 hydraulic: TestHydraulicSystem::new(),
+                consumer: PowerConsumer::from(ElectricalBusType::AlternatingCurrentEssential),
                 generator_output_within_normal_parameters_before_processing_power_consumption_report: false,
             }
         }
 
+        fn with_resistance(mut self) -> Self {
+            self.emer_gen = self.emer_gen.with_resistance(SourceResistance::new(
+                ElectricalResistance::new::<ohm>(0.05),
+                ElectricalResistance::new::<ohm>(0.05),
+            ));
+            self
+        }
+
+        fn power_demand(&mut self, power: Power) {
+            self.consumer.demand(power);
+        }
+
         fn emer_gen_is_powered(&self) -> bool {
             self.emer_gen.is_powered()
         }
@@ -202,10 +240,21 @@ mod emergency_generator_tests {
 
             self.generator_output_within_normal_parameters_before_processing_power_consumption_report = self.emer_gen.output_within_normal_parameters();
         }
+
+        fn get_supplied_power(&mut self) -> SuppliedPower {
+            let mut supplied_power = SuppliedPower::new();
+            supplied_power.add(
+                ElectricalBusType::AlternatingCurrentEssential,
+                self.emer_gen.output(),
+            );
+
+            supplied_power
+        }
     }
     impl SimulationElement for TestAircraft {
         fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
             self.emer_gen.accept(visitor);
+            self.consumer.accept(visitor);
 
             visitor.visit(self);
         }
@@ -213,8 +262,8 @@ mod emergency_generator_tests {
 
     #[test]
     fn when_shutdown_has_no_output() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
 
@@ -223,8 +272,8 @@ mod emergency_generator_tests {
 
     #[test]
     fn when_started_provides_output() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         aircraft.attempt_emer_gen_start();
         test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
@@ -234,8 +283,8 @@ mod emergency_generator_tests {
 
     #[test]
     fn when_started_without_hydraulic_pressure_is_unpowered() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         aircraft.attempt_emer_gen_start();
         aircraft.set_rat_hydraulic_loop_pressurised(false);
@@ -246,8 +295,8 @@ mod emergency_generator_tests {
 
     #[test]
     fn when_shutdown_frequency_not_normal() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
 
@@ -256,8 +305,8 @@ mod emergency_generator_tests {
 
     #[test]
     fn when_started_frequency_normal() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         aircraft.attempt_emer_gen_start();
         test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
@@ -267,8 +316,8 @@ mod emergency_generator_tests {
 
     #[test]
     fn when_shutdown_potential_not_normal() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
 
@@ -277,19 +326,32 @@ mod emergency_generator_tests {
 
     #[test]
     fn when_started_potential_normal() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
+
+        aircraft.attempt_emer_gen_start();
+        test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
+
+        assert!(test_bed.potential_is_normal());
+    }
+
+    #[test]
+    fn when_started_with_resistance_and_demand_potential_sags() {
+        let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_resistance();
 
         aircraft.attempt_emer_gen_start();
+        aircraft.power_demand(Power::new::<watt>(5000.));
         test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
 
+        assert!(test_bed.potential() < ElectricPotential::new::<volt>(115.));
         assert!(test_bed.potential_is_normal());
     }
 
     #[test]
     fn output_not_within_normal_parameters_when_shutdown() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
 
@@ -299,8 +361,8 @@ mod emergency_generator_tests {
 
     #[test]
     fn output_within_normal_parameters_when_started() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         aircraft.attempt_emer_gen_start();
         test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
@@ -318,8 +380,8 @@ mod emergency_generator_tests {
         // supplies potential but the previous tick's frequency and potential are still normal.
         // With this test we ensure that a generator which is no longer supplying power is
         // immediately noticed.
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = EmergencyGeneratorTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         aircraft.attempt_emer_gen_start();
         test_bed.run_aircraft(&mut aircraft, Duration::from_secs(100));
@@ -334,8 +396,8 @@ mod emergency_generator_tests {
 
     #[test]
     fn writes_its_state() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = SimulationTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
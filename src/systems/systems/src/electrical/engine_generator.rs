@@ -1,14 +1,19 @@
 use super::{
-    consumption::PowerConsumptionReport, ElectricalStateWriter, EngineGeneratorPushButtons,
-    Potential, PotentialOrigin, PotentialSource, ProvideFrequency, ProvideLoad, ProvidePotential,
+    consumption::PowerConsumptionReport, ControlledGenerator, ElectricalStateWriter,
+    EngineGeneratorPushButtons, Potential, PotentialOrigin, PotentialSource, ProvideFrequency,
+    ProvideLoad, ProvidePotential, SourceResistance,
 };
 use crate::{
+    failures::{Failure, FailureType},
     shared::{calculate_towards_target_temperature, EngineCorrectedN2, EngineFirePushButtons},
-    simulation::{SimulationElement, SimulationElementVisitor, SimulatorWriter, UpdateContext},
+    simulation::{
+        ChangeDetector, EventEmitter, EventKind, SimulationElement, SimulationElementVisitor,
+        SimulatorWriter, Snapshot, StateDump, UpdateContext, VariableIdentifier, VariableRegistry,
+    },
 };
 use std::cmp::min;
 use uom::si::{
-    electric_potential::volt, f64::*, frequency::hertz, power::watt, ratio::percent,
+    electric_potential::volt, f64::*, frequency::hertz, power::watt, pressure::psi, ratio::percent,
     thermodynamic_temperature::degree_celsius,
 };
 
@@ -21,19 +26,34 @@ pub struct EngineGenerator {
     output_frequency: Frequency,
     output_potential: ElectricPotential,
     load: Ratio,
+    resistance: Option<SourceResistance>,
 }
 impl EngineGenerator {
-    pub fn new(number: usize) -> EngineGenerator {
+    pub fn new(registry: &mut VariableRegistry, number: usize) -> EngineGenerator {
         EngineGenerator {
-            writer: ElectricalStateWriter::new(&format!("ENG_GEN_{}", number)),
+            writer: ElectricalStateWriter::new(registry, &format!("ENG_GEN_{}", number)),
             number,
-            idg: IntegratedDriveGenerator::new(number),
+            idg: IntegratedDriveGenerator::new(registry, number),
             output_frequency: Frequency::new::<hertz>(0.),
             output_potential: ElectricPotential::new::<volt>(0.),
             load: Ratio::new::<percent>(0.),
+            resistance: None,
         }
     }
 
+    /// Makes the output potential sag under load, instead of always being 115V.
+    pub fn with_resistance(mut self, resistance: SourceResistance) -> Self {
+        self.resistance = Some(resistance);
+        self
+    }
+
+    /// Makes the IDG disconnect itself once its oil overheats, instead of
+    /// leaving the disconnection to the crew.
+    pub fn with_automatic_idg_disconnect(mut self) -> Self {
+        self.idg.automatically_disconnects = true;
+        self
+    }
+
     pub fn update(
         &mut self,
         context: &UpdateContext,
@@ -42,15 +62,24 @@ impl EngineGenerator {
         fire_buttons: &impl EngineFirePushButtons,
     ) {
         self.idg
-            .update(context, engine, generator_buttons, fire_buttons);
+            .update(context, engine, generator_buttons, fire_buttons, self.load);
+    }
+
+    /// Indicates if the FAULT light of the IDG push button should illuminate.
+    pub fn idg_has_fault(&self) -> bool {
+        self.idg.has_fault()
     }
 
     /// Indicates if the provided electricity's potential and frequency
-    /// are within normal parameters. Use this to decide if the
-    /// generator contactor should close.
-    /// Load shouldn't be taken into account, as overloading causes an
+    /// are within normal parameters at this moment.
+    /// Don't use this to decide if the generator contactor should close:
+    /// the [`GeneratorControlUnit`] does so, taking into account the delays
+    /// of its protections.
+    /// Load isn't taken into account, as overloading causes an
     /// overtemperature which over time will trigger a mechanical
     /// disconnect of the generator.
+    ///
+    /// [`GeneratorControlUnit`]: struct.GeneratorControlUnit.html
     pub fn output_within_normal_parameters(&self) -> bool {
         self.should_provide_output() && self.frequency_normal() && self.potential_normal()
     }
@@ -74,6 +103,11 @@ impl PotentialSource for EngineGenerator {
 provide_potential!(EngineGenerator, (110.0..=120.0));
 provide_frequency!(EngineGenerator, (390.0..=410.0));
 provide_load!(EngineGenerator);
+impl ControlledGenerator for EngineGenerator {
+    fn is_driven_at_operating_speed(&self) -> bool {
+        self.should_provide_output()
+    }
+}
 impl SimulationElement for EngineGenerator {
     fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
         self.idg.accept(visitor);
@@ -88,15 +122,20 @@ impl SimulationElement for EngineGenerator {
             Frequency::new::<hertz>(0.)
         };
 
+        let consumption =
+            report.total_consumption_of(PotentialOrigin::EngineGenerator(self.number));
+
         self.output_potential = if self.should_provide_output() {
-            ElectricPotential::new::<volt>(115.)
+            let nominal = ElectricPotential::new::<volt>(115.);
+            match self.resistance {
+                Some(resistance) => resistance.solve(nominal, consumption).potential(),
+                None => nominal,
+            }
         } else {
             ElectricPotential::new::<volt>(0.)
         };
 
-        let power_consumption = report
-            .total_consumption_of(PotentialOrigin::EngineGenerator(self.number))
-            .get::<watt>();
+        let power_consumption = consumption.get::<watt>();
         let power_factor_correction = 0.8;
         let maximum_true_power = 90000.;
         self.load = Ratio::new::<percent>(
@@ -110,30 +149,68 @@ impl SimulationElement for EngineGenerator {
 }
 
 struct IntegratedDriveGenerator {
-    oil_outlet_temperature_id: String,
+    oil_outlet_temperature_id: VariableIdentifier,
     oil_outlet_temperature: ThermodynamicTemperature,
-    is_connected_id: String,
+    is_connected_id: VariableIdentifier,
     connected: bool,
+    connected_change: ChangeDetector<bool>,
+    disconnect_cause: &'static str,
+    automatically_disconnects: bool,
     activated: bool,
     number: usize,
+    overheat_failure: Failure,
+    oil_leak_failure: Failure,
+    oil_pressure_id: VariableIdentifier,
+    oil_pressure: Pressure,
+    corrected_n2: Ratio,
 
     time_above_threshold_in_milliseconds: u64,
 }
 impl IntegratedDriveGenerator {
     pub const ENGINE_N2_POWER_UP_OUTPUT_THRESHOLD: f64 = 58.;
     pub const ENGINE_N2_POWER_DOWN_OUTPUT_THRESHOLD: f64 = 56.;
-
-    fn new(number: usize) -> IntegratedDriveGenerator {
+    const OVERHEAT_TEMPERATURE_DELTA_DEGREES_CELSIUS: f64 = 150.;
+    const OVERHEAT_THRESHOLD_DEGREES_CELSIUS: f64 = 185.;
+    /// The temperature rise of the oil above ambient for every percent of engine N2.
+    /// At maximum N2 on a hot day and at rated load the oil remains below the
+    /// overheat threshold.
+    const N2_TEMPERATURE_DELTA_DEGREES_CELSIUS_PER_PERCENT: f64 = 1.;
+    /// The temperature rise of the oil at the generator's rated load. As losses
+    /// increase with the square of the current, overloading heats the oil quickly.
+    const RATED_LOAD_TEMPERATURE_DELTA_DEGREES_CELSIUS: f64 = 30.;
+    const NOMINAL_OIL_PRESSURE_PSI: f64 = 180.;
+    const LOW_OIL_PRESSURE_THRESHOLD_PSI: f64 = 140.;
+    const OIL_LEAK_PRESSURE_PSI: f64 = 50.;
+    /// The charge pump reaches its nominal pressure at this engine speed.
+    const ENGINE_N2_NOMINAL_OIL_PRESSURE: f64 = 10.;
+    /// Faults are inhibited below this engine speed.
+    const ENGINE_N2_FAULT_INHIBIT_THRESHOLD: f64 = 14.;
+
+    fn new(registry: &mut VariableRegistry, number: usize) -> IntegratedDriveGenerator {
         IntegratedDriveGenerator {
-            oil_outlet_temperature_id: format!(
+            oil_outlet_temperature_id: registry.get(&format!(
                 "ELEC_ENG_GEN_{}_IDG_OIL_OUTLET_TEMPERATURE",
                 number
-            ),
+            )),
             oil_outlet_temperature: ThermodynamicTemperature::new::<degree_celsius>(0.),
-            is_connected_id: format!("ELEC_ENG_GEN_{}_IDG_IS_CONNECTED", number),
+            is_connected_id: registry.get(&format!("ELEC_ENG_GEN_{}_IDG_IS_CONNECTED", number)),
             connected: true,
+            connected_change: ChangeDetector::new(true),
+            disconnect_cause: "IDG push button released",
+            automatically_disconnects: false,
             activated: true,
             number,
+            overheat_failure: Failure::new(
+                registry,
+                FailureType::IntegratedDriveGeneratorOverheat(number),
+            ),
+            oil_leak_failure: Failure::new(
+                registry,
+                FailureType::IntegratedDriveGeneratorOilLeak(number),
+            ),
+            oil_pressure_id: registry.get(&format!("ELEC_ENG_GEN_{}_IDG_OIL_PRESSURE", number)),
+            oil_pressure: Pressure::new::<psi>(0.),
+            corrected_n2: Ratio::new::<percent>(0.),
 
             time_above_threshold_in_milliseconds: 0,
         }
@@ -145,20 +222,68 @@ impl IntegratedDriveGenerator {
         engine: &impl EngineCorrectedN2,
         generator_buttons: &impl EngineGeneratorPushButtons,
         fire_buttons: &impl EngineFirePushButtons,
+        generator_load: Ratio,
     ) {
-        if generator_buttons.idg_push_button_is_released(self.number) {
-            // The IDG cannot be reconnected.
-            self.connected = false;
+        // The IDG cannot be reconnected.
+        if self.connected && generator_buttons.idg_push_button_is_released(self.number) {
+            self.disconnect("IDG push button released");
+        } else if self.connected && self.automatically_disconnects && self.is_overheating() {
+            self.disconnect("IDG oil overheat");
         }
 
         self.activated = generator_buttons.engine_gen_push_button_is_on(self.number)
             && !fire_buttons.is_released(self.number);
 
+        self.corrected_n2 = engine.corrected_n2();
         self.update_stable_time(context, engine.corrected_n2());
         self.update_temperature(
             context,
-            self.get_target_temperature(context, engine.corrected_n2()),
+            self.get_target_temperature(context, engine.corrected_n2(), generator_load),
         );
+        self.oil_pressure = self.calculate_oil_pressure(engine.corrected_n2());
+    }
+
+    fn disconnect(&mut self, cause: &'static str) {
+        self.connected = false;
+        self.disconnect_cause = cause;
+    }
+
+    fn calculate_oil_pressure(&self, corrected_n2: Ratio) -> Pressure {
+        if !self.connected {
+            return Pressure::new::<psi>(0.);
+        }
+
+        let nominal = if self.oil_leak_failure.is_active() {
+            IntegratedDriveGenerator::OIL_LEAK_PRESSURE_PSI
+        } else {
+            IntegratedDriveGenerator::NOMINAL_OIL_PRESSURE_PSI
+        };
+
+        Pressure::new::<psi>(
+            nominal
+                * (corrected_n2.get::<percent>()
+                    / IntegratedDriveGenerator::ENGINE_N2_NOMINAL_OIL_PRESSURE)
+                    .min(1.),
+        )
+    }
+
+    fn is_overheating(&self) -> bool {
+        self.oil_outlet_temperature
+            > ThermodynamicTemperature::new::<degree_celsius>(
+                IntegratedDriveGenerator::OVERHEAT_THRESHOLD_DEGREES_CELSIUS,
+            )
+    }
+
+    fn has_low_oil_pressure(&self) -> bool {
+        self.connected
+            && self.oil_pressure
+                < Pressure::new::<psi>(IntegratedDriveGenerator::LOW_OIL_PRESSURE_THRESHOLD_PSI)
+    }
+
+    fn has_fault(&self) -> bool {
+        self.corrected_n2
+            >= Ratio::new::<percent>(IntegratedDriveGenerator::ENGINE_N2_FAULT_INHIBIT_THRESHOLD)
+            && (self.is_overheating() || self.has_low_oil_pressure())
     }
 
     fn provides_stable_power_output(&self) -> bool {
@@ -225,27 +350,94 @@ impl IntegratedDriveGenerator {
         &self,
         context: &UpdateContext,
         corrected_n2: Ratio,
+        generator_load: Ratio,
     ) -> ThermodynamicTemperature {
         if !self.connected {
             return context.ambient_temperature();
         }
 
-        let mut target_idg = corrected_n2.get::<percent>() * 1.8;
+        let mut target_idg = corrected_n2.get::<percent>()
+            * IntegratedDriveGenerator::N2_TEMPERATURE_DELTA_DEGREES_CELSIUS_PER_PERCENT;
+        target_idg += IntegratedDriveGenerator::RATED_LOAD_TEMPERATURE_DELTA_DEGREES_CELSIUS
+            * (generator_load.get::<percent>() / 100.).powi(2);
         let ambient_temperature = context.ambient_temperature().get::<degree_celsius>();
         target_idg += ambient_temperature;
 
         // TODO improve this function with feedback @komp provides.
 
+        if self.overheat_failure.is_active() {
+            target_idg += IntegratedDriveGenerator::OVERHEAT_TEMPERATURE_DELTA_DEGREES_CELSIUS;
+        }
+
         ThermodynamicTemperature::new::<degree_celsius>(target_idg)
     }
 }
 impl SimulationElement for IntegratedDriveGenerator {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.overheat_failure.accept(visitor);
+        self.oil_leak_failure.accept(visitor);
+
+        visitor.visit(self);
+    }
+
     fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_f64(
+        writer.write_quantity(
             &self.oil_outlet_temperature_id,
-            self.oil_outlet_temperature.get::<degree_celsius>(),
+            self.oil_outlet_temperature,
+            degree_celsius,
         );
         writer.write_bool(&self.is_connected_id, self.connected);
+        writer.write_quantity(&self.oil_pressure_id, self.oil_pressure, psi);
+    }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        snapshot.write_f64(
+            &format!("ELEC_ENG_GEN_{}_IDG_OIL_OUTLET_TEMPERATURE", self.number),
+            self.oil_outlet_temperature.get::<degree_celsius>(),
+        );
+        snapshot.write_bool(
+            &format!("ELEC_ENG_GEN_{}_IDG_IS_CONNECTED", self.number),
+            self.connected,
+        );
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(temperature) = snapshot.read_f64(&format!(
+            "ELEC_ENG_GEN_{}_IDG_OIL_OUTLET_TEMPERATURE",
+            self.number
+        )) {
+            self.oil_outlet_temperature =
+                ThermodynamicTemperature::new::<degree_celsius>(temperature);
+        }
+        if let Some(connected) =
+            snapshot.read_bool(&format!("ELEC_ENG_GEN_{}_IDG_IS_CONNECTED", self.number))
+        {
+            self.connected = connected;
+            self.connected_change = ChangeDetector::new(connected);
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state
+            .node("ELEC")
+            .node("IDG")
+            .node(&self.number.to_string());
+        node.write_f64(
+            "OIL_OUTLET_TEMPERATURE",
+            self.oil_outlet_temperature.get::<degree_celsius>(),
+        );
+        node.write_bool("IS_CONNECTED", self.connected);
+    }
+
+    fn emit_events(&mut self, events: &mut EventEmitter) {
+        if self.connected_change.detect(self.connected).is_some() {
+            // The IDG cannot be reconnected, thus it can only become disconnected.
+            events.emit_with_cause(
+                &format!("ELEC/IDG/{}", self.number),
+                EventKind::Disconnected,
+                self.disconnect_cause,
+            );
+        }
     }
 }
 
@@ -327,8 +519,10 @@ mod tests {
                 consumption::{PowerConsumer, SuppliedPower},
                 ElectricalBusType,
             },
-            simulation::{test::SimulationTestBed, Aircraft},
+            simulation::{test::SimulationTestBed, Aircraft, EventBus},
         };
+        use std::time::Duration;
+        use uom::si::electrical_resistance::ohm;
 
         struct EngineGeneratorTestBed {
             test_bed: SimulationTestBed,
@@ -340,6 +534,10 @@ mod tests {
                 }
             }
 
+            fn registry(&mut self) -> &mut VariableRegistry {
+                self.test_bed.registry()
+            }
+
             fn run_aircraft(&mut self, aircraft: &mut impl Aircraft) {
                 self.test_bed.run_aircraft(aircraft);
             }
@@ -359,6 +557,14 @@ mod tests {
             fn load(&mut self) -> Ratio {
                 Ratio::new::<percent>(self.test_bed.read_f64("ELEC_ENG_GEN_1_LOAD"))
             }
+
+            fn potential(&mut self) -> ElectricPotential {
+                ElectricPotential::new::<volt>(self.test_bed.read_f64("ELEC_ENG_GEN_1_POTENTIAL"))
+            }
+
+            fn events(&self) -> &EventBus {
+                self.test_bed.events()
+            }
         }
 
         struct TestAircraft {
@@ -372,9 +578,9 @@ mod tests {
                 bool,
         }
         impl TestAircraft {
-            fn new(running: bool) -> Self {
+            fn new(registry: &mut VariableRegistry, running: bool) -> Self {
                 Self {
-                    engine_gen: EngineGenerator::new(1),
+                    engine_gen: EngineGenerator::new(registry, 1),
                     running,
                     gen_push_button_on: true,
                     idg_push_button_released: false,
@@ -384,12 +590,25 @@ mod tests {
                 }
             }
 
-            fn with_shutdown_engine() -> Self {
-                TestAircraft::new(false)
+            fn with_shutdown_engine(registry: &mut VariableRegistry) -> Self {
+                TestAircraft::new(registry, false)
+            }
+
+            fn with_running_engine(registry: &mut VariableRegistry) -> Self {
+                TestAircraft::new(registry, true)
             }
 
-            fn with_running_engine() -> Self {
-                TestAircraft::new(true)
+            fn with_resistance(mut self) -> Self {
+                self.engine_gen = self.engine_gen.with_resistance(SourceResistance::new(
+                    ElectricalResistance::new::<ohm>(0.005),
+                    ElectricalResistance::new::<ohm>(0.005),
+                ));
+                self
+            }
+
+            fn with_automatic_idg_disconnect(mut self) -> Self {
+                self.engine_gen = self.engine_gen.with_automatic_idg_disconnect();
+                self
             }
 
             fn disconnect_idg(&mut self) {
@@ -466,8 +685,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_provides_output() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -476,8 +695,8 @@ mod tests {
 
         #[test]
         fn when_engine_shutdown_provides_no_output() {
-            let mut aircraft = TestAircraft::with_shutdown_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_shutdown_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -486,19 +705,73 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_idg_disconnected_provides_no_output() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
+
+            aircraft.disconnect_idg();
+            test_bed.run_aircraft(&mut aircraft);
+
+            assert!(!aircraft.generator_is_powered());
+        }
+
+        #[test]
+        fn emits_event_once_when_idg_disconnected() {
+            let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
+            test_bed.run_aircraft(&mut aircraft);
 
             aircraft.disconnect_idg();
             test_bed.run_aircraft(&mut aircraft);
+            test_bed.run_aircraft(&mut aircraft);
+
+            let events: Vec<_> = test_bed.events().iter_from("ELEC/IDG/1").collect();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].kind(), &EventKind::Disconnected);
+            assert_eq!(events[0].tick(), 2);
+        }
+
+        #[test]
+        fn overloaded_generator_overheats_idg() {
+            let mut test_bed = EngineGeneratorTestBed::new();
+            test_bed.test_bed.set_delta(Duration::from_secs(1_000));
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
+
+            aircraft.power_demand(Power::new::<watt>(250000.));
+            for _ in 0..4 {
+                test_bed.run_aircraft(&mut aircraft);
+            }
+
+            assert!(aircraft.engine_gen.idg_has_fault());
+            assert!(aircraft.generator_is_powered());
+        }
+
+        #[test]
+        fn overloaded_generator_with_automatic_idg_disconnect_stops_providing_output() {
+            let mut test_bed = EngineGeneratorTestBed::new();
+            test_bed.test_bed.set_delta(Duration::from_secs(1_000));
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry())
+                .with_automatic_idg_disconnect();
+
+            aircraft.power_demand(Power::new::<watt>(250000.));
+            for _ in 0..4 {
+                test_bed.run_aircraft(&mut aircraft);
+            }
 
             assert!(!aircraft.generator_is_powered());
+            assert_eq!(
+                test_bed
+                    .events()
+                    .find("ELEC/IDG/1", &EventKind::Disconnected)
+                    .unwrap()
+                    .cause(),
+                Some("IDG oil overheat")
+            );
         }
 
         #[test]
         fn when_engine_running_but_generator_off_provides_no_output() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.gen_push_button_off();
             test_bed.run_aircraft(&mut aircraft);
@@ -508,8 +781,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_fire_push_button_released_provides_no_output() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.release_fire_push_button();
             test_bed.run_aircraft(&mut aircraft);
@@ -519,8 +792,8 @@ mod tests {
 
         #[test]
         fn when_engine_shutdown_frequency_not_normal() {
-            let mut aircraft = TestAircraft::with_shutdown_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_shutdown_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -529,8 +802,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_idg_disconnected_frequency_not_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.disconnect_idg();
             test_bed.run_aircraft(&mut aircraft);
@@ -540,8 +813,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_generator_off_frequency_not_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.gen_push_button_off();
             test_bed.run_aircraft(&mut aircraft);
@@ -551,8 +824,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_fire_push_button_released_frequency_not_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.release_fire_push_button();
             test_bed.run_aircraft(&mut aircraft);
@@ -562,8 +835,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_frequency_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -572,8 +845,8 @@ mod tests {
 
         #[test]
         fn when_engine_shutdown_potential_not_normal() {
-            let mut aircraft = TestAircraft::with_shutdown_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_shutdown_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -582,8 +855,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_idg_disconnected_potential_not_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.disconnect_idg();
             test_bed.run_aircraft(&mut aircraft);
@@ -593,8 +866,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_generator_off_provides_potential_not_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.gen_push_button_off();
             test_bed.run_aircraft(&mut aircraft);
@@ -604,8 +877,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_fire_push_button_released_potential_not_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.release_fire_push_button();
             test_bed.run_aircraft(&mut aircraft);
@@ -615,8 +888,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_potential_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -625,8 +898,8 @@ mod tests {
 
         #[test]
         fn when_engine_shutdown_has_no_load() {
-            let mut aircraft = TestAircraft::with_shutdown_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_shutdown_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -635,8 +908,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_idg_disconnected_has_no_load() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.disconnect_idg();
             test_bed.run_aircraft(&mut aircraft);
@@ -646,8 +919,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_generator_off_has_no_load() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.gen_push_button_off();
             test_bed.run_aircraft(&mut aircraft);
@@ -657,8 +930,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_fire_push_button_released_has_no_load() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.release_fire_push_button();
             test_bed.run_aircraft(&mut aircraft);
@@ -668,8 +941,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_but_potential_unused_has_no_load() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -678,8 +951,8 @@ mod tests {
 
         #[test]
         fn when_engine_running_and_potential_used_has_load() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.power_demand(Power::new::<watt>(50000.));
             test_bed.run_aircraft(&mut aircraft);
@@ -689,8 +962,8 @@ mod tests {
 
         #[test]
         fn when_load_below_maximum_it_is_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.power_demand(Power::new::<watt>(90000. / 0.8));
             test_bed.run_aircraft(&mut aircraft);
@@ -700,8 +973,8 @@ mod tests {
 
         #[test]
         fn when_load_exceeds_maximum_not_normal() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.power_demand(Power::new::<watt>((90000. / 0.8) + 1.));
             test_bed.run_aircraft(&mut aircraft);
@@ -711,8 +984,8 @@ mod tests {
 
         #[test]
         fn output_within_normal_parameters_when_load_exceeds_maximum() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             aircraft.power_demand(Power::new::<watt>((90000. / 0.8) + 1.));
             test_bed.run_aircraft(&mut aircraft);
@@ -722,8 +995,8 @@ mod tests {
 
         #[test]
         fn output_not_within_normal_parameters_when_engine_not_running() {
-            let mut aircraft = TestAircraft::with_shutdown_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_shutdown_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -732,8 +1005,8 @@ mod tests {
 
         #[test]
         fn output_within_normal_parameters_when_engine_running() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -749,8 +1022,8 @@ mod tests {
             // supplies potential but the previous tick's frequency and potential are still normal.
             // With this test we ensure that an IDG which is no longer supplying power is
             // immediately noticed and doesn't require another tick.
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
             test_bed.run_aircraft(&mut aircraft);
 
             aircraft.shutdown_engine();
@@ -759,10 +1032,35 @@ mod tests {
             assert!(!aircraft.generator_output_within_normal_parameters_before_processing_power_consumption_report());
         }
 
+        #[test]
+        fn with_resistance_potential_sags_under_load() {
+            let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft =
+                TestAircraft::with_running_engine(test_bed.registry()).with_resistance();
+
+            aircraft.power_demand(Power::new::<watt>(20000.));
+            test_bed.run_aircraft(&mut aircraft);
+
+            assert!(test_bed.potential() < ElectricPotential::new::<volt>(115.));
+            assert!(test_bed.potential_is_normal());
+        }
+
+        #[test]
+        fn with_resistance_and_excessive_load_potential_is_not_normal() {
+            let mut test_bed = EngineGeneratorTestBed::new();
+            let mut aircraft =
+                TestAircraft::with_running_engine(test_bed.registry()).with_resistance();
+
+            aircraft.power_demand(Power::new::<watt>(100000.));
+            test_bed.run_aircraft(&mut aircraft);
+
+            assert!(!test_bed.potential_is_normal());
+        }
+
         #[test]
         fn writes_its_state() {
-            let mut aircraft = TestAircraft::with_running_engine();
             let mut test_bed = SimulationTestBed::new();
+            let mut aircraft = TestAircraft::with_running_engine(test_bed.registry());
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -780,32 +1078,55 @@ mod tests {
         use crate::simulation::test::SimulationTestBed;
 
         use super::*;
+        use ntest::assert_about_eq;
         use std::time::Duration;
+        use uom::si::temperature_interval;
 
-        fn idg() -> IntegratedDriveGenerator {
-            IntegratedDriveGenerator::new(1)
+        fn idg(registry: &mut VariableRegistry) -> IntegratedDriveGenerator {
+            IntegratedDriveGenerator::new(registry, 1)
+        }
+
+        fn run_connected(
+            test_bed: &mut SimulationTestBed,
+            idg: &mut IntegratedDriveGenerator,
+            corrected_n2_percent: f64,
+            generator_load_percent: f64,
+        ) {
+            test_bed.run(idg, |element, context| {
+                element.update(
+                    context,
+                    &TestEngine::new(Ratio::new::<percent>(corrected_n2_percent)),
+                    &TestOverhead::new(true, false),
+                    &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(generator_load_percent),
+                )
+            });
         }
 
         #[test]
         fn writes_its_state() {
-            let mut idg = idg();
             let mut test_bed = SimulationTestBed::new();
+            let mut idg = idg(test_bed.registry());
 
             test_bed.run_without_update(&mut idg);
 
             assert!(test_bed.contains_key("ELEC_ENG_GEN_1_IDG_OIL_OUTLET_TEMPERATURE"));
             assert!(test_bed.contains_key("ELEC_ENG_GEN_1_IDG_IS_CONNECTED"));
+            assert!(test_bed.contains_key("ELEC_ENG_GEN_1_IDG_OIL_PRESSURE"));
         }
 
         #[test]
         fn starts_unstable() {
-            assert_eq!(idg().provides_stable_power_output(), false);
+            assert_eq!(
+                idg(&mut VariableRegistry::new()).provides_stable_power_output(),
+                false
+            );
         }
 
         #[test]
         fn becomes_stable_once_engine_above_threshold_for_500_milliseconds() {
-            let mut idg = idg();
             let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(500));
+            let mut idg = idg(test_bed.registry());
 
             test_bed.run(&mut idg, |element, context| {
                 element.update(
@@ -813,6 +1134,7 @@ mod tests {
                     &TestEngine::new(Ratio::new::<percent>(80.)),
                     &TestOverhead::new(true, false),
                     &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
                 )
             });
 
@@ -821,8 +1143,8 @@ mod tests {
 
         #[test]
         fn does_not_become_stable_before_engine_above_threshold_for_500_milliseconds() {
-            let mut idg = idg();
             let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(499));
+            let mut idg = idg(test_bed.registry());
 
             test_bed.run(&mut idg, |element, context| {
                 element.update(
@@ -830,6 +1152,7 @@ mod tests {
                     &TestEngine::new(Ratio::new::<percent>(80.)),
                     &TestOverhead::new(true, false),
                     &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
                 )
             });
 
@@ -838,14 +1161,15 @@ mod tests {
 
         #[test]
         fn cannot_reconnect_once_disconnected() {
-            let mut idg = idg();
             let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(500));
+            let mut idg = idg(test_bed.registry());
             test_bed.run(&mut idg, |element, context| {
                 element.update(
                     context,
                     &TestEngine::new(Ratio::new::<percent>(80.)),
                     &TestOverhead::new(true, true),
                     &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
                 )
             });
 
@@ -855,6 +1179,7 @@ mod tests {
                     &TestEngine::new(Ratio::new::<percent>(80.)),
                     &TestOverhead::new(true, false),
                     &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
                 )
             });
 
@@ -863,9 +1188,9 @@ mod tests {
 
         #[test]
         fn running_engine_warms_up_idg() {
-            let mut idg = idg();
-            let starting_temperature = idg.oil_outlet_temperature;
             let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(10));
+            let mut idg = idg(test_bed.registry());
+            let starting_temperature = idg.oil_outlet_temperature;
 
             test_bed.run(&mut idg, |element, context| {
                 element.update(
@@ -873,17 +1198,51 @@ mod tests {
                     &TestEngine::new(Ratio::new::<percent>(80.)),
                     &TestOverhead::new(true, false),
                     &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
                 )
             });
 
             assert!(idg.oil_outlet_temperature > starting_temperature);
         }
 
+        #[test]
+        fn overheat_failure_heats_idg_beyond_normal_temperature() {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut idg = idg(test_bed.registry());
+            let mut failed_idg = self::idg(test_bed.registry());
+            test_bed.run(&mut idg, |element, context| {
+                element.update(
+                    context,
+                    &TestEngine::new(Ratio::new::<percent>(80.)),
+                    &TestOverhead::new(true, false),
+                    &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
+                )
+            });
+
+            test_bed.write_bool("FAILURE_ENG_GEN_1_IDG_OVERHEAT", true);
+            test_bed.run(&mut failed_idg, |element, context| {
+                element.update(
+                    context,
+                    &TestEngine::new(Ratio::new::<percent>(80.)),
+                    &TestOverhead::new(true, false),
+                    &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
+                )
+            });
+
+            assert!(
+                failed_idg.oil_outlet_temperature
+                    > idg.oil_outlet_temperature
+                        + TemperatureInterval::new::<temperature_interval::degree_celsius>(50.)
+            );
+        }
+
         #[test]
         fn running_engine_does_not_warm_up_idg_when_disconnected() {
-            let mut idg = idg();
-            let starting_temperature = idg.oil_outlet_temperature;
             let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(10));
+            let mut idg = idg(test_bed.registry());
+            let starting_temperature = idg.oil_outlet_temperature;
 
             test_bed.run(&mut idg, |element, context| {
                 element.update(
@@ -891,6 +1250,7 @@ mod tests {
                     &TestEngine::new(Ratio::new::<percent>(80.)),
                     &TestOverhead::new(true, true),
                     &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
                 )
             });
 
@@ -899,8 +1259,8 @@ mod tests {
 
         #[test]
         fn shutdown_engine_cools_down_idg() {
-            let mut idg = idg();
             let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(10));
+            let mut idg = idg(test_bed.registry());
 
             test_bed.run(&mut idg, |element, context| {
                 element.update(
@@ -908,6 +1268,7 @@ mod tests {
                     &TestEngine::new(Ratio::new::<percent>(80.)),
                     &TestOverhead::new(true, false),
                     &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
                 )
             });
 
@@ -919,10 +1280,184 @@ mod tests {
                     &TestEngine::new(Ratio::new::<percent>(0.)),
                     &TestOverhead::new(true, false),
                     &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
                 )
             });
 
             assert!(idg.oil_outlet_temperature < starting_temperature);
         }
+
+        #[test]
+        fn running_engine_builds_oil_pressure() {
+            let mut test_bed = SimulationTestBed::new();
+            let mut idg = idg(test_bed.registry());
+
+            run_connected(&mut test_bed, &mut idg, 80., 0.);
+
+            assert_about_eq!(
+                idg.oil_pressure.get::<psi>(),
+                IntegratedDriveGenerator::NOMINAL_OIL_PRESSURE_PSI
+            );
+            assert!(!idg.has_fault());
+        }
+
+        #[test]
+        fn disconnected_idg_has_no_oil_pressure() {
+            let mut test_bed = SimulationTestBed::new();
+            let mut idg = idg(test_bed.registry());
+
+            test_bed.run(&mut idg, |element, context| {
+                element.update(
+                    context,
+                    &TestEngine::new(Ratio::new::<percent>(80.)),
+                    &TestOverhead::new(true, true),
+                    &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
+                )
+            });
+
+            assert_about_eq!(idg.oil_pressure.get::<psi>(), 0.);
+            assert!(!idg.has_fault());
+        }
+
+        #[test]
+        fn oil_leak_failure_causes_low_oil_pressure_fault() {
+            let mut test_bed = SimulationTestBed::new();
+            let mut idg = idg(test_bed.registry());
+            test_bed.write_bool("FAILURE_ENG_GEN_1_IDG_OIL_LEAK", true);
+
+            run_connected(&mut test_bed, &mut idg, 80., 0.);
+
+            assert!(idg.has_low_oil_pressure());
+            assert!(idg.has_fault());
+        }
+
+        #[test]
+        fn fault_is_inhibited_at_low_engine_speed() {
+            let mut test_bed = SimulationTestBed::new();
+            let mut idg = idg(test_bed.registry());
+            test_bed.write_bool("FAILURE_ENG_GEN_1_IDG_OIL_LEAK", true);
+
+            run_connected(&mut test_bed, &mut idg, 5., 0.);
+
+            assert!(idg.has_low_oil_pressure());
+            assert!(!idg.has_fault());
+        }
+
+        #[test]
+        fn overheat_failure_causes_overheat_fault() {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut idg = idg(test_bed.registry());
+            test_bed.write_bool("FAILURE_ENG_GEN_1_IDG_OVERHEAT", true);
+
+            run_connected(&mut test_bed, &mut idg, 80., 0.);
+
+            assert!(idg.is_overheating());
+            assert!(idg.has_fault());
+        }
+
+        #[test]
+        fn generator_load_heats_idg() {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut idg = idg(test_bed.registry());
+            let mut loaded_idg = self::idg(test_bed.registry());
+
+            run_connected(&mut test_bed, &mut idg, 80., 0.);
+            run_connected(&mut test_bed, &mut loaded_idg, 80., 100.);
+
+            assert!(loaded_idg.oil_outlet_temperature > idg.oil_outlet_temperature);
+            assert!(!loaded_idg.has_fault());
+        }
+
+        #[test]
+        fn running_at_takeoff_power_on_a_hot_day_does_not_overheat_the_idg() {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(1));
+            test_bed.set_ambient_temperature(ThermodynamicTemperature::new::<degree_celsius>(30.));
+            let mut idg = idg(test_bed.registry());
+
+            for _ in 0..600 {
+                run_connected(&mut test_bed, &mut idg, 97., 100.);
+                assert!(!idg.has_fault());
+            }
+        }
+
+        #[test]
+        fn overloading_the_generator_overheats_the_idg() {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut idg = idg(test_bed.registry());
+
+            run_connected(&mut test_bed, &mut idg, 80., 200.);
+
+            assert!(idg.is_overheating());
+            assert!(idg.has_fault());
+        }
+
+        #[test]
+        fn overheating_idg_remains_connected_by_default() {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut idg = idg(test_bed.registry());
+            test_bed.write_bool("FAILURE_ENG_GEN_1_IDG_OVERHEAT", true);
+
+            run_connected(&mut test_bed, &mut idg, 80., 0.);
+            run_connected(&mut test_bed, &mut idg, 80., 0.);
+
+            assert!(idg.connected);
+        }
+
+        #[test]
+        fn overheating_idg_disconnects_when_automatic_disconnect_enabled() {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(1_000));
+            let mut idg = idg(test_bed.registry());
+            idg.automatically_disconnects = true;
+            test_bed.write_bool("FAILURE_ENG_GEN_1_IDG_OVERHEAT", true);
+
+            run_connected(&mut test_bed, &mut idg, 80., 0.);
+            run_connected(&mut test_bed, &mut idg, 80., 0.);
+
+            assert!(!idg.connected);
+            assert!(!idg.provides_stable_power_output());
+            assert_eq!(
+                test_bed
+                    .events()
+                    .find("ELEC/IDG/1", &EventKind::Disconnected)
+                    .unwrap()
+                    .cause(),
+                Some("IDG oil overheat")
+            );
+        }
+
+        #[test]
+        fn restores_disconnected_state_and_temperature_from_snapshot() {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(10));
+            let mut idg = idg(test_bed.registry());
+            test_bed.run(&mut idg, |element, context| {
+                element.update(
+                    context,
+                    &TestEngine::new(Ratio::new::<percent>(80.)),
+                    &TestOverhead::new(true, false),
+                    &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
+                )
+            });
+            test_bed.run(&mut idg, |element, context| {
+                element.update(
+                    context,
+                    &TestEngine::new(Ratio::new::<percent>(80.)),
+                    &TestOverhead::new(true, true),
+                    &TestFireOverhead::new(false),
+                    Ratio::new::<percent>(0.),
+                )
+            });
+            let snapshot = Snapshot::take(&mut idg);
+
+            let mut restored_idg = self::idg(test_bed.registry());
+            snapshot.restore_into(&mut restored_idg);
+
+            assert!(!restored_idg.connected);
+            assert_eq!(
+                restored_idg.oil_outlet_temperature,
+                idg.oil_outlet_temperature
+            );
+        }
     }
 }
@@ -1,4 +1,7 @@
-use crate::simulation::{SimulationElement, SimulatorReader, SimulatorWriter, UpdateContext};
+use crate::simulation::{
+    SimulationElement, SimulatorReader, SimulatorWriter, UpdateContext, VariableIdentifier,
+    VariableRegistry,
+};
 use uom::si::{electric_potential::volt, f64::*, frequency::hertz};
 
 use super::{
@@ -7,15 +10,18 @@ use super::{
 };
 
 pub struct ExternalPowerSource {
+    is_connected_id: VariableIdentifier,
+
     writer: ElectricalStateWriter,
     is_connected: bool,
     output_frequency: Frequency,
     output_potential: ElectricPotential,
 }
 impl ExternalPowerSource {
-    pub fn new() -> ExternalPowerSource {
+    pub fn new(registry: &mut VariableRegistry) -> ExternalPowerSource {
         ExternalPowerSource {
-            writer: ElectricalStateWriter::new("EXT_PWR"),
+            is_connected_id: registry.get("EXTERNAL POWER AVAILABLE:1"),
+            writer: ElectricalStateWriter::new(registry, "EXT_PWR"),
             is_connected: false,
             output_frequency: Frequency::new::<hertz>(0.),
             output_potential: ElectricPotential::new::<volt>(0.),
@@ -48,7 +54,7 @@ provide_potential!(ExternalPowerSource, (110.0..=120.0));
 provide_frequency!(ExternalPowerSource, (390.0..=410.0));
 impl SimulationElement for ExternalPowerSource {
     fn read(&mut self, reader: &mut SimulatorReader) {
-        self.is_connected = reader.read_bool("EXTERNAL POWER AVAILABLE:1");
+        self.is_connected = reader.read_bool(&self.is_connected_id);
     }
 
     fn write(&self, writer: &mut SimulatorWriter) {
@@ -72,12 +78,6 @@ impl SimulationElement for ExternalPowerSource {
         };
     }
 }
-impl Default for ExternalPowerSource {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[cfg(test)]
 mod external_power_source_tests {
     use super::*;
@@ -93,6 +93,10 @@ mod external_power_source_tests {
             }
         }
 
+        fn registry(&mut self) -> &mut VariableRegistry {
+            self.test_bed.registry()
+        }
+
         fn with_disconnected_external_power(mut self) -> Self {
             self.disconnect_external_power();
             self
@@ -126,9 +130,9 @@ mod external_power_source_tests {
         ext_pwr_output_within_normal_parameters_before_processing_power_consumption_report: bool,
     }
     impl TestAircraft {
-        fn new() -> Self {
+        fn new(registry: &mut VariableRegistry) -> Self {
             Self {
-                ext_pwr: ExternalPowerSource::new(),
+                ext_pwr: ExternalPowerSource::new(registry),
                 ext_pwr_output_within_normal_parameters_before_processing_power_consumption_report: false,
             }
         }
@@ -163,8 +167,8 @@ mod external_power_source_tests {
 
     #[test]
     fn when_disconnected_provides_no_output() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = ExternalPowerTestBed::new().with_disconnected_external_power();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -173,8 +177,8 @@ mod external_power_source_tests {
 
     #[test]
     fn when_connected_provides_output() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = ExternalPowerTestBed::new().with_connected_external_power();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -183,8 +187,8 @@ mod external_power_source_tests {
 
     #[test]
     fn when_disconnected_frequency_not_normal() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = ExternalPowerTestBed::new().with_disconnected_external_power();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -193,8 +197,8 @@ mod external_power_source_tests {
 
     #[test]
     fn when_connected_frequency_normal() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = ExternalPowerTestBed::new().with_connected_external_power();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -203,8 +207,8 @@ mod external_power_source_tests {
 
     #[test]
     fn when_disconnected_potential_not_normal() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = ExternalPowerTestBed::new().with_disconnected_external_power();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -213,8 +217,8 @@ mod external_power_source_tests {
 
     #[test]
     fn when_connected_potential_normal() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = ExternalPowerTestBed::new().with_connected_external_power();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -223,8 +227,8 @@ mod external_power_source_tests {
 
     #[test]
     fn output_not_within_normal_parameters_when_disconnected() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = ExternalPowerTestBed::new().with_disconnected_external_power();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -234,8 +238,8 @@ mod external_power_source_tests {
 
     #[test]
     fn output_within_normal_parameters_when_connected() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = ExternalPowerTestBed::new().with_connected_external_power();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -252,8 +256,8 @@ mod external_power_source_tests {
         // supplies potential but the previous tick's frequency and potential are still normal.
         // With this test we ensure that an EXT PWR source which is no longer supplying power is
         // immediately noticed.
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = ExternalPowerTestBed::new().with_connected_external_power();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
         test_bed.run_aircraft(&mut aircraft);
 
         test_bed.disconnect_external_power();
@@ -265,8 +269,8 @@ mod external_power_source_tests {
 
     #[test]
     fn writes_its_state() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = SimulationTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
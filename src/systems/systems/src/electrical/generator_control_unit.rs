@@ -0,0 +1,532 @@
+use super::{PotentialOrigin, ProvideFrequency, ProvideLoad, ProvidePotential};
+use crate::{
+    failures::{Failure, FailureType},
+    shared::DelayedTrueLogicGate,
+    simulation::{
+        ChangeDetector, EventEmitter, EventKind, SimulationElement, SimulationElementVisitor,
+        UpdateContext, VariableRegistry,
+    },
+};
+use std::{fmt::Display, time::Duration};
+use uom::si::{electric_potential::volt, f64::*, frequency::hertz, ratio::percent};
+
+/// A generator whose output is monitored by a [`GeneratorControlUnit`].
+///
+/// [`GeneratorControlUnit`]: struct.GeneratorControlUnit.html
+pub trait ControlledGenerator: ProvidePotential + ProvideFrequency + ProvideLoad {
+    /// Indicates if the generator is driven at a speed at which it provides power.
+    /// Below this speed, e.g. while its engine shuts down, the generator is
+    /// disconnected without this being a fault.
+    fn is_driven_at_operating_speed(&self) -> bool;
+}
+
+/// The protections of a [`GeneratorControlUnit`] which trip the generator.
+///
+/// [`GeneratorControlUnit`]: struct.GeneratorControlUnit.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeneratorProtection {
+    OverVoltage,
+    UnderVoltage,
+    OverFrequency,
+    UnderFrequency,
+    Overcurrent,
+    /// Protects against a fault in the feeder between the generator and its line
+    /// contactor, which shows as a difference between the current leaving the
+    /// generator and the current arriving at the contactor.
+    Differential,
+}
+impl Display for GeneratorProtection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeneratorProtection::OverVoltage => write!(f, "over voltage"),
+            GeneratorProtection::UnderVoltage => write!(f, "under voltage"),
+            GeneratorProtection::OverFrequency => write!(f, "over frequency"),
+            GeneratorProtection::UnderFrequency => write!(f, "under frequency"),
+            GeneratorProtection::Overcurrent => write!(f, "overcurrent"),
+            GeneratorProtection::Differential => write!(f, "differential"),
+        }
+    }
+}
+
+/// The generator control unit (GCU) controls the line contactor of a generator.
+/// It closes the contactor once the generator's output is within normal parameters,
+/// after which it protects the aircraft's network against the generator's output
+/// leaving its limits for longer than the delay of the protection involved.
+///
+/// A protection trip opens the line contactor and illuminates the FAULT light
+/// of the generator's push button. The trip remains latched until the push
+/// button is switched off, after which switching it on again resets the GCU.
+pub struct GeneratorControlUnit {
+    origin: PotentialOrigin,
+    feeder_failure: Failure,
+    push_button_is_on: bool,
+    power_ready: bool,
+    protections: [(GeneratorProtection, DelayedTrueLogicGate); 6],
+    tripped_by: Option<GeneratorProtection>,
+    tripped_by_change: ChangeDetector<Option<GeneratorProtection>>,
+}
+impl GeneratorControlUnit {
+    const OVER_VOLTAGE_VOLTS: f64 = 130.;
+    const UNDER_VOLTAGE_VOLTS: f64 = 100.;
+    const OVER_FREQUENCY_HERTZ: f64 = 430.;
+    const UNDER_FREQUENCY_HERTZ: f64 = 375.;
+    const OVERCURRENT_LOAD_PERCENT: f64 = 150.;
+
+    const OVER_VOLTAGE_DELAY: Duration = Duration::from_millis(150);
+    const UNDER_VOLTAGE_DELAY: Duration = Duration::from_secs(7);
+    const OVER_FREQUENCY_DELAY: Duration = Duration::from_secs(1);
+    const UNDER_FREQUENCY_DELAY: Duration = Duration::from_secs(1);
+    const OVERCURRENT_DELAY: Duration = Duration::from_secs(10);
+    const DIFFERENTIAL_DELAY: Duration = Duration::from_millis(50);
+
+    pub fn new(registry: &mut VariableRegistry, origin: PotentialOrigin) -> Self {
+        Self {
+            origin,
+            feeder_failure: Failure::new(registry, FailureType::GeneratorFeeder(origin)),
+            push_button_is_on: false,
+            power_ready: false,
+            protections: [
+                (
+                    GeneratorProtection::OverVoltage,
+                    DelayedTrueLogicGate::new(Self::OVER_VOLTAGE_DELAY),
+                ),
+                (
+                    GeneratorProtection::UnderVoltage,
+                    DelayedTrueLogicGate::new(Self::UNDER_VOLTAGE_DELAY),
+                ),
+                (
+                    GeneratorProtection::OverFrequency,
+                    DelayedTrueLogicGate::new(Self::OVER_FREQUENCY_DELAY),
+                ),
+                (
+                    GeneratorProtection::UnderFrequency,
+                    DelayedTrueLogicGate::new(Self::UNDER_FREQUENCY_DELAY),
+                ),
+                (
+                    GeneratorProtection::Overcurrent,
+                    DelayedTrueLogicGate::new(Self::OVERCURRENT_DELAY),
+                ),
+                (
+                    GeneratorProtection::Differential,
+                    DelayedTrueLogicGate::new(Self::DIFFERENTIAL_DELAY),
+                ),
+            ],
+            tripped_by: None,
+            tripped_by_change: ChangeDetector::new(None),
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        context: &UpdateContext,
+        generator: &impl ControlledGenerator,
+        push_button_is_on: bool,
+    ) {
+        self.push_button_is_on = push_button_is_on;
+        if !push_button_is_on {
+            self.tripped_by = None;
+        }
+
+        // Power is ready once the generator's output first comes within normal
+        // parameters, such that a generator which is still spooling up isn't
+        // considered to be under voltage or under frequency.
+        self.power_ready = generator.is_driven_at_operating_speed()
+            && (self.power_ready || (generator.potential_normal() && generator.frequency_normal()));
+
+        let power_ready = self.power_ready;
+        let feeder_failed = self.feeder_failure.is_active();
+        for (protection, gate) in self.protections.iter_mut() {
+            gate.update(
+                context,
+                power_ready && Self::is_exceeded(*protection, generator, feeder_failed),
+            );
+        }
+
+        if push_button_is_on && self.tripped_by.is_none() {
+            self.tripped_by = self
+                .protections
+                .iter()
+                .find(|(_, gate)| gate.output())
+                .map(|(protection, _)| *protection);
+        }
+    }
+
+    fn is_exceeded(
+        protection: GeneratorProtection,
+        generator: &impl ControlledGenerator,
+        feeder_failed: bool,
+    ) -> bool {
+        let potential = generator.potential().get::<volt>();
+        let frequency = generator.frequency().get::<hertz>();
+        match protection {
+            GeneratorProtection::OverVoltage => potential > Self::OVER_VOLTAGE_VOLTS,
+            GeneratorProtection::UnderVoltage => potential < Self::UNDER_VOLTAGE_VOLTS,
+            GeneratorProtection::OverFrequency => frequency > Self::OVER_FREQUENCY_HERTZ,
+            GeneratorProtection::UnderFrequency => frequency < Self::UNDER_FREQUENCY_HERTZ,
+            GeneratorProtection::Overcurrent => {
+                generator.load() > Ratio::new::<percent>(Self::OVERCURRENT_LOAD_PERCENT)
+            }
+            GeneratorProtection::Differential => feeder_failed,
+        }
+    }
+
+    pub fn should_close_line_contactor(&self) -> bool {
+        self.push_button_is_on && self.power_ready && self.tripped_by.is_none()
+    }
+
+    /// Indicates if the FAULT light of the generator's push button should illuminate.
+    pub fn has_fault(&self) -> bool {
+        self.tripped_by.is_some()
+    }
+
+    /// The protection which tripped the generator, if any.
+    pub fn tripped_by(&self) -> Option<GeneratorProtection> {
+        self.tripped_by
+    }
+}
+impl SimulationElement for GeneratorControlUnit {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.feeder_failure.accept(visitor);
+
+        visitor.visit(self);
+    }
+
+    fn emit_events(&mut self, events: &mut EventEmitter) {
+        if self.tripped_by_change.detect(self.tripped_by).is_some() {
+            let source = format!("ELEC/GCU/{}", self.origin);
+            match self.tripped_by {
+                Some(protection) => events.emit_with_cause(
+                    &source,
+                    EventKind::Activated,
+                    &format!("{} protection trip", protection),
+                ),
+                None => {
+                    events.emit_with_cause(&source, EventKind::Deactivated, "push button reset")
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{
+        test::SimulationTestBed, Aircraft, SimulationElement, SimulationElementVisitor,
+    };
+
+    struct TestGenerator {
+        is_driven_at_operating_speed: bool,
+        output_potential: ElectricPotential,
+        output_frequency: Frequency,
+        load: Ratio,
+    }
+    impl TestGenerator {
+        fn new() -> Self {
+            Self {
+                is_driven_at_operating_speed: true,
+                output_potential: ElectricPotential::new::<volt>(115.),
+                output_frequency: Frequency::new::<hertz>(400.),
+                load: Ratio::new::<percent>(50.),
+            }
+        }
+    }
+    provide_potential!(TestGenerator, (110.0..=120.0));
+    provide_frequency!(TestGenerator, (390.0..=410.0));
+    provide_load!(TestGenerator);
+    impl ControlledGenerator for TestGenerator {
+        fn is_driven_at_operating_speed(&self) -> bool {
+            self.is_driven_at_operating_speed
+        }
+    }
+
+    struct TestAircraft {
+        gcu: GeneratorControlUnit,
+        generator: TestGenerator,
+        push_button_is_on: bool,
+    }
+    impl TestAircraft {
+        fn new(registry: &mut VariableRegistry) -> Self {
+            Self {
+                gcu: GeneratorControlUnit::new(registry, PotentialOrigin::EngineGenerator(1)),
+                generator: TestGenerator::new(),
+                push_button_is_on: true,
+            }
+        }
+    }
+    impl Aircraft for TestAircraft {
+        fn update_before_power_distribution(&mut self, context: &UpdateContext) {
+            self.gcu
+                .update(context, &self.generator, self.push_button_is_on);
+        }
+    }
+    impl SimulationElement for TestAircraft {
+        fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+            self.gcu.accept(visitor);
+
+            visitor.visit(self);
+        }
+    }
+
+    struct GeneratorControlUnitTestBed {
+        test_bed: SimulationTestBed,
+        aircraft: TestAircraft,
+    }
+    impl GeneratorControlUnitTestBed {
+        fn new() -> Self {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(100));
+            let aircraft = TestAircraft::new(test_bed.registry());
+
+            Self { test_bed, aircraft }
+        }
+
+        fn run_for(&mut self, duration: Duration) {
+            let ticks = duration.as_millis() / 100;
+            for _ in 0..ticks.max(1) {
+                self.test_bed.run_aircraft(&mut self.aircraft);
+            }
+        }
+
+        fn generator(&mut self) -> &mut TestGenerator {
+            &mut self.aircraft.generator
+        }
+
+        fn set_push_button_on(&mut self, on: bool) {
+            self.aircraft.push_button_is_on = on;
+        }
+
+        fn fail_feeder(&mut self) {
+            self.test_bed.write_bool("FAILURE_ENG_GEN_1_FEEDER", true);
+        }
+
+        fn gcu(&self) -> &GeneratorControlUnit {
+            &self.aircraft.gcu
+        }
+    }
+
+    #[test]
+    fn closes_line_contactor_when_output_is_normal() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+
+        test_bed.run_for(Duration::from_millis(100));
+
+        assert!(test_bed.gcu().should_close_line_contactor());
+        assert!(!test_bed.gcu().has_fault());
+    }
+
+    #[test]
+    fn does_not_close_line_contactor_when_push_button_is_off() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.set_push_button_on(false);
+
+        test_bed.run_for(Duration::from_millis(100));
+
+        assert!(!test_bed.gcu().should_close_line_contactor());
+        assert!(!test_bed.gcu().has_fault());
+    }
+
+    #[test]
+    fn does_not_close_line_contactor_before_output_is_normal() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.generator().output_frequency = Frequency::new::<hertz>(350.);
+
+        test_bed.run_for(Duration::from_secs(10));
+
+        assert!(!test_bed.gcu().should_close_line_contactor());
+        assert!(!test_bed.gcu().has_fault());
+    }
+
+    #[test]
+    fn trips_on_over_voltage_after_delay() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.generator().output_potential = ElectricPotential::new::<volt>(140.);
+        test_bed.run_for(Duration::from_millis(100));
+        assert!(test_bed.gcu().should_close_line_contactor());
+
+        test_bed.run_for(Duration::from_millis(100));
+        assert!(!test_bed.gcu().should_close_line_contactor());
+        assert!(test_bed.gcu().has_fault());
+        assert_eq!(
+            test_bed.gcu().tripped_by(),
+            Some(GeneratorProtection::OverVoltage)
+        );
+    }
+
+    #[test]
+    fn trips_on_under_voltage_after_delay() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.generator().output_potential = ElectricPotential::new::<volt>(90.);
+        test_bed.run_for(Duration::from_secs(6));
+        assert!(!test_bed.gcu().has_fault());
+
+        test_bed.run_for(Duration::from_secs(2));
+        assert_eq!(
+            test_bed.gcu().tripped_by(),
+            Some(GeneratorProtection::UnderVoltage)
+        );
+    }
+
+    #[test]
+    fn trips_on_over_frequency() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.generator().output_frequency = Frequency::new::<hertz>(450.);
+        test_bed.run_for(Duration::from_secs(2));
+
+        assert_eq!(
+            test_bed.gcu().tripped_by(),
+            Some(GeneratorProtection::OverFrequency)
+        );
+    }
+
+    #[test]
+    fn trips_on_under_frequency() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.generator().output_frequency = Frequency::new::<hertz>(360.);
+        test_bed.run_for(Duration::from_secs(2));
+
+        assert_eq!(
+            test_bed.gcu().tripped_by(),
+            Some(GeneratorProtection::UnderFrequency)
+        );
+    }
+
+    #[test]
+    fn trips_on_overcurrent() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.generator().load = Ratio::new::<percent>(200.);
+        test_bed.run_for(Duration::from_secs(11));
+
+        assert_eq!(
+            test_bed.gcu().tripped_by(),
+            Some(GeneratorProtection::Overcurrent)
+        );
+    }
+
+    #[test]
+    fn overload_within_limits_does_not_trip() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.generator().load = Ratio::new::<percent>(120.);
+        test_bed.run_for(Duration::from_secs(60));
+
+        assert!(!test_bed.gcu().has_fault());
+    }
+
+    #[test]
+    fn trips_on_feeder_fault() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.fail_feeder();
+        test_bed.run_for(Duration::from_millis(100));
+
+        assert_eq!(
+            test_bed.gcu().tripped_by(),
+            Some(GeneratorProtection::Differential)
+        );
+    }
+
+    #[test]
+    fn transient_shorter_than_delay_does_not_trip() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.generator().output_frequency = Frequency::new::<hertz>(450.);
+        test_bed.run_for(Duration::from_millis(500));
+        test_bed.generator().output_frequency = Frequency::new::<hertz>(400.);
+        test_bed.run_for(Duration::from_secs(2));
+
+        assert!(!test_bed.gcu().has_fault());
+        assert!(test_bed.gcu().should_close_line_contactor());
+    }
+
+    #[test]
+    fn slowing_down_below_operating_speed_is_not_a_fault() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.generator().is_driven_at_operating_speed = false;
+        test_bed.generator().output_potential = ElectricPotential::new::<volt>(0.);
+        test_bed.generator().output_frequency = Frequency::new::<hertz>(0.);
+        test_bed.run_for(Duration::from_secs(10));
+
+        assert!(!test_bed.gcu().should_close_line_contactor());
+        assert!(!test_bed.gcu().has_fault());
+    }
+
+    #[test]
+    fn trip_remains_latched_when_output_returns_to_normal() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+        test_bed.generator().output_potential = ElectricPotential::new::<volt>(140.);
+        test_bed.run_for(Duration::from_secs(1));
+
+        test_bed.generator().output_potential = ElectricPotential::new::<volt>(115.);
+        test_bed.run_for(Duration::from_secs(1));
+
+        assert!(test_bed.gcu().has_fault());
+        assert!(!test_bed.gcu().should_close_line_contactor());
+    }
+
+    #[test]
+    fn cycling_the_push_button_resets_the_trip() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+        test_bed.generator().output_potential = ElectricPotential::new::<volt>(140.);
+        test_bed.run_for(Duration::from_secs(1));
+        test_bed.generator().output_potential = ElectricPotential::new::<volt>(115.);
+
+        test_bed.set_push_button_on(false);
+        test_bed.run_for(Duration::from_millis(100));
+        assert!(!test_bed.gcu().has_fault());
+
+        test_bed.set_push_button_on(true);
+        test_bed.run_for(Duration::from_millis(100));
+        assert!(test_bed.gcu().should_close_line_contactor());
+    }
+
+    #[test]
+    fn trips_again_after_reset_when_the_fault_persists() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+        test_bed.fail_feeder();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.set_push_button_on(false);
+        test_bed.run_for(Duration::from_millis(100));
+        test_bed.set_push_button_on(true);
+        test_bed.run_for(Duration::from_millis(100));
+
+        assert_eq!(
+            test_bed.gcu().tripped_by(),
+            Some(GeneratorProtection::Differential)
+        );
+    }
+
+    #[test]
+    fn emits_event_when_tripped() {
+        let mut test_bed = GeneratorControlUnitTestBed::new();
+        test_bed.run_for(Duration::from_millis(100));
+
+        test_bed.fail_feeder();
+        test_bed.run_for(Duration::from_millis(100));
+
+        let event = test_bed
+            .test_bed
+            .events()
+            .find("ELEC/GCU/ENG_GEN_1", &EventKind::Activated)
+            .unwrap();
+        assert_eq!(event.cause(), Some("differential protection trip"));
+    }
+}
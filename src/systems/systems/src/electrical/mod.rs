@@ -3,25 +3,36 @@
 mod battery;
 mod battery_charge_limiter;
 pub mod consumption;
+mod converter;
 mod emergency_generator;
 mod engine_generator;
 mod external_power_source;
+mod generator_control_unit;
+mod network;
+mod resistance;
 mod static_inverter;
 mod transformer_rectifier;
 use std::{cmp::Ordering, fmt::Display, hash::Hash, time::Duration};
 
 pub use battery::Battery;
 pub use battery_charge_limiter::BatteryChargeLimiter;
+pub use converter::ConverterCharacteristics;
 pub use emergency_generator::EmergencyGenerator;
 pub use engine_generator::{
     EngineGenerator, INTEGRATED_DRIVE_GENERATOR_STABILIZATION_TIME_IN_MILLISECONDS,
 };
 pub use external_power_source::ExternalPowerSource;
+pub use generator_control_unit::{ControlledGenerator, GeneratorControlUnit, GeneratorProtection};
 use itertools::Itertools;
+pub use network::{ElectricalNetwork, NodeId};
+pub use resistance::{ResistiveSolution, SourceResistance};
 pub use static_inverter::StaticInverter;
 pub use transformer_rectifier::TransformerRectifier;
 
-use crate::simulation::{SimulationElement, SimulatorWriter, UpdateContext};
+use crate::simulation::{
+    ChangeDetector, EventEmitter, EventKind, SimulationElement, SimulatorWriter, Snapshot,
+    StateDump, UpdateContext, VariableIdentifier, VariableRegistry,
+};
 use uom::si::{
     electric_current::ampere, electric_potential::volt, f64::*, frequency::hertz, ratio::percent,
     velocity::knot,
@@ -56,6 +67,19 @@ pub enum PotentialOrigin {
     TransformerRectifier(usize),
     StaticInverter,
 }
+impl Display for PotentialOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PotentialOrigin::EngineGenerator(number) => write!(f, "ENG_GEN_{}", number),
+            PotentialOrigin::ApuGenerator(number) => write!(f, "APU_GEN_{}", number),
+            PotentialOrigin::External => write!(f, "EXT_PWR"),
+            PotentialOrigin::EmergencyGenerator => write!(f, "EMER_GEN"),
+            PotentialOrigin::Battery(number) => write!(f, "BAT_{}", number),
+            PotentialOrigin::TransformerRectifier(number) => write!(f, "TR_{}", number),
+            PotentialOrigin::StaticInverter => write!(f, "STAT_INV"),
+        }
+    }
+}
 
 /// Within an electrical system, electric potential is made available by an origin.
 /// These origins are contained in this type. By knowing the origin of potential
@@ -179,6 +203,12 @@ impl Potential {
     pub fn is_unpowered(&self) -> bool {
         !self.is_powered()
     }
+
+    /// Describes the potential and its origins, e.g. `"BAT_1, BAT_2"`.
+    pub fn dump(&self, state: &mut StateDump) {
+        state.write_f64("POTENTIAL", self.raw.get::<volt>());
+        state.write_text("ORIGINS", &self.origins().join(", "));
+    }
 }
 impl PartialEq for Potential {
     fn eq(&self, other: &Self) -> bool {
@@ -259,15 +289,19 @@ pub trait PotentialTarget {
 /// When closed a contactor conducts the potential towards other targets.
 #[derive(Debug)]
 pub struct Contactor {
-    closed_id: String,
+    id: String,
+    closed_id: VariableIdentifier,
     closed: bool,
+    closed_change: ChangeDetector<bool>,
     input_potential: Potential,
 }
 impl Contactor {
-    pub fn new(id: &str) -> Contactor {
+    pub fn new(registry: &mut VariableRegistry, id: &str) -> Contactor {
         Contactor {
-            closed_id: format!("ELEC_CONTACTOR_{}_IS_CLOSED", id),
+            id: id.to_owned(),
+            closed_id: registry.get(&format!("ELEC_CONTACTOR_{}_IS_CLOSED", id)),
             closed: false,
+            closed_change: ChangeDetector::new(false),
             input_potential: Potential::none(),
         }
     }
@@ -298,6 +332,46 @@ impl SimulationElement for Contactor {
     fn write(&self, writer: &mut SimulatorWriter) {
         writer.write_bool(&self.closed_id, self.is_closed());
     }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        snapshot.write_bool(
+            &format!("ELEC_CONTACTOR_{}_IS_CLOSED", self.id),
+            self.closed,
+        );
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(closed) = snapshot.read_bool(&format!("ELEC_CONTACTOR_{}_IS_CLOSED", self.id)) {
+            self.closed = closed;
+            self.closed_change = ChangeDetector::new(closed);
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state.node("ELEC").node("CONTACTOR").node(&self.id);
+        node.write_bool("IS_CLOSED", self.is_closed());
+        self.input_potential.dump(node.node("INPUT"));
+    }
+
+    fn emit_events(&mut self, events: &mut EventEmitter) {
+        if self.closed_change.detect(self.closed).is_some() {
+            let source = format!("ELEC/CONTACTOR/{}", self.id);
+            if self.closed {
+                let origins = self.input_potential.origins().join(", ");
+                if origins.is_empty() {
+                    events.emit(&source, EventKind::Closed);
+                } else {
+                    events.emit_with_cause(
+                        &source,
+                        EventKind::Closed,
+                        &format!("input from {}", origins),
+                    );
+                }
+            } else {
+                events.emit(&source, EventKind::Opened);
+            }
+        }
+    }
 }
 
 /// The common types of electrical buses within Airbus aircraft.
@@ -335,16 +409,19 @@ impl Display for ElectricalBusType {
 }
 
 pub struct ElectricalBus {
-    bus_powered_id: String,
-    bus_potential_normal_id: String,
+    bus_powered_id: VariableIdentifier,
+    bus_potential_normal_id: VariableIdentifier,
     input_potential: Potential,
     bus_type: ElectricalBusType,
 }
 impl ElectricalBus {
-    pub fn new(bus_type: ElectricalBusType) -> ElectricalBus {
+    pub fn new(registry: &mut VariableRegistry, bus_type: ElectricalBusType) -> ElectricalBus {
         ElectricalBus {
-            bus_powered_id: format!("ELEC_{}_BUS_IS_POWERED", bus_type.to_string()),
-            bus_potential_normal_id: format!("ELEC_{}_BUS_POTENTIAL_NORMAL", bus_type.to_string()),
+            bus_powered_id: registry.get(&format!("ELEC_{}_BUS_IS_POWERED", bus_type.to_string())),
+            bus_potential_normal_id: registry.get(&format!(
+                "ELEC_{}_BUS_POTENTIAL_NORMAL",
+                bus_type.to_string()
+            )),
             input_potential: Potential::none(),
             bus_type,
         }
@@ -394,29 +471,38 @@ impl SimulationElement for ElectricalBus {
             writer.write_bool(&self.bus_potential_normal_id, self.potential_normal())
         }
     }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state
+            .node("ELEC")
+            .node("BUS")
+            .node(&self.bus_type.to_string());
+        node.write_bool("IS_POWERED", self.is_powered());
+        self.input_potential.dump(node);
+    }
 }
 
 pub struct ElectricalStateWriter {
-    current_id: String,
-    current_normal_id: String,
-    potential_id: String,
-    potential_normal_id: String,
-    frequency_id: String,
-    frequency_normal_id: String,
-    load_id: String,
-    load_normal_id: String,
+    current_id: VariableIdentifier,
+    current_normal_id: VariableIdentifier,
+    potential_id: VariableIdentifier,
+    potential_normal_id: VariableIdentifier,
+    frequency_id: VariableIdentifier,
+    frequency_normal_id: VariableIdentifier,
+    load_id: VariableIdentifier,
+    load_normal_id: VariableIdentifier,
 }
 impl ElectricalStateWriter {
-    pub fn new(element_id: &str) -> Self {
+    pub fn new(registry: &mut VariableRegistry, element_id: &str) -> Self {
         Self {
-            current_id: format!("ELEC_{}_CURRENT", element_id),
-            current_normal_id: format!("ELEC_{}_CURRENT_NORMAL", element_id),
-            potential_id: format!("ELEC_{}_POTENTIAL", element_id),
-            potential_normal_id: format!("ELEC_{}_POTENTIAL_NORMAL", element_id),
-            frequency_id: format!("ELEC_{}_FREQUENCY", element_id),
-            frequency_normal_id: format!("ELEC_{}_FREQUENCY_NORMAL", element_id),
-            load_id: format!("ELEC_{}_LOAD", element_id),
-            load_normal_id: format!("ELEC_{}_LOAD_NORMAL", element_id),
+            current_id: registry.get(&format!("ELEC_{}_CURRENT", element_id)),
+            current_normal_id: registry.get(&format!("ELEC_{}_CURRENT_NORMAL", element_id)),
+            potential_id: registry.get(&format!("ELEC_{}_POTENTIAL", element_id)),
+            potential_normal_id: registry.get(&format!("ELEC_{}_POTENTIAL_NORMAL", element_id)),
+            frequency_id: registry.get(&format!("ELEC_{}_FREQUENCY", element_id)),
+            frequency_normal_id: registry.get(&format!("ELEC_{}_FREQUENCY_NORMAL", element_id)),
+            load_id: registry.get(&format!("ELEC_{}_LOAD", element_id)),
+            load_normal_id: registry.get(&format!("ELEC_{}_LOAD_NORMAL", element_id)),
         }
     }
 
@@ -448,22 +534,22 @@ impl ElectricalStateWriter {
     }
 
     fn write_current(&self, source: &impl ProvideCurrent, writer: &mut SimulatorWriter) {
-        writer.write_f64(&self.current_id, source.current().get::<ampere>());
+        writer.write_quantity(&self.current_id, source.current(), ampere);
         writer.write_bool(&self.current_normal_id, source.current_normal());
     }
 
     fn write_potential(&self, source: &impl ProvidePotential, writer: &mut SimulatorWriter) {
-        writer.write_f64(&self.potential_id, source.potential().get::<volt>());
+        writer.write_quantity(&self.potential_id, source.potential(), volt);
         writer.write_bool(&self.potential_normal_id, source.potential_normal());
     }
 
     fn write_frequency(&self, source: &impl ProvideFrequency, writer: &mut SimulatorWriter) {
-        writer.write_f64(&self.frequency_id, source.frequency().get::<hertz>());
+        writer.write_quantity(&self.frequency_id, source.frequency(), hertz);
         writer.write_bool(&self.frequency_normal_id, source.frequency_normal());
     }
 
     fn write_load(&self, source: &impl ProvideLoad, writer: &mut SimulatorWriter) {
-        writer.write_f64(&self.load_id, source.load().get::<percent>());
+        writer.write_quantity(&self.load_id, source.load(), percent);
         writer.write_bool(&self.load_normal_id, source.load_normal());
     }
 }
@@ -953,13 +1039,29 @@ mod tests {
 
         #[test]
         fn writes_its_state() {
-            let mut bus = electrical_bus();
             let mut test_bed = SimulationTestBed::new();
+            let mut bus = electrical_bus(test_bed.registry());
             test_bed.run_without_update(&mut bus);
 
             assert!(test_bed.contains_key("ELEC_AC_2_BUS_IS_POWERED"));
         }
 
+        #[test]
+        fn dumps_its_state() {
+            let mut bus = electrical_bus(&mut VariableRegistry::new());
+            bus.powered_by(&BatteryStub::new(Potential::single(
+                PotentialOrigin::Battery(1),
+                ElectricPotential::new::<volt>(28.),
+            )));
+
+            let dump = StateDump::take(&mut bus);
+            let node = dump.find(&["ELEC", "BUS", "AC_2"]).unwrap();
+
+            assert_eq!(node.read_bool("IS_POWERED"), Some(true));
+            assert_eq!(node.read_f64("POTENTIAL"), Some(28.));
+            assert_eq!(node.read_text("ORIGINS"), Some("BAT_1"));
+        }
+
         struct BatteryStub {
             potential: Potential,
         }
@@ -978,9 +1080,9 @@ mod tests {
             bus: ElectricalBus,
         }
         impl ElectricalBusTestAircraft {
-            fn new(bus_type: ElectricalBusType) -> Self {
+            fn new(registry: &mut VariableRegistry, bus_type: ElectricalBusType) -> Self {
                 Self {
-                    bus: ElectricalBus::new(bus_type),
+                    bus: ElectricalBus::new(registry, bus_type),
                 }
             }
 
@@ -1005,13 +1107,14 @@ mod tests {
             let bat_1 = BatteryStub::new(Potential::single(PotentialOrigin::Battery(1), potential));
             let bat_2 = BatteryStub::new(Potential::single(PotentialOrigin::Battery(2), potential));
 
-            let mut bus = electrical_bus();
+            let mut registry = VariableRegistry::new();
+            let mut bus = electrical_bus(&mut registry);
 
-            let mut contactor_1 = Contactor::new("BAT1");
+            let mut contactor_1 = Contactor::new(&mut registry, "BAT1");
             contactor_1.powered_by(&bat_1);
             contactor_1.close_when(true);
 
-            let mut contactor_2 = Contactor::new("BAT2");
+            let mut contactor_2 = Contactor::new(&mut registry, "BAT2");
             contactor_2.powered_by(&bat_2);
             contactor_2.close_when(true);
 
@@ -1033,7 +1136,7 @@ mod tests {
                 ElectricPotential::new::<volt>(25.),
             ));
 
-            let mut bus = electrical_bus();
+            let mut bus = electrical_bus(&mut VariableRegistry::new());
             execute_or_powered_by_both_batteries(&mut bus, bat_1, bat_2);
 
             assert!(bus.input_potential().is_single(PotentialOrigin::Battery(1)));
@@ -1047,7 +1150,7 @@ mod tests {
             ));
             let bat_2 = BatteryStub::new(Potential::none());
 
-            let mut bus = electrical_bus();
+            let mut bus = electrical_bus(&mut VariableRegistry::new());
             execute_or_powered_by_both_batteries(&mut bus, bat_1, bat_2);
 
             assert!(bus.input_potential().is_single(PotentialOrigin::Battery(1)));
@@ -1061,7 +1164,7 @@ mod tests {
                 ElectricPotential::new::<volt>(28.),
             ));
 
-            let mut bus = electrical_bus();
+            let mut bus = electrical_bus(&mut VariableRegistry::new());
             execute_or_powered_by_both_batteries(&mut bus, bat_1, bat_2);
 
             assert!(bus.input_potential().is_single(PotentialOrigin::Battery(2)));
@@ -1072,7 +1175,7 @@ mod tests {
             let bat_1 = BatteryStub::new(Potential::none());
             let bat_2 = BatteryStub::new(Potential::none());
 
-            let mut bus = electrical_bus();
+            let mut bus = electrical_bus(&mut VariableRegistry::new());
             execute_or_powered_by_both_batteries(&mut bus, bat_1, bat_2);
 
             assert!(bus.input_potential().is_unpowered());
@@ -1080,9 +1183,11 @@ mod tests {
 
         #[test]
         fn bat_bus_at_25_volt_is_abnormal() {
-            let mut aircraft =
-                ElectricalBusTestAircraft::new(ElectricalBusType::DirectCurrentBattery);
             let mut test_bed = SimulationTestBed::new();
+            let mut aircraft = ElectricalBusTestAircraft::new(
+                test_bed.registry(),
+                ElectricalBusType::DirectCurrentBattery,
+            );
 
             aircraft.powered_by_battery_at(ElectricPotential::new::<volt>(25.));
             test_bed.run_aircraft(&mut aircraft);
@@ -1095,9 +1200,11 @@ mod tests {
 
         #[test]
         fn bat_bus_above_25_volt_is_abnormal() {
-            let mut aircraft =
-                ElectricalBusTestAircraft::new(ElectricalBusType::DirectCurrentBattery);
             let mut test_bed = SimulationTestBed::new();
+            let mut aircraft = ElectricalBusTestAircraft::new(
+                test_bed.registry(),
+                ElectricalBusType::DirectCurrentBattery,
+            );
 
             aircraft.powered_by_battery_at(ElectricPotential::new::<volt>(25.01));
             test_bed.run_aircraft(&mut aircraft);
@@ -1107,9 +1214,10 @@ mod tests {
 
         #[test]
         fn writes_potential_normal_when_bat_bus() {
-            let mut bus = ElectricalBus::new(ElectricalBusType::DirectCurrentBattery);
-
             let mut test_bed = SimulationTestBed::new();
+            let mut bus =
+                ElectricalBus::new(test_bed.registry(), ElectricalBusType::DirectCurrentBattery);
+
             test_bed.run_without_update(&mut bus);
 
             assert!(test_bed.contains_key("ELEC_DC_BAT_BUS_POTENTIAL_NORMAL"));
@@ -1117,9 +1225,12 @@ mod tests {
 
         #[test]
         fn does_not_write_potential_normal_when_not_bat_bus() {
-            let mut bus = ElectricalBus::new(ElectricalBusType::AlternatingCurrentEssential);
-
             let mut test_bed = SimulationTestBed::new();
+            let mut bus = ElectricalBus::new(
+                test_bed.registry(),
+                ElectricalBusType::AlternatingCurrentEssential,
+            );
+
             test_bed.run_without_update(&mut bus);
 
             assert!(!test_bed.contains_key("ELEC_DC_BAT_BUS_POTENTIAL_NORMAL"));
@@ -1130,25 +1241,26 @@ mod tests {
             bat_1: BatteryStub,
             bat_2: BatteryStub,
         ) {
-            let mut contactor_1 = Contactor::new("BAT1");
+            let mut registry = VariableRegistry::new();
+            let mut contactor_1 = Contactor::new(&mut registry, "BAT1");
             contactor_1.powered_by(&bat_1);
             contactor_1.close_when(true);
 
-            let mut contactor_2 = Contactor::new("BAT2");
+            let mut contactor_2 = Contactor::new(&mut registry, "BAT2");
             contactor_2.powered_by(&bat_2);
             contactor_2.close_when(true);
 
             bus.or_powered_by_both_batteries(&contactor_1, &contactor_2);
         }
 
-        fn electrical_bus() -> ElectricalBus {
-            ElectricalBus::new(ElectricalBusType::AlternatingCurrent(2))
+        fn electrical_bus(registry: &mut VariableRegistry) -> ElectricalBus {
+            ElectricalBus::new(registry, ElectricalBusType::AlternatingCurrent(2))
         }
     }
 
     #[cfg(test)]
     mod contactor_tests {
-        use crate::simulation::test::SimulationTestBed;
+        use crate::simulation::{test::SimulationTestBed, EventBus};
 
         use super::*;
 
@@ -1241,15 +1353,74 @@ mod tests {
 
         #[test]
         fn writes_its_state() {
-            let mut contactor = contactor();
             let mut test_bed = SimulationTestBed::new();
+            let mut contactor = Contactor::new(test_bed.registry(), "TEST");
             test_bed.run_without_update(&mut contactor);
 
             assert!(test_bed.contains_key("ELEC_CONTACTOR_TEST_IS_CLOSED"));
         }
 
+        #[test]
+        fn dumps_its_state() {
+            let mut contactor = closed_contactor();
+            contactor.powered_by(&StubApuGenerator {});
+
+            let dump = StateDump::take(&mut contactor);
+            let node = dump.find(&["ELEC", "CONTACTOR", "TEST"]).unwrap();
+
+            assert_eq!(node.read_bool("IS_CLOSED"), Some(true));
+            assert_eq!(
+                node.find(&["INPUT"]).unwrap().read_text("ORIGINS"),
+                Some("APU_GEN_1")
+            );
+        }
+
+        #[test]
+        fn emits_events_when_closing_and_opening() {
+            let mut contactor = contactor();
+            let mut bus = EventBus::new();
+            contactor.close_when(true);
+            contactor.powered_by(&StubApuGenerator {});
+            bus.collect(&mut contactor, Duration::from_secs(1));
+            bus.collect(&mut contactor, Duration::from_secs(1));
+            contactor.close_when(false);
+            bus.collect(&mut contactor, Duration::from_secs(1));
+
+            assert_eq!(
+                bus.iter()
+                    .map(|event| event.to_string())
+                    .collect::<Vec<_>>(),
+                vec![
+                    "[1.000s] ELEC/CONTACTOR/TEST closed (input from APU_GEN_1)",
+                    "[3.000s] ELEC/CONTACTOR/TEST opened",
+                ]
+            );
+        }
+
+        #[test]
+        fn restores_closed_state_from_snapshot() {
+            let snapshot = Snapshot::take(&mut closed_contactor());
+            let mut contactor = open_contactor();
+
+            snapshot.restore_into(&mut contactor);
+
+            assert!(contactor.is_closed());
+        }
+
+        #[test]
+        fn restored_closed_state_does_not_emit_event() {
+            let snapshot = Snapshot::take(&mut closed_contactor());
+            let mut contactor = contactor();
+            let mut bus = EventBus::new();
+
+            snapshot.restore_into(&mut contactor);
+            bus.collect(&mut contactor, Duration::from_secs(1));
+
+            assert!(bus.is_empty());
+        }
+
         fn contactor() -> Contactor {
-            Contactor::new("TEST")
+            Contactor::new(&mut VariableRegistry::new(), "TEST")
         }
 
         fn open_contactor() -> Contactor {
@@ -1283,10 +1454,10 @@ mod tests {
             writer: ElectricalStateWriter,
         }
         impl CurrentStateWriterTestAircraft {
-            fn new(write_type: WriteType) -> Self {
+            fn new(registry: &mut VariableRegistry, write_type: WriteType) -> Self {
                 Self {
                     write_type,
-                    writer: ElectricalStateWriter::new("TEST"),
+                    writer: ElectricalStateWriter::new(registry, "TEST"),
                 }
             }
         }
@@ -1309,8 +1480,9 @@ mod tests {
 
         #[test]
         fn writes_direct_current_state() {
-            let mut aircraft = CurrentStateWriterTestAircraft::new(WriteType::DirectCurrent);
             let mut test_bed = SimulationTestBed::new();
+            let mut aircraft =
+                CurrentStateWriterTestAircraft::new(test_bed.registry(), WriteType::DirectCurrent);
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -1322,8 +1494,11 @@ mod tests {
 
         #[test]
         fn writes_alternating_current_state() {
-            let mut aircraft = CurrentStateWriterTestAircraft::new(WriteType::AlternatingCurrent);
             let mut test_bed = SimulationTestBed::new();
+            let mut aircraft = CurrentStateWriterTestAircraft::new(
+                test_bed.registry(),
+                WriteType::AlternatingCurrent,
+            );
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -1335,9 +1510,11 @@ mod tests {
 
         #[test]
         fn writes_alternating_current_with_load_state() {
-            let mut aircraft =
-                CurrentStateWriterTestAircraft::new(WriteType::AlternatingCurrentWithLoad);
             let mut test_bed = SimulationTestBed::new();
+            let mut aircraft = CurrentStateWriterTestAircraft::new(
+                test_bed.registry(),
+                WriteType::AlternatingCurrentWithLoad,
+            );
 
             test_bed.run_aircraft(&mut aircraft);
 
@@ -0,0 +1,403 @@
+use super::{Potential, PotentialOrigin, PotentialSource};
+use uom::si::f64::*;
+
+/// Identifies a node within an [`ElectricalNetwork`].
+///
+/// [`ElectricalNetwork`]: struct.ElectricalNetwork.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug)]
+enum NodeKind {
+    Source,
+    Bus,
+    Contactor,
+    Converter {
+        origin: PotentialOrigin,
+        output: ElectricPotential,
+    },
+}
+
+#[derive(Debug)]
+struct Node {
+    kind: NodeKind,
+    supplied: Potential,
+    closed: bool,
+    available: bool,
+    input: Potential,
+}
+impl Node {
+    fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            supplied: Potential::none(),
+            closed: false,
+            available: true,
+            input: Potential::none(),
+        }
+    }
+
+    fn output(&self) -> Potential {
+        match self.kind {
+            NodeKind::Source => self.supplied,
+            NodeKind::Bus => self.input,
+            NodeKind::Contactor if self.closed => self.input,
+            NodeKind::Contactor => Potential::none(),
+            NodeKind::Converter { origin, output } if self.available && self.input.is_powered() => {
+                Potential::single(origin, output)
+            }
+            NodeKind::Converter { .. } => Potential::none(),
+        }
+    }
+}
+
+/// A declarative description of an electrical network. The network consists of
+/// sources, buses, contactors and converters which are connected by directed edges.
+/// Every tick one supplies the potential of the sources and the state of the contactors
+/// and converters, after which propagation calculates the potential at every node.
+///
+/// Propagation continues until no node's potential changes anymore. Thus the result
+/// doesn't depend on the order in which nodes are added or connected, which is
+/// particularly useful when potential flows in both directions, such as when
+/// one battery powers another through the battery bus.
+///
+/// # Examples
+/// ```rust
+/// # use systems::electrical::{ElectricalNetwork, Potential, PotentialOrigin};
+/// # use uom::si::{electric_potential::volt, f64::*};
+/// let mut network = ElectricalNetwork::new();
+/// let battery = network.add_source();
+/// let contactor = network.add_contactor();
+/// let bus = network.add_bus();
+/// network.connect_both(battery, contactor);
+/// network.connect_both(contactor, bus);
+///
+/// network.supply(
+///     battery,
+///     &Potential::single(PotentialOrigin::Battery(1), ElectricPotential::new::<volt>(28.)),
+/// );
+/// network.set_closed(contactor, true);
+/// network.propagate();
+///
+/// assert!(network.potential(bus).is_single(PotentialOrigin::Battery(1)));
+/// ```
+#[derive(Debug, Default)]
+pub struct ElectricalNetwork {
+    nodes: Vec<Node>,
+    edges: Vec<(usize, usize)>,
+}
+impl ElectricalNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node which supplies the potential given to it through [`supply`].
+    ///
+    /// [`supply`]: #method.supply
+    pub fn add_source(&mut self) -> NodeId {
+        self.add(NodeKind::Source)
+    }
+
+    /// Adds a node which conducts the potential it receives.
+    pub fn add_bus(&mut self) -> NodeId {
+        self.add(NodeKind::Bus)
+    }
+
+    /// Adds a node which conducts the potential it receives while closed.
+    /// Contactors are open until closed through [`set_closed`].
+    ///
+    /// [`set_closed`]: #method.set_closed
+    pub fn add_contactor(&mut self) -> NodeId {
+        self.add(NodeKind::Contactor)
+    }
+
+    /// Adds a node which supplies the given output potential originating from itself
+    /// whenever it receives potential and is available.
+    pub fn add_converter(&mut self, origin: PotentialOrigin, output: ElectricPotential) -> NodeId {
+        self.add(NodeKind::Converter { origin, output })
+    }
+
+    fn add(&mut self, kind: NodeKind) -> NodeId {
+        self.nodes.push(Node::new(kind));
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Connects the nodes such that potential flows from `from` to `to`.
+    pub fn connect(&mut self, from: NodeId, to: NodeId) {
+        self.edges.push((from.0, to.0));
+    }
+
+    /// Connects the nodes such that potential flows in both directions.
+    pub fn connect_both(&mut self, left: NodeId, right: NodeId) {
+        self.connect(left, right);
+        self.connect(right, left);
+    }
+
+    pub fn supply(&mut self, source: NodeId, potential: &impl PotentialSource) {
+        let node = &mut self.nodes[source.0];
+        debug_assert!(
+            matches!(node.kind, NodeKind::Source),
+            "Potential can only be supplied to sources."
+        );
+
+        node.supplied = potential.output();
+    }
+
+    pub fn set_closed(&mut self, contactor: NodeId, closed: bool) {
+        let node = &mut self.nodes[contactor.0];
+        debug_assert!(
+            matches!(node.kind, NodeKind::Contactor),
+            "Only contactors can be closed."
+        );
+
+        node.closed = closed;
+    }
+
+    pub fn set_available(&mut self, converter: NodeId, available: bool) {
+        let node = &mut self.nodes[converter.0];
+        debug_assert!(
+            matches!(node.kind, NodeKind::Converter { .. }),
+            "Only converters can be made unavailable."
+        );
+
+        node.available = available;
+    }
+
+    /// Propagates the supplied potential throughout the network.
+    pub fn propagate(&mut self) {
+        self.nodes
+            .iter_mut()
+            .for_each(|node| node.input = Potential::none());
+
+        // Every iteration propagates potential at least one more node further, unless
+        // the network is stable. The additional iterations allow for nodes which
+        // receive potential of almost equal raw value from multiple paths.
+        let max_iterations = 2 * self.nodes.len() + 1;
+        let mut iterations = 0;
+        let mut changed = true;
+        while changed && iterations < max_iterations {
+            changed = false;
+            iterations += 1;
+
+            let outputs: Vec<Potential> = self.nodes.iter().map(|node| node.output()).collect();
+            let mut inputs = vec![Potential::none(); self.nodes.len()];
+            for &(from, to) in &self.edges {
+                inputs[to] = inputs[to].merge(&outputs[from]);
+            }
+
+            for (node, input) in self.nodes.iter_mut().zip(inputs) {
+                if !same_potential(&node.input, &input) {
+                    node.input = input;
+                    changed = true;
+                }
+            }
+        }
+
+        debug_assert!(
+            !changed,
+            "The electrical network did not stabilise within {} iterations.",
+            max_iterations
+        );
+    }
+
+    /// The potential the node receives from the network. For a source this is the
+    /// potential supplied to it by other nodes, e.g. for charging a battery.
+    pub fn potential(&self, node: NodeId) -> Potential {
+        self.nodes[node.0].input
+    }
+
+    /// The potential the node passes on to the nodes it is connected to.
+    pub fn output(&self, node: NodeId) -> Potential {
+        self.nodes[node.0].output()
+    }
+}
+
+fn same_potential(left: &Potential, right: &Potential) -> bool {
+    left == right && left.origins().eq(right.origins())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uom::si::electric_potential::volt;
+
+    fn battery(number: usize, volts: f64) -> Potential {
+        Potential::single(
+            PotentialOrigin::Battery(number),
+            ElectricPotential::new::<volt>(volts),
+        )
+    }
+
+    struct BatteryBusNetwork {
+        network: ElectricalNetwork,
+        battery_1: NodeId,
+        battery_1_contactor: NodeId,
+        battery_2: NodeId,
+        battery_2_contactor: NodeId,
+        battery_bus: NodeId,
+        hot_bus_2: NodeId,
+    }
+    impl BatteryBusNetwork {
+        fn new() -> Self {
+            let mut network = ElectricalNetwork::new();
+            // Nodes are added such that the second battery is visited before the bus,
+            // which requires more than a single pass to power the second battery.
+            let hot_bus_2 = network.add_bus();
+            let battery_2 = network.add_source();
+            let battery_2_contactor = network.add_contactor();
+            let battery_bus = network.add_bus();
+            let battery_1_contactor = network.add_contactor();
+            let battery_1 = network.add_source();
+
+            network.connect_both(battery_1, battery_1_contactor);
+            network.connect_both(battery_1_contactor, battery_bus);
+            network.connect_both(battery_2, battery_2_contactor);
+            network.connect_both(battery_2_contactor, battery_bus);
+            network.connect(battery_2_contactor, hot_bus_2);
+            network.connect(battery_2, hot_bus_2);
+
+            Self {
+                network,
+                battery_1,
+                battery_1_contactor,
+                battery_2,
+                battery_2_contactor,
+                battery_bus,
+                hot_bus_2,
+            }
+        }
+
+        fn propagate(
+            &mut self,
+            battery_1: Potential,
+            battery_2: Potential,
+            contactors_closed: bool,
+        ) {
+            self.network.supply(self.battery_1, &battery_1);
+            self.network.supply(self.battery_2, &battery_2);
+            self.network
+                .set_closed(self.battery_1_contactor, contactors_closed);
+            self.network
+                .set_closed(self.battery_2_contactor, contactors_closed);
+            self.network.propagate();
+        }
+    }
+
+    #[test]
+    fn open_contactors_do_not_conduct() {
+        let mut test = BatteryBusNetwork::new();
+
+        test.propagate(battery(1, 28.), battery(2, 28.), false);
+
+        assert!(test.network.potential(test.battery_bus).is_unpowered());
+        assert!(test
+            .network
+            .potential(test.hot_bus_2)
+            .is_single(PotentialOrigin::Battery(2)));
+    }
+
+    #[test]
+    fn battery_with_higher_potential_powers_the_other_battery() {
+        let mut test = BatteryBusNetwork::new();
+
+        test.propagate(battery(1, 28.), battery(2, 25.), true);
+
+        assert!(test
+            .network
+            .potential(test.battery_2)
+            .is_single(PotentialOrigin::Battery(1)));
+        assert!(test
+            .network
+            .potential(test.hot_bus_2)
+            .is_single(PotentialOrigin::Battery(1)));
+    }
+
+    #[test]
+    fn batteries_of_equal_potential_both_power_the_bus() {
+        let mut test = BatteryBusNetwork::new();
+
+        test.propagate(battery(1, 28.), battery(2, 28.), true);
+
+        assert!(test
+            .network
+            .potential(test.battery_bus)
+            .is_pair(PotentialOrigin::Battery(1), PotentialOrigin::Battery(2)));
+    }
+
+    #[test]
+    fn potential_of_a_previous_propagation_does_not_remain() {
+        let mut test = BatteryBusNetwork::new();
+        test.propagate(battery(1, 28.), battery(2, 25.), true);
+
+        test.propagate(Potential::none(), battery(2, 25.), false);
+
+        assert!(test.network.potential(test.battery_bus).is_unpowered());
+        assert!(test.network.potential(test.battery_2).is_unpowered());
+    }
+
+    #[test]
+    fn potential_flows_only_in_the_direction_of_the_edge() {
+        let mut network = ElectricalNetwork::new();
+        let source = network.add_source();
+        let bus = network.add_bus();
+        let other_bus = network.add_bus();
+        network.connect(source, bus);
+        network.connect(other_bus, bus);
+
+        network.supply(source, &battery(1, 28.));
+        network.propagate();
+
+        assert!(network.potential(bus).is_powered());
+        assert!(network.potential(other_bus).is_unpowered());
+    }
+
+    #[test]
+    fn converter_outputs_its_own_potential_when_receiving_potential() {
+        let mut network = ElectricalNetwork::new();
+        let source = network.add_source();
+        let converter = network.add_converter(
+            PotentialOrigin::TransformerRectifier(1),
+            ElectricPotential::new::<volt>(28.),
+        );
+        let bus = network.add_bus();
+        network.connect(source, converter);
+        network.connect(converter, bus);
+
+        network.supply(
+            source,
+            &Potential::single(
+                PotentialOrigin::EngineGenerator(1),
+                ElectricPotential::new::<volt>(115.),
+            ),
+        );
+        network.propagate();
+
+        assert!(network
+            .potential(converter)
+            .is_single(PotentialOrigin::EngineGenerator(1)));
+        assert!(network
+            .potential(bus)
+            .is_single(PotentialOrigin::TransformerRectifier(1)));
+        assert_eq!(
+            network.potential(bus).raw(),
+            ElectricPotential::new::<volt>(28.)
+        );
+    }
+
+    #[test]
+    fn unavailable_converter_outputs_nothing() {
+        let mut network = ElectricalNetwork::new();
+        let source = network.add_source();
+        let converter = network.add_converter(
+            PotentialOrigin::TransformerRectifier(1),
+            ElectricPotential::new::<volt>(28.),
+        );
+        network.connect(source, converter);
+
+        network.supply(source, &battery(1, 28.));
+        network.set_available(converter, false);
+        network.propagate();
+
+        assert!(network.output(converter).is_unpowered());
+    }
+}
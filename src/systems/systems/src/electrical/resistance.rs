@@ -0,0 +1,209 @@
+use uom::si::{
+    electric_current::ampere, electric_potential::volt, electrical_resistance::ohm, f64::*,
+    power::watt,
+};
+
+/// The resistance between the internal (open circuit) potential of a source and
+/// the buses it powers. It consists of the source's internal resistance and the
+/// resistance of the feeder wires towards the buses. Sources which are given a
+/// resistance calculate the potential at their output terminals from the power
+/// consumed by their loads, such that the potential sags under load.
+///
+/// # Examples
+/// ```rust
+/// # use systems::electrical::SourceResistance;
+/// # use uom::si::{electric_potential::volt, electrical_resistance::ohm, f64::*, power::watt};
+/// let resistance = SourceResistance::new(
+///     ElectricalResistance::new::<ohm>(0.01),
+///     ElectricalResistance::new::<ohm>(0.01),
+/// );
+///
+/// let solution = resistance.solve(
+///     ElectricPotential::new::<volt>(28.),
+///     Power::new::<watt>(2800.),
+/// );
+///
+/// assert!(solution.potential() < ElectricPotential::new::<volt>(28.));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SourceResistance {
+    internal: ElectricalResistance,
+    feeder: ElectricalResistance,
+}
+impl SourceResistance {
+    pub fn new(internal: ElectricalResistance, feeder: ElectricalResistance) -> Self {
+        Self { internal, feeder }
+    }
+
+    pub fn internal(&self) -> ElectricalResistance {
+        self.internal
+    }
+
+    pub fn feeder(&self) -> ElectricalResistance {
+        self.feeder
+    }
+
+    pub fn total(&self) -> ElectricalResistance {
+        self.internal + self.feeder
+    }
+
+    /// Solves the potential at the source's output terminals and the current it
+    /// supplies when its loads consume the given power.
+    ///
+    /// The load draws a current _I_ such that _P = (E - IR) I_. When the load exceeds
+    /// the maximum power the source is able to transfer, the source supplies that
+    /// maximum instead, at half of its internal potential.
+    pub fn solve(
+        &self,
+        open_circuit_potential: ElectricPotential,
+        load: Power,
+    ) -> ResistiveSolution {
+        let e = open_circuit_potential.get::<volt>();
+        let p = load.get::<watt>().max(0.);
+        let r = self.total().get::<ohm>();
+
+        let current = if e <= 0. {
+            0.
+        } else if r <= 0. {
+            p / e
+        } else {
+            let discriminant = e.powi(2) - 4. * r * p;
+            if discriminant < 0. {
+                e / (2. * r)
+            } else {
+                (e - discriminant.sqrt()) / (2. * r)
+            }
+        };
+
+        ResistiveSolution {
+            potential: ElectricPotential::new::<volt>((e - current * r).max(0.)),
+            current: ElectricCurrent::new::<ampere>(current),
+        }
+    }
+
+    /// The current flowing into the source when potential higher than its
+    /// internal potential is applied to its output terminals, e.g. when
+    /// charging a battery.
+    pub fn charging_current(
+        &self,
+        applied_potential: ElectricPotential,
+        open_circuit_potential: ElectricPotential,
+    ) -> ElectricCurrent {
+        if self.total() <= ElectricalResistance::new::<ohm>(0.) {
+            return ElectricCurrent::new::<ampere>(0.);
+        }
+
+        ((applied_potential - open_circuit_potential) / self.total())
+            .max(ElectricCurrent::new::<ampere>(0.))
+    }
+}
+
+/// The potential and current at a source's output terminals as solved by [`SourceResistance`].
+///
+/// [`SourceResistance`]: struct.SourceResistance.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResistiveSolution {
+    potential: ElectricPotential,
+    current: ElectricCurrent,
+}
+impl ResistiveSolution {
+    pub fn potential(&self) -> ElectricPotential {
+        self.potential
+    }
+
+    pub fn current(&self) -> ElectricCurrent {
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::assert_about_eq;
+
+    fn resistance(total_ohm: f64) -> SourceResistance {
+        SourceResistance::new(
+            ElectricalResistance::new::<ohm>(total_ohm / 2.),
+            ElectricalResistance::new::<ohm>(total_ohm / 2.),
+        )
+    }
+
+    fn solve(resistance: SourceResistance, volts: f64, watts: f64) -> ResistiveSolution {
+        resistance.solve(
+            ElectricPotential::new::<volt>(volts),
+            Power::new::<watt>(watts),
+        )
+    }
+
+    #[test]
+    fn without_load_the_potential_equals_the_open_circuit_potential() {
+        let solution = solve(resistance(0.1), 28., 0.);
+
+        assert_about_eq!(solution.potential().get::<volt>(), 28.);
+        assert_about_eq!(solution.current().get::<ampere>(), 0.);
+    }
+
+    #[test]
+    fn potential_sags_under_load() {
+        let solution = solve(resistance(0.02), 28., 2800.);
+
+        assert!(solution.potential() < ElectricPotential::new::<volt>(28.));
+        assert!(solution.current() > ElectricCurrent::new::<ampere>(100.));
+    }
+
+    #[test]
+    fn solution_supplies_the_consumed_power() {
+        let solution = solve(resistance(0.05), 115., 30000.);
+
+        assert_about_eq!(
+            (solution.potential() * solution.current()).get::<watt>(),
+            30000.,
+            0.001
+        );
+    }
+
+    #[test]
+    fn higher_load_sags_potential_further() {
+        let low = solve(resistance(0.05), 28., 500.);
+        let high = solve(resistance(0.05), 28., 2000.);
+
+        assert!(high.potential() < low.potential());
+        assert!(high.current() > low.current());
+    }
+
+    #[test]
+    fn load_beyond_maximum_power_transfer_halves_the_potential() {
+        let solution = solve(resistance(1.), 28., 1000.);
+
+        assert_about_eq!(solution.potential().get::<volt>(), 14.);
+        assert_about_eq!(solution.current().get::<ampere>(), 14.);
+    }
+
+    #[test]
+    fn without_resistance_the_potential_does_not_sag() {
+        let solution = solve(resistance(0.), 28., 2800.);
+
+        assert_about_eq!(solution.potential().get::<volt>(), 28.);
+        assert_about_eq!(solution.current().get::<ampere>(), 100.);
+    }
+
+    #[test]
+    fn charging_current_depends_on_the_potential_difference() {
+        let current = resistance(0.1).charging_current(
+            ElectricPotential::new::<volt>(28.),
+            ElectricPotential::new::<volt>(27.),
+        );
+
+        assert_about_eq!(current.get::<ampere>(), 10.);
+    }
+
+    #[test]
+    fn no_charging_current_when_applied_potential_is_lower() {
+        let current = resistance(0.1).charging_current(
+            ElectricPotential::new::<volt>(26.),
+            ElectricPotential::new::<volt>(27.),
+        );
+
+        assert_about_eq!(current.get::<ampere>(), 0.);
+    }
+}
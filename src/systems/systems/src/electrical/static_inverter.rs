@@ -1,27 +1,56 @@
 use super::{
     consumption::{PowerConsumption, PowerConsumptionReport},
-    ElectricalStateWriter, Potential, PotentialOrigin, PotentialSource, PotentialTarget,
-    ProvideFrequency, ProvidePotential,
+    ConverterCharacteristics, ElectricalStateWriter, Potential, PotentialOrigin, PotentialSource,
+    PotentialTarget, ProvideFrequency, ProvidePotential, SourceResistance,
 };
-use crate::simulation::{SimulationElement, SimulatorWriter};
-use uom::si::{electric_potential::volt, f64::*, frequency::hertz};
+use crate::simulation::{SimulationElement, SimulatorWriter, VariableIdentifier, VariableRegistry};
+use uom::si::{electric_potential::volt, f64::*, frequency::hertz, power::watt};
 
 pub struct StaticInverter {
     writer: ElectricalStateWriter,
+    heat_dissipation_id: VariableIdentifier,
     input_potential: Potential,
     output_potential: ElectricPotential,
     output_frequency: Frequency,
+    output_power: Power,
+    heat_dissipation: Power,
+    characteristics: ConverterCharacteristics,
+    resistance: Option<SourceResistance>,
 }
 impl StaticInverter {
-    pub fn new() -> StaticInverter {
+    const RATED_OUTPUT_POWER_WATT: f64 = 1000.;
+    const NOMINAL_OUTPUT_POTENTIAL_VOLTS: f64 = 115.;
+
+    pub fn new(registry: &mut VariableRegistry) -> StaticInverter {
         StaticInverter {
-            writer: ElectricalStateWriter::new("STAT_INV"),
+            writer: ElectricalStateWriter::new(registry, "STAT_INV"),
+            heat_dissipation_id: registry.get("ELEC_STAT_INV_HEAT_DISSIPATION"),
             input_potential: Potential::none(),
             output_potential: ElectricPotential::new::<volt>(0.),
             output_frequency: Frequency::new::<hertz>(0.),
+            output_power: Power::new::<watt>(0.),
+            heat_dissipation: Power::new::<watt>(0.),
+            characteristics: ConverterCharacteristics::ideal(
+                Power::new::<watt>(StaticInverter::RATED_OUTPUT_POWER_WATT),
+                ElectricPotential::new::<volt>(StaticInverter::NOMINAL_OUTPUT_POTENTIAL_VOLTS),
+            ),
+            resistance: None,
         }
     }
 
+    /// Replaces the lossless conversion at a constant 115V by the given characteristics.
+    pub fn with_characteristics(mut self, characteristics: ConverterCharacteristics) -> Self {
+        self.characteristics = characteristics;
+        self
+    }
+
+    /// Makes the output potential sag under load, in addition to the regulation
+    /// of the converter's characteristics.
+    pub fn with_resistance(mut self, resistance: SourceResistance) -> Self {
+        self.resistance = Some(resistance);
+        self
+    }
+
     pub fn input_potential(&self) -> Potential {
         self.input_potential
     }
@@ -45,21 +74,30 @@ provide_frequency!(StaticInverter, (390.0..=410.0));
 impl SimulationElement for StaticInverter {
     fn write(&self, writer: &mut SimulatorWriter) {
         self.writer.write_alternating(self, writer);
+        // The power lost in converting DC to AC during the last tick.
+        writer.write_quantity(&self.heat_dissipation_id, self.heat_dissipation, watt);
     }
 
     fn consume_power_in_converters(&mut self, consumption: &mut PowerConsumption) {
         let ac_power = consumption.total_consumption_of(PotentialOrigin::StaticInverter);
 
-        // Add the AC consumption to the STAT INVs input (DC) consumption.
-        // Currently static inverter inefficiency isn't modelled.
-        // It is to be expected that DC consumption should actually be somewhat
-        // higher than AC consumption.
-        consumption.add(&self.input_potential, ac_power);
+        // Add the AC consumption and the conversion losses to the STAT INVs input (DC) consumption.
+        self.heat_dissipation = self.characteristics.heat_dissipation(ac_power);
+        consumption.add(
+            &self.input_potential,
+            self.characteristics.input_power(ac_power),
+        );
     }
 
-    fn process_power_consumption_report<T: PowerConsumptionReport>(&mut self, _: &T) {
+    fn process_power_consumption_report<T: PowerConsumptionReport>(&mut self, report: &T) {
+        self.output_power = report.total_consumption_of(PotentialOrigin::StaticInverter);
+
         self.output_potential = if self.should_provide_output() {
-            ElectricPotential::new::<volt>(115.)
+            let regulated = self.characteristics.output_potential(self.output_power);
+            match self.resistance {
+                Some(resistance) => resistance.solve(regulated, self.output_power).potential(),
+                None => regulated,
+            }
         } else {
             ElectricPotential::new::<volt>(0.)
         };
@@ -71,15 +109,10 @@ impl SimulationElement for StaticInverter {
         };
     }
 }
-impl Default for StaticInverter {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[cfg(test)]
 mod static_inverter_tests {
-    use uom::si::power::watt;
+    use ntest::assert_about_eq;
+    use uom::si::electrical_resistance::ohm;
 
     use super::*;
     use crate::{
@@ -117,6 +150,10 @@ mod static_inverter_tests {
             }
         }
 
+        fn registry(&mut self) -> &mut VariableRegistry {
+            self.test_bed.registry()
+        }
+
         fn run_aircraft(&mut self, aircraft: &mut impl Aircraft) {
             self.test_bed.run_aircraft(aircraft);
         }
@@ -125,25 +162,54 @@ mod static_inverter_tests {
             self.test_bed.read_bool("ELEC_STAT_INV_FREQUENCY_NORMAL")
         }
 
+        fn heat_dissipation(&mut self) -> Power {
+            Power::new::<watt>(self.test_bed.read_f64("ELEC_STAT_INV_HEAT_DISSIPATION"))
+        }
+
         fn potential_is_normal(&mut self) -> bool {
             self.test_bed.read_bool("ELEC_STAT_INV_POTENTIAL_NORMAL")
         }
+
+        fn potential(&mut self) -> ElectricPotential {
+            ElectricPotential::new::<volt>(self.test_bed.read_f64("ELEC_STAT_INV_POTENTIAL"))
+        }
     }
 
     struct TestAircraft {
         static_inverter: StaticInverter,
         consumer: PowerConsumer,
         static_inverter_consumption: Power,
+        battery_consumption: Power,
     }
     impl TestAircraft {
-        fn new() -> Self {
+        fn new(registry: &mut VariableRegistry) -> Self {
             Self {
-                static_inverter: StaticInverter::new(),
+                static_inverter: StaticInverter::new(registry),
                 consumer: PowerConsumer::from(ElectricalBusType::AlternatingCurrentEssential),
                 static_inverter_consumption: Power::new::<watt>(0.),
+                battery_consumption: Power::new::<watt>(0.),
             }
         }
 
+        fn with_losses(mut self) -> Self {
+            self.static_inverter = self.static_inverter.with_characteristics(
+                ConverterCharacteristics::ideal(
+                    Power::new::<watt>(1000.),
+                    ElectricPotential::new::<volt>(115.),
+                )
+                .with_efficiency(&[0., 1.], &[0.8, 0.8]),
+            );
+            self
+        }
+
+        fn with_resistance(mut self) -> Self {
+            self.static_inverter = self.static_inverter.with_resistance(SourceResistance::new(
+                ElectricalResistance::new::<ohm>(0.25),
+                ElectricalResistance::new::<ohm>(0.25),
+            ));
+            self
+        }
+
         fn with_powered_static_inverter(mut self) -> Self {
             self.static_inverter.powered_by(&Powered {});
             self
@@ -165,6 +231,10 @@ mod static_inverter_tests {
         fn static_inverter_consumption(&self) -> Power {
             self.static_inverter_consumption
         }
+
+        fn battery_consumption(&self) -> Power {
+            self.battery_consumption
+        }
     }
     impl Aircraft for TestAircraft {
         fn get_supplied_power(&mut self) -> SuppliedPower {
@@ -193,13 +263,14 @@ mod static_inverter_tests {
         fn process_power_consumption_report<T: PowerConsumptionReport>(&mut self, report: &T) {
             self.static_inverter_consumption =
                 report.total_consumption_of(PotentialOrigin::StaticInverter);
+            self.battery_consumption = report.total_consumption_of(PotentialOrigin::Battery(1));
         }
     }
 
     #[test]
     fn when_unpowered_has_no_output() {
-        let mut aircraft = TestAircraft::new().with_unpowered_static_inverter();
         let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_unpowered_static_inverter();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -208,8 +279,8 @@ mod static_inverter_tests {
 
     #[test]
     fn when_powered_has_output() {
-        let mut aircraft = TestAircraft::new().with_powered_static_inverter();
         let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_powered_static_inverter();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -218,8 +289,8 @@ mod static_inverter_tests {
 
     #[test]
     fn when_unpowered_frequency_is_not_normal() {
-        let mut aircraft = TestAircraft::new().with_unpowered_static_inverter();
         let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_unpowered_static_inverter();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -228,8 +299,8 @@ mod static_inverter_tests {
 
     #[test]
     fn when_powered_frequency_is_normal() {
-        let mut aircraft = TestAircraft::new().with_powered_static_inverter();
         let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_powered_static_inverter();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -238,8 +309,8 @@ mod static_inverter_tests {
 
     #[test]
     fn when_unpowered_potential_is_not_normal() {
-        let mut aircraft = TestAircraft::new().with_unpowered_static_inverter();
         let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_unpowered_static_inverter();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -248,8 +319,8 @@ mod static_inverter_tests {
 
     #[test]
     fn when_powered_potential_is_normal() {
-        let mut aircraft = TestAircraft::new().with_powered_static_inverter();
         let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_powered_static_inverter();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -258,8 +329,8 @@ mod static_inverter_tests {
 
     #[test]
     fn when_unpowered_has_no_consumption() {
-        let mut aircraft = TestAircraft::new().with_unpowered_static_inverter();
         let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_unpowered_static_inverter();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -271,8 +342,8 @@ mod static_inverter_tests {
 
     #[test]
     fn when_powered_without_demand_has_no_consumption() {
-        let mut aircraft = TestAircraft::new().with_powered_static_inverter();
         let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_powered_static_inverter();
 
         aircraft.power_demand(Power::new::<watt>(0.));
         test_bed.run_aircraft(&mut aircraft);
@@ -285,8 +356,8 @@ mod static_inverter_tests {
 
     #[test]
     fn when_powered_with_demand_has_consumption() {
-        let mut aircraft = TestAircraft::new().with_powered_static_inverter();
         let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_powered_static_inverter();
 
         aircraft.power_demand(Power::new::<watt>(200.));
         test_bed.run_aircraft(&mut aircraft);
@@ -297,10 +368,63 @@ mod static_inverter_tests {
         );
     }
 
+    #[test]
+    fn without_losses_input_consumption_equals_output_consumption() {
+        let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry()).with_powered_static_inverter();
+
+        aircraft.power_demand(Power::new::<watt>(200.));
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert_eq!(aircraft.battery_consumption(), Power::new::<watt>(200.));
+        assert_eq!(test_bed.heat_dissipation(), Power::new::<watt>(0.));
+    }
+
+    #[test]
+    fn with_losses_input_consumption_includes_losses() {
+        let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry())
+            .with_losses()
+            .with_powered_static_inverter();
+
+        aircraft.power_demand(Power::new::<watt>(400.));
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert_about_eq!(aircraft.battery_consumption().get::<watt>(), 500.);
+        assert_about_eq!(test_bed.heat_dissipation().get::<watt>(), 100.);
+    }
+
+    #[test]
+    fn when_powered_with_resistance_and_demand_potential_sags() {
+        let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry())
+            .with_resistance()
+            .with_powered_static_inverter();
+
+        aircraft.power_demand(Power::new::<watt>(800.));
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert!(test_bed.potential() < ElectricPotential::new::<volt>(115.));
+        assert!(test_bed.potential_is_normal());
+    }
+
+    #[test]
+    fn when_powered_with_resistance_without_demand_potential_does_not_sag() {
+        let mut test_bed = StaticInverterTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry())
+            .with_resistance()
+            .with_powered_static_inverter();
+
+        aircraft.power_demand(Power::new::<watt>(0.));
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert_about_eq!(test_bed.potential().get::<volt>(), 115.);
+    }
+
     #[test]
     fn writes_its_state() {
-        let mut aircraft = TestAircraft::new();
         let mut test_bed = SimulationTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry());
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -1,37 +1,73 @@
 use super::{
     consumption::{PowerConsumption, PowerConsumptionReport},
-    ElectricalStateWriter, Potential, PotentialOrigin, PotentialSource, PotentialTarget,
-    ProvideCurrent, ProvidePotential,
+    ConverterCharacteristics, ElectricalStateWriter, Potential, PotentialOrigin, PotentialSource,
+    PotentialTarget, ProvideCurrent, ProvidePotential, SourceResistance,
 };
-use crate::simulation::{SimulationElement, SimulatorWriter};
-use uom::si::{electric_current::ampere, electric_potential::volt, f64::*};
+use crate::{
+    failures::{Failure, FailureType},
+    simulation::{
+        SimulationElement, SimulationElementVisitor, SimulatorWriter, VariableIdentifier,
+        VariableRegistry,
+    },
+};
+use uom::si::{electric_current::ampere, electric_potential::volt, f64::*, power::watt};
 
 pub struct TransformerRectifier {
     writer: ElectricalStateWriter,
+    heat_dissipation_id: VariableIdentifier,
     number: usize,
     input_potential: Potential,
-    failed: bool,
+    failure: Failure,
     output_potential: ElectricPotential,
     output_current: ElectricCurrent,
+    output_power: Power,
+    heat_dissipation: Power,
+    characteristics: ConverterCharacteristics,
+    resistance: Option<SourceResistance>,
 }
 impl TransformerRectifier {
-    pub fn new(number: usize) -> TransformerRectifier {
+    const RATED_OUTPUT_CURRENT_AMPERE: f64 = 200.;
+    const NOMINAL_OUTPUT_POTENTIAL_VOLTS: f64 = 28.;
+
+    pub fn new(registry: &mut VariableRegistry, number: usize) -> TransformerRectifier {
         TransformerRectifier {
-            writer: ElectricalStateWriter::new(&format!("TR_{}", number)),
+            writer: ElectricalStateWriter::new(registry, &format!("TR_{}", number)),
+            heat_dissipation_id: registry.get(&format!("ELEC_TR_{}_HEAT_DISSIPATION", number)),
             number,
             input_potential: Potential::none(),
-            failed: false,
+            failure: Failure::new(registry, FailureType::TransformerRectifier(number)),
             output_potential: ElectricPotential::new::<volt>(0.),
             output_current: ElectricCurrent::new::<ampere>(0.),
+            output_power: Power::new::<watt>(0.),
+            heat_dissipation: Power::new::<watt>(0.),
+            characteristics: ConverterCharacteristics::ideal(
+                ElectricCurrent::new::<ampere>(TransformerRectifier::RATED_OUTPUT_CURRENT_AMPERE)
+                    * ElectricPotential::new::<volt>(
+                        TransformerRectifier::NOMINAL_OUTPUT_POTENTIAL_VOLTS,
+                    ),
+                ElectricPotential::new::<volt>(
+                    TransformerRectifier::NOMINAL_OUTPUT_POTENTIAL_VOLTS,
+                ),
+            ),
+            resistance: None,
         }
     }
 
-    pub fn fail(&mut self) {
-        self.failed = true;
+    /// Replaces the lossless conversion at a constant 28V by the given characteristics.
+    pub fn with_characteristics(mut self, characteristics: ConverterCharacteristics) -> Self {
+        self.characteristics = characteristics;
+        self
+    }
+
+    /// Makes the output potential sag under load, in addition to the regulation
+    /// of the converter's characteristics.
+    pub fn with_resistance(mut self, resistance: SourceResistance) -> Self {
+        self.resistance = Some(resistance);
+        self
     }
 
     pub fn failed(&self) -> bool {
-        self.failed
+        self.failure.is_active()
     }
 
     pub fn input_potential(&self) -> Potential {
@@ -39,17 +75,26 @@ impl TransformerRectifier {
     }
 
     fn should_provide_output(&self) -> bool {
-        !self.failed && self.input_potential.is_powered()
+        !self.failed() && self.input_potential.is_powered()
+    }
+
+    fn regulated_output_potential(&self) -> ElectricPotential {
+        self.characteristics.output_potential(self.output_power)
     }
 }
 potential_target!(TransformerRectifier);
 impl PotentialSource for TransformerRectifier {
     fn output(&self) -> Potential {
         if self.should_provide_output() {
-            Potential::single(
-                PotentialOrigin::TransformerRectifier(self.number),
-                ElectricPotential::new::<volt>(28.),
-            )
+            let raw = if self.resistance.is_some()
+                && self.output_potential > ElectricPotential::new::<volt>(0.)
+            {
+                self.output_potential
+            } else {
+                self.regulated_output_potential()
+            };
+
+            Potential::single(PotentialOrigin::TransformerRectifier(self.number), raw)
         } else {
             Potential::none()
         }
@@ -66,37 +111,57 @@ impl ProvideCurrent for TransformerRectifier {
 }
 provide_potential!(TransformerRectifier, (25.0..=31.0));
 impl SimulationElement for TransformerRectifier {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.failure.accept(visitor);
+
+        visitor.visit(self);
+    }
+
     fn write(&self, writer: &mut SimulatorWriter) {
         self.writer.write_direct(self, writer);
+        // The power lost in converting AC to DC during the last tick.
+        writer.write_quantity(&self.heat_dissipation_id, self.heat_dissipation, watt);
     }
 
     fn consume_power_in_converters(&mut self, consumption: &mut PowerConsumption) {
         let dc_power =
             consumption.total_consumption_of(PotentialOrigin::TransformerRectifier(self.number));
 
-        // Add the DC consumption to the TRs input (AC) consumption.
-        // Currently transformer rectifier inefficiency isn't modelled.
-        // It is to be expected that AC consumption should actually be somewhat
-        // higher than DC consumption.
-        consumption.add(&self.input_potential, dc_power);
+        // Add the DC consumption and the conversion losses to the TRs input (AC) consumption.
+        self.heat_dissipation = self.characteristics.heat_dissipation(dc_power);
+        consumption.add(
+            &self.input_potential,
+            self.characteristics.input_power(dc_power),
+        );
     }
 
     fn process_power_consumption_report<T: PowerConsumptionReport>(&mut self, report: &T) {
-        self.output_potential = if self.should_provide_output() {
-            ElectricPotential::new::<volt>(28.)
-        } else {
-            ElectricPotential::new::<volt>(0.)
-        };
-
         let consumption =
             report.total_consumption_of(PotentialOrigin::TransformerRectifier(self.number));
-        self.output_current = consumption / self.output_potential;
+        self.output_power = consumption;
+
+        match self.resistance {
+            Some(resistance) if self.should_provide_output() => {
+                let solution = resistance.solve(self.regulated_output_potential(), consumption);
+                self.output_potential = solution.potential();
+                self.output_current = solution.current();
+            }
+            _ => {
+                self.output_potential = if self.should_provide_output() {
+                    self.regulated_output_potential()
+                } else {
+                    ElectricPotential::new::<volt>(0.)
+                };
+                self.output_current = consumption / self.output_potential;
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod transformer_rectifier_tests {
-    use uom::si::power::watt;
+    use ntest::assert_about_eq;
+    use uom::si::electrical_resistance::ohm;
 
     use super::*;
     use crate::{
@@ -117,6 +182,10 @@ mod transformer_rectifier_tests {
             }
         }
 
+        fn registry(&mut self) -> &mut VariableRegistry {
+            self.test_bed.registry()
+        }
+
         fn run_aircraft(&mut self, aircraft: &mut impl Aircraft) {
             self.test_bed.run_aircraft(aircraft);
         }
@@ -125,6 +194,10 @@ mod transformer_rectifier_tests {
             self.test_bed.read_bool("ELEC_TR_1_CURRENT_NORMAL")
         }
 
+        fn heat_dissipation(&mut self) -> Power {
+            Power::new::<watt>(self.test_bed.read_f64("ELEC_TR_1_HEAT_DISSIPATION"))
+        }
+
         fn potential_is_normal(&mut self) -> bool {
             self.test_bed.read_bool("ELEC_TR_1_POTENTIAL_NORMAL")
         }
@@ -132,34 +205,65 @@ mod transformer_rectifier_tests {
         fn current(&mut self) -> ElectricCurrent {
             ElectricCurrent::new::<ampere>(self.test_bed.read_f64("ELEC_TR_1_CURRENT"))
         }
+
+        fn potential(&mut self) -> ElectricPotential {
+            ElectricPotential::new::<volt>(self.test_bed.read_f64("ELEC_TR_1_POTENTIAL"))
+        }
+
+        fn fail_transformer_rectifier(&mut self) {
+            self.test_bed.write_bool("FAILURE_TR_1", true);
+        }
     }
 
     struct TestAircraft {
         transformer_rectifier: TransformerRectifier,
         consumer: PowerConsumer,
         transformer_rectifier_consumption: Power,
+        apu_generator_consumption: Power,
     }
     impl TestAircraft {
-        fn new() -> Self {
+        fn new(registry: &mut VariableRegistry) -> Self {
             Self {
-                transformer_rectifier: TransformerRectifier::new(1),
+                transformer_rectifier: TransformerRectifier::new(registry, 1),
                 consumer: PowerConsumer::from(ElectricalBusType::DirectCurrent(1)),
                 transformer_rectifier_consumption: Power::new::<watt>(0.),
+                apu_generator_consumption: Power::new::<watt>(0.),
             }
         }
 
+        fn with_characteristics(mut self) -> Self {
+            self.transformer_rectifier = self.transformer_rectifier.with_characteristics(
+                ConverterCharacteristics::ideal(
+                    Power::new::<watt>(5600.),
+                    ElectricPotential::new::<volt>(28.),
+                )
+                .with_efficiency(&[0., 1.], &[0.8, 0.8])
+                .with_regulation(
+                    ElectricPotential::new::<volt>(28.5),
+                    ElectricPotential::new::<volt>(27.5),
+                ),
+            );
+            self
+        }
+
         fn with_powered_transformer_rectifier(mut self) -> Self {
             self.transformer_rectifier.powered_by(&Powered {});
             self
         }
 
-        fn with_unpowered_transformer_rectifier(mut self) -> Self {
-            self.transformer_rectifier.powered_by(&Powerless {});
+        fn with_resistance(mut self) -> Self {
+            self.transformer_rectifier =
+                self.transformer_rectifier
+                    .with_resistance(SourceResistance::new(
+                        ElectricalResistance::new::<ohm>(0.01),
+                        ElectricalResistance::new::<ohm>(0.01),
+                    ));
             self
         }
 
-        fn fail_transformer_rectifier(&mut self) {
-            self.transformer_rectifier.fail();
+        fn with_unpowered_transformer_rectifier(mut self) -> Self {
+            self.transformer_rectifier.powered_by(&Powerless {});
+            self
         }
 
         fn transformer_rectifier_is_powered(&self) -> bool {
@@ -173,6 +277,10 @@ mod transformer_rectifier_tests {
         fn transformer_rectifier_consumption(&self) -> Power {
             self.transformer_rectifier_consumption
         }
+
+        fn apu_generator_consumption(&self) -> Power {
+            self.apu_generator_consumption
+        }
     }
     impl Aircraft for TestAircraft {
         fn get_supplied_power(&mut self) -> SuppliedPower {
@@ -201,6 +309,8 @@ mod transformer_rectifier_tests {
         fn process_power_consumption_report<T: PowerConsumptionReport>(&mut self, report: &T) {
             self.transformer_rectifier_consumption =
                 report.total_consumption_of(PotentialOrigin::TransformerRectifier(1));
+            self.apu_generator_consumption =
+                report.total_consumption_of(PotentialOrigin::ApuGenerator(1));
         }
     }
 
@@ -223,8 +333,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_unpowered_has_no_output() {
-        let mut aircraft = TestAircraft::new().with_unpowered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_unpowered_transformer_rectifier();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -233,8 +344,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_powered_has_output() {
-        let mut aircraft = TestAircraft::new().with_powered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_powered_transformer_rectifier();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -243,10 +355,11 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_powered_but_failed_has_no_output() {
-        let mut aircraft = TestAircraft::new().with_powered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_powered_transformer_rectifier();
 
-        aircraft.fail_transformer_rectifier();
+        test_bed.fail_transformer_rectifier();
         test_bed.run_aircraft(&mut aircraft);
 
         assert!(!aircraft.transformer_rectifier_is_powered());
@@ -254,8 +367,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_unpowered_current_is_not_normal() {
-        let mut aircraft = TestAircraft::new().with_unpowered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_unpowered_transformer_rectifier();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -264,8 +378,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_powered_with_too_little_demand_current_is_not_normal() {
-        let mut aircraft = TestAircraft::new().with_powered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_powered_transformer_rectifier();
 
         aircraft.power_demand(Power::new::<watt>(5. * 28.));
         test_bed.run_aircraft(&mut aircraft);
@@ -275,8 +390,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_powered_with_enough_demand_current_is_normal() {
-        let mut aircraft = TestAircraft::new().with_powered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_powered_transformer_rectifier();
 
         aircraft.power_demand(Power::new::<watt>((5. * 28.) + 1.));
         test_bed.run_aircraft(&mut aircraft);
@@ -286,8 +402,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_unpowered_potential_is_not_normal() {
-        let mut aircraft = TestAircraft::new().with_unpowered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_unpowered_transformer_rectifier();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -296,8 +413,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_powered_potential_is_normal() {
-        let mut aircraft = TestAircraft::new().with_powered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_powered_transformer_rectifier();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -306,8 +424,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_unpowered_has_no_consumption() {
-        let mut aircraft = TestAircraft::new().with_unpowered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_unpowered_transformer_rectifier();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -319,8 +438,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_powered_without_demand_has_no_consumption() {
-        let mut aircraft = TestAircraft::new().with_powered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_powered_transformer_rectifier();
 
         aircraft.power_demand(Power::new::<watt>(0.));
         test_bed.run_aircraft(&mut aircraft);
@@ -333,8 +453,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_powered_with_demand_has_consumption() {
-        let mut aircraft = TestAircraft::new().with_powered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_powered_transformer_rectifier();
 
         aircraft.power_demand(Power::new::<watt>(200.));
         test_bed.run_aircraft(&mut aircraft);
@@ -347,8 +468,9 @@ mod transformer_rectifier_tests {
 
     #[test]
     fn when_powered_with_demand_current_is_based_on_demand() {
-        let mut aircraft = TestAircraft::new().with_powered_transformer_rectifier();
         let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_powered_transformer_rectifier();
 
         aircraft.power_demand(Power::new::<watt>(200.));
         test_bed.run_aircraft(&mut aircraft);
@@ -359,10 +481,69 @@ mod transformer_rectifier_tests {
         );
     }
 
+    #[test]
+    fn when_powered_with_resistance_and_demand_potential_sags() {
+        let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry())
+            .with_resistance()
+            .with_powered_transformer_rectifier();
+
+        aircraft.power_demand(Power::new::<watt>(2800.));
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert!(test_bed.potential() < ElectricPotential::new::<volt>(28.));
+        assert!(test_bed.potential_is_normal());
+        assert!(test_bed.current() > ElectricCurrent::new::<ampere>(100.));
+    }
+
+    #[test]
+    fn when_powered_with_resistance_without_demand_potential_does_not_sag() {
+        let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry())
+            .with_resistance()
+            .with_powered_transformer_rectifier();
+
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert_eq!(test_bed.potential(), ElectricPotential::new::<volt>(28.));
+    }
+
+    #[test]
+    fn with_losses_ac_consumption_includes_losses() {
+        let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry())
+            .with_characteristics()
+            .with_powered_transformer_rectifier();
+
+        aircraft.power_demand(Power::new::<watt>(2000.));
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert_about_eq!(aircraft.apu_generator_consumption().get::<watt>(), 2500.);
+        assert_about_eq!(test_bed.heat_dissipation().get::<watt>(), 500.);
+    }
+
+    #[test]
+    fn with_regulation_potential_droops_with_load() {
+        let mut test_bed = TransformerRectifierTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry())
+            .with_characteristics()
+            .with_powered_transformer_rectifier();
+
+        test_bed.run_aircraft(&mut aircraft);
+        let no_load_potential = test_bed.potential();
+
+        aircraft.power_demand(Power::new::<watt>(5600.));
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert_about_eq!(no_load_potential.get::<volt>(), 28.5);
+        assert_about_eq!(test_bed.potential().get::<volt>(), 27.5);
+    }
+
     #[test]
     fn writes_its_state() {
-        let mut aircraft = TestAircraft::new().with_powered_transformer_rectifier();
         let mut test_bed = SimulationTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry()).with_powered_transformer_rectifier();
 
         test_bed.run_aircraft(&mut aircraft);
 
@@ -2,12 +2,14 @@ use uom::si::{angular_velocity::revolution_per_minute, f64::*, pressure::psi, ra
 
 use crate::{
     shared::EngineCorrectedN2,
-    simulation::{SimulationElement, SimulatorReader, UpdateContext},
+    simulation::{
+        SimulationElement, SimulatorReader, UpdateContext, VariableIdentifier, VariableRegistry,
+    },
 };
 
 use super::Engine;
 pub struct LeapEngine {
-    corrected_n2_id: String,
+    corrected_n2_id: VariableIdentifier,
     corrected_n2: Ratio,
     n2_speed: AngularVelocity,
     hydraulic_pump_output_speed: AngularVelocity,
@@ -23,9 +25,9 @@ impl LeapEngine {
 
     const MIN_IDLE_N2_THRESHOLD: f64 = 60.;
 
-    pub fn new(number: usize) -> LeapEngine {
+    pub fn new(registry: &mut VariableRegistry, number: usize) -> LeapEngine {
         LeapEngine {
-            corrected_n2_id: format!("TURB ENG CORRECTED N2:{}", number),
+            corrected_n2_id: registry.get(&format!("TURB ENG CORRECTED N2:{}", number)),
             corrected_n2: Ratio::new::<percent>(0.),
             n2_speed: AngularVelocity::new::<revolution_per_minute>(0.),
             hydraulic_pump_output_speed: AngularVelocity::new::<revolution_per_minute>(0.),
@@ -47,7 +49,7 @@ impl LeapEngine {
 }
 impl SimulationElement for LeapEngine {
     fn read(&mut self, reader: &mut SimulatorReader) {
-        self.corrected_n2 = Ratio::new::<percent>(reader.read_f64(&self.corrected_n2_id));
+        self.corrected_n2 = reader.read_quantity(&self.corrected_n2_id, percent);
         self.update_parameters();
     }
 }
@@ -3,7 +3,7 @@ use uom::si::f64::*;
 use crate::{
     overhead::FirePushButton,
     shared::{EngineCorrectedN2, EngineFirePushButtons},
-    simulation::{SimulationElement, SimulationElementVisitor},
+    simulation::{SimulationElement, SimulationElementVisitor, VariableRegistry},
 };
 
 pub mod leap_engine;
@@ -20,9 +20,12 @@ pub struct EngineFireOverheadPanel {
     engine_fire_push_buttons: [FirePushButton; 2],
 }
 impl EngineFireOverheadPanel {
-    pub fn new() -> Self {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
         Self {
-            engine_fire_push_buttons: [FirePushButton::new("ENG1"), FirePushButton::new("ENG2")],
+            engine_fire_push_buttons: [
+                FirePushButton::new(registry, "ENG1"),
+                FirePushButton::new(registry, "ENG2"),
+            ],
         }
     }
 }
@@ -40,12 +43,6 @@ impl SimulationElement for EngineFireOverheadPanel {
         visitor.visit(self);
     }
 }
-impl Default for EngineFireOverheadPanel {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[cfg(test)]
 mod engine_fire_overhead_panel_tests {
     use crate::simulation::test::SimulationTestBed;
@@ -54,7 +51,7 @@ mod engine_fire_overhead_panel_tests {
 
     #[test]
     fn after_construction_fire_push_buttons_are_not_released() {
-        let panel = EngineFireOverheadPanel::new();
+        let panel = EngineFireOverheadPanel::new(&mut VariableRegistry::new());
 
         assert_eq!(panel.is_released(1), false);
         assert_eq!(panel.is_released(2), false);
@@ -62,9 +59,9 @@ mod engine_fire_overhead_panel_tests {
 
     #[test]
     fn fire_push_button_is_released_returns_false_when_not_released() {
-        let mut panel = EngineFireOverheadPanel::new();
-
         let mut test_bed = SimulationTestBed::new();
+        let mut panel = EngineFireOverheadPanel::new(test_bed.registry());
+
         test_bed.write_bool("FIRE_BUTTON_ENG1", false);
         test_bed.run(&mut panel, |_, _| {});
 
@@ -73,9 +70,9 @@ mod engine_fire_overhead_panel_tests {
 
     #[test]
     fn fire_push_button_is_released_returns_true_when_released() {
-        let mut panel = EngineFireOverheadPanel::new();
-
         let mut test_bed = SimulationTestBed::new();
+        let mut panel = EngineFireOverheadPanel::new(test_bed.registry());
+
         test_bed.write_bool("FIRE_BUTTON_ENG1", true);
         test_bed.run(&mut panel, |_, _| {});
 
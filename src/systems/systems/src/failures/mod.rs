@@ -0,0 +1,170 @@
+//! Failures can be activated at runtime by setting the simulator variable belonging to the
+//! failure to true. Components which can fail own a [`Failure`], which reads that variable.
+//!
+//! [`Failure`]: struct.Failure.html
+
+use crate::electrical::PotentialOrigin;
+use crate::simulation::{SimulationElement, SimulatorReader, VariableIdentifier, VariableRegistry};
+
+/// Identifies a failure which can be activated in the aircraft's systems.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FailureType {
+    /// The transformer rectifier with the given number no longer provides output.
+    TransformerRectifier(usize),
+    /// The oil of the integrated drive generator with the given number overheats.
+    IntegratedDriveGeneratorOverheat(usize),
+    /// The oil of the integrated drive generator with the given number leaks,
+    /// such that its oil pressure becomes low.
+    IntegratedDriveGeneratorOilLeak(usize),
+    /// A cell of the battery with the given number fails, such that the battery
+    /// no longer provides output and can no longer be charged.
+    BatteryCell(usize),
+    /// The engine driven pump of the engine with the given number no longer provides flow.
+    EngineDrivenPump(usize),
+    BlueElectricPump,
+    YellowElectricPump,
+    PowerTransferUnit,
+    GreenHydraulicLeak,
+    BlueHydraulicLeak,
+    YellowHydraulicLeak,
+    /// The APU fuel valve remains stuck in the position it was in when the failure occurred.
+    ApuFuelValveStuck,
+    /// The feeder between the generator providing the given potential origin and its
+    /// line contactor shorts, which trips the generator's differential protection.
+    GeneratorFeeder(PotentialOrigin),
+}
+impl FailureType {
+    /// The name of the simulator variable which activates the failure.
+    pub fn variable_name(&self) -> String {
+        match self {
+            FailureType::TransformerRectifier(number) => format!("FAILURE_TR_{}", number),
+            FailureType::IntegratedDriveGeneratorOverheat(number) => {
+                format!("FAILURE_ENG_GEN_{}_IDG_OVERHEAT", number)
+            }
+            FailureType::IntegratedDriveGeneratorOilLeak(number) => {
+                format!("FAILURE_ENG_GEN_{}_IDG_OIL_LEAK", number)
+            }
+            FailureType::BatteryCell(number) => format!("FAILURE_BAT_{}_CELL", number),
+            FailureType::EngineDrivenPump(number) => format!("FAILURE_HYD_EDPUMP_{}", number),
+            FailureType::BlueElectricPump => "FAILURE_HYD_BLUE_EPUMP".to_owned(),
+            FailureType::YellowElectricPump => "FAILURE_HYD_YELLOW_EPUMP".to_owned(),
+            FailureType::PowerTransferUnit => "FAILURE_HYD_PTU".to_owned(),
+            FailureType::GreenHydraulicLeak => "FAILURE_HYD_GREEN_LEAK".to_owned(),
+            FailureType::BlueHydraulicLeak => "FAILURE_HYD_BLUE_LEAK".to_owned(),
+            FailureType::YellowHydraulicLeak => "FAILURE_HYD_YELLOW_LEAK".to_owned(),
+            FailureType::ApuFuelValveStuck => "FAILURE_APU_FUEL_VALVE_STUCK".to_owned(),
+            FailureType::GeneratorFeeder(origin) => format!("FAILURE_{}_FEEDER", origin),
+        }
+    }
+}
+
+/// Reads whether or not a failure is active. Add it as a child
+/// to the element which is affected by the failure.
+pub struct Failure {
+    failure_type: FailureType,
+    is_active_id: VariableIdentifier,
+    is_active: bool,
+}
+impl Failure {
+    pub fn new(registry: &mut VariableRegistry, failure_type: FailureType) -> Self {
+        Self {
+            failure_type,
+            is_active_id: registry.get(&failure_type.variable_name()),
+            is_active: false,
+        }
+    }
+
+    pub fn failure_type(&self) -> FailureType {
+        self.failure_type
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+}
+impl SimulationElement for Failure {
+    fn read(&mut self, reader: &mut SimulatorReader) {
+        self.is_active = reader.read_bool(&self.is_active_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{test::SimulationTestBed, Aircraft, SimulationElementVisitor};
+
+    struct TestAircraft {
+        failure: Failure,
+    }
+    impl TestAircraft {
+        fn new(registry: &mut VariableRegistry, failure_type: FailureType) -> Self {
+            Self {
+                failure: Failure::new(registry, failure_type),
+            }
+        }
+    }
+    impl Aircraft for TestAircraft {}
+    impl SimulationElement for TestAircraft {
+        fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+            self.failure.accept(visitor);
+
+            visitor.visit(self);
+        }
+    }
+
+    #[test]
+    fn generator_feeder_failure_is_named_after_the_generator() {
+        assert_eq!(
+            FailureType::GeneratorFeeder(PotentialOrigin::ApuGenerator(1)).variable_name(),
+            "FAILURE_APU_GEN_1_FEEDER"
+        );
+    }
+
+    #[test]
+    fn failure_is_inactive_by_default() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry(), FailureType::TransformerRectifier(1));
+
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert!(!aircraft.failure.is_active());
+    }
+
+    #[test]
+    fn failure_is_activated_by_its_variable() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry(), FailureType::TransformerRectifier(1));
+        test_bed.write_bool("FAILURE_TR_1", true);
+
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert!(aircraft.failure.is_active());
+    }
+
+    #[test]
+    fn failure_is_not_activated_by_the_variable_of_another_failure() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut aircraft =
+            TestAircraft::new(test_bed.registry(), FailureType::TransformerRectifier(1));
+        test_bed.write_bool("FAILURE_TR_2", true);
+
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert!(!aircraft.failure.is_active());
+    }
+
+    #[test]
+    fn failure_can_be_deactivated() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut aircraft = TestAircraft::new(test_bed.registry(), FailureType::PowerTransferUnit);
+        test_bed.write_bool("FAILURE_HYD_PTU", true);
+        test_bed.run_aircraft(&mut aircraft);
+
+        test_bed.write_bool("FAILURE_HYD_PTU", false);
+        test_bed.run_aircraft(&mut aircraft);
+
+        assert!(!aircraft.failure.is_active());
+    }
+}
@@ -0,0 +1,423 @@
+//! The simulator remains the owner of the fuel quantities, as it burns and transfers the fuel.
+//! The types in this module use those quantities to determine the state of the pumps and
+//! valves, and the fuel pressure available to the engines and the APU.
+
+use crate::{
+    pneumatic::Valve,
+    simulation::{
+        SimulationElement, SimulatorReader, SimulatorWriter, Snapshot, StateDump,
+        VariableIdentifier, VariableRegistry,
+    },
+};
+use uom::si::{f64::*, mass::pound, pressure::psi};
+
+/// Signals to a fuel pump whether or not it should run.
+pub trait FuelPumpController {
+    fn should_run_fuel_pump(&self) -> bool;
+}
+
+/// Signals to a fuel valve whether it should open or close.
+pub trait FuelValveController {
+    fn should_open_fuel_valve(&self) -> bool;
+}
+
+/// Something which provides fuel at a certain pressure, such as a pump or a feed line.
+pub trait FuelPressureSource {
+    fn fuel_pressure(&self) -> Pressure;
+}
+
+pub struct FuelTank {
+    quantity_variable_name: String,
+    quantity_id: VariableIdentifier,
+    quantity: Mass,
+}
+impl FuelTank {
+    /// Creates a tank of which the quantity is read from the given simulator variable in pounds.
+    pub fn new(registry: &mut VariableRegistry, quantity_variable_name: &str) -> Self {
+        Self {
+            quantity_variable_name: quantity_variable_name.to_owned(),
+            quantity_id: registry.get(quantity_variable_name),
+            quantity: Mass::new::<pound>(0.),
+        }
+    }
+
+    pub fn quantity(&self) -> Mass {
+        self.quantity
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.quantity <= Mass::new::<pound>(0.)
+    }
+
+    #[cfg(test)]
+    fn set_quantity(&mut self, quantity: Mass) {
+        self.quantity = quantity;
+    }
+}
+impl SimulationElement for FuelTank {
+    fn read(&mut self, reader: &mut SimulatorReader) {
+        self.quantity = reader.read_quantity(&self.quantity_id, pound);
+    }
+
+    // The simulator owns the fuel quantity, thus the restored quantity only lasts
+    // until the simulator's quantity is read.
+    fn save(&self, snapshot: &mut Snapshot) {
+        snapshot.write_f64(&self.quantity_variable_name, self.quantity.get::<pound>());
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(quantity) = snapshot.read_f64(&self.quantity_variable_name) {
+            self.quantity = Mass::new::<pound>(quantity);
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        state
+            .node("FUEL")
+            .node("TANK")
+            .node(&self.quantity_variable_name)
+            .write_f64("QUANTITY", self.quantity.get::<pound>());
+    }
+}
+
+/// A fuel pump, such as a tank booster pump or the APU fuel pump.
+/// The pump only delivers pressure when it is running and has fuel to pump.
+pub struct FuelPump {
+    running_id: VariableIdentifier,
+
+    running: bool,
+    has_fuel_supply: bool,
+    output_pressure: Pressure,
+}
+impl FuelPump {
+    pub fn new(registry: &mut VariableRegistry, id: &str, output_pressure: Pressure) -> Self {
+        Self {
+            running_id: registry.get(&format!("FUEL_PUMP_{}_IS_RUNNING", id)),
+            running: false,
+            has_fuel_supply: false,
+            output_pressure,
+        }
+    }
+
+    pub fn update<T: FuelPumpController>(&mut self, controller: &T, has_fuel_supply: bool) {
+        self.running = controller.should_run_fuel_pump();
+        self.has_fuel_supply = has_fuel_supply;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+}
+impl FuelPressureSource for FuelPump {
+    fn fuel_pressure(&self) -> Pressure {
+        if self.running && self.has_fuel_supply {
+            self.output_pressure
+        } else {
+            Pressure::new::<psi>(0.)
+        }
+    }
+}
+impl SimulationElement for FuelPump {
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_bool(&self.running_id, self.is_running());
+    }
+}
+
+/// A motor operated fuel valve, such as an engine LP valve, the crossfeed valve,
+/// a transfer valve or the APU LP valve.
+pub struct FuelValve {
+    open_id: VariableIdentifier,
+
+    open: bool,
+}
+impl FuelValve {
+    pub fn new(registry: &mut VariableRegistry, id: &str) -> Self {
+        Self {
+            open_id: registry.get(&format!("FUEL_{}_VALVE_IS_OPEN", id)),
+            open: false,
+        }
+    }
+
+    pub fn update<T: FuelValveController>(&mut self, controller: &T) {
+        self.open = controller.should_open_fuel_valve();
+    }
+
+    /// The pressure downstream of the valve given the pressure upstream of it.
+    pub fn downstream_pressure(&self, upstream: &impl FuelPressureSource) -> Pressure {
+        if self.open {
+            upstream.fuel_pressure()
+        } else {
+            Pressure::new::<psi>(0.)
+        }
+    }
+}
+impl Valve for FuelValve {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
+impl SimulationElement for FuelValve {
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_bool(&self.open_id, self.is_open());
+    }
+}
+
+/// A feed line takes the pressure of the highest pressure source connected to it.
+/// Check valves prevent reverse flow into the sources with a lower pressure.
+pub struct FuelFeedLine {
+    pressure: Pressure,
+}
+impl FuelFeedLine {
+    pub fn new() -> Self {
+        Self {
+            pressure: Pressure::new::<psi>(0.),
+        }
+    }
+
+    pub fn update(&mut self, sources: &[&dyn FuelPressureSource]) {
+        self.pressure = sources
+            .iter()
+            .map(|source| source.fuel_pressure())
+            .fold(Pressure::new::<psi>(0.), |max, pressure| max.max(pressure));
+    }
+}
+impl FuelPressureSource for FuelFeedLine {
+    fn fuel_pressure(&self) -> Pressure {
+        self.pressure
+    }
+}
+impl Default for FuelFeedLine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Komp: There is a pressure switch between the fuel valve and the APU.
+/// It switches from 0 to 1 when the pressure is >=17 PSI and the signal is received by the ECB
+/// And there is a small hysteresis, means it switches back to 0 when <=16 PSI
+pub struct FuelPressureSwitch {
+    high_pressure: Pressure,
+    low_pressure: Pressure,
+    has_pressure: bool,
+}
+impl FuelPressureSwitch {
+    pub fn new(high_pressure: Pressure, low_pressure: Pressure) -> Self {
+        Self {
+            high_pressure,
+            low_pressure,
+            has_pressure: false,
+        }
+    }
+
+    pub fn update(&mut self, pressure: Pressure) {
+        if pressure >= self.high_pressure {
+            self.has_pressure = true;
+        } else if pressure <= self.low_pressure {
+            self.has_pressure = false;
+        }
+    }
+
+    pub fn has_pressure(&self) -> bool {
+        self.has_pressure
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::test::SimulationTestBed;
+
+    struct TestController {
+        should: bool,
+    }
+    impl TestController {
+        fn with(should: bool) -> Self {
+            Self { should }
+        }
+    }
+    impl FuelPumpController for TestController {
+        fn should_run_fuel_pump(&self) -> bool {
+            self.should
+        }
+    }
+    impl FuelValveController for TestController {
+        fn should_open_fuel_valve(&self) -> bool {
+            self.should
+        }
+    }
+
+    struct TestPressureSource {
+        pressure: Pressure,
+    }
+    impl TestPressureSource {
+        fn new(pressure_psi: f64) -> Self {
+            Self {
+                pressure: Pressure::new::<psi>(pressure_psi),
+            }
+        }
+    }
+    impl FuelPressureSource for TestPressureSource {
+        fn fuel_pressure(&self) -> Pressure {
+            self.pressure
+        }
+    }
+
+    fn pump(registry: &mut VariableRegistry) -> FuelPump {
+        FuelPump::new(registry, "TEST", Pressure::new::<psi>(30.))
+    }
+
+    #[test]
+    fn tank_reads_its_quantity() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut tank = FuelTank::new(test_bed.registry(), "FUEL TANK LEFT MAIN QUANTITY");
+        test_bed.write_f64("FUEL TANK LEFT MAIN QUANTITY", 1000.);
+
+        test_bed.run_without_update(&mut tank);
+
+        assert_eq!(tank.quantity(), Mass::new::<pound>(1000.));
+        assert!(!tank.is_empty());
+    }
+
+    #[test]
+    fn tank_restores_its_quantity_from_snapshot() {
+        let mut registry = VariableRegistry::new();
+        let mut tank = FuelTank::new(&mut registry, "FUEL TANK LEFT MAIN QUANTITY");
+        tank.set_quantity(Mass::new::<pound>(1000.));
+        let snapshot = Snapshot::take(&mut tank);
+
+        let mut restored = FuelTank::new(&mut registry, "FUEL TANK LEFT MAIN QUANTITY");
+        snapshot.restore_into(&mut restored);
+
+        assert_eq!(restored.quantity(), Mass::new::<pound>(1000.));
+    }
+
+    #[test]
+    fn tank_without_fuel_is_empty() {
+        let mut tank = FuelTank::new(&mut VariableRegistry::new(), "FUEL TANK LEFT MAIN QUANTITY");
+        tank.set_quantity(Mass::new::<pound>(0.));
+
+        assert!(tank.is_empty());
+    }
+
+    #[test]
+    fn running_pump_with_fuel_supply_provides_pressure() {
+        let mut pump = pump(&mut VariableRegistry::new());
+        pump.update(&TestController::with(true), true);
+
+        assert!(pump.is_running());
+        assert_eq!(pump.fuel_pressure(), Pressure::new::<psi>(30.));
+    }
+
+    #[test]
+    fn running_pump_without_fuel_supply_provides_no_pressure() {
+        let mut pump = pump(&mut VariableRegistry::new());
+        pump.update(&TestController::with(true), false);
+
+        assert!(pump.is_running());
+        assert_eq!(pump.fuel_pressure(), Pressure::new::<psi>(0.));
+    }
+
+    #[test]
+    fn stopped_pump_provides_no_pressure() {
+        let mut pump = pump(&mut VariableRegistry::new());
+        pump.update(&TestController::with(false), true);
+
+        assert!(!pump.is_running());
+        assert_eq!(pump.fuel_pressure(), Pressure::new::<psi>(0.));
+    }
+
+    #[test]
+    fn pump_writes_its_state() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut pump = pump(test_bed.registry());
+
+        test_bed.run_without_update(&mut pump);
+
+        assert!(test_bed.contains_key("FUEL_PUMP_TEST_IS_RUNNING"));
+    }
+
+    #[test]
+    fn open_valve_passes_pressure() {
+        let mut valve = FuelValve::new(&mut VariableRegistry::new(), "TEST");
+        valve.update(&TestController::with(true));
+
+        assert!(valve.is_open());
+        assert_eq!(
+            valve.downstream_pressure(&TestPressureSource::new(30.)),
+            Pressure::new::<psi>(30.)
+        );
+    }
+
+    #[test]
+    fn closed_valve_blocks_pressure() {
+        let mut valve = FuelValve::new(&mut VariableRegistry::new(), "TEST");
+        valve.update(&TestController::with(false));
+
+        assert!(!valve.is_open());
+        assert_eq!(
+            valve.downstream_pressure(&TestPressureSource::new(30.)),
+            Pressure::new::<psi>(0.)
+        );
+    }
+
+    #[test]
+    fn valve_writes_its_state() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut valve = FuelValve::new(test_bed.registry(), "TEST");
+
+        test_bed.run_without_update(&mut valve);
+
+        assert!(test_bed.contains_key("FUEL_TEST_VALVE_IS_OPEN"));
+    }
+
+    #[test]
+    fn feed_line_takes_highest_source_pressure() {
+        let mut line = FuelFeedLine::new();
+        line.update(&[
+            &TestPressureSource::new(20.),
+            &TestPressureSource::new(30.),
+            &TestPressureSource::new(0.),
+        ]);
+
+        assert_eq!(line.fuel_pressure(), Pressure::new::<psi>(30.));
+    }
+
+    #[test]
+    fn feed_line_without_sources_has_no_pressure() {
+        let mut line = FuelFeedLine::new();
+        line.update(&[]);
+
+        assert_eq!(line.fuel_pressure(), Pressure::new::<psi>(0.));
+    }
+
+    fn pressure_switch() -> FuelPressureSwitch {
+        FuelPressureSwitch::new(Pressure::new::<psi>(17.), Pressure::new::<psi>(16.))
+    }
+
+    #[test]
+    fn pressure_switch_starts_without_pressure() {
+        assert!(!pressure_switch().has_pressure());
+    }
+
+    #[test]
+    fn pressure_switch_switches_on_at_high_pressure() {
+        let mut switch = pressure_switch();
+        switch.update(Pressure::new::<psi>(17.));
+
+        assert!(switch.has_pressure());
+    }
+
+    #[test]
+    fn pressure_switch_has_hysteresis() {
+        let mut switch = pressure_switch();
+        switch.update(Pressure::new::<psi>(16.5));
+        assert!(!switch.has_pressure());
+
+        switch.update(Pressure::new::<psi>(20.));
+        switch.update(Pressure::new::<psi>(16.5));
+        assert!(switch.has_pressure());
+
+        switch.update(Pressure::new::<psi>(16.));
+        assert!(!switch.has_pressure());
+    }
+}
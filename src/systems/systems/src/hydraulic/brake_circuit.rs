@@ -1,12 +1,18 @@
 use crate::{
     hydraulic::HydraulicLoop,
-    simulation::{SimulationElement, SimulatorWriter, UpdateContext},
+    simulation::{
+        SimulationElement, SimulatorWriter, Snapshot, StateDump, UpdateContext, VariableIdentifier,
+        VariableRegistry,
+    },
 };
 
 use std::f64::consts::E;
 use std::string::String;
 
-use uom::si::{f64::*, pressure::psi, volume::gallon};
+use uom::si::{
+    acceleration::meter_per_second_squared, f64::*, pressure::psi, ratio::ratio, velocity::knot,
+    volume::gallon,
+};
 
 use super::Accumulator;
 
@@ -110,10 +116,10 @@ impl Actuator for BrakeActuator {
 /// Brake model is simplified as we just move brake actuator position from 0 to 1 and take corresponding fluid volume (vol = max_displacement * brake_position).
 /// So it's fairly simplified as we just end up with brake pressure = PRESSURE_FOR_MAX_BRAKE_DEFLECTION_PSI * current_position
 pub struct BrakeCircuit {
-    _id: String,
-    id_left_press: String,
-    id_right_press: String,
-    id_acc_press: String,
+    id: String,
+    id_left_press: VariableIdentifier,
+    id_right_press: VariableIdentifier,
+    id_acc_press: VariableIdentifier,
 
     left_brake_actuator: BrakeActuator,
     right_brake_actuator: BrakeActuator,
@@ -123,6 +129,10 @@ pub struct BrakeCircuit {
     demanded_brake_position_right: f64,
     pressure_applied_right: Pressure,
 
+    /// Part of the demanded brake position released by the anti-skid
+    anti_skid_release_left: f64,
+    anti_skid_release_right: f64,
+
     pressure_limitation: Pressure,
     pressure_limitation_active: bool,
 
@@ -151,6 +161,7 @@ impl BrakeCircuit {
     const ACC_PRESSURE_SENSOR_FILTER_TIMECONST: f64 = 0.1;
 
     pub fn new(
+        registry: &mut VariableRegistry,
         id: &str,
         accumulator_volume: Volume,
         accumulator_fluid_volume_at_init: Volume,
@@ -162,10 +173,10 @@ impl BrakeCircuit {
         }
 
         BrakeCircuit {
-            _id: String::from(id).to_uppercase(),
-            id_left_press: format!("HYD_BRAKE_{}_LEFT_PRESS", id),
-            id_right_press: format!("HYD_BRAKE_{}_RIGHT_PRESS", id),
-            id_acc_press: format!("HYD_BRAKE_{}_ACC_PRESS", id),
+            id: String::from(id),
+            id_left_press: registry.get(&format!("HYD_BRAKE_{}_LEFT_PRESS", id)),
+            id_right_press: registry.get(&format!("HYD_BRAKE_{}_RIGHT_PRESS", id)),
+            id_acc_press: registry.get(&format!("HYD_BRAKE_{}_ACC_PRESS", id)),
 
             // We assume displacement is just split on left and right
             left_brake_actuator: BrakeActuator::new(total_displacement / 2.),
@@ -175,6 +186,8 @@ impl BrakeCircuit {
             pressure_applied_left: Pressure::new::<psi>(0.0),
             demanded_brake_position_right: 0.0,
             pressure_applied_right: Pressure::new::<psi>(0.0),
+            anti_skid_release_left: 0.,
+            anti_skid_release_right: 0.,
             pressure_limitation: Pressure::new::<psi>(0.0),
             pressure_limitation_active: false,
             has_accumulator: has_accu,
@@ -203,10 +216,12 @@ impl BrakeCircuit {
     }
 
     fn update_brake_actuators(&mut self, context: &UpdateContext, hyd_pressure: Pressure) {
-        self.left_brake_actuator
-            .set_position_demand(self.demanded_brake_position_left);
-        self.right_brake_actuator
-            .set_position_demand(self.demanded_brake_position_right);
+        self.left_brake_actuator.set_position_demand(
+            self.demanded_brake_position_left * (1. - self.anti_skid_release_left),
+        );
+        self.right_brake_actuator.set_position_demand(
+            self.demanded_brake_position_right * (1. - self.anti_skid_release_right),
+        );
 
         let actual_max_allowed_pressure: Pressure;
         if self.pressure_limitation_active {
@@ -283,6 +298,14 @@ impl BrakeCircuit {
         self.demanded_brake_position_right = brake_ratio.min(1.0).max(0.0);
     }
 
+    pub fn set_anti_skid_release_left(&mut self, release_ratio: f64) {
+        self.anti_skid_release_left = release_ratio.clamp(0., 1.);
+    }
+
+    pub fn set_anti_skid_release_right(&mut self, release_ratio: f64) {
+        self.anti_skid_release_right = release_ratio.clamp(0., 1.);
+    }
+
     pub fn left_brake_pressure(&self) -> Pressure {
         self.pressure_applied_left
     }
@@ -314,21 +337,257 @@ impl Actuator for BrakeCircuit {
 }
 impl SimulationElement for BrakeCircuit {
     fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_f64(&self.id_left_press, self.left_brake_pressure().get::<psi>());
-        writer.write_f64(
-            &self.id_right_press,
-            self.right_brake_pressure().get::<psi>(),
+        writer.write_quantity(&self.id_left_press, self.left_brake_pressure(), psi);
+        writer.write_quantity(&self.id_right_press, self.right_brake_pressure(), psi);
+        if self.has_accumulator {
+            writer.write_quantity(&self.id_acc_press, self.accumulator_pressure(), psi);
+        }
+    }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        let name = |suffix| format!("HYD_BRAKE_{}_{}", self.id, suffix);
+        snapshot.write_f64(
+            &name("LEFT_POSITION"),
+            self.left_brake_actuator.current_position,
+        );
+        snapshot.write_f64(
+            &name("RIGHT_POSITION"),
+            self.right_brake_actuator.current_position,
         );
         if self.has_accumulator {
-            writer.write_f64(&self.id_acc_press, self.accumulator_pressure().get::<psi>());
+            snapshot.write_f64(
+                &name("ACC_FLUID_VOLUME"),
+                self.accumulator.fluid_volume().get::<gallon>(),
+            );
+            snapshot.write_f64(&name("ACC_PRESS"), self.accumulator_pressure().get::<psi>());
+        }
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        let id = self.id.clone();
+        let name = |suffix| format!("HYD_BRAKE_{}_{}", id, suffix);
+        if let Some(position) = snapshot.read_f64(&name("LEFT_POSITION")) {
+            self.left_brake_actuator.current_position = position;
+            self.pressure_applied_left = self.left_brake_actuator.get_applied_brake_pressure();
+        }
+        if let Some(position) = snapshot.read_f64(&name("RIGHT_POSITION")) {
+            self.right_brake_actuator.current_position = position;
+            self.pressure_applied_right = self.right_brake_actuator.get_applied_brake_pressure();
+        }
+        if self.has_accumulator {
+            if let Some(volume) = snapshot.read_f64(&name("ACC_FLUID_VOLUME")) {
+                self.accumulator
+                    .set_fluid_volume(Volume::new::<gallon>(volume));
+            }
+            if let Some(pressure) = snapshot.read_f64(&name("ACC_PRESS")) {
+                self.accumulator_fluid_pressure_sensor_filtered = Pressure::new::<psi>(pressure);
+            }
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state.node("HYD").node("BRAKE").node(&self.id);
+        node.write_f64("LEFT_PRESSURE", self.left_brake_pressure().get::<psi>());
+        node.write_f64("RIGHT_PRESSURE", self.right_brake_pressure().get::<psi>());
+        if self.has_accumulator {
+            node.write_f64(
+                "ACCUMULATOR_FLUID_VOLUME",
+                self.accumulator.fluid_volume().get::<gallon>(),
+            );
+            node.write_f64(
+                "ACCUMULATOR_PRESSURE",
+                self.accumulator_pressure().get::<psi>(),
+            );
         }
     }
 }
 
+/// Anti-skid of the braked wheels on one side. Compares their wheel speed to the reference
+/// speed of the aircraft and releases brake pressure while the wheels slip too much.
+struct SideAntiSkid {
+    slip_ratio: f64,
+    release: f64,
+}
+impl SideAntiSkid {
+    fn new() -> Self {
+        Self {
+            slip_ratio: 0.,
+            release: 0.,
+        }
+    }
+
+    fn update(
+        &mut self,
+        context: &UpdateContext,
+        is_active: bool,
+        reference_speed: Velocity,
+        wheel_speed: Velocity,
+    ) {
+        self.slip_ratio = if reference_speed.get::<knot>() > AntiSkid::MIN_REFERENCE_SPEED_KNOT {
+            ((reference_speed - wheel_speed) / reference_speed)
+                .get::<ratio>()
+                .clamp(0., 1.)
+        } else {
+            0.
+        };
+
+        if is_active {
+            let release_rate = if self.slip_ratio > AntiSkid::MAX_SLIP_RATIO {
+                AntiSkid::RELEASE_RATE_PER_SECOND
+            } else {
+                -AntiSkid::REAPPLY_RATE_PER_SECOND
+            };
+
+            self.release =
+                (self.release + release_rate * context.delta_as_secs_f64()).clamp(0., 1.);
+        } else {
+            self.release = 0.;
+        }
+    }
+}
+
+/// Anti-skid function of a brake and steering control unit for the left and right brakes.
+/// While active it releases brake pressure on a side whose wheels slip beyond the
+/// maximum slip ratio, and reapplies it once the wheels spin up again. Thus on a
+/// slippery runway the achievable braking is lower than on a dry one.
+///
+/// Unlike the real BSCU, which controls every main wheel separately, the wheels are
+/// controlled per side: the simulator only provides a left and right wheel speed,
+/// and the brake circuit has a single actuator per side. Thus both wheels on a side
+/// are always released together.
+///
+/// When inactive no pressure is ever released, and it's up to the aircraft's
+/// braking logic to limit the brake pressure such that the wheels don't lock.
+pub struct AntiSkid {
+    left: SideAntiSkid,
+    right: SideAntiSkid,
+}
+impl AntiSkid {
+    // Below this speed the wheel speed can't be reliably compared to the reference speed
+    const MIN_REFERENCE_SPEED_KNOT: f64 = 20.;
+
+    // Slip ratio giving the best braking, above which brake pressure is released
+    const MAX_SLIP_RATIO: f64 = 0.15;
+
+    const RELEASE_RATE_PER_SECOND: f64 = 10.;
+    const REAPPLY_RATE_PER_SECOND: f64 = 3.;
+
+    pub fn new() -> Self {
+        Self {
+            left: SideAntiSkid::new(),
+            right: SideAntiSkid::new(),
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        context: &UpdateContext,
+        is_active: bool,
+        reference_speed: Velocity,
+        left_wheel_speed: Velocity,
+        right_wheel_speed: Velocity,
+    ) {
+        self.left
+            .update(context, is_active, reference_speed, left_wheel_speed);
+        self.right
+            .update(context, is_active, reference_speed, right_wheel_speed);
+    }
+
+    /// Part of the left brake demand which is released, from 0 (none) to 1 (all).
+    pub fn left_release(&self) -> f64 {
+        self.left.release
+    }
+
+    /// Part of the right brake demand which is released, from 0 (none) to 1 (all).
+    pub fn right_release(&self) -> f64 {
+        self.right.release
+    }
+
+    pub fn left_slip_ratio(&self) -> f64 {
+        self.left.slip_ratio
+    }
+
+    pub fn right_slip_ratio(&self) -> f64 {
+        self.right.slip_ratio
+    }
+}
+impl Default for AntiSkid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Computes the brake demand required to decelerate the aircraft at a target deceleration.
+/// The demand is integrated from the error between target and actual deceleration, thus it
+/// settles on whatever demand gives the target deceleration for the current runway and weight.
+pub struct AutobrakeDecelerationGovernor {
+    is_engaged: bool,
+    target: Acceleration,
+    current_deceleration: Acceleration,
+    output: f64,
+}
+impl AutobrakeDecelerationGovernor {
+    // Brake demand change per second for each m/s² of deceleration error
+    const INTEGRAL_GAIN: f64 = 0.1;
+
+    // Ratio of the target deceleration above which the deceleration is considered reached
+    const ON_TARGET_RATIO: f64 = 0.8;
+
+    pub fn new() -> Self {
+        Self {
+            is_engaged: false,
+            target: Acceleration::new::<meter_per_second_squared>(0.),
+            current_deceleration: Acceleration::new::<meter_per_second_squared>(0.),
+            output: 0.,
+        }
+    }
+
+    /// Engages the governor while the condition is true. When disengaged,
+    /// the brake demand is released.
+    pub fn engage_when(&mut self, engage_condition: bool) {
+        self.is_engaged = engage_condition;
+
+        if !self.is_engaged {
+            self.output = 0.;
+        }
+    }
+
+    pub fn update(&mut self, context: &UpdateContext, target: Acceleration) {
+        self.target = target;
+        // Body longitudinal acceleration is negative when decelerating
+        self.current_deceleration = -context.long_accel();
+
+        if self.is_engaged {
+            let error = (self.target - self.current_deceleration).get::<meter_per_second_squared>();
+            self.output = (self.output + Self::INTEGRAL_GAIN * error * context.delta_as_secs_f64())
+                .clamp(0., 1.);
+        }
+    }
+
+    pub fn is_engaged(&self) -> bool {
+        self.is_engaged
+    }
+
+    /// Brake demand from 0 (no braking) to 1 (full braking).
+    pub fn output(&self) -> f64 {
+        self.output
+    }
+
+    pub fn is_on_target(&self) -> bool {
+        self.is_engaged && self.current_deceleration >= self.target * Self::ON_TARGET_RATIO
+    }
+}
+impl Default for AutobrakeDecelerationGovernor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
+        failures::FailureType,
         hydraulic::{Fluid, HydraulicLoop},
         simulation::UpdateContext,
     };
@@ -450,6 +709,7 @@ mod tests {
     fn brake_state_at_init() {
         let init_max_vol = Volume::new::<gallon>(1.5);
         let brake_circuit_unprimed = BrakeCircuit::new(
+            &mut VariableRegistry::new(),
             "altn",
             init_max_vol,
             Volume::new::<gallon>(0.0),
@@ -466,6 +726,7 @@ mod tests {
         assert!(brake_circuit_unprimed.accumulator.gas_volume == init_max_vol);
 
         let brake_circuit_primed = BrakeCircuit::new(
+            &mut VariableRegistry::new(),
             "altn",
             init_max_vol,
             init_max_vol / 2.0,
@@ -489,6 +750,7 @@ mod tests {
         hyd_loop.loop_pressure = Pressure::new::<psi>(2500.0);
 
         let mut brake_circuit_primed = BrakeCircuit::new(
+            &mut VariableRegistry::new(),
             "Altn",
             init_max_vol,
             init_max_vol / 2.0,
@@ -524,6 +786,39 @@ mod tests {
         assert!(brake_circuit_primed.accumulator.fluid_volume() >= Volume::new::<gallon>(0.1));
     }
 
+    #[test]
+    fn brake_circuit_restores_brakes_and_accumulator_from_snapshot() {
+        let init_max_vol = Volume::new::<gallon>(1.5);
+        let mut hyd_loop = hydraulic_loop("YELLOW");
+        hyd_loop.loop_pressure = Pressure::new::<psi>(0.);
+        let mut registry = VariableRegistry::new();
+        let mut brake_circuit = BrakeCircuit::new(
+            &mut registry,
+            "ALTN",
+            init_max_vol,
+            init_max_vol / 2.0,
+            Volume::new::<gallon>(0.1),
+        );
+        brake_circuit.set_brake_demand_left(1.0);
+        brake_circuit.update(&context(Duration::from_secs_f64(1.)), &hyd_loop);
+        let snapshot = Snapshot::take(&mut brake_circuit);
+
+        let mut restored = BrakeCircuit::new(
+            &mut registry,
+            "ALTN",
+            init_max_vol,
+            init_max_vol / 2.0,
+            Volume::new::<gallon>(0.1),
+        );
+        snapshot.restore_into(&mut restored);
+
+        assert!(restored.accumulator_fluid_volume() < init_max_vol / 2.0);
+        assert_eq!(
+            StateDump::take(&mut restored),
+            StateDump::take(&mut brake_circuit)
+        );
+    }
+
     #[test]
     fn brake_pressure_rise_no_accumulator() {
         let init_max_vol = Volume::new::<gallon>(0.0);
@@ -531,6 +826,7 @@ mod tests {
         hyd_loop.loop_pressure = Pressure::new::<psi>(2500.0);
 
         let mut brake_circuit_primed = BrakeCircuit::new(
+            &mut VariableRegistry::new(),
             "norm",
             init_max_vol,
             init_max_vol / 2.0,
@@ -572,6 +868,7 @@ mod tests {
         hyd_loop.loop_pressure = Pressure::new::<psi>(3100.0);
 
         let mut brake_circuit_primed = BrakeCircuit::new(
+            &mut VariableRegistry::new(),
             "norm",
             init_max_vol,
             init_max_vol / 2.0,
@@ -613,10 +910,209 @@ mod tests {
         assert!(brake_circuit_primed.right_brake_pressure() <= pressure_limit);
     }
 
+    #[test]
+    fn deceleration_governor_has_no_output_when_not_engaged() {
+        let mut governor = AutobrakeDecelerationGovernor::new();
+
+        governor.update(
+            &context(Duration::from_secs(1)),
+            Acceleration::new::<meter_per_second_squared>(3.),
+        );
+
+        assert!(governor.output() == 0.);
+        assert!(!governor.is_on_target());
+    }
+
+    #[test]
+    fn deceleration_governor_increases_demand_while_decelerating_below_target() {
+        let mut governor = AutobrakeDecelerationGovernor::new();
+        governor.engage_when(true);
+
+        let mut previous_output = governor.output();
+        for _ in 0..10 {
+            governor.update(
+                &context_with_long_acceleration(
+                    Duration::from_millis(100),
+                    Acceleration::new::<meter_per_second_squared>(-1.),
+                ),
+                Acceleration::new::<meter_per_second_squared>(3.),
+            );
+
+            assert!(governor.output() > previous_output);
+            previous_output = governor.output();
+        }
+
+        assert!(!governor.is_on_target());
+    }
+
+    #[test]
+    fn deceleration_governor_decreases_demand_while_decelerating_above_target() {
+        let mut governor = AutobrakeDecelerationGovernor::new();
+        governor.engage_when(true);
+        governor.update(
+            &context(Duration::from_secs(5)),
+            Acceleration::new::<meter_per_second_squared>(3.),
+        );
+        let output_before = governor.output();
+
+        governor.update(
+            &context_with_long_acceleration(
+                Duration::from_secs(1),
+                Acceleration::new::<meter_per_second_squared>(-5.),
+            ),
+            Acceleration::new::<meter_per_second_squared>(3.),
+        );
+
+        assert!(governor.output() < output_before);
+        assert!(governor.is_on_target());
+    }
+
+    #[test]
+    fn deceleration_governor_output_is_limited_to_full_braking() {
+        let mut governor = AutobrakeDecelerationGovernor::new();
+        governor.engage_when(true);
+
+        governor.update(
+            &context(Duration::from_secs(60)),
+            Acceleration::new::<meter_per_second_squared>(6.),
+        );
+
+        assert!(governor.output() == 1.);
+    }
+
+    #[test]
+    fn deceleration_governor_releases_demand_when_disengaged() {
+        let mut governor = AutobrakeDecelerationGovernor::new();
+        governor.engage_when(true);
+        governor.update(
+            &context(Duration::from_secs(1)),
+            Acceleration::new::<meter_per_second_squared>(3.),
+        );
+
+        governor.engage_when(false);
+
+        assert!(governor.output() == 0.);
+        assert!(!governor.is_engaged());
+    }
+
+    #[test]
+    fn anti_skid_does_not_release_when_wheels_roll_at_reference_speed() {
+        let mut anti_skid = AntiSkid::new();
+
+        anti_skid.update(
+            &context(Duration::from_millis(100)),
+            true,
+            Velocity::new::<knot>(120.),
+            Velocity::new::<knot>(120.),
+            Velocity::new::<knot>(118.),
+        );
+
+        assert!(anti_skid.left_release() == 0.);
+        assert!(anti_skid.right_release() == 0.);
+    }
+
+    #[test]
+    fn anti_skid_releases_side_which_slips() {
+        let mut anti_skid = AntiSkid::new();
+
+        anti_skid.update(
+            &context(Duration::from_millis(100)),
+            true,
+            Velocity::new::<knot>(120.),
+            Velocity::new::<knot>(60.),
+            Velocity::new::<knot>(118.),
+        );
+
+        assert!((anti_skid.left_slip_ratio() - 0.5).abs() < 0.001);
+        assert!(anti_skid.left_release() > 0.);
+        assert!(anti_skid.right_release() == 0.);
+    }
+
+    #[test]
+    fn anti_skid_reapplies_once_wheel_spins_up() {
+        let mut anti_skid = AntiSkid::new();
+        anti_skid.update(
+            &context(Duration::from_millis(100)),
+            true,
+            Velocity::new::<knot>(120.),
+            Velocity::new::<knot>(0.),
+            Velocity::new::<knot>(0.),
+        );
+        let release_when_locked = anti_skid.left_release();
+
+        anti_skid.update(
+            &context(Duration::from_millis(100)),
+            true,
+            Velocity::new::<knot>(120.),
+            Velocity::new::<knot>(115.),
+            Velocity::new::<knot>(115.),
+        );
+
+        assert!(anti_skid.left_release() < release_when_locked);
+    }
+
+    #[test]
+    fn anti_skid_does_not_release_when_inactive() {
+        let mut anti_skid = AntiSkid::new();
+
+        anti_skid.update(
+            &context(Duration::from_millis(100)),
+            false,
+            Velocity::new::<knot>(120.),
+            Velocity::new::<knot>(0.),
+            Velocity::new::<knot>(0.),
+        );
+
+        assert!(anti_skid.left_release() == 0.);
+        assert!(anti_skid.right_release() == 0.);
+    }
+
+    #[test]
+    fn anti_skid_does_not_release_below_minimum_reference_speed() {
+        let mut anti_skid = AntiSkid::new();
+
+        anti_skid.update(
+            &context(Duration::from_millis(100)),
+            true,
+            Velocity::new::<knot>(15.),
+            Velocity::new::<knot>(0.),
+            Velocity::new::<knot>(0.),
+        );
+
+        assert!(anti_skid.left_release() == 0.);
+        assert!(anti_skid.right_release() == 0.);
+    }
+
+    #[test]
+    fn anti_skid_release_lowers_brake_pressure() {
+        let mut hyd_loop = hydraulic_loop("GREEN");
+        hyd_loop.loop_pressure = Pressure::new::<psi>(3000.);
+
+        let mut brake_circuit = BrakeCircuit::new(
+            &mut VariableRegistry::new(),
+            "norm",
+            Volume::new::<gallon>(0.),
+            Volume::new::<gallon>(0.),
+            Volume::new::<gallon>(0.13),
+        );
+        brake_circuit.set_brake_demand_left(1.0);
+        brake_circuit.set_brake_demand_right(1.0);
+        brake_circuit.set_anti_skid_release_left(1.0);
+
+        for _ in 0..10 {
+            brake_circuit.update(&context(Duration::from_secs_f64(0.1)), &hyd_loop);
+        }
+
+        assert!(brake_circuit.left_brake_pressure() < Pressure::new::<psi>(50.));
+        assert!(brake_circuit.right_brake_pressure() > Pressure::new::<psi>(2500.));
+    }
+
     fn hydraulic_loop(loop_color: &str) -> HydraulicLoop {
         match loop_color {
             "GREEN" => HydraulicLoop::new(
+                &mut VariableRegistry::new(),
                 loop_color,
+                FailureType::GreenHydraulicLeak,
                 false,
                 true,
                 Volume::new::<gallon>(26.00),
@@ -629,7 +1125,9 @@ mod tests {
                 Pressure::new::<psi>(1750.0),
             ),
             "YELLOW" => HydraulicLoop::new(
+                &mut VariableRegistry::new(),
                 loop_color,
+                FailureType::YellowHydraulicLeak,
                 true,
                 false,
                 Volume::new::<gallon>(10.2),
@@ -642,7 +1140,9 @@ mod tests {
                 Pressure::new::<psi>(1750.0),
             ),
             _ => HydraulicLoop::new(
+                &mut VariableRegistry::new(),
                 loop_color,
+                FailureType::BlueHydraulicLeak,
                 false,
                 false,
                 Volume::new::<gallon>(15.85),
@@ -658,13 +1158,20 @@ mod tests {
     }
 
     fn context(delta_time: Duration) -> UpdateContext {
+        context_with_long_acceleration(delta_time, Acceleration::new::<foot_per_second_squared>(0.))
+    }
+
+    fn context_with_long_acceleration(
+        delta_time: Duration,
+        long_acceleration: Acceleration,
+    ) -> UpdateContext {
         UpdateContext::new(
             delta_time,
             Velocity::new::<knot>(250.),
             Length::new::<foot>(5000.),
             ThermodynamicTemperature::new::<degree_celsius>(25.0),
             true,
-            Acceleration::new::<foot_per_second_squared>(0.),
+            long_acceleration,
         )
     }
 }
@@ -9,11 +9,17 @@ use uom::si::{
     volume_rate::gallon_per_second,
 };
 
+use crate::failures::{Failure, FailureType};
 use crate::shared::interpolation;
 use crate::simulation::UpdateContext;
-use crate::simulation::{SimulationElement, SimulationElementVisitor, SimulatorWriter};
+use crate::simulation::{
+    ChangeDetector, EventEmitter, EventKind, InvariantChecker, SimulationElement,
+    SimulationElementVisitor, SimulatorWriter, Snapshot, StateDump, VariableIdentifier,
+    VariableRegistry,
+};
 
 pub mod brake_circuit;
+pub mod nose_steering;
 use crate::hydraulic::brake_circuit::Actuator;
 
 pub trait PressureSource {
@@ -73,12 +79,19 @@ pub trait PowerTransferUnitController {
 }
 
 pub struct PowerTransferUnit {
+    active_left_to_right_id: VariableIdentifier,
+    active_right_to_left_id: VariableIdentifier,
+    motor_flow_id: VariableIdentifier,
+    valve_opened_id: VariableIdentifier,
+
     is_enabled: bool,
     is_active_right: bool,
     is_active_left: bool,
     flow_to_right: VolumeRate,
     flow_to_left: VolumeRate,
     last_flow: VolumeRate,
+    failure: Failure,
+    active_change: ChangeDetector<bool>,
 }
 impl PowerTransferUnit {
     // Low pass filter to handle flow dynamic: avoids instantaneous flow transient,
@@ -93,14 +106,20 @@ impl PowerTransferUnit {
     // set to 0.5 PTU will only use half of the flow that all pumps are able to generate
     const AGRESSIVENESS_FACTOR: f64 = 0.78;
 
-    pub fn new() -> Self {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
         Self {
+            active_left_to_right_id: registry.get("HYD_PTU_ACTIVE_L2R"),
+            active_right_to_left_id: registry.get("HYD_PTU_ACTIVE_R2L"),
+            motor_flow_id: registry.get("HYD_PTU_MOTOR_FLOW"),
+            valve_opened_id: registry.get("HYD_PTU_VALVE_OPENED"),
             is_enabled: false,
             is_active_right: false,
             is_active_left: false,
             flow_to_right: VolumeRate::new::<gallon_per_second>(0.0),
             flow_to_left: VolumeRate::new::<gallon_per_second>(0.0),
             last_flow: VolumeRate::new::<gallon_per_second>(0.0),
+            failure: Failure::new(registry, FailureType::PowerTransferUnit),
+            active_change: ChangeDetector::new(false),
         }
     }
 
@@ -130,7 +149,9 @@ impl PowerTransferUnit {
 
         let delta_p = loop_left.pressure() - loop_right.pressure();
 
+        // A failed PTU no longer transfers power, even though its valve is still controlled.
         if !self.is_enabled
+            || self.failure.is_active()
             || self.is_active_right && delta_p.get::<psi>() > -5.
             || self.is_active_left && delta_p.get::<psi>() < 5.
         {
@@ -188,19 +209,32 @@ impl PowerTransferUnit {
     }
 }
 impl SimulationElement for PowerTransferUnit {
-    fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_bool("HYD_PTU_ACTIVE_L2R", self.is_active_left);
-        writer.write_bool("HYD_PTU_ACTIVE_R2L", self.is_active_right);
-        writer.write_f64("HYD_PTU_MOTOR_FLOW", self.flow().get::<gallon_per_second>());
-        writer.write_bool("HYD_PTU_VALVE_OPENED", self.is_enabled());
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.failure.accept(visitor);
+
+        visitor.visit(self);
     }
-}
-impl Default for PowerTransferUnit {
-    fn default() -> Self {
-        Self::new()
+
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_bool(&self.active_left_to_right_id, self.is_active_left);
+        writer.write_bool(&self.active_right_to_left_id, self.is_active_right);
+        writer.write_quantity(&self.motor_flow_id, self.flow(), gallon_per_second);
+        writer.write_bool(&self.valve_opened_id, self.is_enabled());
+    }
+
+    fn emit_events(&mut self, events: &mut EventEmitter) {
+        let is_active = self.is_active_left || self.is_active_right;
+        if self.active_change.detect(is_active).is_some() {
+            if self.is_active_left {
+                events.emit_with_cause("HYD/PTU", EventKind::Activated, "left to right");
+            } else if self.is_active_right {
+                events.emit_with_cause("HYD/PTU", EventKind::Activated, "right to left");
+            } else {
+                events.emit("HYD/PTU", EventKind::Deactivated);
+            }
+        }
     }
 }
-
 pub trait HydraulicLoopController {
     fn should_open_fire_shutoff_valve(&self) -> bool;
 }
@@ -306,19 +340,29 @@ impl Accumulator {
         self.fluid_volume
     }
 
+    fn set_fluid_volume(&mut self, fluid_volume: Volume) {
+        self.fluid_volume = fluid_volume;
+        self.gas_volume = self.total_volume - fluid_volume;
+        self.gas_pressure =
+            (self.gas_init_precharge * self.total_volume) / (self.total_volume - self.fluid_volume);
+    }
+
     fn raw_gas_press(&self) -> Pressure {
         self.gas_pressure
     }
 }
 pub struct HydraulicLoop {
-    pressure_id: String,
-    reservoir_id: String,
-    fire_valve_id: String,
+    id: String,
+    pressure_id: VariableIdentifier,
+    reservoir_id: VariableIdentifier,
+    fire_valve_id: VariableIdentifier,
     fluid: Fluid,
     accumulator: Accumulator,
     connected_to_ptu_left_side: bool,
     connected_to_ptu_right_side: bool,
     loop_pressure: Pressure,
+    previous_loop_pressure: Pressure,
+    interpolation_fraction: f64,
     loop_volume: Volume,
     max_loop_volume: Volume,
     high_pressure_volume: Volume,
@@ -335,6 +379,7 @@ pub struct HydraulicLoop {
     is_pressurised: bool,
     total_actuators_consumed_volume: Volume,
     total_actuators_returned_volume: Volume,
+    leak_failure: Failure,
 }
 impl HydraulicLoop {
     // Nitrogen PSI
@@ -345,8 +390,14 @@ impl HydraulicLoop {
     // Gallon per s of flow lost to reservoir @ 3000psi
     const STATIC_LEAK_FLOW_GALLON_PER_SECOND: f64 = 0.05;
 
+    // Gallon per s of flow lost overboard @ 3000psi when the loop is leaking
+    const LEAK_FAILURE_FLOW_GALLON_PER_SECOND: f64 = 0.1;
+
     const DELTA_VOL_LOW_PASS_FILTER: f64 = 0.4;
 
+    // Highest pressure a loop can reach, as pumps stop displacing fluid well below it
+    const MAX_PLAUSIBLE_PRESSURE_PSI: f64 = 3500.;
+
     const ACCUMULATOR_PRESS_BREAKPTS: [f64; 10] = [
         0.0, 1., 5.0, 50.0, 100., 200.0, 500.0, 1000., 2000.0, 10000.0,
     ];
@@ -355,7 +406,9 @@ impl HydraulicLoop {
 
     #[allow(clippy::too_many_arguments)]
     pub fn new(
+        registry: &mut VariableRegistry,
         id: &str,
+        leak_failure_type: FailureType,
         connected_to_ptu_left_side: bool, // Is connected to PTU "left" side: non variable displacement side
         connected_to_ptu_right_side: bool, // Is connected to PTU "right" side: variable displacement side
         loop_volume: Volume,
@@ -368,13 +421,16 @@ impl HydraulicLoop {
         min_pressure_pressurised_hi_hyst: Pressure,
     ) -> Self {
         Self {
-            pressure_id: format!("HYD_{}_PRESSURE", id),
-            reservoir_id: format!("HYD_{}_RESERVOIR", id),
-            fire_valve_id: format!("HYD_{}_FIRE_VALVE_OPENED", id),
+            id: id.to_owned(),
+            pressure_id: registry.get(&format!("HYD_{}_PRESSURE", id)),
+            reservoir_id: registry.get(&format!("HYD_{}_RESERVOIR", id)),
+            fire_valve_id: registry.get(&format!("HYD_{}_FIRE_VALVE_OPENED", id)),
 
             connected_to_ptu_left_side,
             connected_to_ptu_right_side,
             loop_pressure: Pressure::new::<psi>(14.7),
+            previous_loop_pressure: Pressure::new::<psi>(14.7),
+            interpolation_fraction: 1.,
             loop_volume,
             max_loop_volume,
             high_pressure_volume,
@@ -399,6 +455,7 @@ impl HydraulicLoop {
             is_pressurised: false,
             total_actuators_consumed_volume: Volume::new::<gallon>(0.),
             total_actuators_returned_volume: Volume::new::<gallon>(0.),
+            leak_failure: Failure::new(registry, leak_failure_type),
         }
     }
 
@@ -422,6 +479,17 @@ impl HydraulicLoop {
         self.loop_pressure
     }
 
+    /// Interpolates the pressure written to the simulator between the previous and the
+    /// current update of the loop, by the given fraction of the loop's update time step.
+    pub fn interpolate(&mut self, fraction: f64) {
+        self.interpolation_fraction = fraction.max(0.).min(1.);
+    }
+
+    fn interpolated_pressure(&self) -> Pressure {
+        self.previous_loop_pressure
+            + (self.loop_pressure - self.previous_loop_pressure) * self.interpolation_fraction
+    }
+
     pub fn reservoir_volume(&self) -> Volume {
         self.reservoir_volume
     }
@@ -525,6 +593,7 @@ impl HydraulicLoop {
         ptus: Vec<&PowerTransferUnit>,
         controller: &T,
     ) {
+        self.previous_loop_pressure = self.loop_pressure;
         self.fire_shutoff_valve_opened = controller.should_open_fire_shutoff_valve();
 
         let mut delta_vol_max = Volume::new::<gallon>(0.);
@@ -564,6 +633,16 @@ impl HydraulicLoop {
         delta_vol -= static_leaks_vol;
         reservoir_return += static_leaks_vol;
 
+        // Fluid lost through a leak doesn't return to the reservoir
+        if self.leak_failure.is_active() {
+            delta_vol -= Volume::new::<gallon>(
+                Self::LEAK_FAILURE_FLOW_GALLON_PER_SECOND
+                    * context.delta_as_secs_f64()
+                    * (self.loop_pressure.get::<psi>() - 14.7)
+                    / 3000.0,
+            );
+        }
+
         // Updates current delta_vol and reservoir return quantity based on current ptu flows
         self.update_ptu_flows(context, ptus, &mut delta_vol, &mut reservoir_return);
 
@@ -634,13 +713,77 @@ impl HydraulicLoop {
     }
 }
 impl SimulationElement for HydraulicLoop {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.leak_failure.accept(visitor);
+
+        visitor.visit(self);
+    }
+
     fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_f64(&self.pressure_id, self.pressure().get::<psi>());
-        writer.write_f64(&self.reservoir_id, self.reservoir_volume().get::<gallon>());
+        writer.write_quantity(&self.pressure_id, self.interpolated_pressure(), psi);
+        writer.write_quantity(&self.reservoir_id, self.reservoir_volume(), gallon);
         if self.has_fire_valve {
             writer.write_bool(&self.fire_valve_id, self.is_fire_shutoff_valve_opened());
         }
     }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        snapshot.write_f64(
+            &format!("HYD_{}_RESERVOIR", self.id),
+            self.reservoir_volume.get::<gallon>(),
+        );
+        snapshot.write_f64(
+            &format!("HYD_{}_LOOP_VOLUME", self.id),
+            self.loop_volume.get::<gallon>(),
+        );
+        snapshot.write_f64(
+            &format!("HYD_{}_ACCUMULATOR_FLUID_VOLUME", self.id),
+            self.accumulator.fluid_volume().get::<gallon>(),
+        );
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(volume) = snapshot.read_f64(&format!("HYD_{}_RESERVOIR", self.id)) {
+            self.reservoir_volume = Volume::new::<gallon>(volume);
+        }
+        if let Some(volume) = snapshot.read_f64(&format!("HYD_{}_LOOP_VOLUME", self.id)) {
+            self.loop_volume = Volume::new::<gallon>(volume);
+        }
+        if let Some(volume) =
+            snapshot.read_f64(&format!("HYD_{}_ACCUMULATOR_FLUID_VOLUME", self.id))
+        {
+            self.accumulator
+                .set_fluid_volume(Volume::new::<gallon>(volume));
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state.node("HYD").node("LOOP").node(&self.id);
+        node.write_f64("PRESSURE", self.loop_pressure.get::<psi>());
+        node.write_bool("IS_PRESSURISED", self.is_pressurised);
+        node.write_f64("LOOP_VOLUME", self.loop_volume.get::<gallon>());
+        node.write_f64("RESERVOIR_VOLUME", self.reservoir_volume.get::<gallon>());
+        node.write_f64(
+            "ACCUMULATOR_GAS_PRESSURE",
+            self.accumulator.gas_pressure.get::<psi>(),
+        );
+        node.write_bool("PTU_ACTIVE", self.ptu_active);
+        if self.has_fire_valve {
+            node.write_bool("FIRE_VALVE_OPENED", self.fire_shutoff_valve_opened);
+        }
+    }
+
+    fn check_invariants(&self, invariants: &mut InvariantChecker) {
+        invariants.check(
+            "hydraulic reservoir volume never negative",
+            self.reservoir_volume >= Volume::new::<gallon>(0.),
+        );
+        invariants.check(
+            "hydraulic loop pressure bounded",
+            self.loop_pressure >= Pressure::new::<psi>(0.)
+                && self.loop_pressure <= Pressure::new::<psi>(Self::MAX_PLAUSIBLE_PRESSURE_PSI),
+        );
+    }
 }
 
 pub trait PumpController {
@@ -722,11 +865,12 @@ impl PressureSource for Pump {
 }
 
 pub struct ElectricPump {
-    active_id: String,
+    active_id: VariableIdentifier,
 
     is_active: bool,
     rpm: f64,
     pump: Pump,
+    failure: Failure,
 }
 impl ElectricPump {
     const SPOOLUP_TIME: f64 = 1.;
@@ -739,9 +883,9 @@ impl ElectricPump {
     // 1 == No filtering
     const DISPLACEMENT_DYNAMICS: f64 = 1.0;
 
-    pub fn new(id: &str) -> Self {
+    pub fn new(registry: &mut VariableRegistry, id: &str, failure_type: FailureType) -> Self {
         Self {
-            active_id: format!("HYD_{}_EPUMP_ACTIVE", id),
+            active_id: registry.get(&format!("HYD_{}_EPUMP_ACTIVE", id)),
             is_active: false,
             rpm: 0.,
             pump: Pump::new(
@@ -749,6 +893,7 @@ impl ElectricPump {
                 Self::DISPLACEMENT_MAP,
                 Self::DISPLACEMENT_DYNAMICS,
             ),
+            failure: Failure::new(registry, failure_type),
         }
     }
 
@@ -763,10 +908,11 @@ impl ElectricPump {
         controller: &T,
     ) {
         // TODO Simulate speed of pump depending on pump load (flow?/ current?)
-        // Pump startup/shutdown process
-        if self.is_active && self.rpm < Self::NOMINAL_SPEED {
+        // Pump startup/shutdown process. A failed pump spools down even when commanded to run.
+        let is_running = self.is_active && !self.failure.is_active();
+        if is_running && self.rpm < Self::NOMINAL_SPEED {
             self.rpm += (Self::NOMINAL_SPEED / Self::SPOOLUP_TIME) * context.delta_as_secs_f64();
-        } else if !self.is_active && self.rpm > 0.0 {
+        } else if !is_running && self.rpm > 0.0 {
             self.rpm -= (Self::NOMINAL_SPEED / Self::SPOOLDOWN_TIME) * context.delta_as_secs_f64();
         }
 
@@ -786,16 +932,23 @@ impl PressureSource for ElectricPump {
     }
 }
 impl SimulationElement for ElectricPump {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.failure.accept(visitor);
+
+        visitor.visit(self);
+    }
+
     fn write(&self, writer: &mut SimulatorWriter) {
         writer.write_bool(&self.active_id, self.is_active);
     }
 }
 
 pub struct EngineDrivenPump {
-    active_id: String,
+    active_id: VariableIdentifier,
 
     is_active: bool,
     pump: Pump,
+    failure: Failure,
 }
 impl EngineDrivenPump {
     const DISPLACEMENT_BREAKPTS: [f64; 9] = [
@@ -806,15 +959,16 @@ impl EngineDrivenPump {
     // 0.1 == 90% filtering on max displacement transient
     const DISPLACEMENT_DYNAMICS: f64 = 0.95;
 
-    pub fn new(id: &str) -> Self {
+    pub fn new(registry: &mut VariableRegistry, id: &str, failure_type: FailureType) -> Self {
         Self {
-            active_id: format!("HYD_{}_EDPUMP_ACTIVE", id),
+            active_id: registry.get(&format!("HYD_{}_EDPUMP_ACTIVE", id)),
             is_active: false,
             pump: Pump::new(
                 Self::DISPLACEMENT_BREAKPTS,
                 Self::DISPLACEMENT_MAP,
                 Self::DISPLACEMENT_DYNAMICS,
             ),
+            failure: Failure::new(registry, failure_type),
         }
     }
 
@@ -825,6 +979,13 @@ impl EngineDrivenPump {
         pump_rpm: f64,
         controller: &T,
     ) {
+        // A failed pump no longer delivers any flow.
+        let pump_rpm = if self.failure.is_active() {
+            0.
+        } else {
+            pump_rpm
+        };
+
         self.pump.update(context, line, pump_rpm, controller);
         self.is_active = controller.should_pressurise();
     }
@@ -838,12 +999,20 @@ impl PressureSource for EngineDrivenPump {
     }
 }
 impl SimulationElement for EngineDrivenPump {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        self.failure.accept(visitor);
+
+        visitor.visit(self);
+    }
+
     fn write(&self, writer: &mut SimulatorWriter) {
         writer.write_bool(&self.active_id, self.is_active);
     }
 }
 
 struct WindTurbine {
+    rpm_id: VariableIdentifier,
+
     position: f64,
     speed: f64,
     acceleration: f64,
@@ -860,8 +1029,9 @@ impl WindTurbine {
     ];
     const PROP_ALPHA_MAP: [f64; 9] = [45., 45., 45., 45., 35., 25., 1., 1., 1.];
 
-    fn new() -> Self {
+    fn new(registry: &mut VariableRegistry) -> Self {
         Self {
+            rpm_id: registry.get("HYD_RAT_RPM"),
             position: Self::STOWED_ANGLE,
             speed: 0.,
             acceleration: 0.,
@@ -931,15 +1101,9 @@ impl WindTurbine {
 }
 impl SimulationElement for WindTurbine {
     fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_f64("HYD_RAT_RPM", self.rpm());
-    }
-}
-impl Default for WindTurbine {
-    fn default() -> Self {
-        Self::new()
+        writer.write_f64(&self.rpm_id, self.rpm());
     }
 }
-
 struct AlwaysPressurisePumpController {}
 impl AlwaysPressurisePumpController {
     fn new() -> Self {
@@ -962,6 +1126,8 @@ pub trait RamAirTurbineController {
 }
 
 pub struct RamAirTurbine {
+    stow_position_id: VariableIdentifier,
+
     deployment_commanded: bool,
     pump: Pump,
     pump_controller: AlwaysPressurisePumpController,
@@ -981,7 +1147,7 @@ impl RamAirTurbine {
     // Speed to go from 0 to 1 stow position per sec. 1 means full deploying in 1s
     const STOWING_SPEED: f64 = 1.;
 
-    pub fn new() -> Self {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
         let mut max_disp = 0.;
         for v in Self::DISPLACEMENT_MAP.iter() {
             if v > &max_disp {
@@ -990,6 +1156,7 @@ impl RamAirTurbine {
         }
 
         Self {
+            stow_position_id: registry.get("HYD_RAT_STOW_POSITION"),
             deployment_commanded: false,
             pump: Pump::new(
                 Self::DISPLACEMENT_BREAKPTS,
@@ -997,7 +1164,7 @@ impl RamAirTurbine {
                 Self::DISPLACEMENT_DYNAMICS,
             ),
             pump_controller: AlwaysPressurisePumpController::new(),
-            wind_turbine: WindTurbine::new(),
+            wind_turbine: WindTurbine::new(registry),
             position: 0.,
             max_displacement: max_disp,
         }
@@ -1064,18 +1231,16 @@ impl SimulationElement for RamAirTurbine {
     }
 
     fn write(&self, writer: &mut SimulatorWriter) {
-        writer.write_f64("HYD_RAT_STOW_POSITION", self.position);
+        writer.write_f64(&self.stow_position_id, self.position);
     }
 }
-impl Default for RamAirTurbine {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::simulation::UpdateContext;
+    use ntest::assert_about_eq;
+
+    use crate::simulation::{
+        test::SimulationTestBed, InvariantMonitor, InvariantViolationAction, UpdateContext,
+    };
     use uom::si::{
         acceleration::foot_per_second_squared,
         f64::*,
@@ -1141,9 +1306,10 @@ mod tests {
     #[test]
     /// Runs electric pump, checks pressure OK, shut it down, check drop of pressure after 20s
     fn blue_loop_rat_deploy_simulation() {
-        let mut rat = RamAirTurbine::new();
+        let mut registry = VariableRegistry::new();
+        let mut rat = RamAirTurbine::new(&mut registry);
         let mut rat_controller = TestRamAirTurbineController::new();
-        let mut blue_loop = hydraulic_loop("BLUE");
+        let mut blue_loop = hydraulic_loop(&mut registry, "BLUE");
         let blue_loop_controller =
             TestHydraulicLoopController::commanding_open_fire_shutoff_valve();
 
@@ -1219,10 +1385,12 @@ mod tests {
         }
     }
 
-    fn hydraulic_loop(loop_color: &str) -> HydraulicLoop {
+    fn hydraulic_loop(registry: &mut VariableRegistry, loop_color: &str) -> HydraulicLoop {
         match loop_color {
             "GREEN" => HydraulicLoop::new(
+                registry,
                 loop_color,
+                FailureType::GreenHydraulicLeak,
                 true,
                 false,
                 Volume::new::<gallon>(26.41),
@@ -1235,7 +1403,9 @@ mod tests {
                 Pressure::new::<psi>(1750.0),
             ),
             "YELLOW" => HydraulicLoop::new(
+                registry,
                 loop_color,
+                FailureType::YellowHydraulicLeak,
                 false,
                 true,
                 Volume::new::<gallon>(10.2),
@@ -1248,7 +1418,9 @@ mod tests {
                 Pressure::new::<psi>(1750.0),
             ),
             _ => HydraulicLoop::new(
+                registry,
                 loop_color,
+                FailureType::BlueHydraulicLeak,
                 false,
                 false,
                 Volume::new::<gallon>(15.85),
@@ -1263,8 +1435,234 @@ mod tests {
         }
     }
 
-    fn engine_driven_pump() -> EngineDrivenPump {
-        EngineDrivenPump::new("DEFAULT")
+    #[test]
+    fn hydraulic_loop_writes_pressure_interpolated_between_its_last_two_updates() {
+        let mut registry = VariableRegistry::new();
+        let mut hyd_loop = hydraulic_loop(&mut registry, "GREEN");
+        let mut test_bed = SimulationTestBed::seeded_with(registry, &mut hyd_loop);
+        hyd_loop.previous_loop_pressure = Pressure::new::<psi>(1000.);
+        hyd_loop.loop_pressure = Pressure::new::<psi>(3000.);
+
+        hyd_loop.interpolate(0.25);
+        test_bed.run_without_update(&mut hyd_loop);
+        assert_about_eq!(test_bed.read_f64("HYD_GREEN_PRESSURE"), 1500.);
+
+        hyd_loop.interpolate(1.);
+        test_bed.run_without_update(&mut hyd_loop);
+        assert_about_eq!(test_bed.read_f64("HYD_GREEN_PRESSURE"), 3000.);
+    }
+
+    #[test]
+    fn hydraulic_loop_restores_volumes_from_snapshot() {
+        let mut registry = VariableRegistry::new();
+        let mut original_loop = hydraulic_loop(&mut registry, "GREEN");
+        original_loop.reservoir_volume = Volume::new::<gallon>(1.2);
+        original_loop.loop_volume = Volume::new::<gallon>(20.);
+        original_loop
+            .accumulator
+            .set_fluid_volume(Volume::new::<gallon>(0.1));
+        let snapshot = Snapshot::take(&mut original_loop);
+
+        let mut restored_loop = hydraulic_loop(&mut registry, "GREEN");
+        snapshot.restore_into(&mut restored_loop);
+
+        assert_eq!(restored_loop.reservoir_volume(), Volume::new::<gallon>(1.2));
+        assert_eq!(
+            restored_loop.loop_fluid_volume(),
+            Volume::new::<gallon>(20.)
+        );
+        assert_eq!(
+            restored_loop.accumulator_fluid_volume(),
+            Volume::new::<gallon>(0.1)
+        );
+        assert_eq!(
+            restored_loop.accumulator_gas_pressure(),
+            original_loop.accumulator_gas_pressure()
+        );
+    }
+
+    #[test]
+    fn hydraulic_loop_does_not_restore_volumes_of_other_loop() {
+        let mut registry = VariableRegistry::new();
+        let mut original_loop = hydraulic_loop(&mut registry, "GREEN");
+        original_loop.reservoir_volume = Volume::new::<gallon>(1.2);
+        let snapshot = Snapshot::take(&mut original_loop);
+
+        let mut restored_loop = hydraulic_loop(&mut registry, "YELLOW");
+        snapshot.restore_into(&mut restored_loop);
+
+        assert_eq!(restored_loop.reservoir_volume(), Volume::new::<gallon>(3.3));
+    }
+
+    #[test]
+    fn hydraulic_loop_dumps_its_state() {
+        let mut hyd_loop = hydraulic_loop(&mut VariableRegistry::new(), "GREEN");
+        hyd_loop.loop_pressure = Pressure::new::<psi>(3000.);
+        hyd_loop.is_pressurised = true;
+
+        let dump = StateDump::take(&mut hyd_loop);
+        let node = dump.find(&["HYD", "LOOP", "GREEN"]).unwrap();
+
+        assert_eq!(node.read_f64("PRESSURE"), Some(3000.));
+        assert_eq!(node.read_bool("IS_PRESSURISED"), Some(true));
+        assert_eq!(node.read_bool("FIRE_VALVE_OPENED"), Some(true));
+    }
+
+    #[test]
+    fn hydraulic_loop_reports_violated_invariants() {
+        let mut hyd_loop = hydraulic_loop(&mut VariableRegistry::new(), "GREEN");
+        let mut monitor = InvariantMonitor::new(InvariantViolationAction::Count);
+
+        monitor.start_tick();
+        monitor.check(&mut hyd_loop);
+        hyd_loop.reservoir_volume = Volume::new::<gallon>(-0.1);
+        hyd_loop.loop_pressure = Pressure::new::<psi>(5000.);
+        monitor.start_tick();
+        monitor.check(&mut hyd_loop);
+
+        assert_eq!(
+            monitor.violation_count_of("hydraulic reservoir volume never negative"),
+            1
+        );
+        assert_eq!(
+            monitor.violation_count_of("hydraulic loop pressure bounded"),
+            1
+        );
+        assert_eq!(monitor.last_violation().unwrap().tick(), 2);
+    }
+
+    struct TestPowerTransferUnitController {
+        should_enable: bool,
+    }
+    impl TestPowerTransferUnitController {
+        fn commanding_enable() -> Self {
+            Self {
+                should_enable: true,
+            }
+        }
+    }
+    impl PowerTransferUnitController for TestPowerTransferUnitController {
+        fn should_enable(&self) -> bool {
+            self.should_enable
+        }
+    }
+
+    #[test]
+    fn failed_electric_pump_provides_no_flow() {
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(5));
+        let mut pump = ElectricPump::new(
+            test_bed.registry(),
+            "YELLOW",
+            FailureType::YellowElectricPump,
+        );
+        let line = hydraulic_loop(test_bed.registry(), "YELLOW");
+        let controller = TestPumpController::commanding_pressurise();
+        test_bed.write_bool("FAILURE_HYD_YELLOW_EPUMP", true);
+
+        test_bed.run(&mut pump, |pump, context| {
+            pump.update(context, &line, &controller)
+        });
+        test_bed.run(&mut pump, |pump, context| {
+            pump.update(context, &line, &controller)
+        });
+
+        assert_eq!(pump.rpm(), 0.);
+        assert_eq!(pump.delta_vol_max(), Volume::new::<gallon>(0.));
+    }
+
+    #[test]
+    fn failed_engine_driven_pump_provides_no_flow() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut pump = engine_driven_pump(test_bed.registry());
+        let line = hydraulic_loop(test_bed.registry(), "GREEN");
+        let controller = TestPumpController::commanding_pressurise();
+        test_bed.write_bool("FAILURE_HYD_EDPUMP_1", true);
+
+        test_bed.run(&mut pump, |pump, context| {
+            pump.update(context, &line, 4000., &controller)
+        });
+
+        assert_eq!(pump.delta_vol_max(), Volume::new::<gallon>(0.));
+    }
+
+    #[test]
+    fn failed_power_transfer_unit_transfers_no_flow() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut ptu = PowerTransferUnit::new(test_bed.registry());
+        let mut green_loop = hydraulic_loop(test_bed.registry(), "GREEN");
+        green_loop.loop_pressure = Pressure::new::<psi>(3000.);
+        let yellow_loop = hydraulic_loop(test_bed.registry(), "YELLOW");
+        let controller = TestPowerTransferUnitController::commanding_enable();
+        test_bed.write_bool("FAILURE_HYD_PTU", true);
+
+        test_bed.run(&mut ptu, |ptu, _| {
+            ptu.update(&green_loop, &yellow_loop, &controller)
+        });
+
+        assert!(ptu.is_enabled());
+        assert!(!ptu.is_active_left_to_right());
+        assert_eq!(ptu.flow(), VolumeRate::new::<gallon_per_second>(0.));
+    }
+
+    #[test]
+    fn power_transfer_unit_emits_activation_and_deactivation() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut ptu = PowerTransferUnit::new(test_bed.registry());
+        let mut green_loop = hydraulic_loop(test_bed.registry(), "GREEN");
+        green_loop.loop_pressure = Pressure::new::<psi>(3000.);
+        let yellow_loop = hydraulic_loop(test_bed.registry(), "YELLOW");
+        let controller = TestPowerTransferUnitController::commanding_enable();
+
+        test_bed.run(&mut ptu, |ptu, _| {
+            ptu.update(&green_loop, &yellow_loop, &controller)
+        });
+        green_loop.loop_pressure = yellow_loop.loop_pressure;
+        test_bed.run(&mut ptu, |ptu, _| {
+            ptu.update(&green_loop, &yellow_loop, &controller)
+        });
+
+        let events: Vec<_> = test_bed
+            .events()
+            .iter_from("HYD/PTU")
+            .map(|event| (event.kind().clone(), event.cause()))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (EventKind::Activated, Some("left to right")),
+                (EventKind::Deactivated, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn leaking_loop_loses_fluid() {
+        fn total_fluid_after_running(leaking: bool) -> Volume {
+            let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(100));
+            let mut green_loop = hydraulic_loop(test_bed.registry(), "GREEN");
+            green_loop.loop_pressure = Pressure::new::<psi>(3000.);
+            let controller = TestHydraulicLoopController::commanding_open_fire_shutoff_valve();
+            test_bed.write_bool("FAILURE_HYD_GREEN_LEAK", leaking);
+
+            test_bed.run(&mut green_loop, |green_loop, context| {
+                green_loop.update(
+                    context,
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    &controller,
+                )
+            });
+
+            green_loop.loop_fluid_volume() + green_loop.reservoir_volume()
+        }
+
+        assert!(total_fluid_after_running(true) < total_fluid_after_running(false));
+    }
+
+    fn engine_driven_pump(registry: &mut VariableRegistry) -> EngineDrivenPump {
+        EngineDrivenPump::new(registry, "DEFAULT", FailureType::EngineDrivenPump(1))
     }
 
     fn context(delta_time: Duration) -> UpdateContext {
@@ -1284,7 +1682,7 @@ mod tests {
 
         #[test]
         fn starts_inactive() {
-            assert!(!engine_driven_pump().is_active);
+            assert!(!engine_driven_pump(&mut VariableRegistry::new()).is_active);
         }
 
         #[test]
@@ -1319,8 +1717,9 @@ mod tests {
             pressure: Pressure,
             context: &UpdateContext,
         ) -> Volume {
-            let mut edp = engine_driven_pump();
-            let mut line = hydraulic_loop("GREEN");
+            let mut registry = VariableRegistry::new();
+            let mut edp = engine_driven_pump(&mut registry);
+            let mut line = hydraulic_loop(&mut registry, "GREEN");
 
             let engine_driven_pump_controller = TestPumpController::commanding_pressurise();
             line.loop_pressure = pressure;
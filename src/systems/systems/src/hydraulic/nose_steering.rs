@@ -0,0 +1,334 @@
+use crate::{
+    hydraulic::brake_circuit::Actuator,
+    simulation::{
+        SimulationElement, SimulatorWriter, Snapshot, StateDump, UpdateContext, VariableIdentifier,
+        VariableRegistry,
+    },
+};
+
+use uom::si::{
+    angle::degree, angular_velocity::degree_per_second, f64::*, pressure::psi, volume::gallon,
+};
+
+pub trait SteeringController {
+    /// Whether the steering actuator is hydraulically engaged. When not engaged the
+    /// actuator is bypassed, thus it neither moves the wheel nor uses any fluid.
+    fn should_engage(&self) -> bool;
+
+    /// The steering angle requested by the controller, positive to the right.
+    fn requested_position(&self) -> Angle;
+}
+
+/// Hydraulic actuator steering a wheel. The steering speed depends on the pressure
+/// available, and every movement takes fluid from the hydraulic loop and returns it
+/// to the reservoir.
+pub struct SteeringActuator {
+    id: String,
+    position_id: VariableIdentifier,
+    is_available_id: VariableIdentifier,
+
+    max_angle: Angle,
+    max_angular_speed: AngularVelocity,
+    total_displacement: Volume,
+
+    position: Angle,
+    is_available: bool,
+
+    volume_to_actuator_accumulator: Volume,
+    volume_to_res_accumulator: Volume,
+}
+impl SteeringActuator {
+    // Below this pressure the actuator can't move the wheel
+    const MIN_PRESSURE_ALLOWED_TO_MOVE_ACTUATOR_PSI: f64 = 500.;
+    // Pressure at which the actuator moves at its max angular speed
+    const NOMINAL_PRESSURE_PSI: f64 = 3000.;
+
+    /// Creates a steering actuator which moves between minus and plus `max_angle`.
+    /// The `total_displacement` is the volume used to travel from one end to the other.
+    pub fn new(
+        registry: &mut VariableRegistry,
+        id: &str,
+        max_angle: Angle,
+        max_angular_speed: AngularVelocity,
+        total_displacement: Volume,
+    ) -> Self {
+        Self {
+            id: id.to_owned(),
+            position_id: registry.get(&format!("{}_STEERING_ANGLE", id)),
+            is_available_id: registry.get(&format!("{}_STEERING_AVAILABLE", id)),
+
+            max_angle,
+            max_angular_speed,
+            total_displacement,
+
+            position: Angle::new::<degree>(0.),
+            is_available: false,
+
+            volume_to_actuator_accumulator: Volume::new::<gallon>(0.),
+            volume_to_res_accumulator: Volume::new::<gallon>(0.),
+        }
+    }
+
+    pub fn update<T: SteeringController>(
+        &mut self,
+        context: &UpdateContext,
+        loop_pressure: Pressure,
+        controller: &T,
+    ) {
+        self.is_available = controller.should_engage()
+            && loop_pressure.get::<psi>() > Self::MIN_PRESSURE_ALLOWED_TO_MOVE_ACTUATOR_PSI;
+
+        if self.is_available {
+            let requested_position = controller
+                .requested_position()
+                .min(self.max_angle)
+                .max(-self.max_angle);
+
+            let pressure_ratio = (loop_pressure.get::<psi>() / Self::NOMINAL_PRESSURE_PSI).min(1.);
+            let max_delta_degree = self.max_angular_speed.get::<degree_per_second>()
+                * pressure_ratio
+                * context.delta_as_secs_f64();
+
+            let delta_degree = (requested_position - self.position)
+                .get::<degree>()
+                .min(max_delta_degree)
+                .max(-max_delta_degree);
+            self.position += Angle::new::<degree>(delta_degree);
+
+            // Moving either way takes fluid on one side of the actuator and returns it on the other
+            let volume_moved = self.total_displacement * delta_degree.abs()
+                / (2. * self.max_angle.get::<degree>());
+            self.volume_to_actuator_accumulator += volume_moved;
+            self.volume_to_res_accumulator += volume_moved;
+        }
+    }
+
+    pub fn position_feedback(&self) -> Angle {
+        self.position
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.is_available
+    }
+
+    pub fn reset_accumulators(&mut self) {
+        self.volume_to_actuator_accumulator = Volume::new::<gallon>(0.);
+        self.volume_to_res_accumulator = Volume::new::<gallon>(0.);
+    }
+}
+impl Actuator for SteeringActuator {
+    fn used_volume(&self) -> Volume {
+        self.volume_to_actuator_accumulator
+    }
+
+    fn reservoir_return(&self) -> Volume {
+        self.volume_to_res_accumulator
+    }
+}
+impl SimulationElement for SteeringActuator {
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_quantity(&self.position_id, self.position, degree);
+        writer.write_bool(&self.is_available_id, self.is_available);
+    }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        snapshot.write_f64(
+            &format!("{}_STEERING_ANGLE", self.id),
+            self.position.get::<degree>(),
+        );
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(angle) = snapshot.read_f64(&format!("{}_STEERING_ANGLE", self.id)) {
+            self.position = Angle::new::<degree>(angle);
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state.node("HYD").node("STEERING").node(&self.id);
+        node.write_f64("ANGLE", self.position.get::<degree>());
+        node.write_bool("IS_AVAILABLE", self.is_available);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use uom::si::{
+        acceleration::foot_per_second_squared, angle::degree, length::foot,
+        thermodynamic_temperature::degree_celsius, velocity::knot,
+    };
+
+    struct TestSteeringController {
+        should_engage: bool,
+        requested_position: Angle,
+    }
+    impl TestSteeringController {
+        fn engaged_at(requested_position: Angle) -> Self {
+            Self {
+                should_engage: true,
+                requested_position,
+            }
+        }
+
+        fn disengaged_at(requested_position: Angle) -> Self {
+            Self {
+                should_engage: false,
+                requested_position,
+            }
+        }
+    }
+    impl SteeringController for TestSteeringController {
+        fn should_engage(&self) -> bool {
+            self.should_engage
+        }
+
+        fn requested_position(&self) -> Angle {
+            self.requested_position
+        }
+    }
+
+    #[test]
+    fn steering_moves_to_requested_position_with_pressure() {
+        let mut actuator = steering_actuator();
+
+        for _ in 0..50 {
+            actuator.update(
+                &context(Duration::from_millis(100)),
+                Pressure::new::<psi>(3000.),
+                &TestSteeringController::engaged_at(Angle::new::<degree>(30.)),
+            );
+        }
+
+        assert!(actuator.is_available());
+        assert!((actuator.position_feedback().get::<degree>() - 30.).abs() < 0.001);
+    }
+
+    #[test]
+    fn steering_does_not_move_without_pressure() {
+        let mut actuator = steering_actuator();
+
+        actuator.update(
+            &context(Duration::from_secs(1)),
+            Pressure::new::<psi>(200.),
+            &TestSteeringController::engaged_at(Angle::new::<degree>(30.)),
+        );
+
+        assert!(!actuator.is_available());
+        assert!(actuator.position_feedback().get::<degree>() == 0.);
+        assert!(actuator.used_volume() == Volume::new::<gallon>(0.));
+    }
+
+    #[test]
+    fn steering_does_not_move_when_disengaged() {
+        let mut actuator = steering_actuator();
+
+        actuator.update(
+            &context(Duration::from_secs(1)),
+            Pressure::new::<psi>(3000.),
+            &TestSteeringController::disengaged_at(Angle::new::<degree>(30.)),
+        );
+
+        assert!(!actuator.is_available());
+        assert!(actuator.position_feedback().get::<degree>() == 0.);
+    }
+
+    #[test]
+    fn steering_is_limited_to_max_angle() {
+        let mut actuator = steering_actuator();
+
+        for _ in 0..100 {
+            actuator.update(
+                &context(Duration::from_millis(100)),
+                Pressure::new::<psi>(3000.),
+                &TestSteeringController::engaged_at(Angle::new::<degree>(-120.)),
+            );
+        }
+
+        assert!((actuator.position_feedback().get::<degree>() + 75.).abs() < 0.001);
+    }
+
+    #[test]
+    fn steering_moves_slower_with_low_pressure() {
+        let mut full_pressure_actuator = steering_actuator();
+        let mut low_pressure_actuator = steering_actuator();
+
+        full_pressure_actuator.update(
+            &context(Duration::from_millis(100)),
+            Pressure::new::<psi>(3000.),
+            &TestSteeringController::engaged_at(Angle::new::<degree>(30.)),
+        );
+        low_pressure_actuator.update(
+            &context(Duration::from_millis(100)),
+            Pressure::new::<psi>(1000.),
+            &TestSteeringController::engaged_at(Angle::new::<degree>(30.)),
+        );
+
+        assert!(
+            low_pressure_actuator.position_feedback() < full_pressure_actuator.position_feedback()
+        );
+    }
+
+    #[test]
+    fn full_travel_uses_total_displacement() {
+        let mut actuator = steering_actuator();
+
+        for _ in 0..100 {
+            actuator.update(
+                &context(Duration::from_millis(100)),
+                Pressure::new::<psi>(3000.),
+                &TestSteeringController::engaged_at(Angle::new::<degree>(-75.)),
+            );
+        }
+        actuator.reset_accumulators();
+
+        for _ in 0..100 {
+            actuator.update(
+                &context(Duration::from_millis(100)),
+                Pressure::new::<psi>(3000.),
+                &TestSteeringController::engaged_at(Angle::new::<degree>(75.)),
+            );
+        }
+
+        assert!((actuator.used_volume().get::<gallon>() - 0.1).abs() < 0.0001);
+        assert!((actuator.reservoir_return().get::<gallon>() - 0.1).abs() < 0.0001);
+    }
+
+    #[test]
+    fn steering_restores_position_from_snapshot() {
+        let mut actuator = steering_actuator();
+        actuator.update(
+            &context(Duration::from_secs(1)),
+            Pressure::new::<psi>(3000.),
+            &TestSteeringController::engaged_at(Angle::new::<degree>(10.)),
+        );
+        let snapshot = Snapshot::take(&mut actuator);
+
+        let mut restored = steering_actuator();
+        snapshot.restore_into(&mut restored);
+
+        assert_eq!(restored.position_feedback(), actuator.position_feedback());
+    }
+
+    fn steering_actuator() -> SteeringActuator {
+        SteeringActuator::new(
+            &mut VariableRegistry::new(),
+            "NOSE_WHEEL",
+            Angle::new::<degree>(75.),
+            AngularVelocity::new::<degree_per_second>(20.),
+            Volume::new::<gallon>(0.1),
+        )
+    }
+
+    fn context(delta_time: Duration) -> UpdateContext {
+        UpdateContext::new(
+            delta_time,
+            Velocity::new::<knot>(0.),
+            Length::new::<foot>(0.),
+            ThermodynamicTemperature::new::<degree_celsius>(25.0),
+            true,
+            Acceleration::new::<foot_per_second_squared>(0.),
+        )
+    }
+}
@@ -1,102 +1,980 @@
 use crate::{
+    hydraulic::brake_circuit::Actuator,
     shared::LandingGearPosition,
-    simulation::{SimulationElement, SimulatorReader},
+    simulation::{
+        SimulationElement, SimulationElementVisitor, SimulatorReader, SimulatorWriter, Snapshot,
+        StateDump, UpdateContext, VariableIdentifier, VariableRegistry,
+    },
 };
-use uom::si::{f64::*, ratio::percent};
+use uom::si::{f64::*, pressure::psi, ratio::percent, velocity::knot, volume::gallon};
 
-/// Represents a landing gear on Airbus aircraft.
-/// Note that this type somewhat hides the gear's position.
-/// The real aircraft also can only check whether or not the gear is up and
-/// locked or down and locked. No in between state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GearId {
+    Nose = 0,
+    Left = 1,
+    Right = 2,
+}
+impl GearId {
+    const ALL: [GearId; 3] = [GearId::Nose, GearId::Left, GearId::Right];
+
+    fn name(&self) -> &'static str {
+        match self {
+            GearId::Nose => "NOSE",
+            GearId::Left => "LEFT",
+            GearId::Right => "RIGHT",
+        }
+    }
+
+    fn simulator_name(&self) -> &'static str {
+        match self {
+            GearId::Nose => "CENTER",
+            GearId::Left => "LEFT",
+            GearId::Right => "RIGHT",
+        }
+    }
+}
+
+pub trait LandingGearController {
+    /// Whether the gear should be extended or retracted.
+    fn should_extend_gears(&self) -> bool;
+
+    /// Whether hydraulic pressure is supplied to the gear system.
+    /// Without pressure unlocked gears and doors fall open by gravity.
+    fn should_pressurise(&self) -> bool;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GearActuatorDemand {
+    Extend,
+    Retract,
+}
+
+/// A hydraulically actuated part of the gear: a gear leg or a door. Its position goes from
+/// 0 (retracted or closed) to 1 (extended or opened). The uplock holds it retracted. Gear legs
+/// also have a downlock which holds them extended.
+struct GearComponent {
+    position: f64,
+    is_uplocked: bool,
+    has_downlock: bool,
+    is_downlocked: bool,
+
+    // Travel in percent/100 per second at nominal pressure, and when falling by gravity
+    hydraulic_speed: f64,
+    gravity_speed: f64,
+    total_displacement: Volume,
+
+    volume_moved: Volume,
+}
+impl GearComponent {
+    const MIN_PRESSURE_ALLOWED_TO_MOVE_ACTUATOR_PSI: f64 = 500.;
+    const NOMINAL_PRESSURE_PSI: f64 = 3000.;
+
+    fn new_leg(hydraulic_speed: f64, gravity_speed: f64, total_displacement: Volume) -> Self {
+        Self {
+            position: 1.,
+            is_uplocked: false,
+            has_downlock: true,
+            is_downlocked: true,
+            hydraulic_speed,
+            gravity_speed,
+            total_displacement,
+            volume_moved: Volume::new::<gallon>(0.),
+        }
+    }
+
+    fn new_door(hydraulic_speed: f64, gravity_speed: f64, total_displacement: Volume) -> Self {
+        Self {
+            position: 0.,
+            is_uplocked: true,
+            has_downlock: false,
+            is_downlocked: false,
+            hydraulic_speed,
+            gravity_speed,
+            total_displacement,
+            volume_moved: Volume::new::<gallon>(0.),
+        }
+    }
+
+    fn update(
+        &mut self,
+        context: &UpdateContext,
+        pressure: Pressure,
+        demand: GearActuatorDemand,
+        uplock_mechanically_released: bool,
+    ) {
+        let is_pressurised =
+            pressure.get::<psi>() > Self::MIN_PRESSURE_ALLOWED_TO_MOVE_ACTUATOR_PSI;
+
+        // Lock actuators are on the same line as the actuator moving away from the lock
+        if uplock_mechanically_released || (is_pressurised && demand == GearActuatorDemand::Extend)
+        {
+            self.is_uplocked = false;
+        }
+        if is_pressurised && demand == GearActuatorDemand::Retract {
+            self.is_downlocked = false;
+        }
+
+        let speed = if is_pressurised {
+            let pressure_ratio = (pressure.get::<psi>() / Self::NOMINAL_PRESSURE_PSI).min(1.);
+            match demand {
+                GearActuatorDemand::Extend => self.hydraulic_speed * pressure_ratio,
+                GearActuatorDemand::Retract => -self.hydraulic_speed * pressure_ratio,
+            }
+        } else {
+            self.gravity_speed
+        };
+
+        if !self.is_uplocked && !self.is_downlocked {
+            let new_position = (self.position + speed * context.delta_as_secs_f64()).clamp(0., 1.);
+            if is_pressurised {
+                self.volume_moved += self.total_displacement * (new_position - self.position).abs();
+            }
+            self.position = new_position;
+
+            if self.position <= 0. {
+                self.is_uplocked = true;
+            } else if self.position >= 1. && self.has_downlock {
+                self.is_downlocked = true;
+            }
+        }
+    }
+
+    fn is_fully_extended(&self) -> bool {
+        self.position >= 1.
+    }
+
+    fn set_position(&mut self, position: f64) {
+        self.position = position.clamp(0., 1.);
+        self.is_uplocked = self.position <= 0.;
+        self.is_downlocked = self.has_downlock && self.position >= 1.;
+    }
+
+    fn reset_accumulators(&mut self) {
+        self.volume_moved = Volume::new::<gallon>(0.);
+    }
+}
+
+/// A gear leg with its doors. The doors open before the gear moves and close once
+/// the gear is locked in the demanded position, as sequenced by the door sequence valve.
+struct GearAssembly {
+    name: &'static str,
+    position_id: VariableIdentifier,
+    door_position_id: VariableIdentifier,
+    simulator_position_id: VariableIdentifier,
+    leg: GearComponent,
+    door: GearComponent,
+    is_synchronised: bool,
+}
+impl GearAssembly {
+    // The simulator jumping its gear to either end of its travel by more than this is
+    // considered a repositioning of the aircraft rather than the simulator catching up
+    // with our gear.
+    const RESYNCHRONISATION_TOLERANCE_PERCENT: f64 = 1.;
+
+    fn new(
+        registry: &mut VariableRegistry,
+        id: GearId,
+        leg: GearComponent,
+        door: GearComponent,
+    ) -> Self {
+        Self {
+            name: id.name(),
+            position_id: registry.get(&format!("GEAR_{}_POSITION", id.name())),
+            door_position_id: registry.get(&format!("GEAR_DOOR_{}_POSITION", id.name())),
+            simulator_position_id: registry.get(&format!("GEAR {} POSITION", id.simulator_name())),
+            leg,
+            door,
+            is_synchronised: false,
+        }
+    }
+
+    fn update(
+        &mut self,
+        context: &UpdateContext,
+        pressure: Pressure,
+        demand: GearActuatorDemand,
+        is_gravity_extending: bool,
+    ) {
+        if is_gravity_extending {
+            // The crank isolates the gear from hydraulics and releases the door and gear uplocks
+            let no_pressure = Pressure::new::<psi>(0.);
+            self.door
+                .update(context, no_pressure, GearActuatorDemand::Extend, true);
+            self.leg.update(
+                context,
+                no_pressure,
+                GearActuatorDemand::Extend,
+                self.door.is_fully_extended(),
+            );
+        } else {
+            let leg_is_locked_in_demanded_position = match demand {
+                GearActuatorDemand::Extend => self.leg.is_downlocked,
+                GearActuatorDemand::Retract => self.leg.is_uplocked,
+            };
+            let door_demand = if leg_is_locked_in_demanded_position {
+                GearActuatorDemand::Retract
+            } else {
+                GearActuatorDemand::Extend
+            };
+            self.door.update(context, pressure, door_demand, false);
+
+            // The gear actuator is only supplied once the doors are fully open,
+            // but the downlock is released as soon as the gear is selected up
+            if self.door.is_fully_extended() {
+                self.leg.update(context, pressure, demand, false);
+            } else if demand == GearActuatorDemand::Retract {
+                self.leg.is_downlocked = self.leg.is_downlocked
+                    && pressure.get::<psi>()
+                        <= GearComponent::MIN_PRESSURE_ALLOWED_TO_MOVE_ACTUATOR_PSI;
+            }
+        }
+    }
+
+    /// Moves the gear to the given position without using any fluid. A gear at either end
+    /// of its travel is locked with its doors closed, otherwise its doors are open.
+    fn set_position(&mut self, position: Ratio) {
+        self.leg.set_position(position.get::<percent>() / 100.);
+        let is_locked = self.leg.is_uplocked || self.leg.is_downlocked;
+        self.door.set_position(if is_locked { 0. } else { 1. });
+    }
+
+    fn volume_moved(&self) -> Volume {
+        self.leg.volume_moved + self.door.volume_moved
+    }
+
+    fn reset_accumulators(&mut self) {
+        self.leg.reset_accumulators();
+        self.door.reset_accumulators();
+    }
+}
+impl SimulationElement for GearAssembly {
+    fn read(&mut self, reader: &mut SimulatorReader) {
+        // The simulator's flight model has a gear of its own. On the first read our gear takes
+        // the simulator's position, such that a flight can start with the gear retracted.
+        // Afterwards the simulator's gear follows ours, unless the simulator moved it by itself
+        // to either end of its travel, e.g. when the aircraft is repositioned. Any other
+        // movement of the simulator's gear is overwritten by ours.
+        let simulator_position = reader.read_f64(&self.simulator_position_id);
+        let jumped_to_end_of_travel = (simulator_position <= 0. || simulator_position >= 100.)
+            && (simulator_position - self.leg.position * 100.).abs()
+                > Self::RESYNCHRONISATION_TOLERANCE_PERCENT;
+        if !self.is_synchronised || jumped_to_end_of_travel {
+            self.set_position(Ratio::new::<percent>(simulator_position));
+            self.is_synchronised = true;
+        }
+    }
+
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_f64(&self.position_id, self.leg.position * 100.);
+        writer.write_f64(&self.door_position_id, self.door.position * 100.);
+        writer.write_f64(&self.simulator_position_id, self.leg.position * 100.);
+    }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        let name = |suffix| format!("GEAR_{}_{}", self.name, suffix);
+        snapshot.write_f64(&name("POSITION"), self.leg.position * 100.);
+        snapshot.write_bool(&name("IS_UPLOCKED"), self.leg.is_uplocked);
+        snapshot.write_bool(&name("IS_DOWNLOCKED"), self.leg.is_downlocked);
+        snapshot.write_f64(&name("DOOR_POSITION"), self.door.position * 100.);
+        snapshot.write_bool(&name("DOOR_IS_UPLOCKED"), self.door.is_uplocked);
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        let gear_name = self.name;
+        let name = |suffix| format!("GEAR_{}_{}", gear_name, suffix);
+        if let Some(position) = snapshot.read_f64(&name("POSITION")) {
+            self.leg.set_position(position / 100.);
+            // The restored gear takes precedence over the simulator's gear, unless
+            // the simulator jumps its gear to either end of its travel.
+            self.is_synchronised = true;
+        }
+        if let Some(is_uplocked) = snapshot.read_bool(&name("IS_UPLOCKED")) {
+            self.leg.is_uplocked = is_uplocked;
+        }
+        if let Some(is_downlocked) = snapshot.read_bool(&name("IS_DOWNLOCKED")) {
+            self.leg.is_downlocked = is_downlocked;
+        }
+        if let Some(position) = snapshot.read_f64(&name("DOOR_POSITION")) {
+            self.door.set_position(position / 100.);
+        }
+        if let Some(is_uplocked) = snapshot.read_bool(&name("DOOR_IS_UPLOCKED")) {
+            self.door.is_uplocked = is_uplocked;
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state.node("LANDING_GEAR").node(self.name);
+        node.write_f64("POSITION", self.leg.position * 100.);
+        node.write_bool("IS_UPLOCKED", self.leg.is_uplocked);
+        node.write_bool("IS_DOWNLOCKED", self.leg.is_downlocked);
+        node.write_f64("DOOR_POSITION", self.door.position * 100.);
+        node.write_bool("DOOR_IS_UPLOCKED", self.door.is_uplocked);
+    }
+}
+
+/// The hydraulically actuated landing gear of an Airbus aircraft: a nose and two main
+/// gears, each with its doors. Retraction and extension require hydraulic pressure,
+/// while the gravity extension crank lets the gear fall down and lock by gravity.
+/// The gear starts in the position of the simulator's gear, after which the simulator's
+/// gear is kept at the position of this gear.
+///
+/// Like on the real aircraft, other systems should not use the gear's position directly,
+/// but rather the lock sensors reported by a [`LandingGearControlInterfaceUnit`].
+///
+/// [`LandingGearControlInterfaceUnit`]: struct.LandingGearControlInterfaceUnit.html
 pub struct LandingGear {
-    position: Ratio,
+    gravity_extension_handle_id: VariableIdentifier,
+
+    gears: [GearAssembly; 3],
+    is_gravity_extending: bool,
 }
 impl LandingGear {
-    const GEAR_CENTER_POSITION: &'static str = "GEAR CENTER POSITION";
+    const GRAVITY_EXTENSION_HANDLE: &'static str = "GEAR EMERGENCY HANDLE POSITION";
+
+    const LEG_HYDRAULIC_SPEED: f64 = 0.17;
+    const LEG_GRAVITY_SPEED: f64 = 0.1;
+    const DOOR_HYDRAULIC_SPEED: f64 = 0.7;
+    const DOOR_GRAVITY_SPEED: f64 = 0.3;
+
+    const NOSE_LEG_DISPLACEMENT_GALLON: f64 = 0.25;
+    const MAIN_LEG_DISPLACEMENT_GALLON: f64 = 0.5;
+    const DOOR_DISPLACEMENT_GALLON: f64 = 0.05;
 
-    pub fn new() -> Self {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
         Self {
-            position: Ratio::new::<percent>(0.),
+            gravity_extension_handle_id: registry.get(Self::GRAVITY_EXTENSION_HANDLE),
+
+            gears: [
+                LandingGear::gear_assembly(
+                    registry,
+                    GearId::Nose,
+                    Self::NOSE_LEG_DISPLACEMENT_GALLON,
+                ),
+                LandingGear::gear_assembly(
+                    registry,
+                    GearId::Left,
+                    Self::MAIN_LEG_DISPLACEMENT_GALLON,
+                ),
+                LandingGear::gear_assembly(
+                    registry,
+                    GearId::Right,
+                    Self::MAIN_LEG_DISPLACEMENT_GALLON,
+                ),
+            ],
+            is_gravity_extending: false,
+        }
+    }
+
+    fn gear_assembly(
+        registry: &mut VariableRegistry,
+        id: GearId,
+        leg_displacement_gallon: f64,
+    ) -> GearAssembly {
+        GearAssembly::new(
+            registry,
+            id,
+            GearComponent::new_leg(
+                Self::LEG_HYDRAULIC_SPEED,
+                Self::LEG_GRAVITY_SPEED,
+                Volume::new::<gallon>(leg_displacement_gallon),
+            ),
+            GearComponent::new_door(
+                Self::DOOR_HYDRAULIC_SPEED,
+                Self::DOOR_GRAVITY_SPEED,
+                Volume::new::<gallon>(Self::DOOR_DISPLACEMENT_GALLON),
+            ),
+        )
+    }
+
+    pub fn update<T: LandingGearController>(
+        &mut self,
+        context: &UpdateContext,
+        loop_pressure: Pressure,
+        controller: &T,
+    ) {
+        let pressure = if controller.should_pressurise() {
+            loop_pressure
+        } else {
+            Pressure::new::<psi>(0.)
+        };
+        let demand = if controller.should_extend_gears() {
+            GearActuatorDemand::Extend
+        } else {
+            GearActuatorDemand::Retract
+        };
+
+        for gear in self.gears.iter_mut() {
+            gear.update(context, pressure, demand, self.is_gravity_extending);
+        }
+    }
+
+    pub fn is_down_and_locked(&self, id: GearId) -> bool {
+        self.gears[id as usize].leg.is_downlocked
+    }
+
+    pub fn is_up_and_locked(&self, id: GearId) -> bool {
+        self.gears[id as usize].leg.is_uplocked
+    }
+
+    pub fn is_door_closed_and_locked(&self, id: GearId) -> bool {
+        self.gears[id as usize].door.is_uplocked
+    }
+
+    pub fn position(&self, id: GearId) -> Ratio {
+        Ratio::new::<percent>(self.gears[id as usize].leg.position * 100.)
+    }
+
+    pub fn reset_accumulators(&mut self) {
+        for gear in self.gears.iter_mut() {
+            gear.reset_accumulators();
         }
     }
 }
-impl LandingGearPosition for LandingGear {
-    fn is_up_and_locked(&self) -> bool {
-        (self.position.get::<percent>() - 0.).abs() < f64::EPSILON
+impl Actuator for LandingGear {
+    // Gear actuators are double acting: the fluid taken from the loop on one
+    // side is returned to the reservoir from the other side.
+    fn used_volume(&self) -> Volume {
+        self.gears.iter().map(|gear| gear.volume_moved()).sum()
     }
 
-    fn is_down_and_locked(&self) -> bool {
-        (self.position.get::<percent>() - 100.).abs() < f64::EPSILON
+    fn reservoir_return(&self) -> Volume {
+        self.used_volume()
     }
 }
 impl SimulationElement for LandingGear {
+    fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+        for gear in self.gears.iter_mut() {
+            gear.accept(visitor);
+        }
+
+        visitor.visit(self);
+    }
+
     fn read(&mut self, reader: &mut SimulatorReader) {
-        self.position = Ratio::new::<percent>(reader.read_f64(LandingGear::GEAR_CENTER_POSITION));
+        self.is_gravity_extending = reader.read_bool(&self.gravity_extension_handle_id);
+    }
+}
+/// The Landing Gear Control and Interface Unit (LGCIU) controls the gear extension and
+/// retraction based on the gear lever, and provides the gear lock and door sensors to
+/// the other systems.
+pub struct LandingGearControlInterfaceUnit {
+    gear_lever_position_id: VariableIdentifier,
+    downlocked_ids: [VariableIdentifier; 3],
+    uplocked_ids: [VariableIdentifier; 3],
+    door_closed_ids: [VariableIdentifier; 3],
+
+    gear_lever_is_down: bool,
+    should_extend_gears: bool,
+    should_pressurise: bool,
+
+    is_downlocked: [bool; 3],
+    is_uplocked: [bool; 3],
+    is_door_closed: [bool; 3],
+}
+impl LandingGearControlInterfaceUnit {
+    const GEAR_LEVER_POSITION: &'static str = "GEAR HANDLE POSITION";
+
+    // Above this speed the safety valve cuts the hydraulic supply to the gear
+    const SAFETY_VALVE_CLOSING_SPEED_KNOT: f64 = 260.;
+
+    pub fn new(registry: &mut VariableRegistry, number: usize) -> Self {
+        let gear_lever_position_id = registry.get(Self::GEAR_LEVER_POSITION);
+        let mut ids = |sensor: &str| {
+            let mut id = |gear: GearId| {
+                registry.get(&format!("LGCIU_{}_{}_GEAR_{}", number, gear.name(), sensor))
+            };
+            [id(GearId::Nose), id(GearId::Left), id(GearId::Right)]
+        };
+
+        Self {
+            gear_lever_position_id,
+            downlocked_ids: ids("DOWNLOCKED"),
+            uplocked_ids: ids("UPLOCKED"),
+            door_closed_ids: ids("DOOR_CLOSED"),
+
+            gear_lever_is_down: true,
+            should_extend_gears: true,
+            should_pressurise: true,
+
+            is_downlocked: [true; 3],
+            is_uplocked: [false; 3],
+            is_door_closed: [true; 3],
+        }
+    }
+
+    pub fn update(&mut self, context: &UpdateContext, landing_gear: &LandingGear) {
+        for id in GearId::ALL.iter() {
+            self.is_downlocked[*id as usize] = landing_gear.is_down_and_locked(*id);
+            self.is_uplocked[*id as usize] = landing_gear.is_up_and_locked(*id);
+            self.is_door_closed[*id as usize] = landing_gear.is_door_closed_and_locked(*id);
+        }
+
+        // Retraction on ground is inhibited by the weight on wheels
+        self.should_extend_gears = self.gear_lever_is_down || context.is_on_ground();
+        self.should_pressurise = context.indicated_airspeed()
+            < Velocity::new::<knot>(Self::SAFETY_VALVE_CLOSING_SPEED_KNOT);
+    }
+
+    pub fn gear_is_down_and_locked(&self, id: GearId) -> bool {
+        self.is_downlocked[id as usize]
+    }
+
+    pub fn gear_is_up_and_locked(&self, id: GearId) -> bool {
+        self.is_uplocked[id as usize]
+    }
+
+    pub fn door_is_closed(&self, id: GearId) -> bool {
+        self.is_door_closed[id as usize]
+    }
+}
+impl LandingGearController for LandingGearControlInterfaceUnit {
+    fn should_extend_gears(&self) -> bool {
+        self.should_extend_gears
+    }
+
+    fn should_pressurise(&self) -> bool {
+        self.should_pressurise
+    }
+}
+impl LandingGearPosition for LandingGearControlInterfaceUnit {
+    fn is_up_and_locked(&self) -> bool {
+        self.is_uplocked.iter().all(|is_uplocked| *is_uplocked)
+    }
+
+    fn is_down_and_locked(&self) -> bool {
+        self.is_downlocked
+            .iter()
+            .all(|is_downlocked| *is_downlocked)
     }
 }
-impl Default for LandingGear {
-    fn default() -> Self {
-        Self::new()
+impl SimulationElement for LandingGearControlInterfaceUnit {
+    fn read(&mut self, reader: &mut SimulatorReader) {
+        self.gear_lever_is_down = reader.read_bool(&self.gear_lever_position_id);
+    }
+
+    fn write(&self, writer: &mut SimulatorWriter) {
+        for id in GearId::ALL.iter() {
+            let index = *id as usize;
+            writer.write_bool(&self.downlocked_ids[index], self.is_downlocked[index]);
+            writer.write_bool(&self.uplocked_ids[index], self.is_uplocked[index]);
+            writer.write_bool(&self.door_closed_ids[index], self.is_door_closed[index]);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::simulation::test::SimulationTestBed;
+    use crate::simulation::{test::SimulationTestBed, Aircraft};
+    use std::time::Duration;
+
+    struct TestLandingGearController {
+        should_extend_gears: bool,
+        should_pressurise: bool,
+    }
+    impl TestLandingGearController {
+        fn extending() -> Self {
+            Self {
+                should_extend_gears: true,
+                should_pressurise: true,
+            }
+        }
+
+        fn retracting() -> Self {
+            Self {
+                should_extend_gears: false,
+                should_pressurise: true,
+            }
+        }
+    }
+    impl LandingGearController for TestLandingGearController {
+        fn should_extend_gears(&self) -> bool {
+            self.should_extend_gears
+        }
+
+        fn should_pressurise(&self) -> bool {
+            self.should_pressurise
+        }
+    }
+
+    struct TestAircraft {
+        landing_gear: LandingGear,
+        lgciu: LandingGearControlInterfaceUnit,
+        pressure: Pressure,
+    }
+    impl TestAircraft {
+        fn new(registry: &mut VariableRegistry) -> Self {
+            Self {
+                landing_gear: LandingGear::new(registry),
+                lgciu: LandingGearControlInterfaceUnit::new(registry, 1),
+                pressure: Pressure::new::<psi>(3000.),
+            }
+        }
+
+        fn set_pressure(&mut self, pressure: Pressure) {
+            self.pressure = pressure;
+        }
+    }
+    impl Aircraft for TestAircraft {
+        fn update_after_power_distribution(&mut self, context: &UpdateContext) {
+            self.lgciu.update(context, &self.landing_gear);
+            self.landing_gear
+                .update(context, self.pressure, &self.lgciu);
+        }
+    }
+    impl SimulationElement for TestAircraft {
+        fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+            self.landing_gear.accept(visitor);
+            self.lgciu.accept(visitor);
+
+            visitor.visit(self);
+        }
+    }
+
+    #[test]
+    fn gear_is_down_and_locked_with_doors_closed_at_init() {
+        let landing_gear = LandingGear::new(&mut VariableRegistry::new());
+
+        for id in GearId::ALL.iter() {
+            assert!(landing_gear.is_down_and_locked(*id));
+            assert!(!landing_gear.is_up_and_locked(*id));
+            assert!(landing_gear.is_door_closed_and_locked(*id));
+        }
+    }
 
     #[test]
-    fn is_up_and_locked_returns_false_when_fully_down() {
-        let mut landing_gear = LandingGear::new();
-        run_test_bed_on(&mut landing_gear, 100.);
+    fn gear_retracts_with_pressure() {
+        let (mut test_bed, mut landing_gear) = test_bed_with_landing_gear();
+
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(3000.),
+            TestLandingGearController::retracting(),
+            Duration::from_secs(15),
+        );
 
-        assert!(!landing_gear.is_up_and_locked());
+        for id in GearId::ALL.iter() {
+            assert!(landing_gear.is_up_and_locked(*id));
+            assert!(!landing_gear.is_down_and_locked(*id));
+            assert!(landing_gear.is_door_closed_and_locked(*id));
+        }
     }
 
     #[test]
-    fn is_up_and_locked_returns_false_when_somewhat_down() {
-        let mut landing_gear = LandingGear::new();
-        run_test_bed_on(&mut landing_gear, 1.);
+    fn gear_extends_with_pressure() {
+        let (mut test_bed, mut landing_gear) = test_bed_with_landing_gear();
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(3000.),
+            TestLandingGearController::retracting(),
+            Duration::from_secs(15),
+        );
+
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(3000.),
+            TestLandingGearController::extending(),
+            Duration::from_secs(15),
+        );
 
-        assert!(!landing_gear.is_up_and_locked());
+        for id in GearId::ALL.iter() {
+            assert!(landing_gear.is_down_and_locked(*id));
+            assert!(landing_gear.is_door_closed_and_locked(*id));
+        }
     }
 
     #[test]
-    fn is_up_and_locked_returns_true_when_fully_up() {
-        let mut landing_gear = LandingGear::new();
-        run_test_bed_on(&mut landing_gear, 0.);
+    fn gear_does_not_retract_without_pressure() {
+        let (mut test_bed, mut landing_gear) = test_bed_with_landing_gear();
+
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(0.),
+            TestLandingGearController::retracting(),
+            Duration::from_secs(15),
+        );
 
-        assert!(landing_gear.is_up_and_locked());
+        for id in GearId::ALL.iter() {
+            assert!(landing_gear.is_down_and_locked(*id));
+            assert!(landing_gear.position(*id).get::<percent>() >= 100.);
+        }
     }
 
     #[test]
-    fn is_down_and_locked_returns_false_when_fully_up() {
-        let mut landing_gear = LandingGear::new();
-        run_test_bed_on(&mut landing_gear, 0.);
+    fn gear_does_not_move_before_doors_are_open() {
+        let (mut test_bed, mut landing_gear) = test_bed_with_landing_gear();
+
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(3000.),
+            TestLandingGearController::retracting(),
+            Duration::from_millis(500),
+        );
 
-        assert!(!landing_gear.is_down_and_locked());
+        for id in GearId::ALL.iter() {
+            assert!(!landing_gear.is_door_closed_and_locked(*id));
+            assert!(!landing_gear.is_down_and_locked(*id));
+            assert!(landing_gear.position(*id).get::<percent>() >= 100.);
+        }
     }
 
     #[test]
-    fn is_down_and_locked_returns_false_when_somewhat_up() {
-        let mut landing_gear = LandingGear::new();
-        run_test_bed_on(&mut landing_gear, 99.);
+    fn retracting_gear_uses_fluid() {
+        let (mut test_bed, mut landing_gear) = test_bed_with_landing_gear();
+
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(3000.),
+            TestLandingGearController::retracting(),
+            Duration::from_secs(3),
+        );
 
-        assert!(!landing_gear.is_down_and_locked());
+        assert!(landing_gear.used_volume() > Volume::new::<gallon>(0.));
+        assert!(landing_gear.reservoir_return() == landing_gear.used_volume());
     }
 
     #[test]
-    fn is_down_and_locked_returns_true_when_fully_down() {
-        let mut landing_gear = LandingGear::new();
-        run_test_bed_on(&mut landing_gear, 100.);
+    fn gear_extends_by_gravity_with_gravity_extension_handle() {
+        let (mut test_bed, mut aircraft) = test_bed_with_aircraft();
+        test_bed.write_bool("GEAR HANDLE POSITION", false);
+        for _ in 0..150 {
+            test_bed.run_aircraft(&mut aircraft);
+        }
+        assert!(aircraft.lgciu.is_up_and_locked());
 
-        assert!(landing_gear.is_down_and_locked());
+        aircraft.set_pressure(Pressure::new::<psi>(0.));
+        test_bed.write_bool("GEAR EMERGENCY HANDLE POSITION", true);
+        test_bed.write_bool("GEAR HANDLE POSITION", true);
+        for _ in 0..300 {
+            test_bed.run_aircraft(&mut aircraft);
+        }
+
+        assert!(aircraft.lgciu.is_down_and_locked());
+        for id in GearId::ALL.iter() {
+            assert!(!aircraft.lgciu.door_is_closed(*id));
+        }
     }
 
-    fn run_test_bed_on(landing_gear: &mut LandingGear, position: f64) {
-        let mut test_bed = SimulationTestBed::new();
-        test_bed.write_f64(LandingGear::GEAR_CENTER_POSITION, position);
+    #[test]
+    fn gear_starts_retracted_when_simulator_gear_is_retracted() {
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(100));
+        let mut landing_gear = LandingGear::new(test_bed.registry());
+        set_simulator_gear_position(&mut test_bed, 0.);
 
-        test_bed.run_without_update(landing_gear);
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(3000.),
+            TestLandingGearController::retracting(),
+            Duration::from_millis(100),
+        );
+
+        for id in GearId::ALL.iter() {
+            assert!(landing_gear.is_up_and_locked(*id));
+            assert!(landing_gear.is_door_closed_and_locked(*id));
+        }
+    }
+
+    #[test]
+    fn simulator_gear_follows_retracting_gear() {
+        let (mut test_bed, mut landing_gear) = test_bed_with_landing_gear();
+
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(3000.),
+            TestLandingGearController::retracting(),
+            Duration::from_secs(3),
+        );
+
+        for id in GearId::ALL.iter() {
+            let position = landing_gear.position(*id).get::<percent>();
+            assert!(position < 100.);
+            assert_eq!(
+                test_bed.read_f64(&format!("GEAR {} POSITION", id.simulator_name())),
+                position
+            );
+        }
+    }
+
+    #[test]
+    fn gear_takes_position_of_simulator_gear_moved_by_the_simulator() {
+        let (mut test_bed, mut landing_gear) = test_bed_with_landing_gear();
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(3000.),
+            TestLandingGearController::retracting(),
+            Duration::from_secs(15),
+        );
+
+        set_simulator_gear_position(&mut test_bed, 100.);
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(0.),
+            TestLandingGearController::retracting(),
+            Duration::from_millis(100),
+        );
+
+        for id in GearId::ALL.iter() {
+            assert!(landing_gear.is_down_and_locked(*id));
+            assert!(landing_gear.is_door_closed_and_locked(*id));
+        }
+    }
+
+    #[test]
+    fn gear_does_not_follow_simulator_gear_moving_by_itself_without_pressure() {
+        let (mut test_bed, mut landing_gear) = test_bed_with_landing_gear();
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(0.),
+            TestLandingGearController::extending(),
+            Duration::from_millis(100),
+        );
+
+        for step in 1..=10 {
+            set_simulator_gear_position(&mut test_bed, 100. - 5. * step as f64);
+            run_for(
+                &mut test_bed,
+                &mut landing_gear,
+                Pressure::new::<psi>(0.),
+                TestLandingGearController::retracting(),
+                Duration::from_millis(100),
+            );
+        }
+
+        for id in GearId::ALL.iter() {
+            assert!(landing_gear.is_down_and_locked(*id));
+            assert_eq!(
+                test_bed.read_f64(&format!("GEAR {} POSITION", id.simulator_name())),
+                100.
+            );
+        }
+    }
+
+    #[test]
+    fn restored_gear_keeps_its_position_instead_of_taking_the_simulator_gear_position() {
+        let (mut test_bed, mut landing_gear) = test_bed_with_landing_gear();
+        run_for(
+            &mut test_bed,
+            &mut landing_gear,
+            Pressure::new::<psi>(3000.),
+            TestLandingGearController::retracting(),
+            Duration::from_secs(3),
+        );
+        let snapshot = Snapshot::take(&mut landing_gear);
+        let dump = StateDump::take(&mut landing_gear);
+
+        let (mut restored_test_bed, mut restored) = test_bed_with_landing_gear();
+        snapshot.restore_into(&mut restored);
+        assert_eq!(StateDump::take(&mut restored), dump);
+
+        set_simulator_gear_position(&mut restored_test_bed, 50.);
+        for (test_bed, landing_gear) in [
+            (&mut test_bed, &mut landing_gear),
+            (&mut restored_test_bed, &mut restored),
+        ] {
+            run_for(
+                test_bed,
+                landing_gear,
+                Pressure::new::<psi>(0.),
+                TestLandingGearController::retracting(),
+                Duration::from_millis(100),
+            );
+        }
+        for id in GearId::ALL.iter() {
+            assert_eq!(restored.position(*id), landing_gear.position(*id));
+        }
+    }
+
+    #[test]
+    fn lgciu_retracts_gear_when_lever_is_up_in_flight() {
+        let (mut test_bed, mut aircraft) = test_bed_with_aircraft();
+        test_bed.write_bool("GEAR HANDLE POSITION", false);
+
+        for _ in 0..150 {
+            test_bed.run_aircraft(&mut aircraft);
+        }
+
+        assert!(aircraft.lgciu.is_up_and_locked());
+        assert!(!aircraft.lgciu.is_down_and_locked());
+        assert!(test_bed.read_bool("LGCIU_1_LEFT_GEAR_UPLOCKED"));
+        assert!(test_bed.read_bool("LGCIU_1_NOSE_GEAR_DOOR_CLOSED"));
+        assert!(!test_bed.read_bool("LGCIU_1_RIGHT_GEAR_DOWNLOCKED"));
+    }
+
+    #[test]
+    fn lgciu_does_not_retract_gear_on_ground() {
+        let (mut test_bed, mut aircraft) = test_bed_with_aircraft();
+        test_bed.set_on_ground(true);
+        test_bed.write_bool("GEAR HANDLE POSITION", false);
+
+        for _ in 0..150 {
+            test_bed.run_aircraft(&mut aircraft);
+        }
+
+        assert!(aircraft.lgciu.is_down_and_locked());
+    }
+
+    #[test]
+    fn lgciu_does_not_pressurise_gear_above_safety_valve_speed() {
+        let (mut test_bed, mut aircraft) = test_bed_with_aircraft();
+        test_bed.set_indicated_airspeed(Velocity::new::<knot>(280.));
+        test_bed.write_bool("GEAR HANDLE POSITION", false);
+
+        for _ in 0..150 {
+            test_bed.run_aircraft(&mut aircraft);
+        }
+
+        assert!(!aircraft.lgciu.should_pressurise());
+        assert!(aircraft.lgciu.is_down_and_locked());
+    }
+
+    fn test_bed_with_landing_gear() -> (SimulationTestBed, LandingGear) {
+        let mut registry = VariableRegistry::new();
+        let mut landing_gear = LandingGear::new(&mut registry);
+        let mut test_bed = SimulationTestBed::seeded_with(registry, &mut landing_gear);
+        test_bed.set_delta(Duration::from_millis(100));
+
+        (test_bed, landing_gear)
+    }
+
+    fn test_bed_with_aircraft() -> (SimulationTestBed, TestAircraft) {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let mut test_bed = SimulationTestBed::seeded_with(registry, &mut aircraft);
+        test_bed.set_delta(Duration::from_millis(100));
+
+        (test_bed, aircraft)
+    }
+
+    fn set_simulator_gear_position(test_bed: &mut SimulationTestBed, position: f64) {
+        for id in GearId::ALL.iter() {
+            test_bed.write_f64(&format!("GEAR {} POSITION", id.simulator_name()), position);
+        }
+    }
+
+    fn run_for(
+        test_bed: &mut SimulationTestBed,
+        landing_gear: &mut LandingGear,
+        pressure: Pressure,
+        controller: TestLandingGearController,
+        duration: Duration,
+    ) {
+        let steps = (duration.as_millis() / 100) as usize;
+
+        for _ in 0..steps {
+            test_bed.run(landing_gear, |gear, context| {
+                gear.update(context, pressure, &controller)
+            });
+        }
     }
 }
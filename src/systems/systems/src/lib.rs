@@ -10,6 +10,8 @@ mod macros;
 pub mod apu;
 pub mod electrical;
 pub mod engine;
+pub mod failures;
+pub mod fuel;
 pub mod hydraulic;
 pub mod landing_gear;
 pub mod overhead;
@@ -1,25 +1,27 @@
-use crate::simulation::{SimulationElement, SimulatorReader, SimulatorWriter};
+use crate::simulation::{
+    SimulationElement, SimulatorReader, SimulatorWriter, VariableIdentifier, VariableRegistry,
+};
 
 pub struct OnOffFaultPushButton {
-    is_on_id: String,
-    has_fault_id: String,
+    is_on_id: VariableIdentifier,
+    has_fault_id: VariableIdentifier,
 
     is_on: bool,
     has_fault: bool,
 }
 impl OnOffFaultPushButton {
-    pub fn new_on(name: &str) -> Self {
-        Self::new(name, true)
+    pub fn new_on(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, true)
     }
 
-    pub fn new_off(name: &str) -> Self {
-        Self::new(name, false)
+    pub fn new_off(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, false)
     }
 
-    fn new(name: &str, is_on: bool) -> Self {
+    fn new(registry: &mut VariableRegistry, name: &str, is_on: bool) -> Self {
         Self {
-            is_on_id: format!("OVHD_{}_PB_IS_ON", name),
-            has_fault_id: format!("OVHD_{}_PB_HAS_FAULT", name),
+            is_on_id: registry.get(&format!("OVHD_{}_PB_IS_ON", name)),
+            has_fault_id: registry.get(&format!("OVHD_{}_PB_HAS_FAULT", name)),
             is_on,
             has_fault: false,
         }
@@ -66,25 +68,25 @@ impl SimulationElement for OnOffFaultPushButton {
 }
 
 pub struct OnOffAvailablePushButton {
-    is_on_id: String,
-    is_available_id: String,
+    is_on_id: VariableIdentifier,
+    is_available_id: VariableIdentifier,
 
     is_on: bool,
     is_available: bool,
 }
 impl OnOffAvailablePushButton {
-    pub fn new_on(name: &str) -> Self {
-        Self::new(name, true)
+    pub fn new_on(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, true)
     }
 
-    pub fn new_off(name: &str) -> Self {
-        Self::new(name, false)
+    pub fn new_off(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, false)
     }
 
-    fn new(name: &str, is_on: bool) -> Self {
+    fn new(registry: &mut VariableRegistry, name: &str, is_on: bool) -> Self {
         Self {
-            is_on_id: format!("OVHD_{}_PB_IS_ON", name),
-            is_available_id: format!("OVHD_{}_PB_IS_AVAILABLE", name),
+            is_on_id: registry.get(&format!("OVHD_{}_PB_IS_ON", name)),
+            is_available_id: registry.get(&format!("OVHD_{}_PB_IS_AVAILABLE", name)),
             is_on,
             is_available: false,
         }
@@ -131,25 +133,25 @@ impl SimulationElement for OnOffAvailablePushButton {
 }
 
 pub struct NormalAltnFaultPushButton {
-    is_normal_id: String,
-    has_fault_id: String,
+    is_normal_id: VariableIdentifier,
+    has_fault_id: VariableIdentifier,
 
     is_normal: bool,
     has_fault: bool,
 }
 impl NormalAltnFaultPushButton {
-    pub fn new_normal(name: &str) -> Self {
-        Self::new(name, true)
+    pub fn new_normal(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, true)
     }
 
-    pub fn new_altn(name: &str) -> Self {
-        Self::new(name, false)
+    pub fn new_altn(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, false)
     }
 
-    fn new(name: &str, is_normal: bool) -> Self {
+    fn new(registry: &mut VariableRegistry, name: &str, is_normal: bool) -> Self {
         Self {
-            is_normal_id: format!("OVHD_{}_PB_IS_NORMAL", name),
-            has_fault_id: format!("OVHD_{}_PB_HAS_FAULT", name),
+            is_normal_id: registry.get(&format!("OVHD_{}_PB_IS_NORMAL", name)),
+            has_fault_id: registry.get(&format!("OVHD_{}_PB_HAS_FAULT", name)),
             is_normal,
             has_fault: false,
         }
@@ -192,25 +194,25 @@ impl SimulationElement for NormalAltnFaultPushButton {
 }
 
 pub struct AutoOffFaultPushButton {
-    is_auto_id: String,
-    has_fault_id: String,
+    is_auto_id: VariableIdentifier,
+    has_fault_id: VariableIdentifier,
 
     is_auto: bool,
     has_fault: bool,
 }
 impl AutoOffFaultPushButton {
-    pub fn new_auto(name: &str) -> Self {
-        Self::new(name, true)
+    pub fn new_auto(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, true)
     }
 
-    pub fn new_off(name: &str) -> Self {
-        Self::new(name, false)
+    pub fn new_off(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, false)
     }
 
-    fn new(name: &str, is_auto: bool) -> Self {
+    fn new(registry: &mut VariableRegistry, name: &str, is_auto: bool) -> Self {
         Self {
-            is_auto_id: format!("OVHD_{}_PB_IS_AUTO", name),
-            has_fault_id: format!("OVHD_{}_PB_HAS_FAULT", name),
+            is_auto_id: registry.get(&format!("OVHD_{}_PB_IS_AUTO", name)),
+            has_fault_id: registry.get(&format!("OVHD_{}_PB_HAS_FAULT", name)),
             is_auto,
             has_fault: false,
         }
@@ -257,25 +259,25 @@ impl SimulationElement for AutoOffFaultPushButton {
 }
 
 pub struct AutoOnFaultPushButton {
-    is_auto_id: String,
-    has_fault_id: String,
+    is_auto_id: VariableIdentifier,
+    has_fault_id: VariableIdentifier,
 
     is_auto: bool,
     has_fault: bool,
 }
 impl AutoOnFaultPushButton {
-    pub fn new_auto(name: &str) -> Self {
-        Self::new(name, true)
+    pub fn new_auto(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, true)
     }
 
-    pub fn new_on(name: &str) -> Self {
-        Self::new(name, false)
+    pub fn new_on(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, false)
     }
 
-    fn new(name: &str, is_auto: bool) -> Self {
+    fn new(registry: &mut VariableRegistry, name: &str, is_auto: bool) -> Self {
         Self {
-            is_auto_id: format!("OVHD_{}_PB_IS_AUTO", name),
-            has_fault_id: format!("OVHD_{}_PB_HAS_FAULT", name),
+            is_auto_id: registry.get(&format!("OVHD_{}_PB_IS_AUTO", name)),
+            has_fault_id: registry.get(&format!("OVHD_{}_PB_HAS_FAULT", name)),
             is_auto,
             has_fault: false,
         }
@@ -322,25 +324,25 @@ impl SimulationElement for AutoOnFaultPushButton {
 }
 
 pub struct FaultReleasePushButton {
-    is_released_id: String,
-    has_fault_id: String,
+    is_released_id: VariableIdentifier,
+    has_fault_id: VariableIdentifier,
     is_released: bool,
     has_fault: bool,
 }
 impl FaultReleasePushButton {
     #[cfg(test)]
-    pub fn new_released(name: &str) -> Self {
-        Self::new(name, true)
+    pub fn new_released(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, true)
     }
 
-    pub fn new_in(name: &str) -> Self {
-        Self::new(name, false)
+    pub fn new_in(registry: &mut VariableRegistry, name: &str) -> Self {
+        Self::new(registry, name, false)
     }
 
-    fn new(name: &str, is_released: bool) -> Self {
+    fn new(registry: &mut VariableRegistry, name: &str, is_released: bool) -> Self {
         Self {
-            is_released_id: format!("OVHD_{}_PB_IS_RELEASED", name),
-            has_fault_id: format!("OVHD_{}_PB_HAS_FAULT", name),
+            is_released_id: registry.get(&format!("OVHD_{}_PB_IS_RELEASED", name)),
+            has_fault_id: registry.get(&format!("OVHD_{}_PB_HAS_FAULT", name)),
             is_released,
             has_fault: false,
         }
@@ -375,13 +377,13 @@ impl SimulationElement for FaultReleasePushButton {
 }
 
 pub struct FirePushButton {
-    is_released_id: String,
+    is_released_id: VariableIdentifier,
     is_released: bool,
 }
 impl FirePushButton {
-    pub fn new(name: &str) -> Self {
+    pub fn new(registry: &mut VariableRegistry, name: &str) -> Self {
         Self {
-            is_released_id: format!("FIRE_BUTTON_{}", name),
+            is_released_id: registry.get(&format!("FIRE_BUTTON_{}", name)),
             is_released: false,
         }
     }
@@ -405,13 +407,13 @@ impl SimulationElement for FirePushButton {
 }
 
 pub struct FaultIndication {
-    has_fault_id: String,
+    has_fault_id: VariableIdentifier,
     has_fault: bool,
 }
 impl FaultIndication {
-    pub fn new(name: &str) -> Self {
+    pub fn new(registry: &mut VariableRegistry, name: &str) -> Self {
         Self {
-            has_fault_id: format!("OVHD_{}_HAS_FAULT", name),
+            has_fault_id: registry.get(&format!("OVHD_{}_HAS_FAULT", name)),
             has_fault: false,
         }
     }
@@ -427,13 +429,13 @@ impl SimulationElement for FaultIndication {
 }
 
 pub struct MomentaryPushButton {
-    is_pressed_id: String,
+    is_pressed_id: VariableIdentifier,
     is_pressed: bool,
 }
 impl MomentaryPushButton {
-    pub fn new(name: &str) -> Self {
+    pub fn new(registry: &mut VariableRegistry, name: &str) -> Self {
         Self {
-            is_pressed_id: format!("OVHD_{}_IS_PRESSED", name),
+            is_pressed_id: registry.get(&format!("OVHD_{}_IS_PRESSED", name)),
             is_pressed: false,
         }
     }
@@ -456,18 +458,18 @@ mod on_off_fault_push_button_tests {
 
     #[test]
     fn new_on_push_button_is_on() {
-        assert!(OnOffFaultPushButton::new_on("BUTTON").is_on());
+        assert!(OnOffFaultPushButton::new_on(&mut VariableRegistry::new(), "BUTTON").is_on());
     }
 
     #[test]
     fn new_off_push_button_is_off() {
-        assert!(OnOffFaultPushButton::new_off("BUTTON").is_off());
+        assert!(OnOffFaultPushButton::new_off(&mut VariableRegistry::new(), "BUTTON").is_off());
     }
 
     #[test]
     fn writes_its_state() {
-        let mut button = OnOffFaultPushButton::new_on("ELEC_GEN_1");
         let mut test_bed = SimulationTestBed::new();
+        let mut button = OnOffFaultPushButton::new_on(test_bed.registry(), "ELEC_GEN_1");
 
         test_bed.run_without_update(&mut button);
 
@@ -484,18 +486,18 @@ mod on_off_available_push_button_tests {
 
     #[test]
     fn new_on_push_button_is_on() {
-        assert!(OnOffAvailablePushButton::new_on("BUTTON").is_on());
+        assert!(OnOffAvailablePushButton::new_on(&mut VariableRegistry::new(), "BUTTON").is_on());
     }
 
     #[test]
     fn new_off_push_button_is_off() {
-        assert!(OnOffAvailablePushButton::new_off("BUTTON").is_off());
+        assert!(OnOffAvailablePushButton::new_off(&mut VariableRegistry::new(), "BUTTON").is_off());
     }
 
     #[test]
     fn writes_its_state() {
-        let mut button = OnOffAvailablePushButton::new_on("ELEC_EXT_PWR");
         let mut test_bed = SimulationTestBed::new();
+        let mut button = OnOffAvailablePushButton::new_on(test_bed.registry(), "ELEC_EXT_PWR");
 
         test_bed.run_without_update(&mut button);
 
@@ -512,18 +514,23 @@ mod normal_altn_fault_push_button_tests {
 
     #[test]
     fn new_normal_push_button_is_normal() {
-        assert!(NormalAltnFaultPushButton::new_normal("TEST").is_normal());
+        assert!(
+            NormalAltnFaultPushButton::new_normal(&mut VariableRegistry::new(), "TEST").is_normal()
+        );
     }
 
     #[test]
     fn new_altn_push_button_is_altn() {
-        assert!(NormalAltnFaultPushButton::new_altn("TEST").is_altn());
+        assert!(
+            NormalAltnFaultPushButton::new_altn(&mut VariableRegistry::new(), "TEST").is_altn()
+        );
     }
 
     #[test]
     fn writes_its_state() {
-        let mut button = NormalAltnFaultPushButton::new_normal("ELEC_AC_ESS_FEED");
         let mut test_bed = SimulationTestBed::new();
+        let mut button =
+            NormalAltnFaultPushButton::new_normal(test_bed.registry(), "ELEC_AC_ESS_FEED");
 
         test_bed.run_without_update(&mut button);
 
@@ -540,18 +547,18 @@ mod auto_off_fault_push_button_tests {
 
     #[test]
     fn new_auto_push_button_is_auto() {
-        assert!(AutoOffFaultPushButton::new_auto("TEST").is_auto());
+        assert!(AutoOffFaultPushButton::new_auto(&mut VariableRegistry::new(), "TEST").is_auto());
     }
 
     #[test]
     fn new_off_push_button_is_off() {
-        assert!(AutoOffFaultPushButton::new_off("TEST").is_off());
+        assert!(AutoOffFaultPushButton::new_off(&mut VariableRegistry::new(), "TEST").is_off());
     }
 
     #[test]
     fn writes_its_state() {
-        let mut button = AutoOffFaultPushButton::new_auto("ELEC_BUS_TIE");
         let mut test_bed = SimulationTestBed::new();
+        let mut button = AutoOffFaultPushButton::new_auto(test_bed.registry(), "ELEC_BUS_TIE");
 
         test_bed.run_without_update(&mut button);
 
@@ -568,21 +575,21 @@ mod fault_release_push_button_tests {
 
     #[test]
     fn new_in_is_not_released() {
-        let pb = FaultReleasePushButton::new_in("TEST");
+        let pb = FaultReleasePushButton::new_in(&mut VariableRegistry::new(), "TEST");
 
         assert_eq!(pb.is_released(), false);
     }
 
     #[test]
     fn new_released_is_released() {
-        let pb = FaultReleasePushButton::new_released("TEST");
+        let pb = FaultReleasePushButton::new_released(&mut VariableRegistry::new(), "TEST");
 
         assert_eq!(pb.is_released(), true);
     }
 
     #[test]
     fn when_set_as_released_is_released() {
-        let mut pb = FaultReleasePushButton::new_in("TEST");
+        let mut pb = FaultReleasePushButton::new_in(&mut VariableRegistry::new(), "TEST");
         pb.set_released(true);
 
         assert_eq!(pb.is_released(), true);
@@ -590,7 +597,7 @@ mod fault_release_push_button_tests {
 
     #[test]
     fn once_released_stays_released() {
-        let mut pb = FaultReleasePushButton::new_in("TEST");
+        let mut pb = FaultReleasePushButton::new_in(&mut VariableRegistry::new(), "TEST");
         pb.set_released(true);
         pb.set_released(false);
 
@@ -599,8 +606,8 @@ mod fault_release_push_button_tests {
 
     #[test]
     fn writes_its_state() {
-        let mut button = FaultReleasePushButton::new_in("IDG_1");
         let mut test_bed = SimulationTestBed::new();
+        let mut button = FaultReleasePushButton::new_in(test_bed.registry(), "IDG_1");
 
         test_bed.run_without_update(&mut button);
 
@@ -617,14 +624,14 @@ mod fire_push_button_tests {
 
     #[test]
     fn new_fire_push_button_is_not_released() {
-        let pb = FirePushButton::new("TEST");
+        let pb = FirePushButton::new(&mut VariableRegistry::new(), "TEST");
 
         assert_eq!(pb.is_released(), false);
     }
 
     #[test]
     fn when_set_as_released_is_released() {
-        let mut pb = FirePushButton::new("TEST");
+        let mut pb = FirePushButton::new(&mut VariableRegistry::new(), "TEST");
         pb.set(true);
 
         assert_eq!(pb.is_released(), true);
@@ -632,7 +639,7 @@ mod fire_push_button_tests {
 
     #[test]
     fn once_released_stays_released() {
-        let mut pb = FirePushButton::new("TEST");
+        let mut pb = FirePushButton::new(&mut VariableRegistry::new(), "TEST");
         pb.set(true);
         pb.set(false);
 
@@ -641,8 +648,8 @@ mod fire_push_button_tests {
 
     #[test]
     fn writes_its_state() {
-        let mut button = FirePushButton::new("APU");
         let mut test_bed = SimulationTestBed::new();
+        let mut button = FirePushButton::new(test_bed.registry(), "APU");
 
         test_bed.run_without_update(&mut button);
 
@@ -657,13 +664,13 @@ mod fault_indication_tests {
 
     #[test]
     fn new_does_not_have_fault() {
-        assert!(!FaultIndication::new("TEST").has_fault);
+        assert!(!FaultIndication::new(&mut VariableRegistry::new(), "TEST").has_fault);
     }
 
     #[test]
     fn writes_its_state() {
-        let mut button = FaultIndication::new("TEST");
         let mut test_bed = SimulationTestBed::new();
+        let mut button = FaultIndication::new(test_bed.registry(), "TEST");
 
         test_bed.run_without_update(&mut button);
 
@@ -678,13 +685,13 @@ mod momentary_push_button_tests {
 
     #[test]
     fn new_is_not_pressed() {
-        assert!(!MomentaryPushButton::new("TEST").is_pressed());
+        assert!(!MomentaryPushButton::new(&mut VariableRegistry::new(), "TEST").is_pressed());
     }
 
     #[test]
     fn reads_its_state() {
-        let mut button = MomentaryPushButton::new("TEST");
         let mut test_bed = SimulationTestBed::new();
+        let mut button = MomentaryPushButton::new(test_bed.registry(), "TEST");
         test_bed.write_bool("OVHD_TEST_IS_PRESSED", true);
 
         test_bed.run_without_update(&mut button);
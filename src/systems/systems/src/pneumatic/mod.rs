@@ -1,4 +1,18 @@
-//! As we've not yet modelled pneumatic systems and some pneumatic things are needed for the APU, for now this implementation will be very simple.
+//! Bleed air is taken from the engines' compressors or from the APU's load compressor. It is
+//! regulated and cooled before it enters the pneumatic ducts, from where it is supplied to
+//! the consumers (packs, engine starters, etc.).
+//!
+//! Pressures in this module are gauge pressures, as that is what the pressure transducers
+//! in the aircraft measure and what is shown to the crew.
+
+use crate::{
+    shared::{interpolation, EngineCorrectedN2},
+    simulation::{
+        SimulationElement, SimulatorWriter, Snapshot, StateDump, UpdateContext, VariableIdentifier,
+        VariableRegistry,
+    },
+};
+use uom::si::{f64::*, pressure::psi, ratio::percent, thermodynamic_temperature::degree_celsius};
 
 pub trait BleedAirValveState {
     fn bleed_air_valve_is_open(&self) -> bool;
@@ -35,3 +49,600 @@ impl Default for BleedAirValve {
         Self::new()
     }
 }
+
+/// Something which delivers bleed air to a pneumatic duct, such as an engine's
+/// bleed air system or the APU's load compressor.
+pub trait BleedAirSource {
+    fn bleed_air_pressure(&self) -> Pressure;
+    fn bleed_air_temperature(&self) -> ThermodynamicTemperature;
+}
+
+/// Signals to the pressure regulating valve whether it should open or close.
+pub trait PressureRegulatingValveController {
+    fn should_open_pressure_regulating_valve(&self) -> bool;
+}
+
+/// Signals to the crossbleed valve whether it should open or close.
+pub trait CrossBleedValveController {
+    fn should_open_cross_bleed_valve(&self) -> bool;
+}
+
+/// The pressure regulating valve is pneumatically operated and electrically controlled.
+/// It closes when commanded to do so, or when the upstream pressure is too low to keep it
+/// open. When open it regulates the downstream pressure.
+struct PressureRegulatingValve {
+    open: bool,
+}
+impl PressureRegulatingValve {
+    const MINIMUM_UPSTREAM_PRESSURE_TO_OPEN_PSI: f64 = 8.;
+    const REGULATED_PRESSURE_PSI: f64 = 44.;
+
+    fn new() -> Self {
+        Self { open: false }
+    }
+
+    fn update(
+        &mut self,
+        controller: &impl PressureRegulatingValveController,
+        upstream_pressure: Pressure,
+    ) {
+        self.open = controller.should_open_pressure_regulating_valve()
+            && upstream_pressure
+                >= Pressure::new::<psi>(Self::MINIMUM_UPSTREAM_PRESSURE_TO_OPEN_PSI);
+    }
+
+    fn downstream_pressure(&self, upstream_pressure: Pressure) -> Pressure {
+        if self.open {
+            upstream_pressure.min(Pressure::new::<psi>(Self::REGULATED_PRESSURE_PSI))
+        } else {
+            Pressure::new::<psi>(0.)
+        }
+    }
+}
+impl Valve for PressureRegulatingValve {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
+
+/// The precooler is an air to air heat exchanger which cools the bleed air using fan air.
+/// The fan air valve modulates the cooling air flow to keep the outlet temperature at the
+/// regulated temperature.
+struct Precooler {
+    outlet_temperature: ThermodynamicTemperature,
+}
+impl Precooler {
+    const REGULATED_OUTLET_TEMPERATURE_DEGREE_CELSIUS: f64 = 200.;
+
+    fn new() -> Self {
+        Self {
+            outlet_temperature: ThermodynamicTemperature::new::<degree_celsius>(0.),
+        }
+    }
+
+    fn update(&mut self, inlet_temperature: ThermodynamicTemperature) {
+        self.outlet_temperature = inlet_temperature.min(ThermodynamicTemperature::new::<
+            degree_celsius,
+        >(
+            Self::REGULATED_OUTLET_TEMPERATURE_DEGREE_CELSIUS,
+        ));
+    }
+
+    fn outlet_temperature(&self) -> ThermodynamicTemperature {
+        self.outlet_temperature
+    }
+}
+
+/// The bleed air system of a single engine. Air is taken from the intermediate pressure (IP)
+/// stage of the compressor. When the IP stage cannot supply sufficient pressure, such as
+/// at low engine speeds, the high pressure (HP) valve opens and air is taken from the
+/// HP stage instead. The air then passes through the pressure regulating valve (PRV) and
+/// the precooler before it enters the pneumatic duct.
+pub struct EngineBleedAirSystem {
+    hp_valve_open_id: VariableIdentifier,
+    pr_valve_open_id: VariableIdentifier,
+    ip_pressure_id: VariableIdentifier,
+    precooler_outlet_temperature_id: VariableIdentifier,
+
+    ip_pressure: Pressure,
+    hp_pressure: Pressure,
+    upstream_temperature: ThermodynamicTemperature,
+    hp_valve_open: bool,
+    pressure_regulating_valve: PressureRegulatingValve,
+    precooler: Precooler,
+}
+impl EngineBleedAirSystem {
+    const N2_BREAKPOINTS_PERCENT: [f64; 5] = [0., 20., 60., 80., 100.];
+    const IP_PRESSURE_PSI: [f64; 5] = [0., 2., 18., 38., 65.];
+    const HP_PRESSURE_PSI: [f64; 5] = [0., 6., 45., 85., 150.];
+    const IP_TEMPERATURE_RISE_DEGREE_CELSIUS: [f64; 5] = [0., 20., 150., 220., 300.];
+    const HP_TEMPERATURE_RISE_DEGREE_CELSIUS: [f64; 5] = [0., 40., 250., 350., 450.];
+
+    /// The HP valve is closed when the IP stage delivers at least this pressure.
+    const HP_VALVE_CLOSING_IP_PRESSURE_PSI: f64 = 30.;
+
+    pub fn new(registry: &mut VariableRegistry, number: usize) -> Self {
+        Self {
+            hp_valve_open_id: registry.get(&format!("PNEU_ENG_{}_HP_VALVE_OPEN", number)),
+            pr_valve_open_id: registry.get(&format!("PNEU_ENG_{}_PR_VALVE_OPEN", number)),
+            ip_pressure_id: registry.get(&format!("PNEU_ENG_{}_IP_PRESSURE", number)),
+            precooler_outlet_temperature_id: registry
+                .get(&format!("PNEU_ENG_{}_PRECOOLER_OUTLET_TEMPERATURE", number)),
+            ip_pressure: Pressure::new::<psi>(0.),
+            hp_pressure: Pressure::new::<psi>(0.),
+            upstream_temperature: ThermodynamicTemperature::new::<degree_celsius>(0.),
+            hp_valve_open: false,
+            pressure_regulating_valve: PressureRegulatingValve::new(),
+            precooler: Precooler::new(),
+        }
+    }
+
+    pub fn update<T: EngineCorrectedN2, U: PressureRegulatingValveController>(
+        &mut self,
+        context: &UpdateContext,
+        engine: &T,
+        controller: &U,
+    ) {
+        let n2 = engine.corrected_n2().get::<percent>();
+        self.ip_pressure = Pressure::new::<psi>(interpolation(
+            &Self::N2_BREAKPOINTS_PERCENT,
+            &Self::IP_PRESSURE_PSI,
+            n2,
+        ));
+        self.hp_pressure = Pressure::new::<psi>(interpolation(
+            &Self::N2_BREAKPOINTS_PERCENT,
+            &Self::HP_PRESSURE_PSI,
+            n2,
+        ));
+
+        self.hp_valve_open = self.ip_pressure
+            < Pressure::new::<psi>(Self::HP_VALVE_CLOSING_IP_PRESSURE_PSI)
+            && self.hp_pressure
+                >= Pressure::new::<psi>(
+                    PressureRegulatingValve::MINIMUM_UPSTREAM_PRESSURE_TO_OPEN_PSI,
+                );
+
+        let temperature_rise = interpolation(
+            &Self::N2_BREAKPOINTS_PERCENT,
+            if self.hp_valve_open {
+                &Self::HP_TEMPERATURE_RISE_DEGREE_CELSIUS
+            } else {
+                &Self::IP_TEMPERATURE_RISE_DEGREE_CELSIUS
+            },
+            n2,
+        );
+        self.upstream_temperature = ThermodynamicTemperature::new::<degree_celsius>(
+            context.ambient_temperature().get::<degree_celsius>() + temperature_rise,
+        );
+
+        self.pressure_regulating_valve
+            .update(controller, self.upstream_pressure());
+        self.precooler.update(self.upstream_temperature);
+    }
+
+    fn upstream_pressure(&self) -> Pressure {
+        if self.hp_valve_open {
+            self.hp_pressure
+        } else {
+            self.ip_pressure
+        }
+    }
+
+    pub fn ip_pressure(&self) -> Pressure {
+        self.ip_pressure
+    }
+
+    pub fn hp_valve_is_open(&self) -> bool {
+        self.hp_valve_open
+    }
+
+    pub fn pressure_regulating_valve_is_open(&self) -> bool {
+        self.pressure_regulating_valve.is_open()
+    }
+
+    pub fn precooler_outlet_temperature(&self) -> ThermodynamicTemperature {
+        self.precooler.outlet_temperature()
+    }
+}
+impl BleedAirSource for EngineBleedAirSystem {
+    fn bleed_air_pressure(&self) -> Pressure {
+        self.pressure_regulating_valve
+            .downstream_pressure(self.upstream_pressure())
+    }
+
+    fn bleed_air_temperature(&self) -> ThermodynamicTemperature {
+        self.precooler.outlet_temperature()
+    }
+}
+impl SimulationElement for EngineBleedAirSystem {
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_bool(&self.hp_valve_open_id, self.hp_valve_is_open());
+        writer.write_bool(
+            &self.pr_valve_open_id,
+            self.pressure_regulating_valve_is_open(),
+        );
+        writer.write_quantity(&self.ip_pressure_id, self.ip_pressure(), psi);
+        writer.write_quantity(
+            &self.precooler_outlet_temperature_id,
+            self.precooler_outlet_temperature(),
+            degree_celsius,
+        );
+    }
+}
+
+/// The crossbleed valve connects the left and right pneumatic ducts.
+pub struct CrossBleedValve {
+    open_id: VariableIdentifier,
+    open: bool,
+}
+impl CrossBleedValve {
+    pub fn new(registry: &mut VariableRegistry) -> Self {
+        Self {
+            open_id: registry.get("PNEU_XBLEED_VALVE_OPEN"),
+            open: false,
+        }
+    }
+
+    pub fn update(&mut self, controller: &impl CrossBleedValveController) {
+        self.open = controller.should_open_cross_bleed_valve();
+    }
+}
+impl Valve for CrossBleedValve {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
+impl SimulationElement for CrossBleedValve {
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_bool(&self.open_id, self.is_open());
+    }
+}
+/// A pneumatic duct takes the air of the highest pressure source connected to it.
+/// Check valves prevent reverse flow into the sources with a lower pressure.
+/// The pressure and temperature within the duct move towards those of the source over time.
+/// Once no source supplies air anymore, the duct depressurises and cools down to the ambient
+/// temperature.
+pub struct PneumaticDuct {
+    number: usize,
+    pressure_id: VariableIdentifier,
+    temperature_id: VariableIdentifier,
+
+    pressure: Pressure,
+    temperature: ThermodynamicTemperature,
+}
+impl PneumaticDuct {
+    const PRESSURE_TIME_CONSTANT_SECONDS: f64 = 0.5;
+    const TEMPERATURE_TIME_CONSTANT_SECONDS: f64 = 5.;
+    const COOLING_TIME_CONSTANT_SECONDS: f64 = 60.;
+
+    pub fn new(registry: &mut VariableRegistry, number: usize) -> Self {
+        Self {
+            number,
+            pressure_id: registry.get(&format!("PNEU_DUCT_{}_PRESSURE", number)),
+            temperature_id: registry.get(&format!("PNEU_DUCT_{}_TEMPERATURE", number)),
+            pressure: Pressure::new::<psi>(0.),
+            temperature: ThermodynamicTemperature::new::<degree_celsius>(15.),
+        }
+    }
+
+    pub fn update(&mut self, context: &UpdateContext, sources: &[&dyn BleedAirSource]) {
+        let supplying_source = sources
+            .iter()
+            .filter(|source| source.bleed_air_pressure() > Pressure::new::<psi>(0.))
+            .max_by(|a, b| {
+                a.bleed_air_pressure()
+                    .partial_cmp(&b.bleed_air_pressure())
+                    .unwrap()
+            });
+
+        let (target_pressure, target_temperature, temperature_time_constant) =
+            match supplying_source {
+                Some(source) => (
+                    source.bleed_air_pressure(),
+                    source.bleed_air_temperature(),
+                    Self::TEMPERATURE_TIME_CONSTANT_SECONDS,
+                ),
+                None => (
+                    Pressure::new::<psi>(0.),
+                    context.ambient_temperature(),
+                    Self::COOLING_TIME_CONSTANT_SECONDS,
+                ),
+            };
+
+        self.pressure += (target_pressure - self.pressure)
+            * Self::approach_factor(context, Self::PRESSURE_TIME_CONSTANT_SECONDS);
+
+        let current = self.temperature.get::<degree_celsius>();
+        self.temperature = ThermodynamicTemperature::new::<degree_celsius>(
+            current
+                + (target_temperature.get::<degree_celsius>() - current)
+                    * Self::approach_factor(context, temperature_time_constant),
+        );
+    }
+
+    fn approach_factor(context: &UpdateContext, time_constant_seconds: f64) -> f64 {
+        1. - (-context.delta_as_secs_f64() / time_constant_seconds).exp()
+    }
+
+    pub fn pressure(&self) -> Pressure {
+        self.pressure
+    }
+
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        self.temperature
+    }
+}
+impl SimulationElement for PneumaticDuct {
+    fn write(&self, writer: &mut SimulatorWriter) {
+        writer.write_quantity(&self.pressure_id, self.pressure(), psi);
+        writer.write_quantity(
+            &self.temperature_id,
+            self.temperature(),
+            degree_celsius,
+        );
+    }
+
+    fn save(&self, snapshot: &mut Snapshot) {
+        snapshot.write_f64(
+            &format!("PNEU_DUCT_{}_PRESSURE", self.number),
+            self.pressure.get::<psi>(),
+        );
+        snapshot.write_f64(
+            &format!("PNEU_DUCT_{}_TEMPERATURE", self.number),
+            self.temperature.get::<degree_celsius>(),
+        );
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(pressure) = snapshot.read_f64(&format!("PNEU_DUCT_{}_PRESSURE", self.number)) {
+            self.pressure = Pressure::new::<psi>(pressure);
+        }
+        if let Some(temperature) =
+            snapshot.read_f64(&format!("PNEU_DUCT_{}_TEMPERATURE", self.number))
+        {
+            self.temperature = ThermodynamicTemperature::new::<degree_celsius>(temperature);
+        }
+    }
+
+    fn dump(&self, state: &mut StateDump) {
+        let node = state
+            .node("PNEU")
+            .node("DUCT")
+            .node(&self.number.to_string());
+        node.write_f64("PRESSURE", self.pressure.get::<psi>());
+        node.write_f64("TEMPERATURE", self.temperature.get::<degree_celsius>());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::test::SimulationTestBed;
+    use std::time::Duration;
+
+    struct TestEngine {
+        corrected_n2: Ratio,
+    }
+    impl TestEngine {
+        fn new(corrected_n2: Ratio) -> Self {
+            Self { corrected_n2 }
+        }
+    }
+    impl EngineCorrectedN2 for TestEngine {
+        fn corrected_n2(&self) -> Ratio {
+            self.corrected_n2
+        }
+    }
+
+    struct TestValveController {
+        should_open: bool,
+    }
+    impl TestValveController {
+        fn with(should_open: bool) -> Self {
+            Self { should_open }
+        }
+    }
+    impl PressureRegulatingValveController for TestValveController {
+        fn should_open_pressure_regulating_valve(&self) -> bool {
+            self.should_open
+        }
+    }
+    impl CrossBleedValveController for TestValveController {
+        fn should_open_cross_bleed_valve(&self) -> bool {
+            self.should_open
+        }
+    }
+
+    struct TestSource {
+        pressure: Pressure,
+        temperature: ThermodynamicTemperature,
+    }
+    impl TestSource {
+        fn new(pressure_psi: f64, temperature_degree_celsius: f64) -> Self {
+            Self {
+                pressure: Pressure::new::<psi>(pressure_psi),
+                temperature: ThermodynamicTemperature::new::<degree_celsius>(
+                    temperature_degree_celsius,
+                ),
+            }
+        }
+    }
+    impl BleedAirSource for TestSource {
+        fn bleed_air_pressure(&self) -> Pressure {
+            self.pressure
+        }
+
+        fn bleed_air_temperature(&self) -> ThermodynamicTemperature {
+            self.temperature
+        }
+    }
+
+    fn bleed_air_system_at(n2: f64, prv_should_open: bool) -> EngineBleedAirSystem {
+        let mut test_bed = SimulationTestBed::new();
+        let mut system = EngineBleedAirSystem::new(test_bed.registry(), 1);
+        let engine = TestEngine::new(Ratio::new::<percent>(n2));
+        let controller = TestValveController::with(prv_should_open);
+
+        test_bed.run(&mut system, |system, context| {
+            system.update(context, &engine, &controller)
+        });
+
+        system
+    }
+
+    #[test]
+    fn engine_bleed_supplies_no_air_when_engine_is_off() {
+        let system = bleed_air_system_at(0., true);
+
+        assert!(!system.pressure_regulating_valve_is_open());
+        assert_eq!(system.bleed_air_pressure(), Pressure::new::<psi>(0.));
+    }
+
+    #[test]
+    fn hp_valve_is_open_at_idle() {
+        let system = bleed_air_system_at(60., true);
+
+        assert!(system.hp_valve_is_open());
+    }
+
+    #[test]
+    fn hp_valve_is_closed_at_high_power() {
+        let system = bleed_air_system_at(95., true);
+
+        assert!(!system.hp_valve_is_open());
+    }
+
+    #[test]
+    fn pressure_regulating_valve_regulates_downstream_pressure() {
+        let system = bleed_air_system_at(95., true);
+
+        assert!(system.pressure_regulating_valve_is_open());
+        assert_eq!(
+            system.bleed_air_pressure(),
+            Pressure::new::<psi>(PressureRegulatingValve::REGULATED_PRESSURE_PSI)
+        );
+    }
+
+    #[test]
+    fn pressure_regulating_valve_closes_when_commanded() {
+        let system = bleed_air_system_at(95., false);
+
+        assert!(!system.pressure_regulating_valve_is_open());
+        assert_eq!(system.bleed_air_pressure(), Pressure::new::<psi>(0.));
+    }
+
+    #[test]
+    fn precooler_limits_outlet_temperature() {
+        let system = bleed_air_system_at(100., true);
+
+        assert_eq!(
+            system.bleed_air_temperature(),
+            ThermodynamicTemperature::new::<degree_celsius>(
+                Precooler::REGULATED_OUTLET_TEMPERATURE_DEGREE_CELSIUS
+            )
+        );
+    }
+
+    #[test]
+    fn precooler_does_not_heat_air() {
+        let system = bleed_air_system_at(20., true);
+
+        assert!(
+            system.bleed_air_temperature()
+                < ThermodynamicTemperature::new::<degree_celsius>(
+                    Precooler::REGULATED_OUTLET_TEMPERATURE_DEGREE_CELSIUS
+                )
+        );
+    }
+
+    #[test]
+    fn engine_bleed_air_system_writes_its_state() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut system = EngineBleedAirSystem::new(test_bed.registry(), 1);
+        test_bed.run_without_update(&mut system);
+
+        assert!(test_bed.contains_key("PNEU_ENG_1_HP_VALVE_OPEN"));
+        assert!(test_bed.contains_key("PNEU_ENG_1_PR_VALVE_OPEN"));
+        assert!(test_bed.contains_key("PNEU_ENG_1_IP_PRESSURE"));
+        assert!(test_bed.contains_key("PNEU_ENG_1_PRECOOLER_OUTLET_TEMPERATURE"));
+    }
+
+    #[test]
+    fn cross_bleed_valve_follows_controller() {
+        let mut valve = CrossBleedValve::new(&mut VariableRegistry::new());
+        assert!(!valve.is_open());
+
+        valve.update(&TestValveController::with(true));
+        assert!(valve.is_open());
+
+        valve.update(&TestValveController::with(false));
+        assert!(!valve.is_open());
+    }
+
+    fn run_duct(duct: &mut PneumaticDuct, sources: &[&dyn BleedAirSource], seconds: u64) {
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(1));
+        for _ in 0..seconds {
+            test_bed.run(duct, |duct, context| duct.update(context, sources));
+        }
+    }
+
+    #[test]
+    fn duct_pressurises_to_highest_pressure_source() {
+        let mut duct = PneumaticDuct::new(&mut VariableRegistry::new(), 1);
+        let low = TestSource::new(30., 150.);
+        let high = TestSource::new(44., 200.);
+
+        run_duct(&mut duct, &[&low, &high], 120);
+
+        assert!((duct.pressure().get::<psi>() - 44.).abs() < 0.01);
+        assert!((duct.temperature().get::<degree_celsius>() - 200.).abs() < 0.01);
+    }
+
+    #[test]
+    fn duct_pressure_builds_up_over_time() {
+        let mut duct = PneumaticDuct::new(&mut VariableRegistry::new(), 1);
+        let source = TestSource::new(44., 200.);
+
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(100));
+        test_bed.run(&mut duct, |duct, context| duct.update(context, &[&source]));
+
+        assert!(duct.pressure() > Pressure::new::<psi>(0.));
+        assert!(duct.pressure() < Pressure::new::<psi>(44.));
+    }
+
+    #[test]
+    fn duct_depressurises_and_cools_without_source() {
+        let mut duct = PneumaticDuct::new(&mut VariableRegistry::new(), 1);
+        let source = TestSource::new(44., 200.);
+        run_duct(&mut duct, &[&source], 120);
+
+        run_duct(&mut duct, &[], 600);
+
+        assert!(duct.pressure().get::<psi>() < 0.01);
+        assert!(duct.temperature().get::<degree_celsius>() < 20.);
+    }
+
+    #[test]
+    fn duct_restores_pressure_and_temperature_from_snapshot() {
+        let mut duct = PneumaticDuct::new(&mut VariableRegistry::new(), 1);
+        let source = TestSource::new(44., 200.);
+        run_duct(&mut duct, &[&source], 120);
+        let snapshot = Snapshot::take(&mut duct);
+
+        let mut restored = PneumaticDuct::new(&mut VariableRegistry::new(), 1);
+        snapshot.restore_into(&mut restored);
+
+        assert_eq!(restored.pressure(), duct.pressure());
+        assert_eq!(restored.temperature(), duct.temperature());
+    }
+
+    #[test]
+    fn duct_writes_its_state() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut duct = PneumaticDuct::new(test_bed.registry(), 2);
+        test_bed.run_without_update(&mut duct);
+
+        assert!(test_bed.contains_key("PNEU_DUCT_2_PRESSURE"));
+        assert!(test_bed.contains_key("PNEU_DUCT_2_TEMPERATURE"));
+    }
+}
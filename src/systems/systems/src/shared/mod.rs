@@ -1,5 +1,5 @@
 use crate::{
-    electrical::{Potential, PotentialSource},
+    electrical::{ControlledGenerator, Potential, PotentialSource},
     simulation::UpdateContext,
 };
 use num_derive::FromPrimitive;
@@ -15,7 +15,7 @@ pub trait ApuStartContactorsController {
 }
 
 pub trait AuxiliaryPowerUnitElectrical:
-    PotentialSource + ApuStartContactorsController + ApuAvailable
+    PotentialSource + ControlledGenerator + ApuStartContactorsController + ApuAvailable
 {
     fn start_motor_powered_by(&mut self, source: Potential);
     fn output_within_normal_parameters(&self) -> bool;
@@ -151,7 +151,7 @@ pub(crate) fn calculate_towards_target_temperature(
 }
 
 // Interpolate values_map_y at point value_at_point in breakpoints break_points_x
-pub(crate) fn interpolation(xs: &[f64], ys: &[f64], intermediate_x: f64) -> f64 {
+pub fn interpolation(xs: &[f64], ys: &[f64], intermediate_x: f64) -> f64 {
     debug_assert!(xs.len() == ys.len());
     debug_assert!(xs.len() >= 2);
     debug_assert!(ys.len() >= 2);
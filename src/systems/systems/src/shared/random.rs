@@ -1,19 +1,45 @@
 #[cfg(not(any(target_arch = "wasm32", doc)))]
 use rand::Rng;
 
-#[cfg(not(any(target_arch = "wasm32", doc)))]
-pub fn random_number() -> u8 {
-    let mut rng = rand::thread_rng();
+/// A source of random numbers for the [`Simulation`]. Every tick the simulation
+/// takes a seed from the source, from which the random numbers provided by the
+/// [`UpdateContext`] are generated.
+///
+/// [`Simulation`]: ../simulation/struct.Simulation.html
+/// [`UpdateContext`]: ../simulation/struct.UpdateContext.html
+pub trait RandomSource {
+    fn next_u64(&mut self) -> u64;
+}
 
-    rng.gen()
+/// Provides random numbers from the system's random number generator.
+/// Use this when running in the simulator.
+pub struct SystemRandomSource {}
+impl SystemRandomSource {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl Default for SystemRandomSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(any(target_arch = "wasm32", doc)))]
+impl RandomSource for SystemRandomSource {
+    fn next_u64(&mut self) -> u64 {
+        rand::thread_rng().gen()
+    }
 }
 
 #[cfg(any(target_arch = "wasm32", doc))]
-pub fn random_number() -> u8 {
-    let buf = &mut [0, 0, 0, 0];
-    unsafe { wasi_random_get(buf.as_mut_ptr(), buf.len()) };
+impl RandomSource for SystemRandomSource {
+    fn next_u64(&mut self) -> u64 {
+        let buf = &mut [0; 8];
+        unsafe { wasi_random_get(buf.as_mut_ptr(), buf.len()) };
 
-    buf[0]
+        u64::from_le_bytes(*buf)
+    }
 }
 
 #[link(wasm_import_module = "wasi_snapshot_preview1")]
@@ -22,3 +48,94 @@ extern "C" {
     #[cfg(any(target_arch = "wasm32", doc))]
     fn wasi_random_get(buf: *mut u8, buf_len: usize) -> u16;
 }
+
+/// Provides pseudo random numbers which are the same for every run with the same seed.
+/// Use this when the simulation has to be deterministic, such as in tests and replays.
+#[derive(Clone, Copy, Debug)]
+pub struct SeededRandomSource {
+    state: u64,
+}
+impl SeededRandomSource {
+    const SEED_MIXING_CONSTANT: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    pub fn new(seed: u64) -> Self {
+        // The xorshift algorithm never leaves the zero state, thus the seed is mixed with a constant.
+        let state = seed ^ SeededRandomSource::SEED_MIXING_CONSTANT;
+
+        Self {
+            state: if state == 0 {
+                SeededRandomSource::SEED_MIXING_CONSTANT
+            } else {
+                state
+            },
+        }
+    }
+
+    pub fn next_u8(&mut self) -> u8 {
+        (self.next_u64() >> 56) as u8
+    }
+}
+impl RandomSource for SeededRandomSource {
+    /// An implementation of xorshift64*.
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+impl Default for SeededRandomSource {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_results_in_same_numbers() {
+        let mut first = SeededRandomSource::new(42);
+        let mut second = SeededRandomSource::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+    }
+
+    #[test]
+    fn seed_results_in_the_same_numbers_across_versions() {
+        let mut source = SeededRandomSource::new(42);
+
+        // Recordings and tests rely on these numbers, thus they must not change.
+        assert_eq!(source.next_u64(), 590690077802032154);
+        assert_eq!(source.next_u64(), 539994873130563277);
+        assert_eq!(source.next_u8(), 12);
+    }
+
+    #[test]
+    fn different_seed_results_in_different_numbers() {
+        let mut first = SeededRandomSource::new(1);
+        let mut second = SeededRandomSource::new(2);
+
+        assert_ne!(first.next_u64(), second.next_u64());
+    }
+
+    #[test]
+    fn seed_of_mixing_constant_does_not_get_stuck_at_zero() {
+        let mut source = SeededRandomSource::new(SeededRandomSource::SEED_MIXING_CONSTANT);
+
+        assert_ne!(source.next_u64(), 0);
+    }
+
+    #[test]
+    fn u8_numbers_are_spread_over_the_range() {
+        let mut source = SeededRandomSource::new(7);
+        let numbers: Vec<u8> = (0..1000).map(|_| source.next_u8()).collect();
+
+        assert!(numbers.iter().any(|n| *n < 64));
+        assert!(numbers.iter().any(|n| *n > 192));
+    }
+}
@@ -0,0 +1,577 @@
+use std::{
+    any::type_name,
+    collections::BTreeSet,
+    fmt,
+    io::{self, Write},
+    time::Duration,
+};
+
+use super::{
+    json_string, Aircraft, Scheduler, Simulation, SimulationElement, SimulationElementVisitor,
+    SimulatorReader, SimulatorReaderWriter, SimulatorWriter, UpdateContext, VariableIdentifier,
+    VariableRegistry,
+};
+use crate::shared::SeededRandomSource;
+
+/// The type as which a simulator variable is read or written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VariableType {
+    Bool,
+    F64,
+    /// An `f64` holding a quantity in the unit with the given abbreviation.
+    Quantity(&'static str),
+}
+impl VariableType {
+    /// The type of a quantity read or written in the given unit. As units without
+    /// an abbreviation, such as ratio, have no dimension, such values are a plain `f64`.
+    pub(super) fn of_unit<T: uom::si::Unit>() -> Self {
+        match T::abbreviation() {
+            "" => VariableType::F64,
+            abbreviation => VariableType::Quantity(abbreviation),
+        }
+    }
+}
+impl fmt::Display for VariableType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableType::Bool => write!(f, "bool"),
+            VariableType::F64 => write!(f, "f64"),
+            VariableType::Quantity(unit) => write!(f, "f64 ({})", unit),
+        }
+    }
+}
+
+/// Whether the aircraft reads a simulator variable or writes it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VariableDirection {
+    Read,
+    Write,
+}
+impl fmt::Display for VariableDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableDirection::Read => write!(f, "read"),
+            VariableDirection::Write => write!(f, "write"),
+        }
+    }
+}
+
+/// A simulator variable which is read or written by an element of the aircraft.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CatalogueEntry {
+    name: String,
+    direction: VariableDirection,
+    element: String,
+    variable_type: VariableType,
+}
+impl CatalogueEntry {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn direction(&self) -> VariableDirection {
+        self.direction
+    }
+
+    /// The type name of the element which reads or writes the variable.
+    pub fn element(&self) -> &str {
+        &self.element
+    }
+
+    pub fn variable_type(&self) -> VariableType {
+        self.variable_type
+    }
+}
+
+/// Lists every simulator variable an [`Aircraft`] reads and writes, together with
+/// the element which does so and the type as which it is used. The catalogue is
+/// taken by letting each element read from and write to a simulator which only
+/// takes note of the accessed variables.
+///
+/// Some variables are only accessed while an element is in a certain state. Therefore
+/// the elements are catalogued both in the state of a newly created aircraft, and after
+/// simulating the aircraft for a while with every variable read as one. Registered
+/// variables which are accessed in neither state are listed as [`unaccessed`].
+///
+/// # Examples
+/// ```rust
+/// # use systems::simulation::{Aircraft, Catalogue, SimulationElement, SimulatorReader,
+/// #     VariableDirection, VariableIdentifier, VariableRegistry, VariableType};
+/// struct MyAircraft {
+///     is_on_id: VariableIdentifier,
+///     is_on: bool,
+/// }
+/// impl Aircraft for MyAircraft {}
+/// impl SimulationElement for MyAircraft {
+///     fn read(&mut self, reader: &mut SimulatorReader) {
+///         self.is_on = reader.read_bool(&self.is_on_id);
+///     }
+/// }
+///
+/// let catalogue = Catalogue::of(|registry: &mut VariableRegistry| MyAircraft {
+///     is_on_id: registry.get("MY_AIRCRAFT_IS_ON"),
+///     is_on: false,
+/// });
+/// let entry = catalogue.entry("MY_AIRCRAFT_IS_ON", VariableDirection::Read).unwrap();
+/// assert_eq!(entry.variable_type(), VariableType::Bool);
+/// ```
+/// [`Aircraft`]: trait.Aircraft.html
+/// [`unaccessed`]: #method.unaccessed
+#[derive(Debug, Default, PartialEq)]
+pub struct Catalogue {
+    entries: BTreeSet<CatalogueEntry>,
+    unaccessed: BTreeSet<String>,
+}
+impl Catalogue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The time during which the aircraft is simulated before cataloguing it once more.
+    const SIMULATED_DURATION: Duration = Duration::from_secs(10);
+    const SIMULATED_DELTA: Duration = Duration::from_millis(100);
+
+    /// Takes the catalogue of an aircraft created by the given function. Besides the
+    /// variables of the aircraft's elements, this includes the variables read into
+    /// the [`UpdateContext`]. As cataloguing changes the state of the aircraft,
+    /// the aircraft is created for the catalogue only.
+    ///
+    /// [`UpdateContext`]: struct.UpdateContext.html
+    pub fn of<T: Aircraft, U: FnOnce(&mut VariableRegistry) -> T>(create_aircraft: U) -> Self {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = create_aircraft(&mut registry);
+        let mut catalogue = Self::new();
+
+        let mut reader_writer = CataloguingReaderWriter::new(0.);
+        UpdateContext::from_reader(
+            &mut SimulatorReader::new(&mut reader_writer),
+            Duration::from_secs(0),
+            0,
+        );
+        catalogue.add_accesses(type_name::<UpdateContext>(), reader_writer, &registry);
+
+        aircraft.accept(&mut CatalogueVisitor::new(&mut catalogue, &registry, 0.));
+
+        let mut reader_writer = CataloguingReaderWriter::new(1.);
+        let mut random_source = SeededRandomSource::default();
+        let mut scheduler = Scheduler::new();
+        let mut elapsed = Duration::from_secs(0);
+        while elapsed < Self::SIMULATED_DURATION {
+            Simulation::new(
+                &mut aircraft,
+                &mut reader_writer,
+                &mut random_source,
+                &mut scheduler,
+            )
+            .tick(Self::SIMULATED_DELTA);
+            elapsed += Self::SIMULATED_DELTA;
+        }
+
+        aircraft.accept(&mut CatalogueVisitor::new(&mut catalogue, &registry, 1.));
+
+        catalogue.unaccessed = registry
+            .iter()
+            .map(|(_, name)| name)
+            .filter(|name| !catalogue.entries.iter().any(|entry| entry.name == *name))
+            .map(|name| name.to_owned())
+            .collect();
+
+        catalogue
+    }
+
+    fn add_accesses(
+        &mut self,
+        element: &str,
+        reader_writer: CataloguingReaderWriter,
+        registry: &VariableRegistry,
+    ) {
+        for (identifier, direction, variable_type) in reader_writer.accesses {
+            self.entries.insert(CatalogueEntry {
+                name: registry.name(&identifier).to_owned(),
+                direction,
+                element: element.to_owned(),
+                variable_type,
+            });
+        }
+    }
+
+    /// Iterates over the entries ordered by variable name, direction and element.
+    pub fn iter(&self) -> impl Iterator<Item = &CatalogueEntry> {
+        self.entries.iter()
+    }
+
+    /// Returns the first entry for the given variable name and direction.
+    pub fn entry(&self, name: &str, direction: VariableDirection) -> Option<&CatalogueEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name && entry.direction == direction)
+    }
+
+    pub fn reads(&self, name: &str) -> bool {
+        self.entry(name, VariableDirection::Read).is_some()
+    }
+
+    pub fn writes(&self, name: &str) -> bool {
+        self.entry(name, VariableDirection::Write).is_some()
+    }
+
+    /// Iterates over the names of registered variables which weren't read or
+    /// written in any of the catalogued states.
+    pub fn unaccessed(&self) -> impl Iterator<Item = &str> {
+        self.unaccessed.iter().map(|name| name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Writes the catalogue as a Markdown table, followed by a list of the unaccessed variables.
+    pub fn write_markdown<T: Write>(&self, writer: &mut T) -> io::Result<()> {
+        writeln!(writer, "| Name | Direction | Type | Element |")?;
+        writeln!(writer, "| --- | --- | --- | --- |")?;
+        for entry in &self.entries {
+            writeln!(
+                writer,
+                "| {} | {} | {} | `{}` |",
+                entry.name, entry.direction, entry.variable_type, entry.element
+            )?;
+        }
+
+        if !self.unaccessed.is_empty() {
+            writeln!(writer)?;
+            writeln!(
+                writer,
+                "Registered, but neither read nor written in the catalogued states:"
+            )?;
+            writeln!(writer)?;
+            for name in &self.unaccessed {
+                writeln!(writer, "- {}", name)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the catalogue as a JSON array containing an object per entry.
+    pub fn write_json<T: Write>(&self, writer: &mut T) -> io::Result<()> {
+        writeln!(writer, "[")?;
+        for (index, entry) in self.entries.iter().enumerate() {
+            writeln!(
+                writer,
+                "  {{\"name\": {}, \"direction\": \"{}\", \"type\": \"{}\", \"element\": {}}}{}",
+                json_string(&entry.name),
+                entry.direction,
+                entry.variable_type,
+                json_string(&entry.element),
+                if index + 1 < self.entries.len() {
+                    ","
+                } else {
+                    ""
+                }
+            )?;
+        }
+        writeln!(writer, "]")
+    }
+}
+
+/// Visits aircraft components in order to take note of the
+/// variables they read and write.
+struct CatalogueVisitor<'a> {
+    catalogue: &'a mut Catalogue,
+    registry: &'a VariableRegistry,
+    read_value: f64,
+}
+impl<'a> CatalogueVisitor<'a> {
+    fn new(catalogue: &'a mut Catalogue, registry: &'a VariableRegistry, read_value: f64) -> Self {
+        Self {
+            catalogue,
+            registry,
+            read_value,
+        }
+    }
+}
+impl SimulationElementVisitor for CatalogueVisitor<'_> {
+    fn visit<T: SimulationElement>(&mut self, visited: &mut T) {
+        let mut reader_writer = CataloguingReaderWriter::new(self.read_value);
+        visited.read(&mut SimulatorReader::new(&mut reader_writer));
+        visited.write(&mut SimulatorWriter::new(&mut reader_writer));
+
+        self.catalogue
+            .add_accesses(type_name::<T>(), reader_writer, self.registry);
+    }
+}
+
+/// A simulator which reads the same value for every variable and takes note of every access.
+struct CataloguingReaderWriter {
+    read_value: f64,
+    accesses: Vec<(VariableIdentifier, VariableDirection, VariableType)>,
+}
+impl CataloguingReaderWriter {
+    fn new(read_value: f64) -> Self {
+        Self {
+            read_value,
+            accesses: Vec::new(),
+        }
+    }
+}
+impl SimulatorReaderWriter for CataloguingReaderWriter {
+    fn read(&mut self, identifier: &VariableIdentifier) -> f64 {
+        self.read_typed(identifier, VariableType::F64)
+    }
+
+    fn write(&mut self, identifier: &VariableIdentifier, value: f64) {
+        self.write_typed(identifier, VariableType::F64, value);
+    }
+
+    fn read_typed(&mut self, identifier: &VariableIdentifier, variable_type: VariableType) -> f64 {
+        self.accesses
+            .push((*identifier, VariableDirection::Read, variable_type));
+        self.read_value
+    }
+
+    fn write_typed(
+        &mut self,
+        identifier: &VariableIdentifier,
+        variable_type: VariableType,
+        _value: f64,
+    ) {
+        self.accesses
+            .push((*identifier, VariableDirection::Write, variable_type));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uom::si::{f64::*, pressure::psi};
+
+    struct TestElement {
+        position_id: VariableIdentifier,
+        is_on_id: VariableIdentifier,
+        position: f64,
+        is_on: bool,
+    }
+    impl TestElement {
+        fn new(registry: &mut VariableRegistry) -> Self {
+            Self {
+                position_id: registry.get("TEST_POSITION"),
+                is_on_id: registry.get("TEST_IS_ON"),
+                position: 0.,
+                is_on: false,
+            }
+        }
+    }
+    impl SimulationElement for TestElement {
+        fn read(&mut self, reader: &mut SimulatorReader) {
+            self.position = reader.read_f64(&self.position_id);
+        }
+
+        fn write(&self, writer: &mut SimulatorWriter) {
+            writer.write_bool(&self.is_on_id, self.is_on);
+        }
+    }
+
+    /// Writes its pressure only once it has been powered by the simulator for a while.
+    struct ConditionalElement {
+        is_powered_id: VariableIdentifier,
+        pressure_id: VariableIdentifier,
+        is_powered: bool,
+        powered_duration: Duration,
+        pressure: Pressure,
+    }
+    impl ConditionalElement {
+        fn new(registry: &mut VariableRegistry) -> Self {
+            Self {
+                is_powered_id: registry.get("TEST_IS_POWERED"),
+                pressure_id: registry.get("TEST_PRESSURE"),
+                is_powered: false,
+                powered_duration: Duration::from_secs(0),
+                pressure: Pressure::new::<psi>(3000.),
+            }
+        }
+
+        fn update(&mut self, context: &UpdateContext) {
+            if self.is_powered {
+                self.powered_duration += context.delta();
+            }
+        }
+    }
+    impl SimulationElement for ConditionalElement {
+        fn read(&mut self, reader: &mut SimulatorReader) {
+            self.is_powered = reader.read_bool(&self.is_powered_id);
+        }
+
+        fn write(&self, writer: &mut SimulatorWriter) {
+            if self.powered_duration > Duration::from_secs(1) {
+                writer.write_quantity(&self.pressure_id, self.pressure, psi);
+            }
+        }
+    }
+
+    struct TestAircraft {
+        elements: [TestElement; 2],
+        conditional: ConditionalElement,
+    }
+    impl TestAircraft {
+        fn new(registry: &mut VariableRegistry) -> Self {
+            // Registered for the catalogue to find, but never accessed.
+            registry.get("TEST_UNUSED");
+
+            Self {
+                elements: [TestElement::new(registry), TestElement::new(registry)],
+                conditional: ConditionalElement::new(registry),
+            }
+        }
+    }
+    impl Aircraft for TestAircraft {
+        fn update_after_power_distribution(&mut self, context: &UpdateContext) {
+            self.conditional.update(context);
+        }
+    }
+    impl SimulationElement for TestAircraft {
+        fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+            self.elements.iter_mut().for_each(|el| el.accept(visitor));
+            self.conditional.accept(visitor);
+
+            visitor.visit(self);
+        }
+    }
+
+    fn catalogue() -> Catalogue {
+        Catalogue::of(TestAircraft::new)
+    }
+
+    #[test]
+    fn contains_variables_read_by_elements() {
+        let catalogue = catalogue();
+        let entry = catalogue
+            .entry("TEST_POSITION", VariableDirection::Read)
+            .unwrap();
+
+        assert_eq!(entry.variable_type(), VariableType::F64);
+        assert_eq!(entry.element(), type_name::<TestElement>());
+        assert!(!catalogue.writes("TEST_POSITION"));
+    }
+
+    #[test]
+    fn contains_variables_written_by_elements() {
+        let catalogue = catalogue();
+        let entry = catalogue
+            .entry("TEST_IS_ON", VariableDirection::Write)
+            .unwrap();
+
+        assert_eq!(entry.variable_type(), VariableType::Bool);
+        assert!(!catalogue.reads("TEST_IS_ON"));
+    }
+
+    #[test]
+    fn lists_a_variable_accessed_by_multiple_instances_of_an_element_once() {
+        let catalogue = catalogue();
+
+        assert_eq!(
+            catalogue
+                .iter()
+                .filter(|entry| entry.name() == "TEST_POSITION")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn contains_variables_only_written_in_some_states() {
+        let catalogue = catalogue();
+
+        assert!(catalogue.writes("TEST_PRESSURE"));
+    }
+
+    #[test]
+    fn contains_the_unit_of_quantities() {
+        let catalogue = catalogue();
+        let entry = catalogue
+            .entry("TEST_PRESSURE", VariableDirection::Write)
+            .unwrap();
+
+        assert_eq!(entry.variable_type(), VariableType::Quantity("psi"));
+        assert_eq!(entry.variable_type().to_string(), "f64 (psi)");
+    }
+
+    #[test]
+    fn lists_registered_variables_which_are_never_accessed() {
+        let catalogue = catalogue();
+
+        assert_eq!(
+            catalogue.unaccessed().collect::<Vec<_>>(),
+            vec!["TEST_UNUSED"]
+        );
+        assert!(catalogue
+            .entry("TEST_UNUSED", VariableDirection::Read)
+            .is_none());
+    }
+
+    #[test]
+    fn contains_variables_read_into_the_update_context() {
+        let catalogue = catalogue();
+
+        for name in UpdateContext::VARIABLE_NAMES.iter() {
+            let entry = catalogue.entry(name, VariableDirection::Read).unwrap();
+            assert_eq!(entry.element(), type_name::<UpdateContext>());
+        }
+        assert_eq!(
+            catalogue
+                .entry(UpdateContext::IS_ON_GROUND_KEY, VariableDirection::Read)
+                .unwrap()
+                .variable_type(),
+            VariableType::Bool
+        );
+    }
+
+    #[test]
+    fn writes_markdown_table() {
+        let mut markdown = Vec::new();
+        catalogue().write_markdown(&mut markdown).unwrap();
+        let markdown = String::from_utf8(markdown).unwrap();
+
+        assert!(markdown.starts_with("| Name | Direction | Type | Element |\n"));
+        assert!(markdown.contains(&format!(
+            "| TEST_IS_ON | write | bool | `{}` |\n",
+            type_name::<TestElement>()
+        )));
+    }
+
+    #[test]
+    fn writes_unaccessed_variables_after_markdown_table() {
+        let mut markdown = Vec::new();
+        catalogue().write_markdown(&mut markdown).unwrap();
+        let markdown = String::from_utf8(markdown).unwrap();
+
+        assert!(markdown.ends_with(
+            "\nRegistered, but neither read nor written in the catalogued states:\n\n- TEST_UNUSED\n"
+        ));
+    }
+
+    #[test]
+    fn writes_json_array() {
+        let mut json = Vec::new();
+        catalogue().write_json(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+
+        assert!(json.starts_with("[\n"));
+        assert!(json.ends_with("}\n]\n"));
+        assert!(json.contains(&format!(
+            "{{\"name\": \"TEST_POSITION\", \"direction\": \"read\", \"type\": \"f64\", \"element\": \"{}\"}},",
+            type_name::<TestElement>()
+        )));
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(
+            json_string("A \"B\" \\ C\n"),
+            "\"A \\\"B\\\" \\\\ C\\u000a\""
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{self, Write},
+    time::Duration,
+};
+
+use super::{
+    csv_field, SimulationElement, SimulationElementVisitor, SimulatorWriter, VariableIdentifier,
+    VariableRegistry,
+};
+
+/// The kind of state transition described by an [`Event`].
+///
+/// [`Event`]: struct.Event.html
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventKind {
+    Closed,
+    Opened,
+    Activated,
+    Deactivated,
+    Disconnected,
+    StateChanged { from: String, to: String },
+}
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventKind::Closed => write!(f, "closed"),
+            EventKind::Opened => write!(f, "opened"),
+            EventKind::Activated => write!(f, "activated"),
+            EventKind::Deactivated => write!(f, "deactivated"),
+            EventKind::Disconnected => write!(f, "disconnected"),
+            EventKind::StateChanged { from, to } => write!(f, "changed from {} to {}", from, to),
+        }
+    }
+}
+
+/// A state transition of an element, which happened in the tick with the given number.
+/// The source identifies the element using the same path as its [`StateDump`] node,
+/// e.g. `ELEC/CONTACTOR/11XU1`.
+///
+/// [`StateDump`]: struct.StateDump.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    tick: u64,
+    time: Duration,
+    source: String,
+    kind: EventKind,
+    cause: Option<String>,
+}
+impl Event {
+    /// The number of the tick, where the first tick collected by the bus is tick 1.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// The simulated time at the end of the tick in which the event happened.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn kind(&self) -> &EventKind {
+        &self.kind
+    }
+
+    /// Why the transition happened, when known by the element.
+    pub fn cause(&self) -> Option<&str> {
+        self.cause.as_deref()
+    }
+}
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:.3}s] {} {}",
+            self.time.as_secs_f64(),
+            self.source,
+            self.kind
+        )?;
+        if let Some(cause) = &self.cause {
+            write!(f, " ({})", cause)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects the events emitted by elements within a single tick.
+///
+/// # Examples
+/// ```rust
+/// # use systems::simulation::{ChangeDetector, EventEmitter, EventKind, SimulationElement};
+/// struct MyValve {
+///     open: bool,
+///     open_change: ChangeDetector<bool>,
+/// }
+/// impl SimulationElement for MyValve {
+///     fn emit_events(&mut self, events: &mut EventEmitter) {
+///         if self.open_change.detect(self.open).is_some() {
+///             let kind = if self.open { EventKind::Opened } else { EventKind::Closed };
+///             events.emit("MY_VALVE", kind);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct EventEmitter {
+    emitted: Vec<(String, EventKind, Option<String>)>,
+}
+impl EventEmitter {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn emit(&mut self, source: &str, kind: EventKind) {
+        self.emitted.push((source.to_owned(), kind, None));
+    }
+
+    pub fn emit_with_cause(&mut self, source: &str, kind: EventKind, cause: &str) {
+        self.emitted
+            .push((source.to_owned(), kind, Some(cause.to_owned())));
+    }
+}
+
+/// Detects changes of a value an element emits events for, by remembering the
+/// value as it was when the element last emitted its events.
+#[derive(Clone, Copy, Debug)]
+pub struct ChangeDetector<T: Copy + PartialEq> {
+    value: T,
+}
+impl<T: Copy + PartialEq> ChangeDetector<T> {
+    pub fn new(initial_value: T) -> Self {
+        Self {
+            value: initial_value,
+        }
+    }
+
+    /// Returns the previous value when the given value differs from it.
+    pub fn detect(&mut self, value: T) -> Option<T> {
+        if value != self.value {
+            let previous = self.value;
+            self.value = value;
+            Some(previous)
+        } else {
+            None
+        }
+    }
+}
+
+/// Receives the events emitted by the aircraft's elements after every tick of the
+/// [`Simulation`] it is given to. Elements emit events by overriding
+/// [`SimulationElement::emit_events`]. A bus created as a ring buffer only keeps the
+/// most recent events, such that it can run throughout a flight.
+///
+/// # Examples
+/// ```rust
+/// # use std::time::Duration;
+/// # use systems::simulation::{EventBus, EventEmitter, EventKind, SimulationElement};
+/// # struct MyPump {
+/// #     active: bool,
+/// # }
+/// # impl SimulationElement for MyPump {
+/// #     fn emit_events(&mut self, events: &mut EventEmitter) {
+/// #         if self.active {
+/// #             events.emit_with_cause("MY_PUMP", EventKind::Activated, "low pressure");
+/// #         }
+/// #     }
+/// # }
+/// let mut bus = EventBus::ring_buffer(10);
+///
+/// bus.collect(&mut MyPump { active: false }, Duration::from_millis(100));
+/// bus.collect(&mut MyPump { active: true }, Duration::from_millis(100));
+///
+/// let event = bus.find("MY_PUMP", &EventKind::Activated).unwrap();
+/// assert_eq!(event.time(), Duration::from_millis(200));
+/// assert_eq!(event.to_string(), "[0.200s] MY_PUMP activated (low pressure)");
+/// ```
+/// [`Simulation`]: struct.Simulation.html
+/// [`SimulationElement::emit_events`]: trait.SimulationElement.html#method.emit_events
+#[derive(Debug, Default)]
+pub struct EventBus {
+    capacity: Option<usize>,
+    ticks: u64,
+    time: Duration,
+    events: VecDeque<Event>,
+    dropped_count: u64,
+    count_id: Option<VariableIdentifier>,
+    last_event_time_id: Option<VariableIdentifier>,
+}
+impl EventBus {
+    pub const COUNT_KEY: &'static str = "SYSTEMS_EVENT_COUNT";
+    pub const LAST_EVENT_TIME_KEY: &'static str = "SYSTEMS_LAST_EVENT_TIME";
+
+    /// Creates a bus which keeps all events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a bus which keeps the given number of most recent events.
+    pub fn ring_buffer(capacity: usize) -> Self {
+        assert!(capacity > 0);
+        Self {
+            capacity: Some(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Writes the number of events received so far and the simulated time in seconds of
+    /// the most recent event to the simulator, at the end of every tick. Thus instruments
+    /// can tell when something happened, while the events themselves are written to a
+    /// file using [`write_csv`].
+    ///
+    /// [`write_csv`]: #method.write_csv
+    pub fn with_simulator_variables(mut self, registry: &mut VariableRegistry) -> Self {
+        self.count_id = Some(registry.get(Self::COUNT_KEY));
+        self.last_event_time_id = Some(registry.get(Self::LAST_EVENT_TIME_KEY));
+        self
+    }
+
+    /// Receives the events emitted by the given element and all of its children
+    /// during a tick of the given delta.
+    pub fn collect(&mut self, element: &mut impl SimulationElement, delta: Duration) {
+        self.ticks += 1;
+        self.time += delta;
+
+        let mut emitter = EventEmitter::new();
+        let mut visitor = EventVisitor::new(&mut emitter);
+        element.accept(&mut visitor);
+
+        for (source, kind, cause) in emitter.emitted {
+            if let Some(capacity) = self.capacity {
+                if self.events.len() == capacity {
+                    self.events.pop_front();
+                    self.dropped_count += 1;
+                }
+            }
+
+            self.events.push_back(Event {
+                tick: self.ticks,
+                time: self.time,
+                source,
+                kind,
+                cause,
+            });
+        }
+    }
+
+    /// Iterates over the kept events in the order they happened.
+    pub fn iter(&self) -> impl Iterator<Item = &Event> {
+        self.events.iter()
+    }
+
+    /// Iterates over the kept events of the given source in the order they happened.
+    pub fn iter_from<'a>(&'a self, source: &'a str) -> impl Iterator<Item = &'a Event> {
+        self.events
+            .iter()
+            .filter(move |event| event.source == source)
+    }
+
+    /// Returns the first kept event of the given source and kind.
+    pub fn find(&self, source: &str, kind: &EventKind) -> Option<&Event> {
+        self.events
+            .iter()
+            .find(|event| event.source == source && &event.kind == kind)
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// The number of events removed from a ring buffer to make room for newer events.
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_count
+    }
+
+    /// The number of events received so far, including those which were dropped.
+    pub fn count(&self) -> u64 {
+        self.dropped_count + self.events.len() as u64
+    }
+
+    pub(super) fn write(&self, writer: &mut SimulatorWriter) {
+        if let Some(count_id) = &self.count_id {
+            writer.write_f64(count_id, self.count() as f64);
+        }
+
+        if let Some(last_event_time_id) = &self.last_event_time_id {
+            writer.write_f64(
+                last_event_time_id,
+                self.events
+                    .back()
+                    .map_or(0., |event| event.time.as_secs_f64()),
+            );
+        }
+    }
+
+    /// Writes the kept events as CSV, with the time in seconds.
+    pub fn write_csv<T: Write>(&self, writer: &mut T) -> io::Result<()> {
+        writeln!(writer, "time,tick,source,event,cause")?;
+        for event in &self.events {
+            writeln!(
+                writer,
+                "{},{},{},{},{}",
+                event.time.as_secs_f64(),
+                event.tick,
+                csv_field(&event.source),
+                csv_field(&event.kind.to_string()),
+                csv_field(event.cause().unwrap_or(""))
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Visits aircraft components in order to receive their events.
+struct EventVisitor<'a> {
+    emitter: &'a mut EventEmitter,
+}
+impl<'a> EventVisitor<'a> {
+    fn new(emitter: &'a mut EventEmitter) -> Self {
+        Self { emitter }
+    }
+}
+impl SimulationElementVisitor for EventVisitor<'_> {
+    fn visit<T: SimulationElement>(&mut self, visited: &mut T) {
+        visited.emit_events(self.emitter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::test::SimulationTestBed;
+
+    struct TestSwitch {
+        id: String,
+        on: bool,
+        on_change: ChangeDetector<bool>,
+    }
+    impl TestSwitch {
+        fn new(id: &str) -> Self {
+            Self {
+                id: id.to_owned(),
+                on: false,
+                on_change: ChangeDetector::new(false),
+            }
+        }
+    }
+    impl SimulationElement for TestSwitch {
+        fn emit_events(&mut self, events: &mut EventEmitter) {
+            if let Some(was_on) = self.on_change.detect(self.on) {
+                events.emit(
+                    &self.id,
+                    EventKind::StateChanged {
+                        from: was_on.to_string(),
+                        to: self.on.to_string(),
+                    },
+                );
+            }
+        }
+    }
+
+    struct TestAircraft {
+        switches: [TestSwitch; 2],
+    }
+    impl TestAircraft {
+        fn new() -> Self {
+            Self {
+                switches: [TestSwitch::new("A"), TestSwitch::new("B")],
+            }
+        }
+    }
+    impl SimulationElement for TestAircraft {
+        fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+            self.switches.iter_mut().for_each(|el| el.accept(visitor));
+
+            visitor.visit(self);
+        }
+    }
+
+    fn changed(from: bool, to: bool) -> EventKind {
+        EventKind::StateChanged {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn unchanged_elements_emit_no_events() {
+        let mut bus = EventBus::new();
+
+        bus.collect(&mut TestAircraft::new(), Duration::from_secs(1));
+
+        assert!(bus.is_empty());
+    }
+
+    #[test]
+    fn events_are_timestamped_with_the_end_of_their_tick() {
+        let mut aircraft = TestAircraft::new();
+        let mut bus = EventBus::new();
+
+        bus.collect(&mut aircraft, Duration::from_millis(500));
+        aircraft.switches[1].on = true;
+        bus.collect(&mut aircraft, Duration::from_millis(500));
+        bus.collect(&mut aircraft, Duration::from_millis(500));
+
+        assert_eq!(bus.len(), 1);
+        let event = bus.find("B", &changed(false, true)).unwrap();
+        assert_eq!(event.tick(), 2);
+        assert_eq!(event.time(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn keeps_events_in_the_order_they_happened() {
+        let mut aircraft = TestAircraft::new();
+        let mut bus = EventBus::new();
+
+        aircraft.switches[1].on = true;
+        bus.collect(&mut aircraft, Duration::from_secs(1));
+        aircraft.switches[0].on = true;
+        aircraft.switches[1].on = false;
+        bus.collect(&mut aircraft, Duration::from_secs(1));
+
+        assert_eq!(
+            bus.iter()
+                .map(|event| event.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "[1.000s] B changed from false to true",
+                "[2.000s] A changed from false to true",
+                "[2.000s] B changed from true to false",
+            ]
+        );
+        assert_eq!(bus.iter_from("B").count(), 2);
+    }
+
+    #[test]
+    fn ring_buffer_drops_the_oldest_events() {
+        let mut aircraft = TestAircraft::new();
+        let mut bus = EventBus::ring_buffer(2);
+
+        for _ in 0..3 {
+            aircraft.switches[0].on = !aircraft.switches[0].on;
+            bus.collect(&mut aircraft, Duration::from_secs(1));
+        }
+
+        assert_eq!(bus.len(), 2);
+        assert_eq!(bus.dropped_count(), 1);
+        assert_eq!(bus.iter().next().unwrap().tick(), 2);
+    }
+
+    #[test]
+    fn count_includes_dropped_events() {
+        let mut aircraft = TestAircraft::new();
+        let mut bus = EventBus::ring_buffer(1);
+
+        for _ in 0..3 {
+            aircraft.switches[0].on = !aircraft.switches[0].on;
+            bus.collect(&mut aircraft, Duration::from_secs(1));
+        }
+
+        assert_eq!(bus.count(), 3);
+    }
+
+    #[test]
+    fn writes_count_and_time_of_last_event_to_simulator() {
+        let mut test_bed = SimulationTestBed::new();
+        let mut aircraft = TestAircraft::new();
+
+        test_bed.run_without_update(&mut aircraft);
+        aircraft.switches[0].on = true;
+        aircraft.switches[1].on = true;
+        test_bed.run_without_update(&mut aircraft);
+        test_bed.run_without_update(&mut aircraft);
+
+        assert_eq!(test_bed.read_f64(EventBus::COUNT_KEY), 2.);
+        assert_eq!(test_bed.read_f64(EventBus::LAST_EVENT_TIME_KEY), 2.);
+    }
+
+    #[test]
+    fn writes_csv() {
+        let mut bus = EventBus::new();
+        bus.events.push_back(Event {
+            tick: 3,
+            time: Duration::from_millis(1500),
+            source: String::from("ELEC/CONTACTOR/11XU1"),
+            kind: EventKind::Closed,
+            cause: Some(String::from("input from ENG_GEN_1, APU_GEN_1")),
+        });
+
+        let mut csv = Vec::new();
+        bus.write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "time,tick,source,event,cause\n\
+             1.5,3,ELEC/CONTACTOR/11XU1,closed,\"input from ENG_GEN_1, APU_GEN_1\"\n"
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use super::{SimulationElement, SimulationElementVisitor};
+
+/// Collects the invariants checked by elements within a single tick.
+///
+/// # Examples
+/// ```rust
+/// # use systems::simulation::{InvariantChecker, SimulationElement};
+/// struct MyReservoir {
+///     volume: f64,
+/// }
+/// impl SimulationElement for MyReservoir {
+///     fn check_invariants(&self, invariants: &mut InvariantChecker) {
+///         invariants.check("reservoir volume never negative", self.volume >= 0.);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct InvariantChecker {
+    violated: Vec<String>,
+}
+impl InvariantChecker {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks the invariant with the given name, which is violated when it doesn't hold.
+    pub fn check(&mut self, name: &str, holds: bool) {
+        if !holds {
+            self.violated.push(name.to_owned());
+        }
+    }
+}
+
+/// What an [`InvariantMonitor`] does when an invariant is violated.
+/// Every action counts the violations.
+///
+/// [`InvariantMonitor`]: struct.InvariantMonitor.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvariantViolationAction {
+    /// Writes the first violation of every invariant to stderr, such that an invariant
+    /// which stays violated doesn't flood the log.
+    Log,
+    Count,
+    Panic,
+}
+
+/// An invariant which didn't hold after the [`Simulation`] tick with the given number.
+///
+/// [`Simulation`]: struct.Simulation.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvariantViolation {
+    tick: u64,
+    name: String,
+}
+impl InvariantViolation {
+    /// The number of the tick, where the first tick started by the monitor is tick 1.
+    /// When a tick is split into multiple steps, the violation belongs to the tick
+    /// containing the step.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invariant \"{}\" violated in tick {}.",
+            self.name, self.tick
+        )
+    }
+}
+
+/// Evaluates the invariants of the aircraft's elements after every step of the
+/// [`Simulation`] it is given to, and handles violations as configured.
+/// Violations are numbered by the tick of the simulation, which starts a new tick
+/// for every call to its `tick` function.
+/// Elements check their invariants by overriding [`SimulationElement::check_invariants`].
+///
+/// # Examples
+/// ```rust
+/// # use systems::simulation::{InvariantChecker, InvariantMonitor, InvariantViolationAction,
+/// #     SimulationElement};
+/// # struct MyReservoir {
+/// #     volume: f64,
+/// # }
+/// # impl SimulationElement for MyReservoir {
+/// #     fn check_invariants(&self, invariants: &mut InvariantChecker) {
+/// #         invariants.check("reservoir volume never negative", self.volume >= 0.);
+/// #     }
+/// # }
+/// let mut monitor = InvariantMonitor::new(InvariantViolationAction::Count);
+///
+/// monitor.start_tick();
+/// monitor.check(&mut MyReservoir { volume: 1. });
+/// monitor.start_tick();
+/// monitor.check(&mut MyReservoir { volume: -1. });
+/// monitor.check(&mut MyReservoir { volume: -2. });
+///
+/// assert_eq!(monitor.violation_count(), 1);
+/// assert_eq!(monitor.last_violation().unwrap().tick(), 2);
+/// ```
+/// [`Simulation`]: struct.Simulation.html
+/// [`SimulationElement::check_invariants`]: trait.SimulationElement.html#method.check_invariants
+#[derive(Debug)]
+pub struct InvariantMonitor {
+    action: InvariantViolationAction,
+    ticks: u64,
+    violation_counts: BTreeMap<String, u64>,
+    violated_in_tick: BTreeSet<String>,
+    last_violation: Option<InvariantViolation>,
+}
+impl InvariantMonitor {
+    pub fn new(action: InvariantViolationAction) -> Self {
+        Self {
+            action,
+            ticks: 0,
+            violation_counts: BTreeMap::new(),
+            violated_in_tick: BTreeSet::new(),
+            last_violation: None,
+        }
+    }
+
+    pub fn action(&self) -> InvariantViolationAction {
+        self.action
+    }
+
+    /// Starts the next tick, to which the violations of the following checks belong.
+    pub fn start_tick(&mut self) {
+        self.ticks += 1;
+        self.violated_in_tick.clear();
+    }
+
+    /// Evaluates the invariants of the given element and all of its children
+    /// as a step of the current tick.
+    pub fn check(&mut self, element: &mut impl SimulationElement) {
+        let mut checker = InvariantChecker::new();
+        let mut visitor = InvariantVisitor::new(&mut checker);
+        element.accept(&mut visitor);
+
+        for name in checker.violated {
+            let violation = InvariantViolation {
+                tick: self.ticks,
+                name,
+            };
+            let count = self
+                .violation_counts
+                .entry(violation.name.clone())
+                .or_insert(0);
+            match self.action {
+                InvariantViolationAction::Log if *count == 0 => eprintln!("{}", violation),
+                InvariantViolationAction::Log | InvariantViolationAction::Count => {}
+                InvariantViolationAction::Panic => panic!("{}", violation),
+            }
+
+            if self.violated_in_tick.insert(violation.name.clone()) {
+                *count += 1;
+            }
+            self.last_violation = Some(violation);
+        }
+    }
+
+    /// The number of ticks which have been started.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn violation_count(&self) -> u64 {
+        self.violation_counts.values().sum()
+    }
+
+    /// The number of ticks in which the invariant with the given name was violated.
+    pub fn violation_count_of(&self, name: &str) -> u64 {
+        *self.violation_counts.get(name).unwrap_or(&0)
+    }
+
+    /// Iterates over the names of the violated invariants and their violation counts.
+    pub fn violation_counts(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.violation_counts
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+    }
+
+    pub fn last_violation(&self) -> Option<&InvariantViolation> {
+        self.last_violation.as_ref()
+    }
+}
+
+/// Visits aircraft components in order to check their invariants.
+struct InvariantVisitor<'a> {
+    checker: &'a mut InvariantChecker,
+}
+impl<'a> InvariantVisitor<'a> {
+    fn new(checker: &'a mut InvariantChecker) -> Self {
+        Self { checker }
+    }
+}
+impl SimulationElementVisitor for InvariantVisitor<'_> {
+    fn visit<T: SimulationElement>(&mut self, visited: &mut T) {
+        visited.check_invariants(self.checker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestElement {
+        value: f64,
+    }
+    impl SimulationElement for TestElement {
+        fn check_invariants(&self, invariants: &mut InvariantChecker) {
+            invariants.check("value never negative", self.value >= 0.);
+            invariants.check("value below 10", self.value < 10.);
+        }
+    }
+
+    fn check_values(monitor: &mut InvariantMonitor, values: &[f64]) {
+        for value in values {
+            monitor.start_tick();
+            monitor.check(&mut TestElement { value: *value });
+        }
+    }
+
+    #[test]
+    fn holding_invariants_are_not_violations() {
+        let mut monitor = InvariantMonitor::new(InvariantViolationAction::Count);
+
+        check_values(&mut monitor, &[0., 5.]);
+
+        assert_eq!(monitor.ticks(), 2);
+        assert_eq!(monitor.violation_count(), 0);
+        assert_eq!(monitor.last_violation(), None);
+    }
+
+    #[test]
+    fn counts_violations_per_invariant() {
+        let mut monitor = InvariantMonitor::new(InvariantViolationAction::Count);
+
+        check_values(&mut monitor, &[-1., 5., -2., 20.]);
+
+        assert_eq!(monitor.violation_count(), 3);
+        assert_eq!(monitor.violation_count_of("value never negative"), 2);
+        assert_eq!(monitor.violation_count_of("value below 10"), 1);
+        assert_eq!(
+            monitor.violation_counts().collect::<Vec<_>>(),
+            vec![("value below 10", 1), ("value never negative", 2)]
+        );
+    }
+
+    #[test]
+    fn last_violation_contains_tick_and_name() {
+        let mut monitor = InvariantMonitor::new(InvariantViolationAction::Log);
+
+        check_values(&mut monitor, &[5., 20., 5.]);
+
+        let violation = monitor.last_violation().unwrap();
+        assert_eq!(violation.tick(), 2);
+        assert_eq!(violation.name(), "value below 10");
+        assert_eq!(
+            violation.to_string(),
+            "Invariant \"value below 10\" violated in tick 2."
+        );
+    }
+
+    #[test]
+    fn counts_violations_once_per_tick_regardless_of_the_number_of_steps() {
+        let mut monitor = InvariantMonitor::new(InvariantViolationAction::Count);
+
+        monitor.start_tick();
+        monitor.check(&mut TestElement { value: 5. });
+        monitor.start_tick();
+        monitor.check(&mut TestElement { value: -1. });
+        monitor.check(&mut TestElement { value: -2. });
+        monitor.check(&mut TestElement { value: 5. });
+
+        assert_eq!(monitor.ticks(), 2);
+        assert_eq!(monitor.violation_count_of("value never negative"), 1);
+        assert_eq!(monitor.last_violation().unwrap().tick(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invariant \"value never negative\" violated in tick 1.")]
+    fn panics_on_violation_when_configured_to() {
+        let mut monitor = InvariantMonitor::new(InvariantViolationAction::Panic);
+
+        check_values(&mut monitor, &[-1.]);
+    }
+}
@@ -1,23 +1,68 @@
 use std::time::Duration;
 
+mod catalogue;
+mod events;
+mod invariants;
+mod recording;
+mod registry;
+mod scenario;
+mod scheduler;
+mod snapshot;
+mod state_dump;
 mod update_context;
+pub use catalogue::*;
+pub use events::*;
+pub use invariants::*;
+pub use recording::*;
+pub use registry::*;
+pub use scenario::*;
+pub use scheduler::*;
+pub use snapshot::*;
+pub use state_dump::*;
 pub use update_context::*;
 
 pub mod test;
 
-use crate::electrical::consumption::{
-    ElectricPower, PowerConsumption, PowerConsumptionReport, SuppliedPower,
+use crate::{
+    electrical::consumption::{
+        ElectricPower, PowerConsumption, PowerConsumptionReport, SuppliedPower,
+    },
+    shared::RandomSource,
 };
 
 /// Trait for a type which can read and write simulator data.
 /// Using this trait implementors can abstract away the way the code
 /// interacts with the simulator. This separation of concerns is very important
 /// for keeping the majority of the code unit testable.
+///
+/// Variables are identified by the [`VariableIdentifier`] obtained from the
+/// [`VariableRegistry`] with which the aircraft was created.
+///
+/// [`VariableIdentifier`]: struct.VariableIdentifier.html
+/// [`VariableRegistry`]: struct.VariableRegistry.html
 pub trait SimulatorReaderWriter {
-    /// Reads a variable with the given name from the simulator.
-    fn read(&mut self, name: &str) -> f64;
-    /// Writes a variable with the given name to the simulator.
-    fn write(&mut self, name: &str, value: f64);
+    /// Reads the variable with the given identifier from the simulator.
+    fn read(&mut self, identifier: &VariableIdentifier) -> f64;
+    /// Writes the variable with the given identifier to the simulator.
+    fn write(&mut self, identifier: &VariableIdentifier, value: f64);
+    /// Reads the variable with the given identifier, which the aircraft interprets
+    /// as the given type. By default the type is ignored.
+    fn read_typed(&mut self, identifier: &VariableIdentifier, _variable_type: VariableType) -> f64 {
+        self.read(identifier)
+    }
+    /// Writes the variable with the given identifier, which the aircraft provides
+    /// as the given type. By default the type is ignored.
+    fn write_typed(
+        &mut self,
+        identifier: &VariableIdentifier,
+        _variable_type: VariableType,
+        value: f64,
+    ) {
+        self.write(identifier, value);
+    }
+    /// Signals the start of a simulation tick with the given delta,
+    /// before any variables are read.
+    fn start_tick(&mut self, _delta: Duration) {}
 }
 
 /// An [`Aircraft`] that can be simulated by the [`Simulation`].
@@ -25,8 +70,36 @@ pub trait SimulatorReaderWriter {
 /// [`Aircraft`]: trait.Aircraft.html
 /// [`Simulation`]: struct.Simulation.html
 pub trait Aircraft: SimulationElement {
+    /// The fixed rates at which the aircraft is updated in addition to being updated
+    /// once per step of the [`Simulation`]. See [`update_at_fixed_rate`].
+    ///
+    /// [`Simulation`]: struct.Simulation.html
+    /// [`update_at_fixed_rate`]: #method.update_at_fixed_rate
+    fn fixed_rates(&self) -> Vec<FixedRate> {
+        Vec::new()
+    }
+
     fn update_before_power_distribution(&mut self, _context: &UpdateContext) {}
     fn update_after_power_distribution(&mut self, _context: &UpdateContext) {}
+
+    /// Updates the parts of the aircraft running at the given fixed rate. Runs after
+    /// [`update_after_power_distribution`], once for every step of the rate which completed
+    /// since the previous step of the simulation. The context's delta is the rate's time step.
+    ///
+    /// [`update_after_power_distribution`]: #method.update_after_power_distribution
+    fn update_at_fixed_rate(&mut self, _rate: FixedRate, _context: &UpdateContext) {}
+
+    /// Updates the parts of the aircraft running at the sub rate of the given fixed rate.
+    /// Runs after all fixed steps of the rate, once for every sub step of those fixed steps.
+    /// The context's delta is the rate's sub time step.
+    fn update_at_sub_rate(&mut self, _rate: FixedRate, _context: &UpdateContext) {}
+
+    /// Interpolates the outputs of the parts running at the given fixed rate between their
+    /// last two fixed steps, such that they change smoothly at frame rates above the fixed
+    /// rate. Runs after the sub steps of the rate, in every step of the simulation. The
+    /// fraction is the part of the rate's time step which passed since its last fixed step.
+    fn interpolate_fixed_rate(&mut self, _rate: FixedRate, _fraction: f64) {}
+
     fn get_supplied_power(&mut self) -> SuppliedPower {
         SuppliedPower::new()
     }
@@ -75,13 +148,14 @@ pub trait SimulationElement {
     /// # Examples
     /// ```rust
     /// # use systems::simulation::{SimulationElement, SimulationElementVisitor,
-    /// #    SimulatorReader, SimulatorWriter};
+    /// #    SimulatorReader, SimulatorWriter, VariableIdentifier};
     /// struct MySimulationElement {
+    ///     is_on_id: VariableIdentifier,
     ///     is_on: bool,
     /// }
     /// impl SimulationElement for MySimulationElement {
     ///     fn read(&mut self, reader: &mut SimulatorReader) {
-    ///         self.is_on = reader.read_bool("MY_SIMULATOR_ELEMENT_IS_ON");
+    ///         self.is_on = reader.read_bool(&self.is_on_id);
     ///     }
     /// }
     /// ```
@@ -91,19 +165,117 @@ pub trait SimulationElement {
     /// # Examples
     /// ```rust
     /// # use systems::simulation::{SimulationElement, SimulationElementVisitor,
-    /// #    SimulatorReader, SimulatorWriter};
+    /// #    SimulatorReader, SimulatorWriter, VariableIdentifier};
     /// struct MySimulationElement {
+    ///     is_on_id: VariableIdentifier,
     ///     is_on: bool,
     /// }
     /// impl SimulationElement for MySimulationElement {
     ///     fn write(&self, writer: &mut SimulatorWriter) {
-    ///        writer.write_bool("MY_SIMULATOR_ELEMENT_IS_ON", self.is_on);
+    ///        writer.write_bool(&self.is_on_id, self.is_on);
     ///     }
     /// }
     /// ```
     /// [`Simulation`]: struct.Simulation.html
     fn write(&self, _writer: &mut SimulatorWriter) {}
 
+    /// Saves the internal state of the element into a [`Snapshot`], such that it can be
+    /// restored in a later session. Only state which isn't read from the simulator
+    /// needs to be saved.
+    /// # Examples
+    /// ```rust
+    /// # use systems::simulation::{SimulationElement, Snapshot};
+    /// struct MySimulationElement {
+    ///     charge: f64,
+    /// }
+    /// impl SimulationElement for MySimulationElement {
+    ///     fn save(&self, snapshot: &mut Snapshot) {
+    ///        snapshot.write_f64("MY_SIMULATION_ELEMENT_CHARGE", self.charge);
+    ///     }
+    /// }
+    /// ```
+    /// [`Snapshot`]: struct.Snapshot.html
+    fn save(&self, _snapshot: &mut Snapshot) {}
+
+    /// Restores the internal state of the element from a [`Snapshot`].
+    /// Variables which are missing from the snapshot should leave the state untouched.
+    /// # Examples
+    /// ```rust
+    /// # use systems::simulation::{SimulationElement, Snapshot};
+    /// struct MySimulationElement {
+    ///     charge: f64,
+    /// }
+    /// impl SimulationElement for MySimulationElement {
+    ///     fn restore(&mut self, snapshot: &Snapshot) {
+    ///         if let Some(charge) = snapshot.read_f64("MY_SIMULATION_ELEMENT_CHARGE") {
+    ///             self.charge = charge;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    /// [`Snapshot`]: struct.Snapshot.html
+    fn restore(&mut self, _snapshot: &Snapshot) {}
+
+    /// Describes the internal state of the element in a [`StateDump`], for
+    /// debugging purposes. Elements add nodes to the dump to group their values.
+    /// # Examples
+    /// ```rust
+    /// # use systems::simulation::{SimulationElement, StateDump};
+    /// struct MySimulationElement {
+    ///     id: String,
+    ///     pressure: f64,
+    /// }
+    /// impl SimulationElement for MySimulationElement {
+    ///     fn dump(&self, state: &mut StateDump) {
+    ///         state.node("MY_LOOP").node(&self.id).write_f64("PRESSURE", self.pressure);
+    ///     }
+    /// }
+    /// ```
+    /// [`StateDump`]: struct.StateDump.html
+    fn dump(&self, _state: &mut StateDump) {}
+
+    /// Checks the invariants of the element, which should hold at the end of every tick.
+    /// Violations are handled by the [`InvariantMonitor`] given to the [`Simulation`].
+    /// # Examples
+    /// ```rust
+    /// # use systems::simulation::{InvariantChecker, SimulationElement};
+    /// struct MySimulationElement {
+    ///     pressure: f64,
+    /// }
+    /// impl SimulationElement for MySimulationElement {
+    ///     fn check_invariants(&self, invariants: &mut InvariantChecker) {
+    ///         invariants.check("pressure never negative", self.pressure >= 0.);
+    ///     }
+    /// }
+    /// ```
+    /// [`InvariantMonitor`]: struct.InvariantMonitor.html
+    /// [`Simulation`]: struct.Simulation.html
+    fn check_invariants(&self, _invariants: &mut InvariantChecker) {}
+
+    /// Emits events for the state transitions of the element which happened since it
+    /// last emitted events, such as a contactor closing. Use a [`ChangeDetector`] to
+    /// detect the transitions.
+    /// # Examples
+    /// ```rust
+    /// # use systems::simulation::{ChangeDetector, EventEmitter, EventKind, SimulationElement};
+    /// struct MySimulationElement {
+    ///     active: bool,
+    ///     active_change: ChangeDetector<bool>,
+    /// }
+    /// impl SimulationElement for MySimulationElement {
+    ///     fn emit_events(&mut self, events: &mut EventEmitter) {
+    ///         if self.active_change.detect(self.active).is_some() {
+    ///             events.emit(
+    ///                 "MY_PUMP",
+    ///                 if self.active { EventKind::Activated } else { EventKind::Deactivated },
+    ///             );
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    /// [`ChangeDetector`]: struct.ChangeDetector.html
+    fn emit_events(&mut self, _events: &mut EventEmitter) {}
+
     /// Receive power from the aircraft's electrical systems.
     /// The easiest way to deal with power consumption is using the [`PowerConsumer`] type.
     ///
@@ -161,15 +333,17 @@ pub trait SimulationElementVisitor {
 /// Runs the aircraft simulation every time [`tick`] is called.
 /// This orchestrates the:
 /// 1. Reading of data from the simulator into the aircraft state.
-/// 2. Updating of the aircraft state for each tick.
+/// 2. Updating of the aircraft state for each tick and at the aircraft's fixed rates.
 /// 3. Writing of aircraft state data to the simulator.
 ///
 /// # Examples
 /// Basic usage is as follows:
 /// ```rust
 /// # use std::time::Duration;
-/// # use systems::simulation::{Aircraft, SimulationElement, SimulatorReaderWriter, Simulation, UpdateContext};
+/// # use systems::simulation::{Aircraft, Scheduler, SimulationElement, SimulatorReaderWriter,
+/// #     Simulation, UpdateContext, VariableIdentifier};
 /// # use systems::electrical::consumption::SuppliedPower;
+/// # use systems::shared::SystemRandomSource;
 /// # struct MyAircraft {}
 /// # impl MyAircraft {
 /// #     fn new() -> Self {
@@ -190,13 +364,20 @@ pub trait SimulationElementVisitor {
 /// #     }
 /// # }
 /// # impl SimulatorReaderWriter for MySimulatorReaderWriter {
-/// #     fn read(&mut self, name: &str) -> f64 { 0.0 }
-/// #     fn write(&mut self, name: &str, value: f64) { }
+/// #     fn read(&mut self, identifier: &VariableIdentifier) -> f64 { 0.0 }
+/// #     fn write(&mut self, identifier: &VariableIdentifier, value: f64) { }
 /// # }
 /// // Create the Simulation only once.
 /// let mut aircraft = MyAircraft::new();
 /// let mut reader_writer = MySimulatorReaderWriter::new();
-/// let mut simulation = Simulation::new(&mut aircraft, &mut reader_writer);
+/// let mut random_source = SystemRandomSource::new();
+/// let mut scheduler = Scheduler::new();
+/// let mut simulation = Simulation::new(
+///     &mut aircraft,
+///     &mut reader_writer,
+///     &mut random_source,
+///     &mut scheduler,
+/// );
 /// // For each frame, call the tick function.
 /// simulation.tick(Duration::from_millis(50));
 /// ```
@@ -204,20 +385,106 @@ pub trait SimulationElementVisitor {
 pub struct Simulation<'a, T: Aircraft, U: SimulatorReaderWriter> {
     aircraft: &'a mut T,
     simulator_read_writer: &'a mut U,
+    random_source: &'a mut dyn RandomSource,
+    scheduler: &'a mut Scheduler,
+    settings: SimulationSettings,
+    invariant_monitor: Option<&'a mut InvariantMonitor>,
+    event_bus: Option<&'a mut EventBus>,
 }
 impl<'a, T: Aircraft, U: SimulatorReaderWriter> Simulation<'a, T, U> {
-    pub fn new(aircraft: &'a mut T, simulator_read_writer: &'a mut U) -> Self {
+    /// Creates a simulation of the given aircraft. Use a [`SeededRandomSource`]
+    /// when every run of the simulation should result in the same state.
+    /// The [`Scheduler`] keeps track of the aircraft's fixed rates across ticks,
+    /// thus a simulation created for every tick should be given the same scheduler.
+    ///
+    /// [`SeededRandomSource`]: ../shared/struct.SeededRandomSource.html
+    /// [`Scheduler`]: struct.Scheduler.html
+    pub fn new(
+        aircraft: &'a mut T,
+        simulator_read_writer: &'a mut U,
+        random_source: &'a mut dyn RandomSource,
+        scheduler: &'a mut Scheduler,
+    ) -> Self {
+        Self::new_with_settings(
+            aircraft,
+            simulator_read_writer,
+            random_source,
+            scheduler,
+            SimulationSettings::default(),
+        )
+    }
+
+    /// Creates a simulation of the given aircraft, which handles large deltas
+    /// and pauses as configured in the given [`SimulationSettings`].
+    ///
+    /// [`SimulationSettings`]: struct.SimulationSettings.html
+    pub fn new_with_settings(
+        aircraft: &'a mut T,
+        simulator_read_writer: &'a mut U,
+        random_source: &'a mut dyn RandomSource,
+        scheduler: &'a mut Scheduler,
+        settings: SimulationSettings,
+    ) -> Self {
         Simulation {
             aircraft,
             simulator_read_writer,
+            random_source,
+            scheduler,
+            settings,
+            invariant_monitor: None,
+            event_bus: None,
         }
     }
 
+    /// Checks the invariants of the aircraft at the end of every step using the given
+    /// [`InvariantMonitor`]. Without a monitor, invariants are not checked.
+    ///
+    /// [`InvariantMonitor`]: struct.InvariantMonitor.html
+    pub fn with_invariant_monitor(mut self, monitor: &'a mut InvariantMonitor) -> Self {
+        self.invariant_monitor = Some(monitor);
+        self
+    }
+
+    /// Passes the events emitted by the aircraft at the end of every step to the given
+    /// [`EventBus`]. Without a bus, events are not emitted.
+    ///
+    /// [`EventBus`]: struct.EventBus.html
+    pub fn with_event_bus(mut self, bus: &'a mut EventBus) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
+
+    /// The [`EventBus`] given to the simulation, if any.
+    ///
+    /// [`EventBus`]: struct.EventBus.html
+    pub fn event_bus(&self) -> Option<&EventBus> {
+        self.event_bus.as_deref()
+    }
+
     /// Execute a single run of the simulation using the specified `delta` duration
     /// as the amount of time that has passed since the previous run.
+    ///
+    /// When the settings contain a maximum step, a `delta` exceeding it is split into
+    /// multiple equal steps. When pause detection is enabled, a paused tick
+    /// doesn't update the aircraft.
     pub fn tick(&mut self, delta: Duration) {
+        self.simulator_read_writer.start_tick(delta);
+        if let Some(monitor) = self.invariant_monitor.as_mut() {
+            monitor.start_tick();
+        }
+
+        if self.settings.is_paused(delta) {
+            return;
+        }
+
+        for step in self.settings.steps(delta) {
+            self.tick_step(step);
+        }
+    }
+
+    fn tick_step(&mut self, delta: Duration) {
         let mut reader = SimulatorReader::new(self.simulator_read_writer);
-        let context = UpdateContext::from_reader(&mut reader, delta);
+        let context = UpdateContext::from_reader(&mut reader, delta, self.random_source.next_u64());
 
         let mut visitor = SimulatorToSimulationVisitor::new(&mut reader);
         self.aircraft.accept(&mut visitor);
@@ -229,12 +496,140 @@ impl<'a, T: Aircraft, U: SimulatorReaderWriter> Simulation<'a, T, U> {
 
         self.aircraft.update_after_power_distribution(&context);
 
+        for rate in self.aircraft.fixed_rates() {
+            self.update_at_fixed_rate(rate, &context);
+        }
+
         electric_power.consume_in(self.aircraft);
         electric_power.report_consumption_to(self.aircraft);
 
+        if let Some(bus) = self.event_bus.as_mut() {
+            bus.collect(self.aircraft, delta);
+        }
+
+        if let Some(monitor) = self.invariant_monitor.as_mut() {
+            monitor.check(self.aircraft);
+        }
+
         let mut writer = SimulatorWriter::new(self.simulator_read_writer);
         let mut visitor = SimulationToSimulatorVisitor::new(&mut writer);
         self.aircraft.accept(&mut visitor);
+
+        if let Some(bus) = self.event_bus.as_ref() {
+            bus.write(&mut SimulatorWriter::new(self.simulator_read_writer));
+        }
+    }
+
+    fn update_at_fixed_rate(&mut self, rate: FixedRate, context: &UpdateContext) {
+        let number_of_steps = self.scheduler.number_of_steps(rate, context.delta());
+
+        for _ in 0..number_of_steps {
+            let step_context =
+                context.for_fixed_step(rate.time_step(), self.random_source.next_u64());
+            self.aircraft.update_at_fixed_rate(rate, &step_context);
+        }
+
+        for _ in 0..number_of_steps * rate.sub_steps() {
+            let sub_step_context =
+                context.for_fixed_step(rate.sub_time_step(), self.random_source.next_u64());
+            self.aircraft.update_at_sub_rate(rate, &sub_step_context);
+        }
+
+        self.aircraft
+            .interpolate_fixed_rate(rate, self.scheduler.interpolation_fraction(rate));
+    }
+
+    /// Takes a [`Snapshot`] of the aircraft's internal state.
+    ///
+    /// [`Snapshot`]: struct.Snapshot.html
+    pub fn snapshot(&mut self) -> Snapshot {
+        Snapshot::take(self.aircraft)
+    }
+
+    /// Restores the aircraft's internal state from a [`Snapshot`].
+    /// Call this before the first [`tick`].
+    ///
+    /// [`Snapshot`]: struct.Snapshot.html
+    /// [`tick`]: #method.tick
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        snapshot.restore_into(self.aircraft);
+    }
+
+    /// Takes a [`StateDump`] of the aircraft's internal state, describing
+    /// the state as it was at the end of the last tick.
+    ///
+    /// [`StateDump`]: struct.StateDump.html
+    pub fn dump_state(&mut self) -> StateDump {
+        StateDump::take(self.aircraft)
+    }
+}
+
+/// Configures how the [`Simulation`] handles the deltas it is given.
+/// By default every delta is passed on as is.
+///
+/// [`Simulation`]: struct.Simulation.html
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SimulationSettings {
+    max_step: Option<Duration>,
+    detects_pauses: bool,
+}
+impl SimulationSettings {
+    // Deltas shorter than this are considered zero, as the simulator may pass a tiny
+    // rather than an exact zero delta while paused
+    const MIN_DELTA: Duration = Duration::from_micros(1);
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Splits deltas exceeding the given duration into multiple equal steps, such that
+    /// a high simulation rate or a long stutter results in the same state as running
+    /// at normal speed.
+    pub fn with_max_step(mut self, max_step: Duration) -> Self {
+        assert!(max_step > Duration::from_secs(0));
+        self.max_step = Some(max_step);
+        self
+    }
+
+    /// Considers the simulation paused when the delta is zero or shorter than a microsecond,
+    /// in which case the aircraft isn't updated.
+    ///
+    /// Only the delta itself is considered. A simulator which keeps passing regular deltas
+    /// while paused, or repeats the same delta for a frozen frame, isn't detected as
+    /// paused; telling such ticks apart requires the simulator's own pause state.
+    pub fn with_pause_detection(mut self) -> Self {
+        self.detects_pauses = true;
+        self
+    }
+
+    pub fn max_step(&self) -> Option<Duration> {
+        self.max_step
+    }
+
+    /// Whether a tick of the given delta is considered paused. See [`with_pause_detection`].
+    ///
+    /// [`with_pause_detection`]: #method.with_pause_detection
+    pub fn is_paused(&self, delta: Duration) -> bool {
+        self.detects_pauses && delta < Self::MIN_DELTA
+    }
+
+    fn steps(&self, delta: Duration) -> Vec<Duration> {
+        match self.max_step {
+            Some(max_step) if delta > max_step => {
+                let mut number_of_steps = (delta.as_nanos() / max_step.as_nanos()) as u32;
+                if delta > max_step * number_of_steps {
+                    number_of_steps += 1;
+                }
+                let step = delta / number_of_steps;
+
+                let mut steps = vec![step; number_of_steps as usize - 1];
+                // The last step catches the remainder of the division
+                steps.push(delta - step * (number_of_steps - 1));
+
+                steps
+            }
+            _ => vec![delta],
+        }
     }
 }
 
@@ -285,36 +680,68 @@ impl<'a> SimulatorReader<'a> {
     /// # Examples
     /// ```rust
     /// # use systems::simulation::{SimulationElement, SimulationElementVisitor,
-    /// #    SimulatorReader, SimulatorWriter};
+    /// #    SimulatorReader, SimulatorWriter, VariableIdentifier};
     /// struct MySimulationElement {
+    ///     n_id: VariableIdentifier,
     ///     n: f64,
     /// }
     /// impl SimulationElement for MySimulationElement {
     ///     fn read(&mut self, reader: &mut SimulatorReader) {
-    ///         self.n = reader.read_f64("MY_SIMULATOR_ELEMENT_IS_ON");
+    ///         self.n = reader.read_f64(&self.n_id);
     ///     }
     /// }
     /// ```
-    pub fn read_f64(&mut self, name: &str) -> f64 {
-        self.simulator_read_writer.read(name)
+    pub fn read_f64(&mut self, identifier: &VariableIdentifier) -> f64 {
+        self.simulator_read_writer
+            .read_typed(identifier, VariableType::F64)
     }
 
     /// Reads a `bool` from the simulator.
     /// # Examples
     /// ```rust
     /// # use systems::simulation::{SimulationElement, SimulationElementVisitor,
-    /// #    SimulatorReader, SimulatorWriter};
+    /// #    SimulatorReader, SimulatorWriter, VariableIdentifier};
     /// struct MySimulationElement {
+    ///     is_on_id: VariableIdentifier,
     ///     is_on: bool,
     /// }
     /// impl SimulationElement for MySimulationElement {
     ///     fn read(&mut self, reader: &mut SimulatorReader) {
-    ///         self.is_on = reader.read_bool("MY_SIMULATOR_ELEMENT_IS_ON");
+    ///         self.is_on = reader.read_bool(&self.is_on_id);
     ///     }
     /// }
     /// ```
-    pub fn read_bool(&mut self, name: &str) -> bool {
-        to_bool(self.read_f64(name))
+    pub fn read_bool(&mut self, identifier: &VariableIdentifier) -> bool {
+        to_bool(
+            self.simulator_read_writer
+                .read_typed(identifier, VariableType::Bool),
+        )
+    }
+
+    /// Reads a quantity from the simulator, which provides it in the given unit.
+    /// # Examples
+    /// ```rust
+    /// # use systems::simulation::{SimulationElement, SimulatorReader, VariableIdentifier};
+    /// # use uom::si::{f64::*, pressure::psi};
+    /// struct MySimulationElement {
+    ///     pressure_id: VariableIdentifier,
+    ///     pressure: Pressure,
+    /// }
+    /// impl SimulationElement for MySimulationElement {
+    ///     fn read(&mut self, reader: &mut SimulatorReader) {
+    ///         self.pressure = reader.read_quantity(&self.pressure_id, psi);
+    ///     }
+    /// }
+    /// ```
+    pub fn read_quantity<T, U: VariableUnit<T>>(
+        &mut self,
+        identifier: &VariableIdentifier,
+        _unit: U,
+    ) -> T {
+        U::from_f64(
+            self.simulator_read_writer
+                .read_typed(identifier, VariableType::of_unit::<U>()),
+        )
     }
 }
 
@@ -333,36 +760,138 @@ impl<'a> SimulatorWriter<'a> {
     /// # Examples
     /// ```rust
     /// # use systems::simulation::{SimulationElement, SimulationElementVisitor,
-    /// #    SimulatorReader, SimulatorWriter};
+    /// #    SimulatorReader, SimulatorWriter, VariableIdentifier};
     /// struct MySimulationElement {
+    ///     n_id: VariableIdentifier,
     ///     n: f64,
     /// }
     /// impl SimulationElement for MySimulationElement {
     ///     fn write(&self, writer: &mut SimulatorWriter) {
-    ///        writer.write_f64("MY_SIMULATOR_ELEMENT_N", self.n);
+    ///        writer.write_f64(&self.n_id, self.n);
     ///     }
     /// }
     /// ```
-    pub fn write_f64(&mut self, name: &str, value: f64) {
-        self.simulator_read_writer.write(name, value);
+    pub fn write_f64(&mut self, identifier: &VariableIdentifier, value: f64) {
+        self.simulator_read_writer
+            .write_typed(identifier, VariableType::F64, value);
     }
 
     /// Write a `bool` to the simulator.
     /// # Examples
     /// ```rust
     /// # use systems::simulation::{SimulationElement, SimulationElementVisitor,
-    /// #    SimulatorReader, SimulatorWriter};
+    /// #    SimulatorReader, SimulatorWriter, VariableIdentifier};
     /// struct MySimulationElement {
+    ///     is_on_id: VariableIdentifier,
     ///     is_on: bool,
     /// }
     /// impl SimulationElement for MySimulationElement {
     ///     fn write(&self, writer: &mut SimulatorWriter) {
-    ///        writer.write_bool("MY_SIMULATOR_ELEMENT_IS_ON", self.is_on);
+    ///        writer.write_bool(&self.is_on_id, self.is_on);
     ///     }
     /// }
     /// ```
-    pub fn write_bool(&mut self, name: &str, value: bool) {
-        self.simulator_read_writer.write(name, from_bool(value));
+    pub fn write_bool(&mut self, identifier: &VariableIdentifier, value: bool) {
+        self.simulator_read_writer
+            .write_typed(identifier, VariableType::Bool, from_bool(value));
+    }
+
+    /// Write a quantity to the simulator in the given unit.
+    /// # Examples
+    /// ```rust
+    /// # use systems::simulation::{SimulationElement, SimulatorWriter, VariableIdentifier};
+    /// # use uom::si::{f64::*, pressure::psi};
+    /// struct MySimulationElement {
+    ///     pressure_id: VariableIdentifier,
+    ///     pressure: Pressure,
+    /// }
+    /// impl SimulationElement for MySimulationElement {
+    ///     fn write(&self, writer: &mut SimulatorWriter) {
+    ///        writer.write_quantity(&self.pressure_id, self.pressure, psi);
+    ///     }
+    /// }
+    /// ```
+    pub fn write_quantity<T, U: VariableUnit<T>>(
+        &mut self,
+        identifier: &VariableIdentifier,
+        value: T,
+        _unit: U,
+    ) {
+        self.simulator_read_writer.write_typed(
+            identifier,
+            VariableType::of_unit::<U>(),
+            U::to_f64(value),
+        );
+    }
+}
+
+/// A unit in which a quantity of type `T` is read from or written to the simulator.
+/// It is implemented for the units of the quantities exchanged with the simulator.
+pub trait VariableUnit<T>: uom::si::Unit {
+    fn from_f64(value: f64) -> T;
+    fn to_f64(quantity: T) -> f64;
+}
+
+macro_rules! variable_units {
+    ($($quantity:ident in $module:ident),* $(,)?) => {
+        $(
+            impl<U> VariableUnit<uom::si::f64::$quantity> for U
+            where
+                U: uom::si::$module::Unit + uom::Conversion<f64, T = f64>,
+            {
+                fn from_f64(value: f64) -> uom::si::f64::$quantity {
+                    uom::si::f64::$quantity::new::<U>(value)
+                }
+
+                fn to_f64(quantity: uom::si::f64::$quantity) -> f64 {
+                    quantity.get::<U>()
+                }
+            }
+        )*
+    };
+}
+
+variable_units!(
+    Acceleration in acceleration,
+    Angle in angle,
+    ElectricCharge in electric_charge,
+    ElectricCurrent in electric_current,
+    ElectricPotential in electric_potential,
+    Frequency in frequency,
+    Length in length,
+    Mass in mass,
+    Power in power,
+    Pressure in pressure,
+    Ratio in ratio,
+    ThermodynamicTemperature in thermodynamic_temperature,
+    Velocity in velocity,
+    Volume in volume,
+    VolumeRate in volume_rate,
+);
+
+/// Formats the given value as a JSON string, including the surrounding quotes.
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+/// Formats the given value as a CSV field, quoting it when it contains separators or quotes.
+fn csv_field(value: &str) -> String {
+    if value.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
     }
 }
 
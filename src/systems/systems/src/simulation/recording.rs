@@ -0,0 +1,693 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    io::{self, Read, Write},
+    time::Duration,
+};
+
+use super::{
+    Aircraft, Scheduler, Simulation, SimulationSettings, SimulatorReaderWriter, VariableIdentifier,
+    VariableRegistry,
+};
+use crate::shared::SeededRandomSource;
+
+const MAGIC: &[u8; 8] = b"A32NXREC";
+const FORMAT_VERSION: u32 = 2;
+
+const TICK_RECORD: u8 = b'T';
+const NAME_RECORD: u8 = b'N';
+const READ_RECORD: u8 = b'R';
+const WRITE_RECORD: u8 = b'W';
+
+/// Wraps a [`SimulatorReaderWriter`], recording every read and write together
+/// with the delta of the tick in which they occurred.
+///
+/// To keep the recording compact, the names of the variables in the registry are
+/// written only once at the start, and values are only recorded when they differ
+/// from the previous tick.
+/// The random numbers of the simulation can only be replayed when the recorded
+/// [`Simulation`] uses a [`SeededRandomSource`] created with the given seed.
+/// Writing to the underlying output stops at the first I/O error, which is
+/// returned by [`finish`].
+///
+/// [`SimulatorReaderWriter`]: ../trait.SimulatorReaderWriter.html
+/// [`finish`]: #method.finish
+/// [`Simulation`]: ../struct.Simulation.html
+/// [`SeededRandomSource`]: ../../shared/struct.SeededRandomSource.html
+pub struct RecordingSimulatorReaderWriter<T: SimulatorReaderWriter, U: Write> {
+    inner: T,
+    output: U,
+    error: Option<io::Error>,
+    last_read_values: Vec<Option<f64>>,
+    last_written_values: Vec<Option<f64>>,
+}
+impl<T: SimulatorReaderWriter, U: Write> RecordingSimulatorReaderWriter<T, U> {
+    /// Creates a recording of the variables registered in the given registry.
+    /// The registry should be the one with which the aircraft was created.
+    pub fn new(inner: T, mut output: U, random_seed: u64, registry: &VariableRegistry) -> Self {
+        let error = output
+            .write_all(MAGIC)
+            .and_then(|_| output.write_all(&FORMAT_VERSION.to_le_bytes()))
+            .and_then(|_| output.write_all(&random_seed.to_le_bytes()))
+            .err();
+
+        let mut recorder = Self {
+            inner,
+            output,
+            error,
+            last_read_values: vec![None; registry.len()],
+            last_written_values: vec![None; registry.len()],
+        };
+
+        for (identifier, name) in registry.iter() {
+            recorder.record_name(&identifier, name);
+        }
+
+        recorder
+    }
+
+    /// Flushes the recording and returns the output,
+    /// or the first error which occurred while recording.
+    pub fn finish(mut self) -> io::Result<U> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+
+        self.output.flush()?;
+        Ok(self.output)
+    }
+
+    fn record(&mut self, bytes: &[u8]) {
+        if self.error.is_none() {
+            self.error = self.output.write_all(bytes).err();
+        }
+    }
+
+    fn record_name(&mut self, identifier: &VariableIdentifier, name: &str) {
+        let mut bytes = vec![NAME_RECORD];
+        bytes.extend_from_slice(&(identifier.index() as u16).to_le_bytes());
+        bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(name.as_bytes());
+        self.record(&bytes);
+    }
+
+    fn record_value(&mut self, record_type: u8, identifier: &VariableIdentifier, value: f64) {
+        let mut bytes = [0; 11];
+        bytes[0] = record_type;
+        bytes[1..3].copy_from_slice(&(identifier.index() as u16).to_le_bytes());
+        bytes[3..11].copy_from_slice(&value.to_le_bytes());
+        self.record(&bytes);
+    }
+}
+impl<T: SimulatorReaderWriter, U: Write> SimulatorReaderWriter
+    for RecordingSimulatorReaderWriter<T, U>
+{
+    fn start_tick(&mut self, delta: Duration) {
+        self.inner.start_tick(delta);
+
+        let mut bytes = vec![TICK_RECORD];
+        bytes.extend_from_slice(&(delta.as_nanos() as u64).to_le_bytes());
+        self.record(&bytes);
+    }
+
+    fn read(&mut self, identifier: &VariableIdentifier) -> f64 {
+        let value = self.inner.read(identifier);
+
+        if self.last_read_values[identifier.index()] != Some(value) {
+            self.last_read_values[identifier.index()] = Some(value);
+            self.record_value(READ_RECORD, identifier, value);
+        }
+
+        value
+    }
+
+    fn write(&mut self, identifier: &VariableIdentifier, value: f64) {
+        self.inner.write(identifier, value);
+
+        if self.last_written_values[identifier.index()] != Some(value) {
+            self.last_written_values[identifier.index()] = Some(value);
+            self.record_value(WRITE_RECORD, identifier, value);
+        }
+    }
+}
+
+/// A single tick of a [`Recording`]. Contains the variables
+/// which changed since the previous tick.
+///
+/// [`Recording`]: struct.Recording.html
+#[derive(Debug, PartialEq)]
+pub struct RecordedTick {
+    delta: Duration,
+    reads: Vec<(String, f64)>,
+    writes: Vec<(String, f64)>,
+}
+impl RecordedTick {
+    fn new(delta: Duration) -> Self {
+        Self {
+            delta,
+            reads: Vec::new(),
+            writes: Vec::new(),
+        }
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+}
+
+/// A recording of simulator reads and writes, as created by [`RecordingSimulatorReaderWriter`].
+///
+/// [`RecordingSimulatorReaderWriter`]: struct.RecordingSimulatorReaderWriter.html
+#[derive(Debug, PartialEq)]
+pub struct Recording {
+    random_seed: u64,
+    ticks: Vec<RecordedTick>,
+}
+impl Recording {
+    pub fn read_from<T: Read>(mut reader: T) -> Result<Self, RecordingError> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(RecordingError::MissingHeader);
+        }
+
+        let version = u32::from_le_bytes(read_array(&mut reader)?);
+        if version != FORMAT_VERSION {
+            return Err(RecordingError::UnsupportedVersion(version));
+        }
+
+        let random_seed = u64::from_le_bytes(read_array(&mut reader)?);
+
+        let mut names: Vec<String> = Vec::new();
+        let mut ticks: Vec<RecordedTick> = Vec::new();
+        loop {
+            let mut record_type = [0; 1];
+            if reader.read(&mut record_type)? == 0 {
+                break;
+            }
+
+            match record_type[0] {
+                TICK_RECORD => {
+                    let nanos = u64::from_le_bytes(read_array(&mut reader)?);
+                    ticks.push(RecordedTick::new(Duration::from_nanos(nanos)));
+                }
+                NAME_RECORD => {
+                    let id = u16::from_le_bytes(read_array(&mut reader)?) as usize;
+                    let length = u16::from_le_bytes(read_array(&mut reader)?) as usize;
+                    let mut name = vec![0; length];
+                    reader.read_exact(&mut name)?;
+
+                    if id != names.len() {
+                        return Err(RecordingError::InvalidRecord);
+                    }
+                    names.push(String::from_utf8(name).map_err(|_| RecordingError::InvalidRecord)?);
+                }
+                READ_RECORD | WRITE_RECORD => {
+                    let id = u16::from_le_bytes(read_array(&mut reader)?) as usize;
+                    let value = f64::from_le_bytes(read_array(&mut reader)?);
+
+                    let name = names.get(id).ok_or(RecordingError::InvalidRecord)?.clone();
+                    let tick = ticks.last_mut().ok_or(RecordingError::InvalidRecord)?;
+                    if record_type[0] == READ_RECORD {
+                        tick.reads.push((name, value));
+                    } else {
+                        tick.writes.push((name, value));
+                    }
+                }
+                _ => return Err(RecordingError::InvalidRecord),
+            }
+        }
+
+        Ok(Self { random_seed, ticks })
+    }
+
+    pub fn random_seed(&self) -> u64 {
+        self.random_seed
+    }
+
+    pub fn ticks(&self) -> &[RecordedTick] {
+        &self.ticks
+    }
+}
+
+fn read_array<T: Read, const N: usize>(reader: &mut T) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(io::Error),
+    MissingHeader,
+    UnsupportedVersion(u32),
+    InvalidRecord,
+}
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingError::Io(error) => write!(f, "Failed to read recording: {}", error),
+            RecordingError::MissingHeader => write!(f, "The recording header is missing."),
+            RecordingError::UnsupportedVersion(version) => write!(
+                f,
+                "Recording version {} is not the supported version {}.",
+                version, FORMAT_VERSION
+            ),
+            RecordingError::InvalidRecord => write!(f, "The recording contains an invalid record."),
+        }
+    }
+}
+impl Error for RecordingError {}
+impl From<io::Error> for RecordingError {
+    fn from(error: io::Error) -> Self {
+        RecordingError::Io(error)
+    }
+}
+
+/// A write which differs between the recording and the replay.
+/// `None` indicates the variable wasn't written.
+#[derive(Debug, PartialEq)]
+pub struct ReplayDifference {
+    pub tick: usize,
+    pub name: String,
+    pub recorded: Option<f64>,
+    pub replayed: Option<f64>,
+}
+impl fmt::Display for ReplayDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Tick {}: {} recorded {:?}, replayed {:?}",
+            self.tick, self.name, self.recorded, self.replayed
+        )
+    }
+}
+
+/// Provides the recorded reads to the aircraft and collects its writes during a replay.
+struct ReplaySimulatorReaderWriter {
+    values: HashMap<VariableIdentifier, f64>,
+    writes: HashMap<VariableIdentifier, f64>,
+}
+impl ReplaySimulatorReaderWriter {
+    fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+            writes: HashMap::new(),
+        }
+    }
+}
+impl SimulatorReaderWriter for ReplaySimulatorReaderWriter {
+    fn read(&mut self, identifier: &VariableIdentifier) -> f64 {
+        *self.values.get(identifier).unwrap_or(&0.)
+    }
+
+    fn write(&mut self, identifier: &VariableIdentifier, value: f64) {
+        self.writes.insert(*identifier, value);
+    }
+}
+
+/// Feeds the recorded reads into the given aircraft tick by tick, and returns
+/// the writes which differ from those found in the recording.
+/// The aircraft should be in the same state as the recorded aircraft was when
+/// the recording started, which usually means it is newly created. The registry
+/// should be the one with which the aircraft was created. Recorded reads of
+/// variables unknown to the registry are ignored.
+pub fn replay<T: Aircraft>(
+    aircraft: &mut T,
+    registry: &VariableRegistry,
+    recording: &Recording,
+) -> Vec<ReplayDifference> {
+    replay_with_settings(aircraft, registry, recording, SimulationSettings::default())
+}
+
+/// Replays the recording like [`replay`], using the given [`SimulationSettings`].
+/// These should be equal to the settings of the recorded [`Simulation`].
+///
+/// [`replay`]: fn.replay.html
+/// [`SimulationSettings`]: ../struct.SimulationSettings.html
+/// [`Simulation`]: ../struct.Simulation.html
+pub fn replay_with_settings<T: Aircraft>(
+    aircraft: &mut T,
+    registry: &VariableRegistry,
+    recording: &Recording,
+    settings: SimulationSettings,
+) -> Vec<ReplayDifference> {
+    let mut reader_writer = ReplaySimulatorReaderWriter::new();
+    let mut random_source = SeededRandomSource::new(recording.random_seed());
+    let mut scheduler = Scheduler::new();
+    let mut recorded_writes: HashMap<String, f64> = HashMap::new();
+    let mut differences = Vec::new();
+
+    for (index, tick) in recording.ticks().iter().enumerate() {
+        for (name, value) in &tick.reads {
+            if let Some(identifier) = registry.find(name) {
+                reader_writer.values.insert(identifier, *value);
+            }
+        }
+        for (name, value) in &tick.writes {
+            recorded_writes.insert(name.clone(), *value);
+        }
+
+        reader_writer.writes.clear();
+        Simulation::new_with_settings(
+            aircraft,
+            &mut reader_writer,
+            &mut random_source,
+            &mut scheduler,
+            settings,
+        )
+        .tick(tick.delta);
+
+        // A paused tick doesn't write anything, thus there's nothing to compare.
+        if settings.is_paused(tick.delta) {
+            continue;
+        }
+
+        for (identifier, replayed) in &reader_writer.writes {
+            let name = registry.name(identifier);
+            let recorded = recorded_writes.get(name).copied();
+            if recorded != Some(*replayed) {
+                differences.push(ReplayDifference {
+                    tick: index,
+                    name: name.to_owned(),
+                    recorded,
+                    replayed: Some(*replayed),
+                });
+            }
+        }
+        for (name, recorded) in &recorded_writes {
+            let is_replayed = match registry.find(name) {
+                Some(identifier) => reader_writer.writes.contains_key(&identifier),
+                None => false,
+            };
+            if !is_replayed {
+                differences.push(ReplayDifference {
+                    tick: index,
+                    name: name.clone(),
+                    recorded: Some(*recorded),
+                    replayed: None,
+                });
+            }
+        }
+    }
+
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{SimulationElement, SimulatorReader, SimulatorWriter, UpdateContext};
+
+    struct TestReaderWriter {
+        values: HashMap<VariableIdentifier, f64>,
+    }
+    impl TestReaderWriter {
+        fn new() -> Self {
+            Self {
+                values: HashMap::new(),
+            }
+        }
+    }
+    impl SimulatorReaderWriter for TestReaderWriter {
+        fn read(&mut self, identifier: &VariableIdentifier) -> f64 {
+            *self.values.get(identifier).unwrap_or(&0.)
+        }
+
+        fn write(&mut self, identifier: &VariableIdentifier, value: f64) {
+            self.values.insert(*identifier, value);
+        }
+    }
+
+    /// Integrates the input over time, such that replaying with
+    /// a different delta or input results in different writes.
+    struct TestAircraft {
+        input_id: VariableIdentifier,
+        total_id: VariableIdentifier,
+        random_id: VariableIdentifier,
+
+        input: f64,
+        total: f64,
+        multiplier: f64,
+        random: u8,
+    }
+    impl TestAircraft {
+        fn new(registry: &mut VariableRegistry) -> Self {
+            Self::new_with_multiplier(registry, 1.)
+        }
+
+        fn new_with_multiplier(registry: &mut VariableRegistry, multiplier: f64) -> Self {
+            Self {
+                input_id: registry.get("INPUT"),
+                total_id: registry.get("TOTAL"),
+                random_id: registry.get("RANDOM"),
+                input: 0.,
+                total: 0.,
+                multiplier,
+                random: 0,
+            }
+        }
+    }
+    impl Aircraft for TestAircraft {
+        fn update_before_power_distribution(&mut self, context: &UpdateContext) {
+            self.total += self.input * self.multiplier * context.delta_as_secs_f64();
+            self.random = context.random_number();
+        }
+    }
+    impl SimulationElement for TestAircraft {
+        fn read(&mut self, reader: &mut SimulatorReader) {
+            self.input = reader.read_f64(&self.input_id);
+        }
+
+        fn write(&self, writer: &mut SimulatorWriter) {
+            writer.write_f64(&self.total_id, self.total);
+            writer.write_f64(&self.random_id, self.random as f64);
+        }
+    }
+
+    const RANDOM_SEED: u64 = 42;
+
+    fn record(inputs: &[f64]) -> Vec<u8> {
+        let ticks: Vec<(f64, Duration)> = inputs
+            .iter()
+            .map(|input| (*input, Duration::from_millis(500)))
+            .collect();
+
+        record_with_settings(&ticks, SimulationSettings::default())
+    }
+
+    fn record_with_settings(ticks: &[(f64, Duration)], settings: SimulationSettings) -> Vec<u8> {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let mut reader_writer = RecordingSimulatorReaderWriter::new(
+            TestReaderWriter::new(),
+            Vec::new(),
+            RANDOM_SEED,
+            &registry,
+        );
+        let mut random_source = SeededRandomSource::new(RANDOM_SEED);
+        let mut scheduler = Scheduler::new();
+
+        for (input, delta) in ticks {
+            reader_writer.inner.values.insert(aircraft.input_id, *input);
+            Simulation::new_with_settings(
+                &mut aircraft,
+                &mut reader_writer,
+                &mut random_source,
+                &mut scheduler,
+                settings,
+            )
+            .tick(*delta);
+        }
+
+        reader_writer.finish().unwrap()
+    }
+
+    fn recording(inputs: &[f64]) -> Recording {
+        Recording::read_from(record(inputs).as_slice()).unwrap()
+    }
+
+    #[test]
+    fn records_tick_delta() {
+        let recording = recording(&[1., 1.]);
+
+        assert_eq!(recording.ticks().len(), 2);
+        assert_eq!(recording.ticks()[0].delta(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn records_only_changed_values() {
+        let recording = recording(&[1., 1., 0.]);
+
+        let input_reads: Vec<usize> = recording
+            .ticks()
+            .iter()
+            .map(|tick| occurrences(&tick.reads, "INPUT"))
+            .collect();
+        let total_writes: Vec<usize> = recording
+            .ticks()
+            .iter()
+            .map(|tick| occurrences(&tick.writes, "TOTAL"))
+            .collect();
+        assert_eq!(input_reads, vec![1, 0, 1]);
+        assert_eq!(total_writes, vec![1, 1, 0]);
+    }
+
+    fn occurrences(values: &[(String, f64)], name: &str) -> usize {
+        values.iter().filter(|(n, _)| n == name).count()
+    }
+
+    #[test]
+    fn records_random_seed() {
+        assert_eq!(recording(&[1.]).random_seed(), RANDOM_SEED);
+    }
+
+    #[test]
+    fn records_writes() {
+        let recording = recording(&[2.]);
+
+        assert!(recording.ticks()[0]
+            .writes
+            .contains(&("TOTAL".to_owned(), 1.)));
+    }
+
+    #[test]
+    fn passes_reads_and_writes_through_to_inner_reader_writer() {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let mut inner = TestReaderWriter::new();
+        inner.values.insert(aircraft.input_id, 4.);
+        let mut reader_writer =
+            RecordingSimulatorReaderWriter::new(inner, Vec::new(), 0, &registry);
+
+        Simulation::new(
+            &mut aircraft,
+            &mut reader_writer,
+            &mut SeededRandomSource::default(),
+            &mut Scheduler::new(),
+        )
+        .tick(Duration::from_secs(1));
+
+        assert!((reader_writer.inner.values[&aircraft.total_id] - 4.).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn replay_of_same_aircraft_has_no_differences() {
+        let recording = recording(&[1., 2., 2., 0., 5.]);
+
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+
+        let differences = replay(&mut aircraft, &registry, &recording);
+
+        assert_eq!(differences, vec![]);
+    }
+
+    #[test]
+    fn replay_of_recording_with_paused_tick_has_no_differences() {
+        let settings = SimulationSettings::new().with_pause_detection();
+        let bytes = record_with_settings(
+            &[
+                (1., Duration::from_millis(500)),
+                (2., Duration::from_secs(0)),
+                (2., Duration::from_millis(500)),
+            ],
+            settings,
+        );
+        let recording = Recording::read_from(bytes.as_slice()).unwrap();
+
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+
+        let differences = replay_with_settings(&mut aircraft, &registry, &recording, settings);
+
+        assert_eq!(recording.ticks().len(), 3);
+        assert_eq!(differences, vec![]);
+    }
+
+    #[test]
+    fn replay_of_changed_aircraft_has_differences() {
+        let recording = recording(&[1., 2.]);
+
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new_with_multiplier(&mut registry, 2.);
+
+        let differences = replay(&mut aircraft, &registry, &recording);
+
+        assert_eq!(
+            differences,
+            vec![
+                ReplayDifference {
+                    tick: 0,
+                    name: "TOTAL".to_owned(),
+                    recorded: Some(0.5),
+                    replayed: Some(1.),
+                },
+                ReplayDifference {
+                    tick: 1,
+                    name: "TOTAL".to_owned(),
+                    recorded: Some(1.5),
+                    replayed: Some(3.),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn reading_without_header_fails() {
+        assert!(matches!(
+            Recording::read_from(&b"NOTAREC\0\x01\0\0\0"[..]),
+            Err(RecordingError::MissingHeader)
+        ));
+    }
+
+    #[test]
+    fn reading_newer_version_fails() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+
+        assert!(matches!(
+            Recording::read_from(bytes.as_slice()),
+            Err(RecordingError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn reading_older_version_fails() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&(FORMAT_VERSION - 1).to_le_bytes());
+        bytes.extend_from_slice(&RANDOM_SEED.to_le_bytes());
+
+        assert!(matches!(
+            Recording::read_from(bytes.as_slice()),
+            Err(RecordingError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn reading_value_before_tick_fails() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&RANDOM_SEED.to_le_bytes());
+        bytes.extend_from_slice(&[NAME_RECORD, 0, 0, 1, 0, b'A']);
+        bytes.extend_from_slice(&[READ_RECORD, 0, 0]);
+        bytes.extend_from_slice(&1_f64.to_le_bytes());
+
+        assert!(matches!(
+            Recording::read_from(bytes.as_slice()),
+            Err(RecordingError::InvalidRecord)
+        ));
+    }
+
+    #[test]
+    fn reading_truncated_recording_fails() {
+        let bytes = record(&[1.]);
+
+        assert!(matches!(
+            Recording::read_from(&bytes[..bytes.len() - 1]),
+            Err(RecordingError::Io(_))
+        ));
+    }
+}
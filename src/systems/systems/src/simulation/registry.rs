@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use super::UpdateContext;
+
+/// Identifies a simulator variable which has been registered with a [`VariableRegistry`].
+/// Reading and writing a variable through its identifier avoids formatting and
+/// hashing its name every tick.
+///
+/// [`VariableRegistry`]: struct.VariableRegistry.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VariableIdentifier(usize);
+impl VariableIdentifier {
+    pub(super) const fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// The position of the variable within the registry, starting at zero.
+    /// Useful for storing variable data in a `Vec`.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// Interns the names of the simulator variables which are read and written by
+/// the aircraft. Elements register their variables once when they are created,
+/// and use the returned [`VariableIdentifier`] from then on. Once the aircraft
+/// is created, the registry contains the full list of variables it uses.
+///
+/// # Examples
+/// ```rust
+/// # use systems::simulation::{SimulationElement, SimulatorReader, VariableIdentifier,
+/// #     VariableRegistry};
+/// struct MySimulationElement {
+///     is_on_id: VariableIdentifier,
+///     is_on: bool,
+/// }
+/// impl MySimulationElement {
+///     fn new(registry: &mut VariableRegistry, name: &str) -> Self {
+///         Self {
+///             is_on_id: registry.get(&format!("MY_{}_IS_ON", name)),
+///             is_on: false,
+///         }
+///     }
+/// }
+/// impl SimulationElement for MySimulationElement {
+///     fn read(&mut self, reader: &mut SimulatorReader) {
+///         self.is_on = reader.read_bool(&self.is_on_id);
+///     }
+/// }
+/// ```
+/// [`VariableIdentifier`]: struct.VariableIdentifier.html
+#[derive(Clone, Debug)]
+pub struct VariableRegistry {
+    identifiers: HashMap<String, VariableIdentifier>,
+    names: Vec<String>,
+}
+impl VariableRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            identifiers: HashMap::new(),
+            names: Vec::new(),
+        };
+
+        // The variables of the update context are registered first, such that the
+        // simulation can read them without having access to the registry.
+        for name in UpdateContext::VARIABLE_NAMES.iter() {
+            registry.get(name);
+        }
+
+        registry
+    }
+
+    /// Returns the identifier of the variable with the given name,
+    /// registering the variable when it is unknown.
+    pub fn get(&mut self, name: &str) -> VariableIdentifier {
+        if let Some(identifier) = self.identifiers.get(name) {
+            return *identifier;
+        }
+
+        let identifier = VariableIdentifier(self.names.len());
+        self.identifiers.insert(name.to_owned(), identifier);
+        self.names.push(name.to_owned());
+
+        identifier
+    }
+
+    /// Returns the identifier of the variable with the given name,
+    /// or `None` when the variable isn't registered.
+    pub fn find(&self, name: &str) -> Option<VariableIdentifier> {
+        self.identifiers.get(name).copied()
+    }
+
+    pub fn name(&self, identifier: &VariableIdentifier) -> &str {
+        &self.names[identifier.0]
+    }
+
+    /// Iterates over all registered variables in the order of their identifiers.
+    pub fn iter(&self) -> impl Iterator<Item = (VariableIdentifier, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (VariableIdentifier(index), name.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+impl Default for VariableRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registering_a_name_twice_returns_the_same_identifier() {
+        let mut registry = VariableRegistry::new();
+
+        let first = registry.get("TEST_VARIABLE");
+        let second = registry.get("TEST_VARIABLE");
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn different_names_get_different_identifiers() {
+        let mut registry = VariableRegistry::new();
+
+        let first = registry.get("TEST_VARIABLE_1");
+        let second = registry.get("TEST_VARIABLE_2");
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn identifiers_are_consecutive() {
+        let mut registry = VariableRegistry::new();
+        let length = registry.len();
+
+        let identifier = registry.get("TEST_VARIABLE");
+
+        assert_eq!(identifier.index(), length);
+        assert_eq!(registry.len(), length + 1);
+    }
+
+    #[test]
+    fn provides_name_of_identifier() {
+        let mut registry = VariableRegistry::new();
+
+        let identifier = registry.get("TEST_VARIABLE");
+
+        assert_eq!(registry.name(&identifier), "TEST_VARIABLE");
+    }
+
+    #[test]
+    fn find_doesnt_register_unknown_names() {
+        let mut registry = VariableRegistry::new();
+        let length = registry.len();
+
+        assert_eq!(registry.find("TEST_VARIABLE"), None);
+        assert_eq!(registry.len(), length);
+
+        let identifier = registry.get("TEST_VARIABLE");
+        assert_eq!(registry.find("TEST_VARIABLE"), Some(identifier));
+    }
+
+    #[test]
+    fn update_context_variables_are_registered_up_front() {
+        let registry = VariableRegistry::new();
+
+        for (index, name) in UpdateContext::VARIABLE_NAMES.iter().enumerate() {
+            assert_eq!(registry.find(name).map(|id| id.index()), Some(index));
+        }
+    }
+}
@@ -0,0 +1,805 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead, Write},
+    time::Duration,
+};
+
+use super::{
+    csv_field, from_bool, Aircraft, EventBus, InvariantMonitor, InvariantViolationAction,
+    Scheduler, Simulation, SimulationSettings, SimulationToSimulatorVisitor, SimulatorReaderWriter,
+    SimulatorWriter, StateDump, UpdateContext, VariableIdentifier, VariableRegistry,
+};
+use crate::shared::SeededRandomSource;
+
+/// Sets a simulator variable to the given value once the given time has passed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScenarioAction {
+    time: Duration,
+    name: String,
+    value: f64,
+}
+impl ScenarioAction {
+    pub fn new(time: Duration, name: &str, value: f64) -> Self {
+        Self {
+            time,
+            name: name.to_owned(),
+            value,
+        }
+    }
+
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+/// A list of timed simulator variable writes, such as the steps of a cockpit procedure,
+/// which can be run against an aircraft by a [`ScenarioRunner`].
+///
+/// A scenario file contains one action per line:
+/// ```text
+/// # Comments and empty lines are ignored.
+/// at t=0s set OVHD_ELEC_BAT_1_PB_IS_AUTO=1
+/// at 500ms set OVHD_ELEC_BAT_2_PB_IS_AUTO=true
+/// at 1.5min set SIM ON GROUND=0
+/// ```
+/// Times are given in `ms`, `s` or `min`, and are in seconds without a unit.
+/// Values are numbers, or `true` and `false`.
+///
+/// [`ScenarioRunner`]: struct.ScenarioRunner.html
+#[derive(Debug, Default, PartialEq)]
+pub struct Scenario {
+    actions: Vec<ScenarioAction>,
+}
+impl Scenario {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read_from<T: BufRead>(reader: T) -> Result<Self, ScenarioError> {
+        let mut scenario = Self::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let action =
+                parse_action(trimmed).ok_or(ScenarioError::InvalidLine(index + 1, line.clone()))?;
+            scenario.add(action);
+        }
+
+        Ok(scenario)
+    }
+
+    /// Adds the action, keeping the actions ordered by time.
+    /// Actions with the same time are executed in the order they were added.
+    pub fn add(&mut self, action: ScenarioAction) {
+        let position = self
+            .actions
+            .iter()
+            .position(|existing| existing.time > action.time)
+            .unwrap_or(self.actions.len());
+        self.actions.insert(position, action);
+    }
+
+    pub fn actions(&self) -> &[ScenarioAction] {
+        &self.actions
+    }
+}
+
+fn parse_action(line: &str) -> Option<ScenarioAction> {
+    let line = line.strip_prefix("at ")?.trim_start();
+    let (time, assignment) = line.split_once(" set ")?;
+    let (name, value) = assignment.rsplit_once('=')?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(ScenarioAction::new(
+        parse_time(time.trim())?,
+        name,
+        parse_value(value.trim())?,
+    ))
+}
+
+fn parse_time(time: &str) -> Option<Duration> {
+    let time = time.strip_prefix("t=").unwrap_or(time);
+    let (number, seconds_per_unit) = if let Some(number) = time.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = time.strip_suffix("min") {
+        (number, 60.)
+    } else if let Some(number) = time.strip_suffix('s') {
+        (number, 1.)
+    } else {
+        (time, 1.)
+    };
+
+    let seconds = number.trim().parse::<f64>().ok()? * seconds_per_unit;
+    if seconds.is_finite() && seconds >= 0. {
+        Some(Duration::from_secs_f64(seconds))
+    } else {
+        None
+    }
+}
+
+fn parse_value(value: &str) -> Option<f64> {
+    match value {
+        "true" => Some(from_bool(true)),
+        "false" => Some(from_bool(false)),
+        _ => value.parse().ok(),
+    }
+}
+
+#[derive(Debug)]
+pub enum ScenarioError {
+    Io(io::Error),
+    /// The line with the given number and content isn't a valid action.
+    InvalidLine(usize, String),
+    /// The variable isn't read or written by the aircraft.
+    UnknownVariable(String),
+}
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioError::Io(error) => write!(f, "Failed to run scenario: {}", error),
+            ScenarioError::InvalidLine(number, line) => {
+                write!(f, "Line {} of the scenario is invalid: {}", number, line)
+            }
+            ScenarioError::UnknownVariable(name) => {
+                write!(f, "The variable {} isn't used by the aircraft.", name)
+            }
+        }
+    }
+}
+impl Error for ScenarioError {}
+impl From<io::Error> for ScenarioError {
+    fn from(error: io::Error) -> Self {
+        ScenarioError::Io(error)
+    }
+}
+
+/// Runs an aircraft without the simulator, by executing the actions of a [`Scenario`]
+/// and writing the values of the selected variables after every tick as CSV.
+///
+/// The variables live in memory, and start out with the values written by the newly
+/// created aircraft. The aircraft starts on the ground at sea level on a standard day;
+/// a scenario can change this by setting the variables of the [`UpdateContext`] at
+/// `t=0s`.
+///
+/// # Examples
+/// ```rust
+/// # use std::time::Duration;
+/// # use systems::simulation::{Aircraft, Scenario, ScenarioAction, ScenarioRunner,
+/// #     SimulationElement, SimulatorReader, SimulatorWriter, VariableIdentifier,
+/// #     VariableRegistry};
+/// # struct MyAircraft {
+/// #     is_on_id: VariableIdentifier,
+/// #     is_lit_id: VariableIdentifier,
+/// #     is_on: bool,
+/// # }
+/// # impl Aircraft for MyAircraft {}
+/// # impl SimulationElement for MyAircraft {
+/// #     fn read(&mut self, reader: &mut SimulatorReader) {
+/// #         self.is_on = reader.read_bool(&self.is_on_id);
+/// #     }
+/// #     fn write(&self, writer: &mut SimulatorWriter) {
+/// #         writer.write_bool(&self.is_lit_id, self.is_on);
+/// #     }
+/// # }
+/// let mut registry = VariableRegistry::new();
+/// let mut aircraft = MyAircraft {
+///     is_on_id: registry.get("MY_PB_IS_ON"),
+///     is_lit_id: registry.get("MY_LIGHT_IS_LIT"),
+///     is_on: false,
+/// };
+///
+/// let mut scenario = Scenario::new();
+/// scenario.add(ScenarioAction::new(Duration::from_secs(1), "MY_PB_IS_ON", 1.));
+///
+/// let mut csv = Vec::new();
+/// ScenarioRunner::new(&mut aircraft, &registry)
+///     .with_step(Duration::from_secs(1))
+///     .run(&scenario, Duration::from_secs(2), &["MY_LIGHT_IS_LIT"], &mut csv)
+///     .unwrap();
+///
+/// assert_eq!(String::from_utf8(csv).unwrap(), "time,MY_LIGHT_IS_LIT\n1,0\n2,1\n");
+/// ```
+/// [`Scenario`]: struct.Scenario.html
+/// [`UpdateContext`]: struct.UpdateContext.html
+pub struct ScenarioRunner<'a, T: Aircraft> {
+    aircraft: &'a mut T,
+    registry: &'a VariableRegistry,
+    reader_writer: MemorySimulatorReaderWriter,
+    random_source: SeededRandomSource,
+    scheduler: Scheduler,
+    settings: SimulationSettings,
+    step: Duration,
+    time: Duration,
+    invariant_monitor: InvariantMonitor,
+    event_bus: EventBus,
+    ticks: usize,
+    dump_interval: Option<usize>,
+    dumps: Vec<(Duration, StateDump)>,
+}
+impl<'a, T: Aircraft> ScenarioRunner<'a, T> {
+    const DEFAULT_STEP: Duration = Duration::from_millis(50);
+
+    /// Creates a runner for the given aircraft, which has been created with the given registry.
+    pub fn new(aircraft: &'a mut T, registry: &'a VariableRegistry) -> Self {
+        let mut reader_writer = MemorySimulatorReaderWriter::new(registry);
+
+        let mut writer = SimulatorWriter::new(&mut reader_writer);
+        let mut visitor = SimulationToSimulatorVisitor::new(&mut writer);
+        aircraft.accept(&mut visitor);
+
+        for (name, value) in [
+            (UpdateContext::IS_ON_GROUND_KEY, from_bool(true)),
+            (UpdateContext::AMBIENT_TEMPERATURE_KEY, 15.),
+            (UpdateContext::AMBIENT_PRESSURE_KEY, 29.92),
+        ]
+        .iter()
+        {
+            if let Some(identifier) = registry.find(name) {
+                reader_writer.write(&identifier, *value);
+            }
+        }
+
+        Self {
+            aircraft,
+            registry,
+            reader_writer,
+            random_source: SeededRandomSource::new(0),
+            scheduler: Scheduler::new(),
+            settings: SimulationSettings::default(),
+            step: Self::DEFAULT_STEP,
+            time: Duration::from_secs(0),
+            invariant_monitor: InvariantMonitor::new(InvariantViolationAction::Count),
+            event_bus: EventBus::new(),
+            ticks: 0,
+            dump_interval: None,
+            dumps: Vec::new(),
+        }
+    }
+
+    pub fn with_settings(mut self, settings: SimulationSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Sets the delta of every tick, which defaults to 50 ms.
+    pub fn with_step(mut self, step: Duration) -> Self {
+        assert!(step > Duration::from_secs(0));
+        self.step = step;
+        self
+    }
+
+    pub fn with_random_seed(mut self, seed: u64) -> Self {
+        self.random_source = SeededRandomSource::new(seed);
+        self
+    }
+
+    /// Sets what happens when an invariant is violated, which defaults to counting it.
+    pub fn with_invariant_action(mut self, action: InvariantViolationAction) -> Self {
+        self.invariant_monitor = InvariantMonitor::new(action);
+        self
+    }
+
+    /// Takes a [`StateDump`] of the aircraft after every given number of ticks, for following
+    /// how the internal state changes during the runs. Without an interval, no dumps are taken.
+    ///
+    /// [`StateDump`]: struct.StateDump.html
+    pub fn with_dump_interval(mut self, ticks: usize) -> Self {
+        assert!(ticks > 0);
+        self.dump_interval = Some(ticks);
+        self
+    }
+
+    /// The monitor which checked the aircraft's invariants during the runs so far.
+    pub fn invariant_monitor(&self) -> &InvariantMonitor {
+        &self.invariant_monitor
+    }
+
+    /// The events emitted by the aircraft during the runs so far.
+    pub fn events(&self) -> &EventBus {
+        &self.event_bus
+    }
+
+    /// The state dumps taken during the runs so far, together with the time at which
+    /// they were taken. See [`with_dump_interval`].
+    ///
+    /// [`with_dump_interval`]: #method.with_dump_interval
+    pub fn dumps(&self) -> &[(Duration, StateDump)] {
+        &self.dumps
+    }
+
+    /// Writes the state dumps taken during the runs so far as a JSON array,
+    /// containing an object with the `time` in seconds and the `state` of every dump.
+    pub fn write_dumps_json<U: Write>(&self, writer: &mut U) -> io::Result<()> {
+        writeln!(writer, "[")?;
+        for (index, (time, dump)) in self.dumps.iter().enumerate() {
+            writeln!(writer, "  {{")?;
+            writeln!(writer, "    \"time\": {},", time.as_secs_f64())?;
+            write!(writer, "    \"state\": ")?;
+            dump.write_json_object(writer, 2)?;
+            writeln!(writer)?;
+            writeln!(
+                writer,
+                "  }}{}",
+                if index + 1 < self.dumps.len() {
+                    ","
+                } else {
+                    ""
+                }
+            )?;
+        }
+        writeln!(writer, "]")
+    }
+
+    /// The simulated time which passed since the runner was created.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// Runs the scenario for the given duration, writing the time in seconds and the values
+    /// of the given output variables to the CSV after every tick. Actions are executed before
+    /// the first tick starting at or after their time. The scenario's time starts when the
+    /// runner is created, thus running a scenario in multiple parts continues where the
+    /// previous run ended.
+    pub fn run<U: Write>(
+        &mut self,
+        scenario: &Scenario,
+        duration: Duration,
+        outputs: &[&str],
+        csv: &mut U,
+    ) -> Result<(), ScenarioError> {
+        let actions = scenario
+            .actions()
+            .iter()
+            .map(|action| Ok((self.identifier(action.name())?, action)))
+            .collect::<Result<Vec<_>, ScenarioError>>()?;
+        let outputs = outputs
+            .iter()
+            .map(|name| self.identifier(name))
+            .collect::<Result<Vec<_>, ScenarioError>>()?;
+
+        write!(csv, "time")?;
+        for identifier in &outputs {
+            write!(csv, ",{}", csv_field(self.registry.name(identifier)))?;
+        }
+        writeln!(csv)?;
+
+        let start = self.time;
+        let end = start + duration;
+        let mut pending = actions
+            .iter()
+            .skip_while(|(_, action)| action.time() < start)
+            .peekable();
+        while self.time < end {
+            while let Some((identifier, action)) =
+                pending.next_if(|(_, action)| action.time() <= self.time)
+            {
+                self.reader_writer.write(identifier, action.value());
+            }
+
+            let delta = self.step.min(end - self.time);
+            Simulation::new_with_settings(
+                self.aircraft,
+                &mut self.reader_writer,
+                &mut self.random_source,
+                &mut self.scheduler,
+                self.settings,
+            )
+            .with_invariant_monitor(&mut self.invariant_monitor)
+            .with_event_bus(&mut self.event_bus)
+            .tick(delta);
+            self.time += delta;
+            self.ticks += 1;
+
+            if matches!(self.dump_interval, Some(interval) if self.ticks.is_multiple_of(interval)) {
+                self.dumps.push((self.time, StateDump::take(self.aircraft)));
+            }
+
+            write!(csv, "{}", self.time.as_secs_f64())?;
+            for identifier in &outputs {
+                write!(csv, ",{}", self.reader_writer.read(identifier))?;
+            }
+            writeln!(csv)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the current value of the variable with the given name.
+    pub fn read(&mut self, name: &str) -> Option<f64> {
+        let identifier = self.registry.find(name)?;
+        Some(self.reader_writer.read(&identifier))
+    }
+
+    /// Takes a [`StateDump`] of the aircraft's internal state.
+    ///
+    /// [`StateDump`]: struct.StateDump.html
+    pub fn dump_state(&mut self) -> StateDump {
+        StateDump::take(self.aircraft)
+    }
+
+    fn identifier(&self, name: &str) -> Result<VariableIdentifier, ScenarioError> {
+        self.registry
+            .find(name)
+            .ok_or_else(|| ScenarioError::UnknownVariable(name.to_owned()))
+    }
+}
+
+/// Keeps the value of every variable in memory, in place of the simulator.
+struct MemorySimulatorReaderWriter {
+    values: Vec<f64>,
+}
+impl MemorySimulatorReaderWriter {
+    fn new(registry: &VariableRegistry) -> Self {
+        Self {
+            values: vec![0.; registry.len()],
+        }
+    }
+}
+impl SimulatorReaderWriter for MemorySimulatorReaderWriter {
+    fn read(&mut self, identifier: &VariableIdentifier) -> f64 {
+        self.values[identifier.index()]
+    }
+
+    fn write(&mut self, identifier: &VariableIdentifier, value: f64) {
+        self.values[identifier.index()] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{
+        ChangeDetector, EventEmitter, EventKind, InvariantChecker, SimulationElement,
+        SimulatorReader,
+    };
+
+    struct TestAircraft {
+        switch_id: VariableIdentifier,
+        position_id: VariableIdentifier,
+        switch: bool,
+        switch_change: ChangeDetector<bool>,
+        position: f64,
+    }
+    impl TestAircraft {
+        fn new(registry: &mut VariableRegistry) -> Self {
+            Self {
+                switch_id: registry.get("TEST_SWITCH"),
+                position_id: registry.get("TEST_POSITION"),
+                switch: false,
+                switch_change: ChangeDetector::new(false),
+                position: 0.,
+            }
+        }
+    }
+    impl Aircraft for TestAircraft {
+        fn update_before_power_distribution(&mut self, context: &UpdateContext) {
+            if self.switch {
+                self.position += context.delta_as_secs_f64();
+            }
+        }
+    }
+    impl SimulationElement for TestAircraft {
+        fn read(&mut self, reader: &mut SimulatorReader) {
+            self.switch = reader.read_bool(&self.switch_id);
+        }
+
+        fn write(&self, writer: &mut SimulatorWriter) {
+            writer.write_f64(&self.position_id, self.position);
+        }
+
+        fn check_invariants(&self, invariants: &mut InvariantChecker) {
+            invariants.check("position at most 2", self.position <= 2.);
+        }
+
+        fn dump(&self, state: &mut StateDump) {
+            state.node("TEST").write_f64("POSITION", self.position);
+        }
+
+        fn emit_events(&mut self, events: &mut EventEmitter) {
+            if self.switch_change.detect(self.switch).is_some() {
+                events.emit(
+                    "TEST/SWITCH",
+                    if self.switch {
+                        EventKind::Activated
+                    } else {
+                        EventKind::Deactivated
+                    },
+                );
+            }
+        }
+    }
+
+    fn scenario(text: &str) -> Result<Scenario, ScenarioError> {
+        Scenario::read_from(text.as_bytes())
+    }
+
+    fn run(scenario: &Scenario, duration: Duration) -> Result<String, ScenarioError> {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let mut csv = Vec::new();
+
+        ScenarioRunner::new(&mut aircraft, &registry)
+            .with_step(Duration::from_secs(1))
+            .run(scenario, duration, &["TEST_POSITION"], &mut csv)?;
+
+        Ok(String::from_utf8(csv).unwrap())
+    }
+
+    #[test]
+    fn reads_actions_ordered_by_time() {
+        let scenario = scenario(
+            "# Battery on\n\
+             \n\
+             at t=10s set OVHD_ELEC_BAT_1_PB_IS_AUTO=1\n\
+             at 500ms set SIM ON GROUND=true\n\
+             at 1.5min set TEST=-2.5\n\
+             at 0 set OVHD_ELEC_BAT_2_PB_IS_AUTO=false\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            scenario.actions(),
+            &[
+                ScenarioAction::new(Duration::from_secs(0), "OVHD_ELEC_BAT_2_PB_IS_AUTO", 0.),
+                ScenarioAction::new(Duration::from_millis(500), "SIM ON GROUND", 1.),
+                ScenarioAction::new(Duration::from_secs(10), "OVHD_ELEC_BAT_1_PB_IS_AUTO", 1.),
+                ScenarioAction::new(Duration::from_secs(90), "TEST", -2.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn actions_at_the_same_time_keep_their_order() {
+        let scenario = scenario("at 1s set TEST=1\nat 1s set TEST=2\n").unwrap();
+
+        assert_eq!(scenario.actions()[0].value(), 1.);
+        assert_eq!(scenario.actions()[1].value(), 2.);
+    }
+
+    #[test]
+    fn invalid_line_results_in_error_with_line_number() {
+        for line in &[
+            "set TEST=1",
+            "at 1s TEST=1",
+            "at 1h set TEST=1",
+            "at -1s set TEST=1",
+            "at 1s set TEST=on",
+            "at 1s set =1",
+        ] {
+            match scenario(&format!("at 0s set TEST=1\n{}\n", line)) {
+                Err(ScenarioError::InvalidLine(2, invalid)) => assert_eq!(&invalid, line),
+                result => panic!(
+                    "Expected an invalid line error for {}, got {:?}",
+                    line, result
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn writes_outputs_after_every_tick() {
+        let csv = run(
+            &scenario("at 2s set TEST_SWITCH=1").unwrap(),
+            Duration::from_secs(4),
+        );
+
+        assert_eq!(csv.unwrap(), "time,TEST_POSITION\n1,0\n2,0\n3,1\n4,2\n");
+    }
+
+    #[test]
+    fn last_tick_is_shortened_to_end_at_the_duration() {
+        let csv = run(
+            &scenario("at 0s set TEST_SWITCH=1").unwrap(),
+            Duration::from_millis(1500),
+        );
+
+        assert_eq!(csv.unwrap(), "time,TEST_POSITION\n1,1\n1.5,1.5\n");
+    }
+
+    #[test]
+    fn unknown_variable_results_in_error() {
+        let result = run(
+            &scenario("at 0s set TEST_UNKNOWN=1").unwrap(),
+            Duration::from_secs(1),
+        );
+
+        assert!(
+            matches!(result, Err(ScenarioError::UnknownVariable(name)) if name == "TEST_UNKNOWN")
+        );
+    }
+
+    #[test]
+    fn starts_on_the_ground_with_the_values_written_by_the_aircraft() {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        aircraft.position = 5.;
+
+        let mut runner = ScenarioRunner::new(&mut aircraft, &registry);
+
+        assert_eq!(runner.read("TEST_POSITION"), Some(5.));
+        assert_eq!(runner.read(UpdateContext::IS_ON_GROUND_KEY), Some(1.));
+        assert_eq!(runner.read("TEST_UNKNOWN"), None);
+    }
+
+    #[test]
+    fn continues_where_the_previous_run_ended() {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let scenario = scenario("at 0s set TEST_SWITCH=1\nat 2s set TEST_SWITCH=0").unwrap();
+        let mut runner =
+            ScenarioRunner::new(&mut aircraft, &registry).with_step(Duration::from_secs(1));
+
+        runner
+            .run(&scenario, Duration::from_secs(1), &[], &mut io::sink())
+            .unwrap();
+        let mut csv = Vec::new();
+        runner
+            .run(
+                &scenario,
+                Duration::from_secs(2),
+                &["TEST_POSITION"],
+                &mut csv,
+            )
+            .unwrap();
+
+        assert_eq!(runner.time(), Duration::from_secs(3));
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "time,TEST_POSITION\n2,2\n3,2\n"
+        );
+    }
+
+    #[test]
+    fn counts_invariant_violations_of_every_tick() {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let scenario = scenario("at 0s set TEST_SWITCH=1").unwrap();
+        let mut runner =
+            ScenarioRunner::new(&mut aircraft, &registry).with_step(Duration::from_secs(1));
+
+        runner
+            .run(&scenario, Duration::from_secs(4), &[], &mut io::sink())
+            .unwrap();
+
+        let monitor = runner.invariant_monitor();
+        assert_eq!(monitor.ticks(), 4);
+        assert_eq!(monitor.violation_count_of("position at most 2"), 2);
+        assert_eq!(monitor.last_violation().unwrap().tick(), 4);
+    }
+
+    #[test]
+    fn numbers_invariant_violations_by_tick_when_ticks_are_split_into_steps() {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let scenario = scenario("at 0s set TEST_SWITCH=1").unwrap();
+        let mut runner = ScenarioRunner::new(&mut aircraft, &registry)
+            .with_step(Duration::from_secs(1))
+            .with_settings(SimulationSettings::new().with_max_step(Duration::from_millis(250)));
+
+        runner
+            .run(&scenario, Duration::from_secs(4), &[], &mut io::sink())
+            .unwrap();
+
+        let monitor = runner.invariant_monitor();
+        assert_eq!(monitor.ticks(), 4);
+        assert_eq!(monitor.violation_count_of("position at most 2"), 2);
+        assert_eq!(monitor.last_violation().unwrap().tick(), 4);
+    }
+
+    #[test]
+    fn collects_events_of_every_tick() {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let scenario = scenario("at 1s set TEST_SWITCH=1\nat 3s set TEST_SWITCH=0").unwrap();
+        let mut runner =
+            ScenarioRunner::new(&mut aircraft, &registry).with_step(Duration::from_secs(1));
+
+        runner
+            .run(&scenario, Duration::from_secs(4), &[], &mut io::sink())
+            .unwrap();
+
+        assert_eq!(
+            runner
+                .events()
+                .iter()
+                .map(|event| event.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "[2.000s] TEST/SWITCH activated",
+                "[4.000s] TEST/SWITCH deactivated"
+            ]
+        );
+    }
+
+    #[test]
+    fn takes_state_dumps_at_the_given_interval() {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let scenario = scenario("at 0s set TEST_SWITCH=1").unwrap();
+        let mut runner = ScenarioRunner::new(&mut aircraft, &registry)
+            .with_step(Duration::from_secs(1))
+            .with_dump_interval(2);
+
+        runner
+            .run(&scenario, Duration::from_secs(3), &[], &mut io::sink())
+            .unwrap();
+        runner
+            .run(&scenario, Duration::from_secs(2), &[], &mut io::sink())
+            .unwrap();
+
+        assert_eq!(
+            runner
+                .dumps()
+                .iter()
+                .map(|(time, dump)| (
+                    time.as_secs(),
+                    dump.find(&["TEST"]).unwrap().read_f64("POSITION").unwrap()
+                ))
+                .collect::<Vec<_>>(),
+            vec![(2, 2.), (4, 4.)]
+        );
+    }
+
+    #[test]
+    fn takes_no_state_dumps_without_an_interval() {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let mut runner =
+            ScenarioRunner::new(&mut aircraft, &registry).with_step(Duration::from_secs(1));
+
+        runner
+            .run(
+                &Scenario::new(),
+                Duration::from_secs(3),
+                &[],
+                &mut io::sink(),
+            )
+            .unwrap();
+
+        assert!(runner.dumps().is_empty());
+    }
+
+    #[test]
+    fn writes_state_dumps_as_json_array() {
+        let mut registry = VariableRegistry::new();
+        let mut aircraft = TestAircraft::new(&mut registry);
+        let scenario = scenario("at 0s set TEST_SWITCH=1").unwrap();
+        let mut runner = ScenarioRunner::new(&mut aircraft, &registry)
+            .with_step(Duration::from_millis(500))
+            .with_dump_interval(1);
+
+        runner
+            .run(&scenario, Duration::from_secs(1), &[], &mut io::sink())
+            .unwrap();
+        let mut json = Vec::new();
+        runner.write_dumps_json(&mut json).unwrap();
+
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            "[\n  {\n    \"time\": 0.5,\n    \"state\": {\n      \"TEST\": {\n        \"POSITION\": 0.5\n      }\n    }\n  },\n  {\n    \"time\": 1,\n    \"state\": {\n      \"TEST\": {\n        \"POSITION\": 1\n      }\n    }\n  }\n]\n"
+        );
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("SIM ON GROUND"), "SIM ON GROUND");
+        assert_eq!(csv_field("A,\"B\""), "\"A,\"\"B\"\"\"");
+    }
+}
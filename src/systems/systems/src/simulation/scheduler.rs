@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+/// A fixed rate at which an [`Aircraft`] is updated, independent of the simulator's
+/// frame rate. Optionally a faster sub rate is run as a multiple of the fixed rate,
+/// for things which require more precise solving such as physics.
+///
+/// [`Aircraft`]: trait.Aircraft.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedRate {
+    time_step: Duration,
+    sub_steps: u32,
+}
+impl FixedRate {
+    pub const fn new(time_step: Duration) -> Self {
+        Self {
+            time_step,
+            sub_steps: 0,
+        }
+    }
+
+    /// Runs the given number of sub steps for every fixed step.
+    pub const fn with_sub_steps(mut self, sub_steps: u32) -> Self {
+        self.sub_steps = sub_steps;
+        self
+    }
+
+    pub fn time_step(&self) -> Duration {
+        self.time_step
+    }
+
+    pub fn sub_time_step(&self) -> Duration {
+        self.time_step / self.sub_steps.max(1)
+    }
+
+    pub fn sub_steps(&self) -> u32 {
+        self.sub_steps
+    }
+}
+
+/// Keeps track of the time passed for each [`FixedRate`] of an [`Aircraft`] across the
+/// ticks of the [`Simulation`]. Time which doesn't add up to a complete step is carried
+/// over to the next tick, thus the scheduler should live as long as the aircraft.
+///
+/// [`FixedRate`]: struct.FixedRate.html
+/// [`Aircraft`]: trait.Aircraft.html
+/// [`Simulation`]: struct.Simulation.html
+#[derive(Default)]
+pub struct Scheduler {
+    time_to_catch_up: Vec<(FixedRate, Duration)>,
+}
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulates the time passed since the previous step of the simulation and
+    /// returns the number of fixed steps of the rate which should run during this step.
+    pub(super) fn number_of_steps(&mut self, rate: FixedRate, delta: Duration) -> u32 {
+        assert!(rate.time_step > Duration::from_secs(0));
+
+        let index = match self
+            .time_to_catch_up
+            .iter()
+            .position(|(scheduled, _)| *scheduled == rate)
+        {
+            Some(index) => index,
+            None => {
+                self.time_to_catch_up.push((rate, Duration::from_secs(0)));
+                self.time_to_catch_up.len() - 1
+            }
+        };
+
+        let time_to_catch_up = &mut self.time_to_catch_up[index].1;
+        *time_to_catch_up += delta;
+
+        let number_of_steps = (time_to_catch_up.as_nanos() / rate.time_step.as_nanos()) as u32;
+        *time_to_catch_up -= rate.time_step * number_of_steps;
+
+        number_of_steps
+    }
+
+    /// The fraction of the rate's time step which has passed since its last fixed step,
+    /// for interpolating the outputs of the rate between its last two fixed steps.
+    pub(super) fn interpolation_fraction(&self, rate: FixedRate) -> f64 {
+        self.time_to_catch_up
+            .iter()
+            .find(|(scheduled, _)| *scheduled == rate)
+            .map_or(0., |(_, time_to_catch_up)| {
+                time_to_catch_up.as_secs_f64() / rate.time_step.as_secs_f64()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: FixedRate = FixedRate::new(Duration::from_millis(100));
+
+    #[test]
+    fn runs_no_step_when_less_than_a_step_has_passed() {
+        let mut scheduler = Scheduler::new();
+
+        assert_eq!(
+            scheduler.number_of_steps(RATE, Duration::from_millis(60)),
+            0
+        );
+    }
+
+    #[test]
+    fn carries_over_remaining_time_to_next_tick() {
+        let mut scheduler = Scheduler::new();
+
+        scheduler.number_of_steps(RATE, Duration::from_millis(60));
+
+        assert_eq!(
+            scheduler.number_of_steps(RATE, Duration::from_millis(60)),
+            1
+        );
+    }
+
+    #[test]
+    fn runs_multiple_steps_for_long_tick() {
+        let mut scheduler = Scheduler::new();
+
+        assert_eq!(
+            scheduler.number_of_steps(RATE, Duration::from_millis(350)),
+            3
+        );
+        assert_eq!(
+            scheduler.number_of_steps(RATE, Duration::from_millis(50)),
+            1
+        );
+    }
+
+    #[test]
+    fn keeps_time_of_each_rate_separately() {
+        let mut scheduler = Scheduler::new();
+        let fast_rate = FixedRate::new(Duration::from_millis(20));
+
+        scheduler.number_of_steps(RATE, Duration::from_millis(60));
+
+        assert_eq!(
+            scheduler.number_of_steps(fast_rate, Duration::from_millis(60)),
+            3
+        );
+        assert_eq!(
+            scheduler.number_of_steps(RATE, Duration::from_millis(60)),
+            1
+        );
+    }
+
+    #[test]
+    fn interpolation_fraction_is_the_remaining_time_relative_to_the_time_step() {
+        let mut scheduler = Scheduler::new();
+
+        scheduler.number_of_steps(RATE, Duration::from_millis(130));
+
+        assert!((scheduler.interpolation_fraction(RATE) - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn interpolation_fraction_of_an_unscheduled_rate_is_zero() {
+        let scheduler = Scheduler::new();
+
+        assert_eq!(scheduler.interpolation_fraction(RATE), 0.);
+    }
+
+    #[test]
+    fn sub_time_step_divides_time_step() {
+        let rate = RATE.with_sub_steps(4);
+
+        assert_eq!(rate.sub_steps(), 4);
+        assert_eq!(rate.sub_time_step(), Duration::from_millis(25));
+    }
+
+    #[test]
+    fn total_number_of_steps_is_independent_of_frame_rate() {
+        let mut slow_scheduler = Scheduler::new();
+        let mut fast_scheduler = Scheduler::new();
+
+        let slow_steps: u32 = (0..30)
+            .map(|_| slow_scheduler.number_of_steps(RATE, Duration::from_millis(100)))
+            .sum();
+        let fast_steps: u32 = (0..180)
+            .map(|_| fast_scheduler.number_of_steps(RATE, Duration::from_micros(16_667)))
+            .sum();
+
+        assert_eq!(slow_steps, 30);
+        assert_eq!(fast_steps, 30);
+    }
+}
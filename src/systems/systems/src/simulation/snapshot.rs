@@ -0,0 +1,372 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+use super::{from_bool, to_bool, SimulationElement, SimulationElementVisitor};
+
+/// Contains the internal state of the aircraft's systems which isn't read from
+/// the simulator, such as the charge of a battery or the EGT of the APU.
+/// A snapshot can be written to and read from a versioned file format, allowing
+/// the state of the systems to be kept across simulator sessions.
+///
+/// # Examples
+/// ```rust
+/// # use systems::simulation::{Snapshot, SimulationElement};
+/// struct MySimulationElement {
+///     charge: f64,
+/// }
+/// impl SimulationElement for MySimulationElement {
+///     fn save(&self, snapshot: &mut Snapshot) {
+///         snapshot.write_f64("MY_SIMULATION_ELEMENT_CHARGE", self.charge);
+///     }
+///
+///     fn restore(&mut self, snapshot: &Snapshot) {
+///         if let Some(charge) = snapshot.read_f64("MY_SIMULATION_ELEMENT_CHARGE") {
+///             self.charge = charge;
+///         }
+///     }
+/// }
+///
+/// let mut element = MySimulationElement { charge: 5. };
+/// let snapshot = Snapshot::take(&mut element);
+///
+/// let mut restored = MySimulationElement { charge: 10. };
+/// snapshot.restore_into(&mut restored);
+/// assert_eq!(restored.charge, 5.);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Snapshot {
+    values: BTreeMap<String, f64>,
+}
+impl Snapshot {
+    /// The version of the file format written by [`write_to`].
+    /// Files of this version or older can be read by [`read_from`].
+    ///
+    /// [`write_to`]: #method.write_to
+    /// [`read_from`]: #method.read_from
+    pub const FORMAT_VERSION: u32 = 1;
+    const HEADER: &'static str = "A32NX_SYSTEMS_SNAPSHOT";
+
+    pub fn new() -> Self {
+        Self {
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// Takes a snapshot of the given element and all of its children.
+    pub fn take(element: &mut impl SimulationElement) -> Self {
+        let mut snapshot = Self::new();
+        let mut visitor = SimulationToSnapshotVisitor::new(&mut snapshot);
+        element.accept(&mut visitor);
+
+        snapshot
+    }
+
+    /// Restores the state contained in the snapshot into the given element and all of its children.
+    pub fn restore_into(&self, element: &mut impl SimulationElement) {
+        let mut visitor = SnapshotToSimulationVisitor::new(self);
+        element.accept(&mut visitor);
+    }
+
+    pub fn write_f64(&mut self, name: &str, value: f64) {
+        self.values.insert(name.to_owned(), value);
+    }
+
+    pub fn write_bool(&mut self, name: &str, value: bool) {
+        self.write_f64(name, from_bool(value));
+    }
+
+    /// Reads an `f64` from the snapshot. Returns `None` when the snapshot
+    /// doesn't contain the variable, for example because it was written
+    /// before the variable was introduced.
+    pub fn read_f64(&self, name: &str) -> Option<f64> {
+        self.values.get(name).copied()
+    }
+
+    /// Reads a `bool` from the snapshot. Returns `None` when the snapshot
+    /// doesn't contain the variable.
+    pub fn read_bool(&self, name: &str) -> Option<bool> {
+        self.read_f64(name).map(to_bool)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Writes the snapshot in the current file format version.
+    /// The format consists of a header line containing the version,
+    /// followed by one line per variable containing its name and value.
+    pub fn write_to<T: Write>(&self, writer: &mut T) -> io::Result<()> {
+        writeln!(writer, "{} {}", Self::HEADER, Self::FORMAT_VERSION)?;
+        for (name, value) in &self.values {
+            writeln!(writer, "{} {}", name, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a snapshot previously written by [`write_to`].
+    ///
+    /// [`write_to`]: #method.write_to
+    pub fn read_from<T: BufRead>(reader: T) -> Result<Self, SnapshotError> {
+        let mut lines = reader.lines();
+
+        let header = lines.next().ok_or(SnapshotError::MissingHeader)??;
+        let version = match header.split_once(' ') {
+            Some((Self::HEADER, version)) => version
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| SnapshotError::MissingHeader)?,
+            _ => return Err(SnapshotError::MissingHeader),
+        };
+
+        if version > Self::FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let mut snapshot = Self::new();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // The header is the first line, thus the first variable is found on line 2.
+            let line_number = index + 2;
+            let (name, value) = line
+                .rsplit_once(' ')
+                .ok_or(SnapshotError::InvalidLine(line_number))?;
+            let value = value
+                .parse::<f64>()
+                .map_err(|_| SnapshotError::InvalidLine(line_number))?;
+
+            snapshot.write_f64(name, value);
+        }
+
+        Ok(snapshot)
+    }
+}
+impl Default for Snapshot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    MissingHeader,
+    UnsupportedVersion(u32),
+    InvalidLine(usize),
+}
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(error) => write!(f, "Failed to read snapshot: {}", error),
+            SnapshotError::MissingHeader => write!(f, "The snapshot header is missing."),
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "Snapshot version {} is newer than the supported version {}.",
+                version,
+                Snapshot::FORMAT_VERSION
+            ),
+            SnapshotError::InvalidLine(line) => {
+                write!(f, "The snapshot contains an invalid line at line {}.", line)
+            }
+        }
+    }
+}
+impl Error for SnapshotError {}
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        SnapshotError::Io(error)
+    }
+}
+
+/// Visits aircraft components in order to save their internal state into a snapshot.
+struct SimulationToSnapshotVisitor<'a> {
+    snapshot: &'a mut Snapshot,
+}
+impl<'a> SimulationToSnapshotVisitor<'a> {
+    fn new(snapshot: &'a mut Snapshot) -> Self {
+        SimulationToSnapshotVisitor { snapshot }
+    }
+}
+impl SimulationElementVisitor for SimulationToSnapshotVisitor<'_> {
+    fn visit<T: SimulationElement>(&mut self, visited: &mut T) {
+        visited.save(self.snapshot);
+    }
+}
+
+/// Visits aircraft components in order to restore their internal state from a snapshot.
+struct SnapshotToSimulationVisitor<'a> {
+    snapshot: &'a Snapshot,
+}
+impl<'a> SnapshotToSimulationVisitor<'a> {
+    fn new(snapshot: &'a Snapshot) -> Self {
+        SnapshotToSimulationVisitor { snapshot }
+    }
+}
+impl SimulationElementVisitor for SnapshotToSimulationVisitor<'_> {
+    fn visit<T: SimulationElement>(&mut self, visited: &mut T) {
+        visited.restore(self.snapshot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestElement {
+        charge: f64,
+        is_connected: bool,
+    }
+    impl TestElement {
+        fn new(charge: f64, is_connected: bool) -> Self {
+            Self {
+                charge,
+                is_connected,
+            }
+        }
+    }
+    impl SimulationElement for TestElement {
+        fn save(&self, snapshot: &mut Snapshot) {
+            snapshot.write_f64("TEST_CHARGE", self.charge);
+            snapshot.write_bool("TEST_IS_CONNECTED", self.is_connected);
+        }
+
+        fn restore(&mut self, snapshot: &Snapshot) {
+            if let Some(charge) = snapshot.read_f64("TEST_CHARGE") {
+                self.charge = charge;
+            }
+            if let Some(is_connected) = snapshot.read_bool("TEST_IS_CONNECTED") {
+                self.is_connected = is_connected;
+            }
+        }
+    }
+
+    struct TestParent {
+        children: [TestElement; 2],
+    }
+    impl SimulationElement for TestParent {
+        fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+            self.children.iter_mut().for_each(|el| el.accept(visitor));
+
+            visitor.visit(self);
+        }
+    }
+
+    fn read(text: &str) -> Result<Snapshot, SnapshotError> {
+        Snapshot::read_from(text.as_bytes())
+    }
+
+    #[test]
+    fn take_saves_element_state() {
+        let snapshot = Snapshot::take(&mut TestElement::new(12.5, false));
+
+        assert_eq!(snapshot.read_f64("TEST_CHARGE"), Some(12.5));
+        assert_eq!(snapshot.read_bool("TEST_IS_CONNECTED"), Some(false));
+    }
+
+    #[test]
+    fn take_visits_children() {
+        let mut parent = TestParent {
+            children: [TestElement::new(1., true), TestElement::new(2., true)],
+        };
+
+        let snapshot = Snapshot::take(&mut parent);
+
+        assert_eq!(snapshot.len(), 2);
+    }
+
+    #[test]
+    fn restore_into_restores_element_state() {
+        let snapshot = Snapshot::take(&mut TestElement::new(12.5, false));
+        let mut element = TestElement::new(23., true);
+
+        snapshot.restore_into(&mut element);
+
+        assert!((element.charge - 12.5).abs() < f64::EPSILON);
+        assert!(!element.is_connected);
+    }
+
+    #[test]
+    fn restore_into_leaves_state_missing_from_snapshot_untouched() {
+        let mut snapshot = Snapshot::new();
+        snapshot.write_f64("TEST_CHARGE", 3.);
+        let mut element = TestElement::new(23., true);
+
+        snapshot.restore_into(&mut element);
+
+        assert!((element.charge - 3.).abs() < f64::EPSILON);
+        assert!(element.is_connected);
+    }
+
+    #[test]
+    fn written_snapshot_can_be_read() {
+        let snapshot = Snapshot::take(&mut TestElement::new(0.1 + 0.2, true));
+        let mut bytes = Vec::new();
+        snapshot.write_to(&mut bytes).unwrap();
+
+        let read_snapshot = Snapshot::read_from(bytes.as_slice()).unwrap();
+
+        assert_eq!(read_snapshot, snapshot);
+    }
+
+    #[test]
+    fn written_snapshot_starts_with_header_and_version() {
+        let mut bytes = Vec::new();
+        Snapshot::new().write_to(&mut bytes).unwrap();
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            format!("A32NX_SYSTEMS_SNAPSHOT {}\n", Snapshot::FORMAT_VERSION)
+        );
+    }
+
+    #[test]
+    fn reading_skips_empty_lines() {
+        let snapshot = read("A32NX_SYSTEMS_SNAPSHOT 1\n\nTEST_CHARGE 2.5\n\n").unwrap();
+
+        assert_eq!(snapshot.read_f64("TEST_CHARGE"), Some(2.5));
+    }
+
+    #[test]
+    fn reading_without_header_fails() {
+        assert!(matches!(
+            read("TEST_CHARGE 2.5\n"),
+            Err(SnapshotError::MissingHeader)
+        ));
+        assert!(matches!(read(""), Err(SnapshotError::MissingHeader)));
+    }
+
+    #[test]
+    fn reading_newer_version_fails() {
+        assert!(matches!(
+            read(&format!(
+                "A32NX_SYSTEMS_SNAPSHOT {}\n",
+                Snapshot::FORMAT_VERSION + 1
+            )),
+            Err(SnapshotError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn reading_invalid_line_fails_with_line_number() {
+        assert!(matches!(
+            read("A32NX_SYSTEMS_SNAPSHOT 1\nTEST_CHARGE 2.5\nTEST_CHARGE\n"),
+            Err(SnapshotError::InvalidLine(3))
+        ));
+        assert!(matches!(
+            read("A32NX_SYSTEMS_SNAPSHOT 1\nTEST_CHARGE abc\n"),
+            Err(SnapshotError::InvalidLine(2))
+        ));
+    }
+}
@@ -0,0 +1,327 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use super::{json_string, SimulationElement, SimulationElementVisitor};
+
+/// A value contained in a [`StateDump`].
+///
+/// [`StateDump`]: struct.StateDump.html
+#[derive(Clone, Debug, PartialEq)]
+pub enum StateValue {
+    Bool(bool),
+    F64(f64),
+    Text(String),
+    Node(StateDump),
+}
+
+/// A tree describing the internal state of the aircraft's systems at a given moment,
+/// such as which contactors are closed, the origins of the potential on each bus
+/// and the state of the APU turbine. Unlike a [`Snapshot`], the dump is meant for
+/// humans debugging the systems, and thus can be written as a JSON document.
+///
+/// Elements describe their state by overriding [`SimulationElement::dump`]. They add
+/// nodes to group their values, such that the dump of the aircraft forms a hierarchy.
+///
+/// # Examples
+/// ```rust
+/// # use systems::simulation::{SimulationElement, StateDump};
+/// struct MyContactor {
+///     id: String,
+///     closed: bool,
+/// }
+/// impl SimulationElement for MyContactor {
+///     fn dump(&self, state: &mut StateDump) {
+///         state
+///             .node("ELEC")
+///             .node("CONTACTOR")
+///             .node(&self.id)
+///             .write_bool("IS_CLOSED", self.closed);
+///     }
+/// }
+///
+/// let mut contactor = MyContactor { id: String::from("1PC1"), closed: true };
+/// let dump = StateDump::take(&mut contactor);
+///
+/// let node = dump.find(&["ELEC", "CONTACTOR", "1PC1"]).unwrap();
+/// assert_eq!(node.read_bool("IS_CLOSED"), Some(true));
+/// ```
+/// [`Snapshot`]: struct.Snapshot.html
+/// [`SimulationElement::dump`]: trait.SimulationElement.html#method.dump
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StateDump {
+    values: BTreeMap<String, StateValue>,
+}
+impl StateDump {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes a dump of the given element and all of its children.
+    pub fn take(element: &mut impl SimulationElement) -> Self {
+        let mut dump = Self::new();
+        let mut visitor = SimulationToStateDumpVisitor::new(&mut dump);
+        element.accept(&mut visitor);
+
+        dump
+    }
+
+    /// Returns the child node with the given name, adding it when it doesn't exist yet.
+    /// A value with the same name is replaced by the node.
+    pub fn node(&mut self, name: &str) -> &mut StateDump {
+        let value = self
+            .values
+            .entry(name.to_owned())
+            .or_insert_with(|| StateValue::Node(StateDump::new()));
+        if !matches!(value, StateValue::Node(_)) {
+            *value = StateValue::Node(StateDump::new());
+        }
+
+        match value {
+            StateValue::Node(node) => node,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn write_bool(&mut self, name: &str, value: bool) {
+        self.write(name, StateValue::Bool(value));
+    }
+
+    pub fn write_f64(&mut self, name: &str, value: f64) {
+        self.write(name, StateValue::F64(value));
+    }
+
+    pub fn write_text(&mut self, name: &str, value: &str) {
+        self.write(name, StateValue::Text(value.to_owned()));
+    }
+
+    fn write(&mut self, name: &str, value: StateValue) {
+        self.values.insert(name.to_owned(), value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StateValue> {
+        self.values.get(name)
+    }
+
+    /// Returns the node found by following the given path of node names.
+    /// An empty path returns the node itself.
+    pub fn find(&self, path: &[&str]) -> Option<&StateDump> {
+        path.iter()
+            .try_fold(self, |node, name| match node.get(name) {
+                Some(StateValue::Node(child)) => Some(child),
+                _ => None,
+            })
+    }
+
+    pub fn read_bool(&self, name: &str) -> Option<bool> {
+        match self.get(name) {
+            Some(StateValue::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn read_f64(&self, name: &str) -> Option<f64> {
+        match self.get(name) {
+            Some(StateValue::F64(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn read_text(&self, name: &str) -> Option<&str> {
+        match self.get(name) {
+            Some(StateValue::Text(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Iterates over the names and values of the node, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &StateValue)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Writes the dump as a JSON document, in which every node is an object.
+    /// Non-finite numbers are written as `null`.
+    pub fn write_json<T: Write>(&self, writer: &mut T) -> io::Result<()> {
+        self.write_json_object(writer, 0)?;
+        writeln!(writer)
+    }
+
+    pub(super) fn write_json_object<T: Write>(
+        &self,
+        writer: &mut T,
+        depth: usize,
+    ) -> io::Result<()> {
+        if self.values.is_empty() {
+            return write!(writer, "{{}}");
+        }
+
+        writeln!(writer, "{{")?;
+        let indent = "  ".repeat(depth + 1);
+        for (index, (name, value)) in self.values.iter().enumerate() {
+            write!(writer, "{}{}: ", indent, json_string(name))?;
+            match value {
+                StateValue::Bool(value) => write!(writer, "{}", value)?,
+                StateValue::F64(value) if value.is_finite() => write!(writer, "{}", value)?,
+                StateValue::F64(_) => write!(writer, "null")?,
+                StateValue::Text(value) => write!(writer, "{}", json_string(value))?,
+                StateValue::Node(node) => node.write_json_object(writer, depth + 1)?,
+            }
+            writeln!(
+                writer,
+                "{}",
+                if index + 1 < self.values.len() {
+                    ","
+                } else {
+                    ""
+                }
+            )?;
+        }
+        write!(writer, "{}}}", "  ".repeat(depth))
+    }
+}
+
+/// Visits aircraft components in order to dump their internal state.
+struct SimulationToStateDumpVisitor<'a> {
+    dump: &'a mut StateDump,
+}
+impl<'a> SimulationToStateDumpVisitor<'a> {
+    fn new(dump: &'a mut StateDump) -> Self {
+        Self { dump }
+    }
+}
+impl SimulationElementVisitor for SimulationToStateDumpVisitor<'_> {
+    fn visit<T: SimulationElement>(&mut self, visited: &mut T) {
+        visited.dump(self.dump);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestElement {
+        id: String,
+        is_on: bool,
+        pressure: f64,
+    }
+    impl TestElement {
+        fn new(id: &str, is_on: bool, pressure: f64) -> Self {
+            Self {
+                id: id.to_owned(),
+                is_on,
+                pressure,
+            }
+        }
+    }
+    impl SimulationElement for TestElement {
+        fn dump(&self, state: &mut StateDump) {
+            let node = state.node("TEST").node(&self.id);
+            node.write_bool("IS_ON", self.is_on);
+            node.write_f64("PRESSURE", self.pressure);
+        }
+    }
+
+    struct TestAircraft {
+        elements: [TestElement; 2],
+        mode: String,
+    }
+    impl SimulationElement for TestAircraft {
+        fn accept<T: SimulationElementVisitor>(&mut self, visitor: &mut T) {
+            self.elements.iter_mut().for_each(|el| el.accept(visitor));
+
+            visitor.visit(self);
+        }
+
+        fn dump(&self, state: &mut StateDump) {
+            state.node("TEST").write_text("MODE", &self.mode);
+        }
+    }
+
+    fn aircraft() -> TestAircraft {
+        TestAircraft {
+            elements: [
+                TestElement::new("1", true, 3000.),
+                TestElement::new("2", false, f64::NAN),
+            ],
+            mode: String::from("NORMAL \"A\""),
+        }
+    }
+
+    #[test]
+    fn contains_the_state_of_all_elements() {
+        let dump = StateDump::take(&mut aircraft());
+
+        let first = dump.find(&["TEST", "1"]).unwrap();
+        assert_eq!(first.read_bool("IS_ON"), Some(true));
+        assert_eq!(first.read_f64("PRESSURE"), Some(3000.));
+
+        let second = dump.find(&["TEST", "2"]).unwrap();
+        assert_eq!(second.read_bool("IS_ON"), Some(false));
+
+        assert_eq!(
+            dump.find(&["TEST"]).unwrap().read_text("MODE"),
+            Some("NORMAL \"A\"")
+        );
+    }
+
+    #[test]
+    fn reading_a_value_of_another_type_returns_none() {
+        let dump = StateDump::take(&mut aircraft());
+        let first = dump.find(&["TEST", "1"]).unwrap();
+
+        assert_eq!(first.read_f64("IS_ON"), None);
+        assert_eq!(first.read_bool("MISSING"), None);
+        assert!(dump.find(&["TEST", "1", "IS_ON"]).is_none());
+    }
+
+    #[test]
+    fn node_replaces_value_with_the_same_name() {
+        let mut dump = StateDump::new();
+        dump.write_f64("A", 1.);
+
+        dump.node("A").write_f64("B", 2.);
+
+        assert_eq!(dump.find(&["A"]).unwrap().read_f64("B"), Some(2.));
+    }
+
+    #[test]
+    fn writes_json_document() {
+        let mut json = Vec::new();
+        StateDump::take(&mut aircraft())
+            .write_json(&mut json)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            r#"{
+  "TEST": {
+    "1": {
+      "IS_ON": true,
+      "PRESSURE": 3000
+    },
+    "2": {
+      "IS_ON": false,
+      "PRESSURE": null
+    },
+    "MODE": "NORMAL \"A\""
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn empty_dump_is_written_as_empty_object() {
+        let mut json = Vec::new();
+        StateDump::new().write_json(&mut json).unwrap();
+
+        assert_eq!(String::from_utf8(json).unwrap(), "{}\n");
+    }
+}
@@ -1,25 +1,41 @@
 use std::{collections::HashMap, time::Duration};
 use uom::si::{
-    acceleration::foot_per_second_squared, f64::*, length::foot,
-    thermodynamic_temperature::degree_celsius, velocity::knot,
+    acceleration::foot_per_second_squared, angle::degree, f64::*, length::foot,
+    pressure::inch_of_mercury, ratio::ratio, thermodynamic_temperature::degree_celsius,
+    velocity::foot_per_minute, velocity::knot,
 };
 
-use crate::electrical::consumption::SuppliedPower;
+use crate::{
+    electrical::consumption::SuppliedPower, landing_gear::GearId, shared::SeededRandomSource,
+};
 
 use super::{
-    from_bool, to_bool, Aircraft, Simulation, SimulationElement, SimulationElementVisitor,
+    from_bool, to_bool, Aircraft, EventBus, InvariantMonitor, InvariantViolationAction, Scheduler,
+    Simulation, SimulationElement, SimulationElementVisitor, SimulationSettings,
     SimulationToSimulatorVisitor, SimulatorReaderWriter, SimulatorWriter, UpdateContext,
+    VariableIdentifier, VariableRegistry,
 };
 
 /// The simulation test bed handles the testing of [`Aircraft`] and [`SimulationElement`]
-/// by running a full simulation tick on them.
+/// by running a full simulation tick on them. The random numbers provided by the
+/// [`UpdateContext`] are the same for every test run, unless a different seed is set.
+/// Elements under test should be created with the test bed's [`registry`], such that
+/// their variables can be accessed by name. A violated invariant fails the test.
 ///
 /// [`Aircraft`]: ../trait.Aircraft.html
 /// [`SimulationElement`]: ../trait.SimulationElement.html
+/// [`UpdateContext`]: ../struct.UpdateContext.html
+/// [`registry`]: #method.registry
 pub struct SimulationTestBed {
+    registry: VariableRegistry,
     reader_writer: TestReaderWriter,
+    random_source: SeededRandomSource,
+    scheduler: Scheduler,
     get_supplied_power_fn: Box<dyn Fn() -> SuppliedPower>,
     delta: Duration,
+    settings: SimulationSettings,
+    invariant_monitor: InvariantMonitor,
+    event_bus: EventBus,
 }
 impl SimulationTestBed {
     pub fn new() -> Self {
@@ -27,26 +43,42 @@ impl SimulationTestBed {
     }
 
     pub fn new_with_delta(delta: Duration) -> Self {
+        Self::new_with_registry(delta, VariableRegistry::new())
+    }
+
+    fn new_with_registry(delta: Duration, mut registry: VariableRegistry) -> Self {
+        let event_bus = EventBus::new().with_simulator_variables(&mut registry);
         let mut test_bed = Self {
+            registry,
             reader_writer: TestReaderWriter::new(),
+            random_source: SeededRandomSource::default(),
+            scheduler: Scheduler::new(),
             get_supplied_power_fn: Box::new(SuppliedPower::new),
             delta,
+            settings: SimulationSettings::default(),
+            invariant_monitor: InvariantMonitor::new(InvariantViolationAction::Panic),
+            event_bus,
         };
 
         test_bed.set_indicated_airspeed(Velocity::new::<knot>(250.));
         test_bed.set_indicated_altitude(Length::new::<foot>(5000.));
         test_bed.set_ambient_temperature(ThermodynamicTemperature::new::<degree_celsius>(0.));
+        test_bed.set_ambient_pressure(Pressure::new::<inch_of_mercury>(24.89));
+        test_bed.set_pressure_altitude(Length::new::<foot>(5000.));
+        test_bed.set_true_airspeed(Velocity::new::<knot>(270.));
+        test_bed.set_mach_number(Ratio::new::<ratio>(0.41));
         test_bed.set_on_ground(false);
 
         test_bed
     }
 
-    /// Creates an instance seeded with the state found in the given element.
+    /// Creates an instance seeded with the state found in the given element,
+    /// which was created with the given registry.
     ///
     /// By default the unseeded simulation will return 0.0 or false for any requested
     /// variables. If this is a problem for your test, then use this function.
-    pub fn seeded_with(element: &mut impl SimulationElement) -> Self {
-        let mut test_bed = Self::new();
+    pub fn seeded_with(registry: VariableRegistry, element: &mut impl SimulationElement) -> Self {
+        let mut test_bed = Self::new_with_registry(Duration::from_secs(1), registry);
 
         let mut writer = SimulatorWriter::new(&mut test_bed.reader_writer);
         let mut visitor = SimulationToSimulatorVisitor::new(&mut writer);
@@ -60,7 +92,15 @@ impl SimulationTestBed {
     /// [`Aircraft`]: ../trait.Aircraft.html
     /// [`Simulation`]: ../struct.Simulation.html
     pub fn run_aircraft(&mut self, aircraft: &mut impl Aircraft) {
-        let mut simulation = Simulation::new(aircraft, &mut self.reader_writer);
+        let mut simulation = Simulation::new_with_settings(
+            aircraft,
+            &mut self.reader_writer,
+            &mut self.random_source,
+            &mut self.scheduler,
+            self.settings,
+        )
+        .with_invariant_monitor(&mut self.invariant_monitor)
+        .with_event_bus(&mut self.event_bus);
         simulation.tick(self.delta);
     }
 
@@ -123,60 +163,125 @@ impl SimulationTestBed {
         update_fn: U,
         before_power_distribution: bool,
     ) {
+        let get_supplied_power_fn = std::mem::replace(
+            &mut self.get_supplied_power_fn,
+            Box::new(SuppliedPower::new),
+        );
         let mut aircraft = TestAircraft::new(
             element,
             update_fn,
-            (self.get_supplied_power_fn)(),
+            &*get_supplied_power_fn,
             before_power_distribution,
         );
 
         self.run_aircraft(&mut aircraft);
+        self.get_supplied_power_fn = get_supplied_power_fn;
     }
 
     pub fn set_delta(&mut self, delta: Duration) {
         self.delta = delta;
     }
 
+    /// Sets how the [`Simulation`] handles large deltas and pauses.
+    ///
+    /// [`Simulation`]: ../struct.Simulation.html
+    pub fn set_simulation_settings(&mut self, settings: SimulationSettings) {
+        self.settings = settings;
+    }
+
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.random_source = SeededRandomSource::new(seed);
+    }
+
     pub fn set_indicated_airspeed(&mut self, indicated_airspeed: Velocity) {
-        self.reader_writer.write_f64(
+        self.write_f64(
             UpdateContext::INDICATED_AIRSPEED_KEY,
             indicated_airspeed.get::<knot>(),
         );
     }
 
     pub fn indicated_airspeed(&mut self) -> Velocity {
-        Velocity::new::<knot>(
-            self.reader_writer
-                .read_f64(UpdateContext::INDICATED_AIRSPEED_KEY),
-        )
+        Velocity::new::<knot>(self.read_f64(UpdateContext::INDICATED_AIRSPEED_KEY))
     }
 
     pub fn set_indicated_altitude(&mut self, indicated_altitude: Length) {
-        self.reader_writer.write_f64(
+        self.write_f64(
             UpdateContext::INDICATED_ALTITUDE_KEY,
             indicated_altitude.get::<foot>(),
         );
     }
 
     pub fn set_ambient_temperature(&mut self, ambient_temperature: ThermodynamicTemperature) {
-        self.reader_writer.write_f64(
+        self.write_f64(
             UpdateContext::AMBIENT_TEMPERATURE_KEY,
             ambient_temperature.get::<degree_celsius>(),
         );
     }
 
     pub fn set_on_ground(&mut self, on_ground: bool) {
-        self.reader_writer
-            .write_bool(UpdateContext::IS_ON_GROUND_KEY, on_ground);
+        self.write_bool(UpdateContext::IS_ON_GROUND_KEY, on_ground);
     }
 
     pub fn set_long_acceleration(&mut self, accel: Acceleration) {
-        self.reader_writer.write_f64(
+        self.write_f64(
             UpdateContext::ACCEL_BODY_Z_KEY,
             accel.get::<foot_per_second_squared>(),
         );
     }
 
+    pub fn set_ambient_pressure(&mut self, ambient_pressure: Pressure) {
+        self.write_f64(
+            UpdateContext::AMBIENT_PRESSURE_KEY,
+            ambient_pressure.get::<inch_of_mercury>(),
+        );
+    }
+
+    pub fn set_pressure_altitude(&mut self, pressure_altitude: Length) {
+        self.write_f64(
+            UpdateContext::PRESSURE_ALTITUDE_KEY,
+            pressure_altitude.get::<foot>(),
+        );
+    }
+
+    pub fn set_true_airspeed(&mut self, true_airspeed: Velocity) {
+        self.write_f64(
+            UpdateContext::TRUE_AIRSPEED_KEY,
+            true_airspeed.get::<knot>(),
+        );
+    }
+
+    pub fn set_mach_number(&mut self, mach_number: Ratio) {
+        self.write_f64(UpdateContext::MACH_NUMBER_KEY, mach_number.get::<ratio>());
+    }
+
+    pub fn set_vertical_speed(&mut self, vertical_speed: Velocity) {
+        self.write_f64(
+            UpdateContext::VERTICAL_SPEED_KEY,
+            vertical_speed.get::<foot_per_minute>(),
+        );
+    }
+
+    /// Sets the pitch, positive nose up.
+    pub fn set_pitch(&mut self, pitch: Angle) {
+        self.write_f64(UpdateContext::PITCH_KEY, -pitch.get::<degree>());
+    }
+
+    /// Sets the bank, positive right wing down.
+    pub fn set_bank(&mut self, bank: Angle) {
+        self.write_f64(UpdateContext::BANK_KEY, -bank.get::<degree>());
+    }
+
+    pub fn set_ground_speed(&mut self, ground_speed: Velocity) {
+        self.write_f64(UpdateContext::GROUND_SPEED_KEY, ground_speed.get::<knot>());
+    }
+
+    pub fn set_gear_compression(&mut self, gear_id: GearId, compression: Ratio) {
+        self.write_f64(
+            UpdateContext::GEAR_COMPRESSION_KEYS[gear_id as usize],
+            compression.get::<ratio>(),
+        );
+    }
+
     pub fn supplied_power_fn(
         mut self,
         supplied_power_fn: impl Fn() -> SuppliedPower + 'static,
@@ -185,24 +290,39 @@ impl SimulationTestBed {
         self
     }
 
+    /// The events emitted by the elements during all ticks run so far.
+    pub fn events(&self) -> &EventBus {
+        &self.event_bus
+    }
+
+    /// The registry with which elements under test should be created.
+    pub fn registry(&mut self) -> &mut VariableRegistry {
+        &mut self.registry
+    }
+
     pub fn write_bool(&mut self, name: &str, value: bool) {
-        self.reader_writer.write_bool(name, value);
+        self.write_f64(name, from_bool(value));
     }
 
     pub fn write_f64(&mut self, name: &str, value: f64) {
-        self.reader_writer.write_f64(name, value);
+        let identifier = self.registry.get(name);
+        self.reader_writer.write(&identifier, value);
     }
 
     pub fn read_bool(&mut self, name: &str) -> bool {
-        self.reader_writer.read_bool(name)
+        to_bool(self.read_f64(name))
     }
 
     pub fn read_f64(&mut self, name: &str) -> f64 {
-        self.reader_writer.read_f64(name)
+        let identifier = self.registry.get(name);
+        self.reader_writer.read(&identifier)
     }
 
     pub fn contains_key(&self, name: &str) -> bool {
-        self.reader_writer.contains_key(name)
+        match self.registry.find(name) {
+            Some(identifier) => self.reader_writer.contains_key(&identifier),
+            None => false,
+        }
     }
 }
 impl Default for SimulationTestBed {
@@ -214,20 +334,20 @@ impl Default for SimulationTestBed {
 struct TestAircraft<'a, T: SimulationElement, U: Fn(&mut T, &UpdateContext)> {
     element: &'a mut T,
     update_fn: U,
-    supplied_power: Option<SuppliedPower>,
+    get_supplied_power_fn: &'a dyn Fn() -> SuppliedPower,
     update_before_power_distribution: bool,
 }
 impl<'a, T: SimulationElement, U: Fn(&mut T, &UpdateContext)> TestAircraft<'a, T, U> {
     fn new(
         element: &'a mut T,
         update_fn: U,
-        supplied_power: SuppliedPower,
+        get_supplied_power_fn: &'a dyn Fn() -> SuppliedPower,
         update_before_power_distribution: bool,
     ) -> Self {
         Self {
             element,
             update_fn,
-            supplied_power: Some(supplied_power),
+            get_supplied_power_fn,
             update_before_power_distribution,
         }
     }
@@ -246,7 +366,7 @@ impl<'a, T: SimulationElement, U: Fn(&mut T, &UpdateContext)> Aircraft for TestA
     }
 
     fn get_supplied_power(&mut self) -> SuppliedPower {
-        self.supplied_power.take().unwrap()
+        (self.get_supplied_power_fn)()
     }
 }
 impl<'a, T: SimulationElement, U: Fn(&mut T, &UpdateContext)> SimulationElement
@@ -260,7 +380,7 @@ impl<'a, T: SimulationElement, U: Fn(&mut T, &UpdateContext)> SimulationElement
 }
 
 struct TestReaderWriter {
-    variables: HashMap<String, f64>,
+    variables: HashMap<VariableIdentifier, f64>,
 }
 impl TestReaderWriter {
     fn new() -> Self {
@@ -269,33 +389,17 @@ impl TestReaderWriter {
         }
     }
 
-    fn contains_key(&self, name: &str) -> bool {
-        self.variables.contains_key(name)
-    }
-
-    fn write_bool(&mut self, name: &str, value: bool) {
-        self.write(name, from_bool(value));
-    }
-
-    fn write_f64(&mut self, name: &str, value: f64) {
-        self.write(name, value);
-    }
-
-    fn read_bool(&mut self, name: &str) -> bool {
-        to_bool(self.read(name))
-    }
-
-    fn read_f64(&mut self, name: &str) -> f64 {
-        self.read(name)
+    fn contains_key(&self, identifier: &VariableIdentifier) -> bool {
+        self.variables.contains_key(identifier)
     }
 }
 impl SimulatorReaderWriter for TestReaderWriter {
-    fn read(&mut self, name: &str) -> f64 {
-        *self.variables.get(name).unwrap_or(&0.)
+    fn read(&mut self, identifier: &VariableIdentifier) -> f64 {
+        *self.variables.get(identifier).unwrap_or(&0.)
     }
 
-    fn write(&mut self, name: &str, value: f64) {
-        self.variables.insert(name.to_owned(), value);
+    fn write(&mut self, identifier: &VariableIdentifier, value: f64) {
+        self.variables.insert(*identifier, value);
     }
 }
 impl Default for TestReaderWriter {
@@ -309,8 +413,9 @@ mod tests {
     use super::*;
     use crate::{
         electrical::consumption::{PowerConsumption, PowerConsumptionReport, SuppliedPower},
-        simulation::{SimulatorReader, SimulatorWriter},
+        simulation::{InvariantChecker, SimulatorReader, SimulatorWriter},
     };
+    use uom::si::mass_density::kilogram_per_cubic_meter;
 
     #[derive(Clone, Copy, Debug, PartialEq)]
     enum CallOrder {
@@ -415,4 +520,257 @@ mod tests {
             Some(CallOrder::Before)
         );
     }
+
+    #[derive(Default)]
+    struct TimeIntegratingElement {
+        deltas: Vec<Duration>,
+        value: f64,
+    }
+    impl TimeIntegratingElement {
+        fn update(&mut self, context: &UpdateContext) {
+            self.deltas.push(context.delta());
+            // Approaches 1 over time, such that the result depends on the size of the steps
+            self.value += (1. - self.value) * 0.5 * context.delta_as_secs_f64();
+        }
+    }
+    impl SimulationElement for TimeIntegratingElement {}
+
+    #[test]
+    fn delta_exceeding_max_step_is_split_into_equal_steps() {
+        let mut element = TimeIntegratingElement::default();
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(250));
+        test_bed.set_simulation_settings(
+            SimulationSettings::new().with_max_step(Duration::from_millis(100)),
+        );
+
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        assert_eq!(element.deltas.len(), 3);
+        assert_eq!(
+            element.deltas.iter().sum::<Duration>(),
+            Duration::from_millis(250)
+        );
+        assert!(element
+            .deltas
+            .iter()
+            .all(|delta| *delta <= Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn delta_within_max_step_is_not_split() {
+        let mut element = TimeIntegratingElement::default();
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(50));
+        test_bed.set_simulation_settings(
+            SimulationSettings::new().with_max_step(Duration::from_millis(100)),
+        );
+
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        assert_eq!(element.deltas, vec![Duration::from_millis(50)]);
+    }
+
+    #[test]
+    fn reaches_same_state_at_normal_and_sixteen_times_sim_rate() {
+        let settings = SimulationSettings::new().with_max_step(Duration::from_millis(50));
+
+        let mut normal_rate_element = TimeIntegratingElement::default();
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(50));
+        test_bed.set_simulation_settings(settings);
+        for _ in 0..64 {
+            test_bed.run(&mut normal_rate_element, |el, context| el.update(context));
+        }
+
+        let mut high_rate_element = TimeIntegratingElement::default();
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(800));
+        test_bed.set_simulation_settings(settings);
+        for _ in 0..4 {
+            test_bed.run(&mut high_rate_element, |el, context| el.update(context));
+        }
+
+        assert!((normal_rate_element.value - high_rate_element.value).abs() < 1e-9);
+    }
+
+    #[test]
+    fn without_max_step_high_sim_rate_results_in_different_state() {
+        let mut normal_rate_element = TimeIntegratingElement::default();
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(50));
+        for _ in 0..64 {
+            test_bed.run(&mut normal_rate_element, |el, context| el.update(context));
+        }
+
+        let mut high_rate_element = TimeIntegratingElement::default();
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_millis(800));
+        for _ in 0..4 {
+            test_bed.run(&mut high_rate_element, |el, context| el.update(context));
+        }
+
+        assert!((normal_rate_element.value - high_rate_element.value).abs() > 1e-3);
+    }
+
+    #[test]
+    fn paused_simulation_does_not_update_aircraft() {
+        let mut element = TimeIntegratingElement::default();
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(0));
+        test_bed.set_simulation_settings(SimulationSettings::new().with_pause_detection());
+
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        assert!(element.deltas.is_empty());
+    }
+
+    #[test]
+    fn zero_delta_updates_aircraft_without_pause_detection() {
+        let mut element = TimeIntegratingElement::default();
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(0));
+
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        assert_eq!(element.deltas, vec![Duration::from_secs(0)]);
+    }
+
+    #[test]
+    fn simulation_resumes_after_pause() {
+        let mut element = TimeIntegratingElement::default();
+        let mut test_bed = SimulationTestBed::new_with_delta(Duration::from_secs(0));
+        test_bed.set_simulation_settings(SimulationSettings::new().with_pause_detection());
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        test_bed.set_delta(Duration::from_millis(50));
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        assert_eq!(element.deltas, vec![Duration::from_millis(50)]);
+    }
+
+    struct InvariantBreakingElement {}
+    impl SimulationElement for InvariantBreakingElement {
+        fn check_invariants(&self, invariants: &mut InvariantChecker) {
+            invariants.check("never holds", false);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invariant \"never holds\" violated in tick 1.")]
+    fn violated_invariant_fails_the_test() {
+        let mut test_bed = SimulationTestBed::new();
+
+        test_bed.run_without_update(&mut InvariantBreakingElement {});
+    }
+
+    #[derive(Default)]
+    struct ContextCapturingElement {
+        context: Option<UpdateContext>,
+    }
+    impl ContextCapturingElement {
+        fn update(&mut self, context: &UpdateContext) {
+            self.context = Some(context.clone());
+        }
+
+        fn context(&self) -> &UpdateContext {
+            self.context.as_ref().unwrap()
+        }
+    }
+    impl SimulationElement for ContextCapturingElement {}
+
+    #[test]
+    fn context_provides_air_data() {
+        let mut element = ContextCapturingElement::default();
+        let mut test_bed = SimulationTestBed::new();
+        test_bed.set_ambient_pressure(Pressure::new::<inch_of_mercury>(20.58));
+        test_bed.set_pressure_altitude(Length::new::<foot>(10000.));
+        test_bed.set_true_airspeed(Velocity::new::<knot>(300.));
+        test_bed.set_mach_number(Ratio::new::<ratio>(0.47));
+        test_bed.set_vertical_speed(Velocity::new::<foot_per_minute>(-1500.));
+
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        let context = element.context();
+        assert!((context.ambient_pressure().get::<inch_of_mercury>() - 20.58).abs() < 1e-9);
+        assert!((context.pressure_altitude().get::<foot>() - 10000.).abs() < 1e-9);
+        assert!((context.true_airspeed().get::<knot>() - 300.).abs() < 1e-9);
+        assert!((context.mach_number().get::<ratio>() - 0.47).abs() < 1e-9);
+        assert!((context.vertical_speed().get::<foot_per_minute>() + 1500.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn context_attitude_is_positive_nose_up_and_right_wing_down() {
+        let mut element = ContextCapturingElement::default();
+        let mut test_bed = SimulationTestBed::new();
+        test_bed.set_pitch(Angle::new::<degree>(5.));
+        test_bed.set_bank(Angle::new::<degree>(-20.));
+
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        assert!((element.context().pitch().get::<degree>() - 5.).abs() < 1e-9);
+        assert!((element.context().bank().get::<degree>() + 20.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn context_provides_compression_per_gear() {
+        let mut element = ContextCapturingElement::default();
+        let mut test_bed = SimulationTestBed::new();
+        test_bed.set_ground_speed(Velocity::new::<knot>(12.));
+        test_bed.set_gear_compression(GearId::Nose, Ratio::new::<ratio>(0.2));
+        test_bed.set_gear_compression(GearId::Right, Ratio::new::<ratio>(0.6));
+
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        let context = element.context();
+        assert!((context.ground_speed().get::<knot>() - 12.).abs() < 1e-9);
+        assert!((context.gear_compression(GearId::Nose).get::<ratio>() - 0.2).abs() < 1e-9);
+        assert!((context.gear_compression(GearId::Left).get::<ratio>()).abs() < 1e-9);
+        assert!((context.gear_compression(GearId::Right).get::<ratio>() - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn context_air_density_at_sea_level_standard_conditions() {
+        let mut element = ContextCapturingElement::default();
+        let mut test_bed = SimulationTestBed::new();
+        test_bed.set_ambient_pressure(Pressure::new::<inch_of_mercury>(29.92));
+        test_bed.set_ambient_temperature(ThermodynamicTemperature::new::<degree_celsius>(15.));
+
+        test_bed.run(&mut element, |el, context| el.update(context));
+
+        let density = element
+            .context()
+            .ambient_air_density()
+            .get::<kilogram_per_cubic_meter>();
+        assert!((density - 1.225).abs() < 0.001);
+    }
+
+    #[test]
+    fn context_created_in_code_defaults_to_standard_pressure_and_level_flight() {
+        let context = UpdateContext::new(
+            Duration::from_millis(50),
+            Velocity::new::<knot>(250.),
+            Length::new::<foot>(5000.),
+            ThermodynamicTemperature::new::<degree_celsius>(25.),
+            false,
+            Acceleration::new::<foot_per_second_squared>(0.),
+        );
+
+        assert!((context.ambient_pressure().get::<inch_of_mercury>() - 29.92).abs() < 1e-9);
+        assert!((context.pressure_altitude().get::<foot>() - 5000.).abs() < 1e-9);
+        assert!((context.true_airspeed().get::<knot>() - 250.).abs() < 1e-9);
+        assert!((context.vertical_speed().get::<foot_per_minute>()).abs() < 1e-9);
+        assert!((context.ground_speed().get::<knot>()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn context_created_in_code_can_be_given_kinematics() {
+        let context = UpdateContext::new(
+            Duration::from_millis(50),
+            Velocity::new::<knot>(0.),
+            Length::new::<foot>(0.),
+            ThermodynamicTemperature::new::<degree_celsius>(25.),
+            true,
+            Acceleration::new::<foot_per_second_squared>(0.),
+        )
+        .with_ground_speed(Velocity::new::<knot>(20.))
+        .with_attitude(Angle::new::<degree>(2.), Angle::new::<degree>(0.))
+        .with_gear_compression(GearId::Left, Ratio::new::<ratio>(0.5));
+
+        assert!((context.ground_speed().get::<knot>() - 20.).abs() < 1e-9);
+        assert!((context.pitch().get::<degree>() - 2.).abs() < 1e-9);
+        assert!((context.gear_compression(GearId::Left).get::<ratio>() - 0.5).abs() < 1e-9);
+    }
 }
@@ -1,14 +1,17 @@
-use std::time::Duration;
+use std::{cell::Cell, time::Duration};
 use uom::si::{
-    acceleration::foot_per_second_squared, f64::*, length::foot,
-    thermodynamic_temperature::degree_celsius, time::second, velocity::knot,
+    acceleration::foot_per_second_squared, angle::degree, f64::*, length::foot,
+    mass_density::kilogram_per_cubic_meter, pressure::inch_of_mercury, pressure::pascal,
+    ratio::ratio, thermodynamic_temperature::degree_celsius, thermodynamic_temperature::kelvin,
+    time::second, velocity::foot_per_minute, velocity::knot,
 };
 
-use super::SimulatorReader;
+use super::{SimulatorReader, VariableIdentifier};
+use crate::{landing_gear::GearId, shared::SeededRandomSource};
 
 /// Provides data unowned by any system in the aircraft system simulation
 /// for the purpose of handling a simulation tick.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct UpdateContext {
     delta: Duration,
     indicated_airspeed: Velocity,
@@ -16,6 +19,16 @@ pub struct UpdateContext {
     ambient_temperature: ThermodynamicTemperature,
     is_on_ground: bool,
     longitudinal_acceleration: Acceleration,
+    ambient_pressure: Pressure,
+    pressure_altitude: Length,
+    true_airspeed: Velocity,
+    mach_number: Ratio,
+    vertical_speed: Velocity,
+    pitch: Angle,
+    bank: Angle,
+    ground_speed: Velocity,
+    gear_compression: [Ratio; 3],
+    random: Cell<SeededRandomSource>,
 }
 impl UpdateContext {
     pub(crate) const AMBIENT_TEMPERATURE_KEY: &'static str = "AMBIENT TEMPERATURE";
@@ -23,7 +36,67 @@ impl UpdateContext {
     pub(crate) const INDICATED_ALTITUDE_KEY: &'static str = "INDICATED ALTITUDE";
     pub(crate) const IS_ON_GROUND_KEY: &'static str = "SIM ON GROUND";
     pub(crate) const ACCEL_BODY_Z_KEY: &'static str = "ACCELERATION BODY Z";
+    pub(crate) const AMBIENT_PRESSURE_KEY: &'static str = "AMBIENT PRESSURE";
+    pub(crate) const PRESSURE_ALTITUDE_KEY: &'static str = "PRESSURE ALTITUDE";
+    pub(crate) const TRUE_AIRSPEED_KEY: &'static str = "AIRSPEED TRUE";
+    pub(crate) const MACH_NUMBER_KEY: &'static str = "AIRSPEED MACH";
+    pub(crate) const VERTICAL_SPEED_KEY: &'static str = "VERTICAL SPEED";
+    pub(crate) const PITCH_KEY: &'static str = "PLANE PITCH DEGREES";
+    pub(crate) const BANK_KEY: &'static str = "PLANE BANK DEGREES";
+    pub(crate) const GROUND_SPEED_KEY: &'static str = "GROUND VELOCITY";
+    pub(crate) const GEAR_COMPRESSION_KEYS: [&'static str; 3] = [
+        "CONTACT POINT COMPRESSION:0",
+        "CONTACT POINT COMPRESSION:1",
+        "CONTACT POINT COMPRESSION:2",
+    ];
 
+    /// The names of the variables read into the context. Every [`VariableRegistry`] registers
+    /// these first, thus the identifier of each variable is its index in this list.
+    ///
+    /// [`VariableRegistry`]: struct.VariableRegistry.html
+    pub(crate) const VARIABLE_NAMES: [&'static str; 16] = [
+        UpdateContext::AMBIENT_TEMPERATURE_KEY,
+        UpdateContext::INDICATED_AIRSPEED_KEY,
+        UpdateContext::INDICATED_ALTITUDE_KEY,
+        UpdateContext::IS_ON_GROUND_KEY,
+        UpdateContext::ACCEL_BODY_Z_KEY,
+        UpdateContext::AMBIENT_PRESSURE_KEY,
+        UpdateContext::PRESSURE_ALTITUDE_KEY,
+        UpdateContext::TRUE_AIRSPEED_KEY,
+        UpdateContext::MACH_NUMBER_KEY,
+        UpdateContext::VERTICAL_SPEED_KEY,
+        UpdateContext::PITCH_KEY,
+        UpdateContext::BANK_KEY,
+        UpdateContext::GROUND_SPEED_KEY,
+        UpdateContext::GEAR_COMPRESSION_KEYS[0],
+        UpdateContext::GEAR_COMPRESSION_KEYS[1],
+        UpdateContext::GEAR_COMPRESSION_KEYS[2],
+    ];
+    const AMBIENT_TEMPERATURE_ID: VariableIdentifier = VariableIdentifier::new(0);
+    const INDICATED_AIRSPEED_ID: VariableIdentifier = VariableIdentifier::new(1);
+    const INDICATED_ALTITUDE_ID: VariableIdentifier = VariableIdentifier::new(2);
+    const IS_ON_GROUND_ID: VariableIdentifier = VariableIdentifier::new(3);
+    const ACCEL_BODY_Z_ID: VariableIdentifier = VariableIdentifier::new(4);
+    const AMBIENT_PRESSURE_ID: VariableIdentifier = VariableIdentifier::new(5);
+    const PRESSURE_ALTITUDE_ID: VariableIdentifier = VariableIdentifier::new(6);
+    const TRUE_AIRSPEED_ID: VariableIdentifier = VariableIdentifier::new(7);
+    const MACH_NUMBER_ID: VariableIdentifier = VariableIdentifier::new(8);
+    const VERTICAL_SPEED_ID: VariableIdentifier = VariableIdentifier::new(9);
+    const PITCH_ID: VariableIdentifier = VariableIdentifier::new(10);
+    const BANK_ID: VariableIdentifier = VariableIdentifier::new(11);
+    const GROUND_SPEED_ID: VariableIdentifier = VariableIdentifier::new(12);
+    const GEAR_COMPRESSION_IDS: [VariableIdentifier; 3] = [
+        VariableIdentifier::new(13),
+        VariableIdentifier::new(14),
+        VariableIdentifier::new(15),
+    ];
+
+    // Specific gas constant of dry air
+    const AIR_GAS_CONSTANT_JOULE_PER_KILOGRAM_KELVIN: f64 = 287.058;
+
+    /// Creates a context from the given air data. The remaining quantities default to
+    /// standard sea level pressure, level flight and no ground movement. Use the `with_*`
+    /// functions to set them.
     pub fn new(
         delta: Duration,
         indicated_airspeed: Velocity,
@@ -39,26 +112,93 @@ impl UpdateContext {
             ambient_temperature,
             is_on_ground,
             longitudinal_acceleration,
+            ambient_pressure: Pressure::new::<inch_of_mercury>(29.92),
+            pressure_altitude: indicated_altitude,
+            true_airspeed: indicated_airspeed,
+            mach_number: Ratio::new::<ratio>(0.),
+            vertical_speed: Velocity::new::<foot_per_minute>(0.),
+            pitch: Angle::new::<degree>(0.),
+            bank: Angle::new::<degree>(0.),
+            ground_speed: Velocity::new::<knot>(0.),
+            gear_compression: [Ratio::new::<ratio>(0.); 3],
+            random: Cell::new(SeededRandomSource::default()),
         }
     }
 
+    pub fn with_ambient_pressure(mut self, ambient_pressure: Pressure) -> Self {
+        self.ambient_pressure = ambient_pressure;
+        self
+    }
+
+    pub fn with_pressure_altitude(mut self, pressure_altitude: Length) -> Self {
+        self.pressure_altitude = pressure_altitude;
+        self
+    }
+
+    pub fn with_true_airspeed(mut self, true_airspeed: Velocity) -> Self {
+        self.true_airspeed = true_airspeed;
+        self
+    }
+
+    pub fn with_mach_number(mut self, mach_number: Ratio) -> Self {
+        self.mach_number = mach_number;
+        self
+    }
+
+    pub fn with_vertical_speed(mut self, vertical_speed: Velocity) -> Self {
+        self.vertical_speed = vertical_speed;
+        self
+    }
+
+    /// Sets the attitude of the aircraft, pitch positive nose up and bank positive right wing down.
+    pub fn with_attitude(mut self, pitch: Angle, bank: Angle) -> Self {
+        self.pitch = pitch;
+        self.bank = bank;
+        self
+    }
+
+    pub fn with_ground_speed(mut self, ground_speed: Velocity) -> Self {
+        self.ground_speed = ground_speed;
+        self
+    }
+
+    pub fn with_gear_compression(mut self, gear_id: GearId, compression: Ratio) -> Self {
+        self.gear_compression[gear_id as usize] = compression;
+        self
+    }
+
     /// Creates a context based on the data that was read from the simulator.
-    pub(super) fn from_reader(reader: &mut SimulatorReader, delta_time: Duration) -> UpdateContext {
+    pub(super) fn from_reader(
+        reader: &mut SimulatorReader,
+        delta_time: Duration,
+        random_seed: u64,
+    ) -> UpdateContext {
         UpdateContext {
-            ambient_temperature: ThermodynamicTemperature::new::<degree_celsius>(
-                reader.read_f64(UpdateContext::AMBIENT_TEMPERATURE_KEY),
-            ),
-            indicated_airspeed: Velocity::new::<knot>(
-                reader.read_f64(UpdateContext::INDICATED_AIRSPEED_KEY),
-            ),
-            indicated_altitude: Length::new::<foot>(
-                reader.read_f64(UpdateContext::INDICATED_ALTITUDE_KEY),
-            ),
-            is_on_ground: reader.read_bool(UpdateContext::IS_ON_GROUND_KEY),
+            ambient_temperature: reader
+                .read_quantity(&UpdateContext::AMBIENT_TEMPERATURE_ID, degree_celsius),
+            indicated_airspeed: reader.read_quantity(&UpdateContext::INDICATED_AIRSPEED_ID, knot),
+            indicated_altitude: reader.read_quantity(&UpdateContext::INDICATED_ALTITUDE_ID, foot),
+            is_on_ground: reader.read_bool(&UpdateContext::IS_ON_GROUND_ID),
             delta: delta_time,
-            longitudinal_acceleration: Acceleration::new::<foot_per_second_squared>(
-                reader.read_f64(UpdateContext::ACCEL_BODY_Z_KEY),
-            ),
+            longitudinal_acceleration: reader
+                .read_quantity(&UpdateContext::ACCEL_BODY_Z_ID, foot_per_second_squared),
+            ambient_pressure: reader
+                .read_quantity(&UpdateContext::AMBIENT_PRESSURE_ID, inch_of_mercury),
+            pressure_altitude: reader.read_quantity(&UpdateContext::PRESSURE_ALTITUDE_ID, foot),
+            true_airspeed: reader.read_quantity(&UpdateContext::TRUE_AIRSPEED_ID, knot),
+            mach_number: reader.read_quantity(&UpdateContext::MACH_NUMBER_ID, ratio),
+            vertical_speed: reader
+                .read_quantity(&UpdateContext::VERTICAL_SPEED_ID, foot_per_minute),
+            // The simulator's pitch and bank are positive nose down and left wing down
+            pitch: -reader.read_quantity::<Angle, _>(&UpdateContext::PITCH_ID, degree),
+            bank: -reader.read_quantity::<Angle, _>(&UpdateContext::BANK_ID, degree),
+            ground_speed: reader.read_quantity(&UpdateContext::GROUND_SPEED_ID, knot),
+            gear_compression: [
+                reader.read_quantity(&UpdateContext::GEAR_COMPRESSION_IDS[0], ratio),
+                reader.read_quantity(&UpdateContext::GEAR_COMPRESSION_IDS[1], ratio),
+                reader.read_quantity(&UpdateContext::GEAR_COMPRESSION_IDS[2], ratio),
+            ],
+            random: Cell::new(SeededRandomSource::new(random_seed)),
         }
     }
 
@@ -98,10 +238,82 @@ impl UpdateContext {
         self.longitudinal_acceleration
     }
 
+    pub fn ambient_pressure(&self) -> Pressure {
+        self.ambient_pressure
+    }
+
+    pub fn pressure_altitude(&self) -> Length {
+        self.pressure_altitude
+    }
+
+    pub fn true_airspeed(&self) -> Velocity {
+        self.true_airspeed
+    }
+
+    pub fn mach_number(&self) -> Ratio {
+        self.mach_number
+    }
+
+    /// The vertical speed, positive when climbing.
+    pub fn vertical_speed(&self) -> Velocity {
+        self.vertical_speed
+    }
+
+    /// The pitch of the aircraft, positive nose up.
+    pub fn pitch(&self) -> Angle {
+        self.pitch
+    }
+
+    /// The bank of the aircraft, positive right wing down.
+    pub fn bank(&self) -> Angle {
+        self.bank
+    }
+
+    pub fn ground_speed(&self) -> Velocity {
+        self.ground_speed
+    }
+
+    /// The compression of the given gear's shock absorber, from 0 (fully extended) to 1 (fully compressed).
+    pub fn gear_compression(&self, gear_id: GearId) -> Ratio {
+        self.gear_compression[gear_id as usize]
+    }
+
+    /// The density of the ambient air, derived from the ambient pressure and temperature.
+    pub fn ambient_air_density(&self) -> MassDensity {
+        MassDensity::new::<kilogram_per_cubic_meter>(
+            self.ambient_pressure.get::<pascal>()
+                / (Self::AIR_GAS_CONSTANT_JOULE_PER_KILOGRAM_KELVIN
+                    * self.ambient_temperature.get::<kelvin>()),
+        )
+    }
+
+    /// Returns a random number. The numbers are generated from a seed provided
+    /// by the [`Simulation`]'s random source, thus a simulation with a seeded
+    /// random source results in the same numbers for every run.
+    ///
+    /// [`Simulation`]: struct.Simulation.html
+    pub fn random_number(&self) -> u8 {
+        let mut random = self.random.get();
+        let number = random.next_u8();
+        self.random.set(random);
+
+        number
+    }
+
     pub fn with_delta(&self, delta: Duration) -> Self {
-        let mut copy: UpdateContext = *self;
+        let mut copy: UpdateContext = self.clone();
         copy.delta = delta;
 
         copy
     }
+
+    /// Creates the context of a fixed step run within the step of this context. The
+    /// fixed step has its own delta and random numbers, such that consecutive fixed
+    /// steps don't receive the same random numbers.
+    pub(super) fn for_fixed_step(&self, delta: Duration, random_seed: u64) -> Self {
+        let mut context = self.with_delta(delta);
+        context.random = Cell::new(SeededRandomSource::new(random_seed));
+
+        context
+    }
 }